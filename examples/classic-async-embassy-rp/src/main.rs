@@ -43,8 +43,7 @@ async fn main(spawner: Spawner) {
 
     // Create, initialise and calibrate the controller
     info!("initialising controller");
-    let mut controller = Classic::new(i2c, Delay);
-    controller.init().await.unwrap();
+    let mut controller = Classic::new(i2c, Delay).await.unwrap();
 
     let hi_res = false;
 
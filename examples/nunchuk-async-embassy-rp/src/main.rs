@@ -43,8 +43,7 @@ async fn main(spawner: Spawner) {
 
     // Create, initialise and calibrate the controller
     info!("initialising controller");
-    let mut controller = Nunchuk::new(i2c, Delay);
-    controller.init().await.unwrap();
+    let mut controller = Nunchuk::new(i2c, Delay).await.unwrap();
 
     info!("begin polling controller");
     loop {
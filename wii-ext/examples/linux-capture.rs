@@ -0,0 +1,30 @@
+//! Capture reports from a controller on a Linux i2c bus, and print them as `test_data` source
+//!
+//! Run with `cargo run --example linux-capture --features linux,capture -- /dev/i2c-1 PREFIX`,
+//! eg: `PDP_LINK`. Add `nunchuk` to capture nunchuk states instead of classic controller ones.
+//! Follow each prompt, then press enter.
+
+use std::io::Write;
+use wii_ext::blocking_impl::classic::Classic;
+use wii_ext::capture::{capture, CLASSIC_STEPS, NUNCHUK_STEPS};
+use wii_ext::linux::StdDelay;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut args = std::env::args().skip(1);
+    let path = args.next().unwrap_or_else(|| "/dev/i2c-1".into());
+    let prefix = args.next().unwrap_or_else(|| "NEW".into());
+    let steps = match args.next().as_deref() {
+        Some("nunchuk") => NUNCHUK_STEPS,
+        _ => CLASSIC_STEPS,
+    };
+    let i2c = wii_ext::linux::open(&path)?;
+    let mut classic = Classic::new_uninit(i2c, StdDelay).init_no_calibration()?;
+    let captures = capture(&mut classic, steps, true, |step, hires| {
+        let mode = if hires { "hi-res" } else { "standard" };
+        eprint!("[{mode}] {}, then press enter ", step.prompt);
+        std::io::stderr().flush().ok();
+        std::io::stdin().read_line(&mut String::new()).ok();
+    })?;
+    print!("{}", captures.to_source(&prefix, &prefix));
+    Ok(())
+}
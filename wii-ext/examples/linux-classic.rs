@@ -0,0 +1,23 @@
+//! Poll a classic controller connected to a Linux i2c bus and print its readings
+//!
+//! Run with `cargo run --example linux-classic --features linux -- /dev/i2c-1`
+
+use std::time::Duration;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let path = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "/dev/i2c-1".into());
+    let mut classic = wii_ext::linux::classic(&path)?;
+    // Use hi-resolution mode if the controller supports it
+    if let Err(e) = classic.enable_hires() {
+        eprintln!("hi-resolution mode not available: {e}");
+    }
+    loop {
+        match classic.read() {
+            Ok(input) => println!("{input:?}"),
+            Err(e) => eprintln!("read failed: {e}"),
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+}
@@ -0,0 +1,26 @@
+//! Poll two classic controllers on channels 0 and 1 of a TCA9548A i2c mux
+//!
+//! Run with `cargo run --example linux-mux --features linux,mux -- /dev/i2c-1`
+
+use core::cell::RefCell;
+use embedded_hal_bus::i2c::RefCellDevice;
+use std::time::Duration;
+use wii_ext::blocking_impl::classic::Classic;
+use wii_ext::linux::StdDelay;
+use wii_ext::mux::{MuxedBus, TCA9548A_ADDR};
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let path = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "/dev/i2c-1".into());
+    let bus = RefCell::new(wii_ext::linux::open(&path)?);
+    let player1 = MuxedBus::new(RefCellDevice::new(&bus), TCA9548A_ADDR, 0).unwrap();
+    let player2 = MuxedBus::new(RefCellDevice::new(&bus), TCA9548A_ADDR, 1).unwrap();
+    let mut player1 = Classic::new(player1, StdDelay)?;
+    let mut player2 = Classic::new(player2, StdDelay)?;
+    loop {
+        println!("1: {:?}", player1.read()?);
+        println!("2: {:?}", player2.read()?);
+        std::thread::sleep(Duration::from_millis(100));
+    }
+}
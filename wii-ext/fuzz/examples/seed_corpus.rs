@@ -0,0 +1,68 @@
+//! Seed the fuzz corpora with reports captured from real controllers
+//!
+//! Run from the `fuzz` directory, before fuzzing for the first time:
+//! `cargo run --example seed_corpus`
+
+use std::fs;
+use std::path::Path;
+use wii_ext::test_data::*;
+
+fn seed(target: &str, inputs: &[(&str, &[u8])]) -> std::io::Result<()> {
+    let dir = Path::new("corpus").join(target);
+    fs::create_dir_all(&dir)?;
+    for (name, data) in inputs {
+        fs::write(dir.join(name), data)?;
+    }
+    Ok(())
+}
+
+fn main() -> std::io::Result<()> {
+    let standard: &[(&str, &[u8])] = &[
+        ("classic_idle", &CLASSIC_IDLE),
+        ("classic_btn_a", &CLASSIC_BTN_A),
+        ("classic_pad_u", &CLASSIC_PAD_U),
+        ("classic_ljoy_ul", &CLASSIC_LJOY_UL),
+        ("classic_rjoy_dr", &CLASSIC_RJOY_DR),
+        ("classic_ltrig_w_button", &CLASSIC_LTRIG_W_BUTTON),
+        ("nes_idle", &NES_IDLE),
+        ("snes_idle", &SNES_IDLE),
+        ("pro_idle", &PRO_IDLE),
+        ("pro_btn_home", &PRO_BTN_HOME),
+        ("pdp_link_idle", &PDP_LINK_IDLE),
+    ];
+    let hires: &[(&str, &[u8])] = &[
+        ("classic_hd_idle", &CLASSIC_HD_IDLE),
+        ("classic_hd_ltrig", &CLASSIC_HD_LTRIG),
+        ("classic_hd_btn_x", &CLASSIC_HD_BTN_X),
+        ("nes_hd_idle", &NES_HD_IDLE),
+        ("snes_hd_idle", &SNES_HD_IDLE),
+        ("pro_hd_idle", &PRO_HD_IDLE),
+        ("pro_hd_ljoy_ur", &PRO_HD_LJOY_UR),
+        ("pdp_link_hd_idle", &PDP_LINK_HD_IDLE),
+        ("pdp_link_hd_rtrig", &PDP_LINK_HD_RTRIG),
+    ];
+    seed("classic", standard)?;
+    seed("classic", hires)?;
+    seed("classic_hd", hires)?;
+    seed(
+        "nunchuk",
+        &[
+            ("idle", &NUNCHUCK_IDLE),
+            ("joy_u", &NUNCHUCK_JOY_U),
+            ("joy_l", &NUNCHUCK_JOY_L),
+            ("btn_c", &NUNCHUCK_BTN_C),
+            ("btn_z", &NUNCHUCK_BTN_Z),
+        ],
+    )?;
+    seed(
+        "identify_controller",
+        &[
+            ("nunchuk", &NUNCHUCK_ID),
+            ("classic", &CLASSIC_ID),
+            ("pro", &PRO_ID),
+            ("nes", &NES_ID),
+            ("snes", &SNES_ID),
+        ],
+    )?;
+    Ok(())
+}
@@ -0,0 +1,19 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use wii_ext::core::classic::ClassicReading;
+
+// Any input must decode without panicking, and anything that decodes must survive
+// being encoded again in the same format
+fuzz_target!(|data: &[u8]| {
+    let Some(reading) = ClassicReading::from_data(data) else {
+        assert!(data.len() != 6 && data.len() != 8);
+        return;
+    };
+    let again = if data.len() == 6 {
+        ClassicReading::from_data(&reading.to_report())
+    } else {
+        ClassicReading::from_data(&reading.to_hd_report())
+    };
+    assert_eq!(again, Some(reading));
+});
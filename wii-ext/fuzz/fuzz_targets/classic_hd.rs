@@ -0,0 +1,16 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use wii_ext::core::classic::ClassicReading;
+use wii_ext::core::ExtHdReport;
+
+fuzz_target!(|report: ExtHdReport| {
+    let reading = ClassicReading::from_data(&report).unwrap();
+    // Hi-res reports keep every bit the reading has, except the unused ones
+    let again = ClassicReading::from_data(&reading.to_hd_report()).unwrap();
+    assert_eq!(again, reading);
+    // Dropping to standard resolution loses precision, but must be stable after that
+    let standard = ClassicReading::from_data(&reading.to_report()).unwrap();
+    let again = ClassicReading::from_data(&standard.to_report()).unwrap();
+    assert_eq!(again, standard);
+});
@@ -0,0 +1,11 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use wii_ext::core::{identify_controller, ControllerIdReport};
+
+fuzz_target!(|id: ControllerIdReport| {
+    // Only the documented ID layout is recognised
+    if identify_controller(id).is_some() {
+        assert_eq!(id[2..4], [0xA4, 0x20]);
+    }
+});
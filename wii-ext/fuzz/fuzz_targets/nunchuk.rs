@@ -0,0 +1,12 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use wii_ext::core::nunchuk::NunchukReading;
+
+fuzz_target!(|data: &[u8]| {
+    let Some(reading) = NunchukReading::from_data(data) else {
+        return;
+    };
+    let again = NunchukReading::from_data(&reading.to_report());
+    assert_eq!(again, Some(reading));
+});
@@ -4,3 +4,8 @@ pub mod classic;
 pub mod interface;
 /// Async nunchuk controller driver
 pub mod nunchuk;
+#[cfg(feature = "embassy")]
+pub mod poller;
+/// Stream helpers for the async drivers
+#[cfg(feature = "futures")]
+pub(crate) mod stream;
@@ -1,12 +1,25 @@
+// The deprecated `AsyncImplError` is still defined here, and its trait impls use it
+#![allow(deprecated)]
+
 use crate::core::{
-    ControllerIdReport, ControllerType, ExtHdReport, ExtReport, EXT_I2C_ADDR,
-    INTERMESSAGE_DELAY_MICROSEC_U32,
+    id_has_signature, ControllerIdReport, ControllerType, ExtAltReport, ExtHdReport, ExtReport,
+    RetryPolicy, Timing, EXT_I2C_ADDR, ID_READ_ATTEMPTS, REGISTER_SPACE_SIZE,
 };
+use crate::logging;
+use crate::Error;
+use core::future::{poll_fn, Future};
+use core::mem::size_of;
+use core::pin::pin;
+use core::task::Poll;
 use embedded_hal_async;
 
 #[cfg(feature = "defmt_print")]
 use defmt;
 
+/// Errors from the async drivers, without the bus error
+///
+/// Kept for code written against earlier versions. Any [`Error`] converts into this with `?`.
+#[deprecated(note = "use `wii_ext::Error` instead")]
 #[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
 #[derive(Debug)]
 pub enum AsyncImplError {
@@ -14,6 +27,117 @@ pub enum AsyncImplError {
     InvalidInputData,
     Error,
     ParseError,
+    /// The operation did not complete before the timeout future resolved
+    Timeout,
+    /// The controller has returned the same non-idle report too many times in a row
+    Stuck,
+    /// The connected controller is not the type this driver expects
+    UnexpectedController(Option<ControllerType>),
+    /// The connected controller is no longer the one that was identified earlier
+    ControllerChanged {
+        previous: Option<ControllerType>,
+        current: Option<ControllerType>,
+    },
+}
+
+impl AsyncImplError {
+    /// Whether re-initialising the controller and trying again might fix this error
+    ///
+    /// Bus errors and corrupt data are usually transient. A different controller
+    /// being connected is not something a retry can fix.
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            Self::I2C
+                | Self::InvalidInputData
+                | Self::Error
+                | Self::ParseError
+                | Self::Timeout
+                | Self::Stuck
+        )
+    }
+}
+
+impl core::fmt::Display for AsyncImplError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::I2C => f.write_str("i2c bus error"),
+            Self::InvalidInputData => f.write_str("invalid report data from controller"),
+            Self::Error => f.write_str("driver error"),
+            Self::ParseError => f.write_str("could not parse report"),
+            Self::Timeout => f.write_str("timed out waiting for controller"),
+            Self::Stuck => f.write_str("controller keeps returning the same report"),
+            Self::UnexpectedController(found) => {
+                write!(f, "unexpected controller type: {:?}", found)
+            }
+            Self::ControllerChanged { previous, current } => {
+                write!(f, "controller changed from {:?} to {:?}", previous, current)
+            }
+        }
+    }
+}
+
+impl core::error::Error for AsyncImplError {}
+
+impl<E> From<Error<E>> for AsyncImplError {
+    fn from(e: Error<E>) -> Self {
+        match e {
+            Error::I2C(_) | Error::RetriesExhausted(_) => Self::I2C,
+            Error::InvalidInputData
+            | Error::InconsistentData
+            | Error::RejectedReport(_)
+            | Error::NotResponding
+            | Error::OutOfRange { .. } => Self::InvalidInputData,
+            Error::ReadNotStarted | Error::WriteVerifyFailed { .. } => Self::Error,
+            Error::Timeout => Self::Timeout,
+            Error::Stuck => Self::Stuck,
+            Error::UnexpectedController(found) => Self::UnexpectedController(found),
+            Error::ControllerChanged { previous, current } => {
+                Self::ControllerChanged { previous, current }
+            }
+        }
+    }
+}
+
+/// Run `operation`, giving up with `Error::Timeout` if `timeout` resolves first
+///
+/// `operation` is dropped on timeout, which may leave an i2c transaction half-finished.
+/// Every report read starts by setting the read cursor, so the next read recovers from this.
+pub(crate) async fn with_timeout<T, E>(
+    operation: impl Future<Output = Result<T, Error<E>>>,
+    timeout: impl Future,
+) -> Result<T, Error<E>> {
+    let mut operation = pin!(operation);
+    let mut timeout = pin!(timeout);
+    poll_fn(|cx| {
+        if let Poll::Ready(result) = operation.as_mut().poll(cx) {
+            Poll::Ready(result)
+        } else if timeout.as_mut().poll(cx).is_ready() {
+            Poll::Ready(Err(Error::Timeout))
+        } else {
+            Poll::Pending
+        }
+    })
+    .await
+}
+
+/// Run a bus transaction, retrying bus errors as allowed by the interface's retry policy
+macro_rules! with_retries {
+    ($self:ident, $transaction:expr) => {{
+        let mut attempt = 1;
+        loop {
+            match $transaction {
+                Err(Error::I2C(e)) if $self.retry.max_attempts > 1 => {
+                    if attempt >= $self.retry.max_attempts {
+                        break Err(Error::RetriesExhausted(e));
+                    }
+                    attempt += 1;
+                    $self.delay.delay_us($self.retry.backoff_us).await;
+                }
+                result => break result,
+            }
+        }
+    }};
 }
 
 #[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
@@ -21,16 +145,98 @@ pub enum AsyncImplError {
 pub struct InterfaceAsync<I2C, Delay> {
     i2cdev: I2C,
     delay: Delay,
+    fast_polling: bool,
+    /// Where the read cursor is expected to be, if known
+    cursor: Option<u8>,
+    combined_reads: bool,
+    /// The seek for the next report read will be sent with the read itself
+    deferred_seek: bool,
+    timing: Timing,
+    retry: RetryPolicy,
+    /// Wait this long for the next sample instead of the usual read delay
+    sample_delay_override: Option<u32>,
+    /// Read back every register write
+    verify_writes: bool,
+    /// A register has read back wrong since the interface was created
+    write_verify_failed: bool,
+    /// Reads of the controller ID before accepting one without a valid signature
+    id_attempts: u8,
 }
 
-impl<I2C, Delay> InterfaceAsync<I2C, Delay>
+impl<I2C, E, Delay> InterfaceAsync<I2C, Delay>
 where
-    I2C: embedded_hal_async::i2c::I2c,
+    I2C: embedded_hal_async::i2c::I2c<Error = E>,
     Delay: embedded_hal_async::delay::DelayNs,
 {
     /// Create async interface for wii-extension controller
     pub fn new(i2cdev: I2C, delay: Delay) -> Self {
-        Self { i2cdev, delay }
+        Self {
+            i2cdev,
+            delay,
+            fast_polling: false,
+            cursor: None,
+            combined_reads: false,
+            deferred_seek: false,
+            timing: Timing::default(),
+            retry: RetryPolicy::NONE,
+            sample_delay_override: None,
+            verify_writes: false,
+            write_verify_failed: false,
+            id_attempts: ID_READ_ATTEMPTS,
+        }
+    }
+
+    /// Set the delays used for init and reads
+    pub(crate) fn set_timing(&mut self, timing: Timing) {
+        self.timing = timing;
+    }
+
+    pub(crate) fn set_id_attempts(&mut self, attempts: u8) {
+        self.id_attempts = attempts;
+    }
+
+    /// Retry failed bus transactions according to `retry`
+    pub(crate) fn set_retry_policy(&mut self, retry: RetryPolicy) {
+        self.retry = retry;
+    }
+
+    /// Wait `micros` for the next sample only, or the usual read delay if `None`
+    pub(crate) fn override_sample_delay(&mut self, micros: Option<u32>) {
+        self.sample_delay_override = micros;
+    }
+
+    /// Skip the seek before a report read when the cursor is known to be at 0
+    ///
+    /// Genuine controllers wrap the read cursor back to 0 after a report read, but
+    /// clones differ, so this is off by default.
+    pub(crate) fn set_fast_polling(&mut self, fast_polling: bool) {
+        self.fast_polling = fast_polling;
+    }
+
+    /// Send the seek and report read as a single write_read transaction
+    ///
+    /// This replaces the delay between seek and read with a repeated start, which
+    /// not every controller tolerates, so this is off by default.
+    pub(crate) fn set_combined_reads(&mut self, combined_reads: bool) {
+        self.combined_reads = combined_reads;
+    }
+
+    /// Read back each register after writing it, failing with `Error::WriteVerifyFailed`
+    ///
+    /// Some clones silently ignore writes. This doubles the bus traffic of configuration,
+    /// so it is off by default.
+    pub(crate) fn set_verify_writes(&mut self, verify_writes: bool) {
+        self.verify_writes = verify_writes;
+    }
+
+    /// Whether register writes are read back as they are made
+    pub(crate) fn verifies_writes(&self) -> bool {
+        self.verify_writes
+    }
+
+    /// Whether a write has ever failed verification
+    pub(crate) fn write_verify_failed(&self) -> bool {
+        self.write_verify_failed
     }
 
     /// Destroy i2c interface, allowing recovery of i2c and delay
@@ -39,47 +245,120 @@ where
     }
 
     /// Access delay stored in interface
-    pub(super) async fn delay_us(&mut self, micros: u32) {
+    pub(crate) async fn delay_us(&mut self, micros: u32) {
         self.delay.delay_us(micros).await
     }
 
     /// Read report data from the wii-extension controller
-    pub(super) async fn read_ext_report(&mut self) -> Result<ExtReport, AsyncImplError> {
-        self.start_sample().await?;
-        self.delay_us(INTERMESSAGE_DELAY_MICROSEC_U32).await;
+    pub(crate) async fn read_report(&mut self) -> Result<ExtReport, Error<E>> {
         let mut buffer: ExtReport = ExtReport::default();
-        self.i2cdev
-            .read(EXT_I2C_ADDR as u8, &mut buffer)
-            .await
-            .map_err(|_| AsyncImplError::I2C)
-            .and(Ok(buffer))
+        self.read_report_into(&mut buffer).await?;
+        Ok(buffer)
+    }
+
+    /// Read report data from the wii-extension controller into `buf`
+    ///
+    /// The report is read at the cursor, so call `start_sample()` and `wait_for_sample()` first.
+    pub(crate) async fn read_report_into(&mut self, buf: &mut ExtReport) -> Result<(), Error<E>> {
+        self.read_into(buf).await
     }
 
     /// Read a high-resolution version of the report data from the wii-extension controller
-    pub(super) async fn read_hd_report(&mut self) -> Result<ExtHdReport, AsyncImplError> {
-        self.start_sample().await?;
-        self.delay_us(INTERMESSAGE_DELAY_MICROSEC_U32).await;
+    pub(crate) async fn read_hd_report(&mut self) -> Result<ExtHdReport, Error<E>> {
         let mut buffer: ExtHdReport = ExtHdReport::default();
-        self.i2cdev
-            .read(EXT_I2C_ADDR as u8, &mut buffer)
-            .await
-            .map_err(|_| AsyncImplError::I2C)
-            .and(Ok(buffer))
+        self.read_hd_report_into(&mut buffer).await?;
+        Ok(buffer)
+    }
+
+    /// Read a high-resolution version of the report data into `buf`
+    pub(crate) async fn read_hd_report_into(
+        &mut self,
+        buf: &mut ExtHdReport,
+    ) -> Result<(), Error<E>> {
+        self.read_into(buf).await
+    }
+
+    /// Read a data format 0x02 version of the report data into `buf`
+    pub(crate) async fn read_alt_report_into(
+        &mut self,
+        buf: &mut ExtAltReport,
+    ) -> Result<(), Error<E>> {
+        self.read_into(buf).await
+    }
+
+    /// Start a sample, wait for it, then read it into `buf`
+    pub(crate) async fn read_sample_into(&mut self, buf: &mut [u8]) -> Result<(), Error<E>> {
+        self.start_sample().await?;
+        self.wait_for_sample().await;
+        self.read_into(buf).await
+    }
+
+    /// Wait long enough for the extension controller to prepare a sample
+    ///
+    /// Not needed when the seek will be combined with the read.
+    pub(crate) async fn wait_for_sample(&mut self) {
+        let delay = self
+            .sample_delay_override
+            .take()
+            .unwrap_or(self.timing.read_delay_us);
+        if self.deferred_seek {
+            return;
+        }
+        self.delay_us(delay).await;
+    }
+
+    /// Whether the read cursor is known to be at the start of the report
+    pub(crate) fn cursor_at_report(&self) -> bool {
+        self.cursor == Some(0)
+    }
+
+    /// Read a report at the cursor, without seeking first
+    pub(crate) async fn read_at_cursor(&mut self, buf: &mut [u8]) -> Result<(), Error<E>> {
+        self.deferred_seek = false;
+        self.read_into(buf).await
+    }
+
+    /// Read a report at the cursor, sending the seek first if it was deferred
+    async fn read_into(&mut self, buf: &mut [u8]) -> Result<(), Error<E>> {
+        let cursor = if core::mem::take(&mut self.deferred_seek) {
+            self.cursor = None;
+            with_retries!(
+                self,
+                logging::write_read::<I2C>(
+                    self.i2cdev.write_read(EXT_I2C_ADDR as u8, &[0], buf).await,
+                    &[0],
+                    buf,
+                )
+            )?;
+            Some(0)
+        } else {
+            let cursor = self.cursor.take();
+            with_retries!(
+                self,
+                logging::read::<I2C>(self.i2cdev.read(EXT_I2C_ADDR as u8, buf).await, buf)
+            )?;
+            cursor
+        };
+        // A report read from 0 leaves the cursor back at 0
+        self.cursor = cursor.filter(|&c| c == 0);
+        Ok(())
     }
 
     /// Send the init sequence to the Wii extension controller
-    pub(super) async fn init(&mut self) -> Result<(), AsyncImplError> {
+    pub(crate) async fn init(&mut self) -> Result<(), Error<E>> {
         // Extension controllers by default will use encrypted communication, as that is what the Wii does.
         // We can disable this encryption by writing some magic values
         // This is described at https://wiibrew.org/wiki/Wiimote/Extension_Controllers#The_New_Way
 
         // Reset to base register first - this should recover a controller in a weird state.
         // Use longer delays here than normal reads - the system seems more unreliable performing these commands
-        self.delay_us(100_000).await;
-        self.set_read_register_address_with_delay(0).await?;
-        self.set_register_with_delay(0xF0, 0x55).await?;
-        self.set_register_with_delay(0xFB, 0x00).await?;
-        self.delay_us(100_000).await;
+        self.delay_us(self.timing.settle_us).await;
+        self.set_read_register_address(0).await?;
+        self.delay_us(self.timing.init_step_us).await;
+        self.set_register(0xF0, 0x55).await?;
+        self.delay_us(self.timing.init_step_us).await;
+        self.set_register(0xFB, 0x00).await?;
+        self.delay_us(self.timing.settle_us).await;
         Ok(())
     }
 
@@ -88,9 +367,15 @@ where
     /// This enables the controller's high-resolution report data mode, which returns each
     /// analogue axis as a u8, rather than packing smaller integers in a structure.
     /// If your controllers supports this mode, you should use it. It is much better.
-    pub(super) async fn enable_hires(&mut self) -> Result<(), AsyncImplError> {
-        self.set_register_with_delay(0xFE, 0x03).await?;
-        self.delay_us(100_000).await;
+    pub(crate) async fn enable_hires(&mut self) -> Result<(), Error<E>> {
+        self.set_report_mode(0x03).await
+    }
+
+    /// Write the report format register, 0xFE, giving the controller time to switch
+    pub(crate) async fn set_report_mode(&mut self, value: u8) -> Result<(), Error<E>> {
+        self.delay_us(self.timing.init_step_us).await;
+        self.set_register(0xFE, value).await?;
+        self.delay_us(self.timing.settle_us).await;
         Ok(())
     }
 
@@ -100,15 +385,18 @@ where
     /// increments the register read postion on each read operation, and also on
     /// every write operation.
     /// This should be called before a read operation to ensure you get the correct data
-    pub(super) async fn set_read_register_address(
-        &mut self,
-        byte0: u8,
-    ) -> Result<(), AsyncImplError> {
-        self.i2cdev
-            .write(EXT_I2C_ADDR as u8, &[byte0])
-            .await
-            .map_err(|_| AsyncImplError::I2C)
-            .and(Ok(()))
+    pub(crate) async fn set_read_register_address(&mut self, byte0: u8) -> Result<(), Error<E>> {
+        self.cursor = None;
+        self.deferred_seek = false;
+        with_retries!(
+            self,
+            logging::write::<I2C>(
+                self.i2cdev.write(EXT_I2C_ADDR as u8, &[byte0]).await,
+                &[byte0],
+            )
+        )?;
+        self.cursor = Some(byte0);
+        Ok(())
     }
 
     /// Set the cursor position for the next i2c read after a small delay
@@ -118,52 +406,159 @@ where
     /// every write operation.
     /// This should be called before a read operation to ensure you get the correct data
     /// The delay helps ensure that required timings are met
-    pub(super) async fn set_read_register_address_with_delay(
+    pub(crate) async fn set_read_register_address_with_delay(
         &mut self,
         byte0: u8,
-    ) -> Result<(), AsyncImplError> {
-        self.delay_us(INTERMESSAGE_DELAY_MICROSEC_U32).await;
+    ) -> Result<(), Error<E>> {
+        self.delay_us(self.timing.read_delay_us).await;
         let res = self.set_read_register_address(byte0);
         res.await
     }
 
     /// Set a single register at target address
-    pub(super) async fn set_register(&mut self, addr: u8, byte1: u8) -> Result<(), AsyncImplError> {
-        self.i2cdev
-            .write(EXT_I2C_ADDR as u8, &[addr, byte1])
-            .await
-            .map_err(|_| AsyncImplError::I2C)
-            .and(Ok(()))
+    ///
+    /// A seek deferred by `start_sample()` is kept, so it is still sent with the next read.
+    pub(crate) async fn set_register(&mut self, addr: u8, byte1: u8) -> Result<(), Error<E>> {
+        self.cursor = None;
+        with_retries!(
+            self,
+            logging::write::<I2C>(
+                self.i2cdev.write(EXT_I2C_ADDR as u8, &[addr, byte1]).await,
+                &[addr, byte1],
+            )
+        )?;
+        if self.verify_writes {
+            self.verify_register(addr, byte1).await?;
+        }
+        Ok(())
+    }
+
+    /// Read back a register that has just been written
+    ///
+    /// A whole report is read, as not every controller answers shorter reads. The cursor is
+    /// left unknown, so the next report read seeks back to 0.
+    async fn verify_register(&mut self, addr: u8, expected: u8) -> Result<(), Error<E>> {
+        let mut buf = ExtReport::default();
+        self.set_read_register_address_with_delay(addr).await?;
+        self.delay_us(self.timing.read_delay_us).await;
+        self.read_at_cursor(&mut buf).await?;
+        self.cursor = None;
+        match buf[0] {
+            got if got == expected => Ok(()),
+            got => {
+                self.write_verify_failed = true;
+                logging::anomaly("register read back wrong", &[addr, expected, got]);
+                Err(Error::WriteVerifyFailed {
+                    addr,
+                    expected,
+                    got,
+                })
+            }
+        }
     }
 
     /// Set a single register at target address after a small delay
-    pub(super) async fn set_register_with_delay(
+    pub(crate) async fn set_register_with_delay(
         &mut self,
         addr: u8,
         byte1: u8,
-    ) -> Result<(), AsyncImplError> {
-        self.delay_us(INTERMESSAGE_DELAY_MICROSEC_U32).await;
+    ) -> Result<(), Error<E>> {
+        self.delay_us(self.timing.read_delay_us).await;
         let res = self.set_register(addr, byte1);
         res.await
     }
 
+    /// Read a block of registers starting at `addr`, then reset the read cursor to 0
+    pub(crate) async fn read_registers(
+        &mut self,
+        addr: u8,
+        buf: &mut [u8],
+    ) -> Result<(), Error<E>> {
+        self.set_read_register_address_with_delay(addr).await?;
+        self.delay_us(self.timing.read_delay_us).await;
+        self.cursor = None;
+        with_retries!(
+            self,
+            logging::read::<I2C>(self.i2cdev.read(EXT_I2C_ADDR as u8, buf).await, buf)
+        )?;
+        self.set_read_register_address_with_delay(0).await
+    }
+
+    /// Read sequential registers starting at `start` in report-sized chunks, then reset the
+    /// read cursor to 0
+    ///
+    /// Reads stop at the end of the register space (0xFF), returns the number of bytes read
+    pub(crate) async fn dump_registers(
+        &mut self,
+        start: u8,
+        buf: &mut [u8],
+    ) -> Result<usize, Error<E>> {
+        let len = buf.len().min(REGISTER_SPACE_SIZE - start as usize);
+        self.set_read_register_address_with_delay(start).await?;
+        self.cursor = None;
+        for chunk in buf[..len].chunks_mut(size_of::<ExtReport>()) {
+            self.delay_us(self.timing.read_delay_us).await;
+            with_retries!(
+                self,
+                logging::read::<I2C>(self.i2cdev.read(EXT_I2C_ADDR as u8, chunk).await, chunk)
+            )?;
+        }
+        self.set_read_register_address_with_delay(0).await?;
+        Ok(len)
+    }
+
+    /// Write a single register, then reset the read cursor to 0
+    pub(crate) async fn write_register(&mut self, addr: u8, value: u8) -> Result<(), Error<E>> {
+        self.set_register_with_delay(addr, value).await?;
+        self.set_read_register_address_with_delay(0).await
+    }
+
     /// Read the controller type ID register from the extension controller
-    pub(super) async fn read_id(&mut self) -> Result<ControllerIdReport, AsyncImplError> {
+    ///
+    /// Bus errors and IDs without the extension controller signature are read again, up to
+    /// `id_attempts` reads in all. The last result is returned if none are valid.
+    pub(crate) async fn read_id(&mut self) -> Result<ControllerIdReport, Error<E>> {
+        let mut attempt = 1;
+        loop {
+            let result = self.read_id_once().await;
+            let retry = match &result {
+                Ok(id) => !id_has_signature(id),
+                Err(Error::I2C(_) | Error::RetriesExhausted(_)) => true,
+                Err(_) => false,
+            };
+            if !retry || attempt >= self.id_attempts {
+                return result;
+            }
+            attempt += 1;
+            self.delay_us(self.timing.read_delay_us).await;
+        }
+    }
+
+    /// Read the controller type ID register once
+    async fn read_id_once(&mut self) -> Result<ControllerIdReport, Error<E>> {
         self.set_read_register_address(0xfa).await?;
-        let i2c_id = self.read_ext_report().await?;
+        let i2c_id = self.read_report().await?;
         Ok(i2c_id)
     }
 
     /// Determine the controller type based on the type ID of the extension controller
-    pub(super) async fn identify_controller(
-        &mut self,
-    ) -> Result<Option<ControllerType>, AsyncImplError> {
+    pub(crate) async fn identify_controller(&mut self) -> Result<Option<ControllerType>, Error<E>> {
         let i2c_id = self.read_id().await?;
         Ok(crate::core::identify_controller(i2c_id))
     }
 
     /// Instruct the extension controller to start preparing a sample by setting the read cursor to 0
-    pub(super) async fn start_sample(&mut self) -> Result<(), AsyncImplError> {
+    ///
+    /// In fast polling mode this is skipped when the cursor is already known to be at 0.
+    /// With combined reads, the seek is sent together with the next report read instead.
+    pub(crate) async fn start_sample(&mut self) -> Result<(), Error<E>> {
+        if self.fast_polling && self.cursor == Some(0) {
+            return Ok(());
+        }
+        if self.combined_reads {
+            self.deferred_seek = true;
+            return Ok(());
+        }
         self.set_read_register_address(0x00).await?;
         Ok(())
     }
@@ -0,0 +1,135 @@
+//! Poll a controller in the background and publish the latest reading
+//!
+//! [`run()`] owns a driver, reads it at a fixed interval and publishes each result through an
+//! embassy-sync [`Watch`]. Consumers wait for readings with a [`ReadingReceiver`]:
+//!
+//! ```ignore
+//! static READINGS: Readings<CriticalSectionRawMutex, ClassicReadingCalibrated, 2> = Watch::new();
+//!
+//! #[embassy_executor::task]
+//! async fn poll(classic: Classic<I2c<'static, I2C0, Async>, Delay>) {
+//!     poller::run(classic, Delay, 10_000, &READINGS).await
+//! }
+//!
+//! // In another task
+//! let mut readings = ReadingReceiver::new(&READINGS).unwrap();
+//! let input = readings.next_reading().await;
+//! ```
+
+use crate::async_impl::classic::Classic;
+use crate::async_impl::nunchuk::Nunchuk;
+use crate::core::classic::{ClassicReadingCalibrated, Resolution};
+use crate::core::nunchuk::NunchukReadingCalibrated;
+use crate::core::Ready;
+use crate::Error;
+use core::future::Future;
+use embassy_sync::blocking_mutex::raw::RawMutex;
+use embassy_sync::watch::{Receiver, Watch};
+use embedded_hal_async::delay::DelayNs;
+use embedded_hal_async::i2c::{I2c, SevenBitAddress};
+
+/// Read attempts made for each poll, re-initialising the controller between attempts
+pub const RECOVERY_ATTEMPTS: u8 = 3;
+
+/// Where the poller publishes readings
+///
+/// Each poll publishes the reading, or `None` if the controller could not be read.
+pub type Readings<M, R, const N: usize> = Watch<M, Option<R>, N>;
+
+/// A controller driver that can be polled by [`run()`]
+pub trait Controller {
+    /// Calibrated reading type
+    type Reading: Clone;
+    /// Error returned by reads
+    type Error;
+
+    /// Do a read, re-initialising the controller and retrying if it fails
+    fn read_with_recovery(
+        &mut self,
+        max_attempts: u8,
+    ) -> impl Future<Output = Result<Self::Reading, Self::Error>>;
+}
+
+impl<I2C, E, DELAY, MODE> Controller for Classic<I2C, DELAY, Ready, MODE>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+    DELAY: DelayNs,
+    MODE: Resolution,
+{
+    type Reading = ClassicReadingCalibrated;
+    type Error = Error<E>;
+
+    async fn read_with_recovery(&mut self, max_attempts: u8) -> Result<Self::Reading, Error<E>> {
+        Classic::read_with_recovery(self, max_attempts).await
+    }
+}
+
+impl<I2C, E, DELAY> Controller for Nunchuk<I2C, DELAY, Ready>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+    DELAY: DelayNs,
+{
+    type Reading = NunchukReadingCalibrated;
+    type Error = Error<E>;
+
+    async fn read_with_recovery(&mut self, max_attempts: u8) -> Result<Self::Reading, Error<E>> {
+        Nunchuk::read_with_recovery(self, max_attempts).await
+    }
+}
+
+/// Poll `controller` forever, publishing each result to `readings`
+///
+/// `delay` waits `period_us` microseconds between the end of one read and the start of the
+/// next. Failed reads are retried with the re-init recovery path (up to [`RECOVERY_ATTEMPTS`]
+/// attempts), and if that doesn't help `None` is published and polling carries on.
+pub async fn run<C, D, M, const N: usize>(
+    mut controller: C,
+    mut delay: D,
+    period_us: u32,
+    readings: &Readings<M, C::Reading, N>,
+) -> !
+where
+    C: Controller,
+    D: DelayNs,
+    M: RawMutex,
+{
+    let sender = readings.sender();
+    loop {
+        let reading = controller.read_with_recovery(RECOVERY_ATTEMPTS).await;
+        sender.send(reading.ok());
+        delay.delay_us(period_us).await;
+    }
+}
+
+/// A consumer of the readings published by [`run()`]
+pub struct ReadingReceiver<'a, M: RawMutex, R: Clone, const N: usize>(
+    Receiver<'a, M, Option<R>, N>,
+);
+
+impl<'a, M: RawMutex, R: Clone, const N: usize> ReadingReceiver<'a, M, R, N> {
+    /// Start receiving readings, or `None` if `readings` already has `N` receivers
+    pub fn new(readings: &'a Readings<M, R, N>) -> Option<Self> {
+        readings.receiver().map(Self)
+    }
+
+    /// Wait for the next poll, returning `None` if the controller could not be read
+    pub async fn changed(&mut self) -> Option<R> {
+        self.0.changed().await
+    }
+
+    /// Wait for the next successful reading
+    pub async fn next_reading(&mut self) -> R {
+        loop {
+            if let Some(reading) = self.0.changed().await {
+                return reading;
+            }
+        }
+    }
+
+    /// The latest published reading, without waiting
+    ///
+    /// Returns `None` if nothing has been published yet or the last poll failed.
+    pub fn latest(&mut self) -> Option<R> {
+        self.0.try_get().flatten()
+    }
+}
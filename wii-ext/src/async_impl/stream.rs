@@ -0,0 +1,52 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{ready, Context, Poll};
+use futures_core::Stream;
+use pin_project_lite::pin_project;
+
+pin_project! {
+    /// A stream that produces each item by running `f` on the state left by the previous one
+    pub(crate) struct Unfold<T, F, Fut> {
+        state: Option<T>,
+        f: F,
+        #[pin]
+        next: Option<Fut>,
+    }
+}
+
+/// Build a stream from a seed state and a function producing the next item and state
+///
+/// Only one item is in progress at a time, and the state is held by the stream between items.
+pub(crate) fn unfold<T, F, Fut, Item>(state: T, f: F) -> Unfold<T, F, Fut>
+where
+    F: FnMut(T) -> Fut,
+    Fut: Future<Output = (Item, T)>,
+{
+    Unfold {
+        state: Some(state),
+        f,
+        next: None,
+    }
+}
+
+impl<T, F, Fut, Item> Stream for Unfold<T, F, Fut>
+where
+    F: FnMut(T) -> Fut,
+    Fut: Future<Output = (Item, T)>,
+{
+    type Item = Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Item>> {
+        let mut this = self.project();
+        if let Some(state) = this.state.take() {
+            this.next.set(Some((this.f)(state)));
+        }
+        let Some(next) = this.next.as_mut().as_pin_mut() else {
+            return Poll::Ready(None);
+        };
+        let (item, state) = ready!(next.poll(cx));
+        this.next.set(None);
+        *this.state = Some(state);
+        Poll::Ready(Some(item))
+    }
+}
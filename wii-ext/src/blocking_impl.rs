@@ -2,5 +2,6 @@
 pub mod classic;
 /// Blocking i2c interface code
 pub mod interface;
+pub mod manager;
 /// Blocking nunchuk controller driver
 pub mod nunchuk;
@@ -1,25 +1,59 @@
 use crate::core::{
-    ControllerIdReport, ControllerType, ExtHdReport, ExtReport, EXT_I2C_ADDR,
-    INTERMESSAGE_DELAY_MICROSEC_U32 as INTERMESSAGE_DELAY_MICROSEC,
+    id_has_signature, ControllerIdReport, ControllerType, ExtAltReport, ExtHdReport, ExtReport,
+    RetryPolicy, Timing, EXT_I2C_ADDR, ID_READ_ATTEMPTS, REGISTER_SPACE_SIZE,
 };
+use crate::logging;
+use crate::Error;
+use core::mem::size_of;
 use embedded_hal::i2c::{I2c, SevenBitAddress};
 
+/// Run a bus transaction, retrying bus errors as allowed by the interface's retry policy
+macro_rules! with_retries {
+    ($self:ident, $transaction:expr) => {{
+        let mut attempt = 1;
+        loop {
+            match $transaction {
+                Err(Error::I2C(e)) if $self.retry.max_attempts > 1 => {
+                    if attempt >= $self.retry.max_attempts {
+                        break Err(Error::RetriesExhausted(e));
+                    }
+                    attempt += 1;
+                    $self.delay.delay_us($self.retry.backoff_us);
+                }
+                result => break result,
+            }
+        }
+    }};
+}
+
 #[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
 #[derive(Debug, Default)]
 pub struct Interface<I2C, Delay> {
     i2cdev: I2C,
     delay: Delay,
+    fast_polling: bool,
+    /// Where the read cursor is expected to be, if known
+    cursor: Option<u8>,
+    combined_reads: bool,
+    /// The seek for the next report read will be sent with the read itself
+    deferred_seek: bool,
+    /// A sample has been started with `start_sample()` and not collected yet
+    sample_started: bool,
+    timing: Timing,
+    retry: RetryPolicy,
+    /// Wait this long for the next sample instead of the usual read delay
+    sample_delay_override: Option<u32>,
+    /// Read back every register write
+    verify_writes: bool,
+    /// A register has read back wrong since the interface was created
+    write_verify_failed: bool,
+    /// Reads of the controller ID before accepting one without a valid signature
+    id_attempts: u8,
 }
 
-#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
-#[derive(Debug)]
 /// Errors in this crate
-pub enum BlockingImplError<E> {
-    /// I²C bus communication error
-    I2C(E),
-    /// Invalid input data provided
-    InvalidInputData,
-}
+#[deprecated(note = "use `wii_ext::Error` instead")]
+pub type BlockingImplError<E> = Error<E>;
 
 impl<I2C, E, Delay> Interface<I2C, Delay>
 where
@@ -27,7 +61,74 @@ where
     Delay: embedded_hal::delay::DelayNs,
 {
     pub fn new(i2cdev: I2C, delay: Delay) -> Interface<I2C, Delay> {
-        Interface { i2cdev, delay }
+        Interface {
+            i2cdev,
+            delay,
+            fast_polling: false,
+            cursor: None,
+            combined_reads: false,
+            deferred_seek: false,
+            sample_started: false,
+            timing: Timing::default(),
+            retry: RetryPolicy::NONE,
+            sample_delay_override: None,
+            verify_writes: false,
+            write_verify_failed: false,
+            id_attempts: ID_READ_ATTEMPTS,
+        }
+    }
+
+    /// Set the delays used for init and reads
+    pub(crate) fn set_timing(&mut self, timing: Timing) {
+        self.timing = timing;
+    }
+
+    pub(crate) fn set_id_attempts(&mut self, attempts: u8) {
+        self.id_attempts = attempts;
+    }
+
+    /// Retry failed bus transactions according to `retry`
+    pub(crate) fn set_retry_policy(&mut self, retry: RetryPolicy) {
+        self.retry = retry;
+    }
+
+    /// Wait `micros` for the next sample only, or the usual read delay if `None`
+    pub(crate) fn override_sample_delay(&mut self, micros: Option<u32>) {
+        self.sample_delay_override = micros;
+    }
+
+    /// Skip the seek before a report read when the cursor is known to be at 0
+    ///
+    /// Genuine controllers wrap the read cursor back to 0 after a report read, but
+    /// clones differ, so this is off by default.
+    pub(crate) fn set_fast_polling(&mut self, fast_polling: bool) {
+        self.fast_polling = fast_polling;
+    }
+
+    /// Send the seek and report read as a single write_read transaction
+    ///
+    /// This replaces the delay between seek and read with a repeated start, which
+    /// not every controller tolerates, so this is off by default.
+    pub(crate) fn set_combined_reads(&mut self, combined_reads: bool) {
+        self.combined_reads = combined_reads;
+    }
+
+    /// Read back each register after writing it, failing with `Error::WriteVerifyFailed`
+    ///
+    /// Some clones silently ignore writes. This doubles the bus traffic of configuration,
+    /// so it is off by default.
+    pub(crate) fn set_verify_writes(&mut self, verify_writes: bool) {
+        self.verify_writes = verify_writes;
+    }
+
+    /// Whether register writes are read back as they are made
+    pub(crate) fn verifies_writes(&self) -> bool {
+        self.verify_writes
+    }
+
+    /// Whether a write has ever failed verification
+    pub(crate) fn write_verify_failed(&self) -> bool {
+        self.write_verify_failed
     }
 
     /// Recover data members
@@ -35,49 +136,96 @@ where
         (self.i2cdev, self.delay)
     }
 
+    /// Access delay stored in interface
+    pub(crate) fn delay_us(&mut self, micros: u32) {
+        self.delay.delay_us(micros)
+    }
+
     /// Send the init sequence to the Wii extension controller
-    pub(super) fn init(&mut self) -> Result<(), BlockingImplError<E>> {
+    pub(crate) fn init(&mut self) -> Result<(), Error<E>> {
         // Extension controllers by default will use encrypted communication, as that is what the Wii does.
         // We can disable this encryption by writing some magic values
         // This is described at https://wiibrew.org/wiki/Wiimote/Extension_Controllers#The_New_Way
 
         // Reset to base register first - this should recover a controller in a weird state.
         // Use longer delays here than normal reads - the system seems more unreliable performing these commands
-        self.delay.delay_us(INTERMESSAGE_DELAY_MICROSEC * 2);
+        self.delay.delay_us(self.timing.settle_us);
         self.set_read_register_address(0)?;
-        self.delay.delay_us(INTERMESSAGE_DELAY_MICROSEC * 2);
+        self.delay.delay_us(self.timing.init_step_us);
         self.set_register(0xF0, 0x55)?;
-        self.delay.delay_us(INTERMESSAGE_DELAY_MICROSEC * 2);
+        self.delay.delay_us(self.timing.init_step_us);
         self.set_register(0xFB, 0x00)?;
-        self.delay.delay_us(INTERMESSAGE_DELAY_MICROSEC * 2);
+        self.delay.delay_us(self.timing.settle_us);
         Ok(())
     }
 
-    pub(super) fn read_id(&mut self) -> Result<ControllerIdReport, BlockingImplError<E>> {
+    /// Read the controller type ID register from the extension controller
+    ///
+    /// Bus errors and IDs without the extension controller signature are read again, up to
+    /// `id_attempts` reads in all. The last result is returned if none are valid.
+    pub(crate) fn read_id(&mut self) -> Result<ControllerIdReport, Error<E>> {
+        let mut attempt = 1;
+        loop {
+            let result = self.read_id_once();
+            let retry = match &result {
+                Ok(id) => !id_has_signature(id),
+                Err(Error::I2C(_) | Error::RetriesExhausted(_)) => true,
+                Err(_) => false,
+            };
+            if !retry || attempt >= self.id_attempts {
+                return result;
+            }
+            attempt += 1;
+            self.delay.delay_us(self.timing.read_delay_us);
+        }
+    }
+
+    /// Read the controller type ID register once
+    fn read_id_once(&mut self) -> Result<ControllerIdReport, Error<E>> {
         self.set_read_register_address(0xfa)?;
         let i2c_id = self.read_report()?;
         Ok(i2c_id)
     }
 
     /// Determine the controller type based on the type ID of the extension controller
-    pub(super) fn identify_controller(
-        &mut self,
-    ) -> Result<Option<ControllerType>, BlockingImplError<E>> {
+    pub(crate) fn identify_controller(&mut self) -> Result<Option<ControllerType>, Error<E>> {
         let i2c_id = self.read_id()?;
         Ok(crate::core::identify_controller(i2c_id))
     }
 
     /// tell the extension controller to prepare a sample by setting the read cursor to 0
-    pub(super) fn start_sample(&mut self) -> Result<(), BlockingImplError<E>> {
-        self.set_read_register_address(0x00)?;
+    ///
+    /// In fast polling mode this is skipped when the cursor is already known to be at 0.
+    /// With combined reads, the seek is sent together with the next report read instead.
+    pub(crate) fn start_sample(&mut self) -> Result<(), Error<E>> {
+        if self.fast_polling && self.cursor == Some(0) {
+            // The cursor is already in place
+        } else if self.combined_reads {
+            self.deferred_seek = true;
+        } else {
+            self.set_read_register_address(0x00)?;
+        }
+        self.sample_started = true;
         Ok(())
     }
 
-    /// tell the extension controller to prepare a sample by setting the read cursor to 0
-    pub(super) fn start_sample_and_wait(&mut self) -> Result<(), BlockingImplError<E>> {
-        self.set_read_register_address(0x00)?;
-        self.delay.delay_us(INTERMESSAGE_DELAY_MICROSEC);
-        Ok(())
+    /// Check whether a sample has been started since the last call, clearing the flag
+    pub(crate) fn take_sample_started(&mut self) -> bool {
+        core::mem::take(&mut self.sample_started)
+    }
+
+    /// Wait long enough for the extension controller to prepare a sample
+    ///
+    /// Not needed when the seek will be combined with the read.
+    pub(crate) fn wait_for_sample(&mut self) {
+        let delay = self
+            .sample_delay_override
+            .take()
+            .unwrap_or(self.timing.read_delay_us);
+        if self.deferred_seek {
+            return;
+        }
+        self.delay.delay_us(delay);
     }
 
     /// Set the cursor position for the next i2c read
@@ -86,53 +234,194 @@ where
     /// increments the register read postion on each read operation, and also on
     /// every write operation.
     /// This should be called before a read operation to ensure you get the correct data
-    pub(super) fn set_read_register_address(
-        &mut self,
-        byte0: u8,
-    ) -> Result<(), BlockingImplError<E>> {
-        self.i2cdev
-            .write(EXT_I2C_ADDR as u8, &[byte0])
-            .map_err(BlockingImplError::I2C)
-            .and(Ok(()))
+    pub(crate) fn set_read_register_address(&mut self, byte0: u8) -> Result<(), Error<E>> {
+        self.cursor = None;
+        self.deferred_seek = false;
+        with_retries!(
+            self,
+            logging::write::<I2C>(self.i2cdev.write(EXT_I2C_ADDR as u8, &[byte0]), &[byte0])
+        )?;
+        self.cursor = Some(byte0);
+        Ok(())
     }
 
     /// Set a single register at target address
-    pub(super) fn set_register(&mut self, addr: u8, byte1: u8) -> Result<(), BlockingImplError<E>> {
-        self.i2cdev
-            .write(EXT_I2C_ADDR as u8, &[addr, byte1])
-            .map_err(BlockingImplError::I2C)
-            .and(Ok(()))
+    ///
+    /// A seek deferred by `start_sample()` is kept, so it is still sent with the next read.
+    pub(crate) fn set_register(&mut self, addr: u8, byte1: u8) -> Result<(), Error<E>> {
+        self.cursor = None;
+        with_retries!(
+            self,
+            logging::write::<I2C>(
+                self.i2cdev.write(EXT_I2C_ADDR as u8, &[addr, byte1]),
+                &[addr, byte1],
+            )
+        )?;
+        if self.verify_writes {
+            self.verify_register(addr, byte1)?;
+        }
+        Ok(())
+    }
+
+    /// Read back a register that has just been written
+    ///
+    /// A whole report is read, as not every controller answers shorter reads. The cursor is
+    /// left unknown, so the next report read seeks back to 0.
+    fn verify_register(&mut self, addr: u8, expected: u8) -> Result<(), Error<E>> {
+        let mut buf = ExtReport::default();
+        self.delay.delay_us(self.timing.read_delay_us);
+        self.set_read_register_address(addr)?;
+        self.delay.delay_us(self.timing.read_delay_us);
+        self.read_at_cursor(&mut buf)?;
+        self.cursor = None;
+        match buf[0] {
+            got if got == expected => Ok(()),
+            got => {
+                self.write_verify_failed = true;
+                logging::anomaly("register read back wrong", &[addr, expected, got]);
+                Err(Error::WriteVerifyFailed {
+                    addr,
+                    expected,
+                    got,
+                })
+            }
+        }
     }
 
     /// Read the button/axis data from the classic controller
-    pub(super) fn read_report(&mut self) -> Result<ExtReport, BlockingImplError<E>> {
+    pub(crate) fn read_report(&mut self) -> Result<ExtReport, Error<E>> {
         let mut buffer: ExtReport = ExtReport::default();
-        self.i2cdev
-            .read(EXT_I2C_ADDR as u8, &mut buffer)
-            .map_err(BlockingImplError::I2C)
-            .and(Ok(buffer))
+        self.read_report_into(&mut buffer)?;
+        Ok(buffer)
+    }
+
+    /// Read the button/axis data from the controller into `buf`
+    ///
+    /// The report is read at the cursor, so call `start_sample()` and `wait_for_sample()` first.
+    pub(crate) fn read_report_into(&mut self, buf: &mut ExtReport) -> Result<(), Error<E>> {
+        self.read_into(buf)
+    }
+
+    /// Start a sample, wait for it, then read it into `buf`
+    ///
+    /// This is a whole read on its own, so it also ends any read started with `start_sample()`.
+    pub(crate) fn read_sample_into(&mut self, buf: &mut [u8]) -> Result<(), Error<E>> {
+        self.start_sample()?;
+        self.wait_for_sample();
+        self.take_sample_started();
+        self.read_into(buf)
+    }
+
+    /// Whether the read cursor is known to be at the start of the report
+    pub(crate) fn cursor_at_report(&self) -> bool {
+        self.cursor == Some(0)
+    }
+
+    /// Read a report at the cursor, without seeking first
+    pub(crate) fn read_at_cursor(&mut self, buf: &mut [u8]) -> Result<(), Error<E>> {
+        self.deferred_seek = false;
+        self.read_into(buf)
     }
 
-    pub(super) fn enable_hires(&mut self) -> Result<(), BlockingImplError<E>> {
-        self.delay.delay_us(INTERMESSAGE_DELAY_MICROSEC * 2);
-        self.set_register(0xFE, 0x03)?;
-        self.delay.delay_us(INTERMESSAGE_DELAY_MICROSEC * 2);
+    /// Read a report at the cursor, sending the seek first if it was deferred
+    fn read_into(&mut self, buf: &mut [u8]) -> Result<(), Error<E>> {
+        let cursor = if core::mem::take(&mut self.deferred_seek) {
+            self.cursor = None;
+            with_retries!(
+                self,
+                logging::write_read::<I2C>(
+                    self.i2cdev.write_read(EXT_I2C_ADDR as u8, &[0], buf),
+                    &[0],
+                    buf,
+                )
+            )?;
+            Some(0)
+        } else {
+            let cursor = self.cursor.take();
+            with_retries!(
+                self,
+                logging::read::<I2C>(self.i2cdev.read(EXT_I2C_ADDR as u8, buf), buf)
+            )?;
+            cursor
+        };
+        // A report read from 0 leaves the cursor back at 0
+        self.cursor = cursor.filter(|&c| c == 0);
         Ok(())
     }
 
-    pub(super) fn disable_hires(&mut self) -> Result<(), BlockingImplError<E>> {
-        self.delay.delay_us(INTERMESSAGE_DELAY_MICROSEC * 2);
-        self.set_register(0xFE, 0x01)?;
-        self.delay.delay_us(INTERMESSAGE_DELAY_MICROSEC * 2);
+    /// Read a block of registers starting at `addr`, then reset the read cursor to 0
+    pub(crate) fn read_registers(&mut self, addr: u8, buf: &mut [u8]) -> Result<(), Error<E>> {
+        self.delay.delay_us(self.timing.read_delay_us);
+        self.set_read_register_address(addr)?;
+        self.delay.delay_us(self.timing.read_delay_us);
+        self.cursor = None;
+        with_retries!(
+            self,
+            logging::read::<I2C>(self.i2cdev.read(EXT_I2C_ADDR as u8, buf), buf)
+        )?;
+        self.delay.delay_us(self.timing.read_delay_us);
+        self.set_read_register_address(0)
+    }
+
+    /// Read sequential registers starting at `start` in report-sized chunks, then reset the
+    /// read cursor to 0
+    ///
+    /// Reads stop at the end of the register space (0xFF), returns the number of bytes read
+    pub(crate) fn dump_registers(&mut self, start: u8, buf: &mut [u8]) -> Result<usize, Error<E>> {
+        let len = buf.len().min(REGISTER_SPACE_SIZE - start as usize);
+        self.delay.delay_us(self.timing.read_delay_us);
+        self.set_read_register_address(start)?;
+        self.cursor = None;
+        for chunk in buf[..len].chunks_mut(size_of::<ExtReport>()) {
+            self.delay.delay_us(self.timing.read_delay_us);
+            with_retries!(
+                self,
+                logging::read::<I2C>(self.i2cdev.read(EXT_I2C_ADDR as u8, chunk), chunk)
+            )?;
+        }
+        self.delay.delay_us(self.timing.read_delay_us);
+        self.set_read_register_address(0)?;
+        Ok(len)
+    }
+
+    /// Write a single register, then reset the read cursor to 0
+    pub(crate) fn write_register(&mut self, addr: u8, value: u8) -> Result<(), Error<E>> {
+        self.delay.delay_us(self.timing.read_delay_us);
+        self.set_register(addr, value)?;
+        self.delay.delay_us(self.timing.read_delay_us);
+        self.set_read_register_address(0)
+    }
+
+    pub(crate) fn enable_hires(&mut self) -> Result<(), Error<E>> {
+        self.set_report_mode(0x03)
+    }
+
+    pub(crate) fn disable_hires(&mut self) -> Result<(), Error<E>> {
+        self.set_report_mode(0x01)
+    }
+
+    /// Write the report format register, 0xFE, giving the controller time to switch
+    pub(crate) fn set_report_mode(&mut self, value: u8) -> Result<(), Error<E>> {
+        self.delay.delay_us(self.timing.init_step_us);
+        self.set_register(0xFE, value)?;
+        self.delay.delay_us(self.timing.settle_us);
         Ok(())
     }
 
     /// Read a high-resolution version of the button/axis data from the classic controller
-    pub(super) fn read_hd_report(&mut self) -> Result<ExtHdReport, BlockingImplError<E>> {
+    pub(crate) fn read_hd_report(&mut self) -> Result<ExtHdReport, Error<E>> {
         let mut buffer: ExtHdReport = ExtHdReport::default();
-        self.i2cdev
-            .read(EXT_I2C_ADDR as u8, &mut buffer)
-            .map_err(BlockingImplError::I2C)
-            .and(Ok(buffer))
+        self.read_hd_report_into(&mut buffer)?;
+        Ok(buffer)
+    }
+
+    /// Read a high-resolution version of the button/axis data into `buf`
+    pub(crate) fn read_hd_report_into(&mut self, buf: &mut ExtHdReport) -> Result<(), Error<E>> {
+        self.read_into(buf)
+    }
+
+    /// Read a data format 0x02 version of the button/axis data into `buf`
+    pub(crate) fn read_alt_report_into(&mut self, buf: &mut ExtAltReport) -> Result<(), Error<E>> {
+        self.read_into(buf)
     }
 }
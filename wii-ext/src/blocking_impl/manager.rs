@@ -0,0 +1,210 @@
+//! Poll several controllers from one place
+//!
+//! A [`Manager`] holds up to `N` blocking drivers, of either type, and reads them in turn.
+//! Each slot keeps its latest reading and whether it is connected. A controller that fails to
+//! read is marked disconnected and re-initialised on later polls, without holding up the
+//! other slots.
+//!
+//! The drivers can be on separate buses, or share one through a mux (see `wii_ext::mux`),
+//! as long as they have the same bus error type:
+//!
+//! ```ignore
+//! let mut player1 = Classic::new(bus1, &mut delay)?;
+//! let mut player2 = Nunchuk::new(bus2, &mut delay)?;
+//! let mut manager = Manager::new([Some(&mut player1 as _), Some(&mut player2 as _)]);
+//! loop {
+//!     manager.poll_all();
+//!     if let Some(Reading::Classic(input)) = manager.latest(0) {
+//!         // ...
+//!     }
+//! }
+//! ```
+
+use crate::blocking_impl::classic::Classic;
+use crate::blocking_impl::nunchuk::Nunchuk;
+use crate::core::classic::{ClassicReadingCalibrated, Resolution};
+use crate::core::nunchuk::NunchukReadingCalibrated;
+use crate::core::{Ready, Stats};
+use crate::Error;
+use embedded_hal::delay::DelayNs;
+use embedded_hal::i2c::{I2c, SevenBitAddress};
+
+/// A reading from any kind of controller
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reading {
+    Classic(ClassicReadingCalibrated),
+    Nunchuk(NunchukReadingCalibrated),
+}
+
+/// A controller driver that can be held by a [`Manager`]
+///
+/// `E` is the bus error type.
+pub trait Controller<E> {
+    /// Do a read, and return values relative to calibration
+    fn read(&mut self) -> Result<Reading, Error<E>>;
+    /// Send the init sequence again, keeping the calibration
+    fn reinit(&mut self) -> Result<(), Error<E>>;
+    /// The driver's read statistics
+    fn stats(&self) -> Stats;
+}
+
+impl<I2C, E, DELAY, MODE> Controller<E> for Classic<I2C, DELAY, Ready, MODE>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+    DELAY: DelayNs,
+    MODE: Resolution,
+{
+    fn read(&mut self) -> Result<Reading, Error<E>> {
+        Classic::read(self).map(Reading::Classic)
+    }
+
+    fn reinit(&mut self) -> Result<(), Error<E>> {
+        Classic::reinit(self)
+    }
+
+    fn stats(&self) -> Stats {
+        Classic::stats(self)
+    }
+}
+
+impl<I2C, E, DELAY> Controller<E> for Nunchuk<I2C, DELAY, Ready>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+    DELAY: DelayNs,
+{
+    fn read(&mut self) -> Result<Reading, Error<E>> {
+        Nunchuk::read(self).map(Reading::Nunchuk)
+    }
+
+    fn reinit(&mut self) -> Result<(), Error<E>> {
+        Nunchuk::reinit(self)
+    }
+
+    fn stats(&self) -> Stats {
+        Nunchuk::stats(self)
+    }
+}
+
+/// Whether a slot's controller is responding
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SlotState {
+    /// There is no controller in this slot
+    Empty,
+    /// The last read succeeded
+    Connected,
+    /// The last read failed. The controller is re-initialised each time the slot is polled
+    Disconnected,
+}
+
+struct Slot<'a, E> {
+    controller: Option<&'a mut dyn Controller<E>>,
+    state: SlotState,
+    latest: Option<Reading>,
+    disconnects: u32,
+}
+
+impl<E> Slot<'_, E> {
+    fn poll(&mut self) {
+        let Some(controller) = self.controller.as_mut() else {
+            return;
+        };
+        if self.state == SlotState::Disconnected && controller.reinit().is_err() {
+            return;
+        }
+        match controller.read() {
+            Ok(reading) => {
+                self.latest = Some(reading);
+                self.state = SlotState::Connected;
+            }
+            Err(_) => {
+                self.latest = None;
+                if self.state == SlotState::Connected {
+                    self.disconnects = self.disconnects.saturating_add(1);
+                }
+                self.state = SlotState::Disconnected;
+            }
+        }
+    }
+}
+
+/// Polls up to `N` controllers in turn, tracking each one's latest reading
+///
+/// Slots are numbered from 0. Out of range slots behave as empty ones.
+pub struct Manager<'a, E, const N: usize> {
+    slots: [Slot<'a, E>; N],
+    next: usize,
+}
+
+impl<'a, E, const N: usize> Manager<'a, E, N> {
+    /// Manage these controllers. Slots are given in order, `None` for an empty slot
+    ///
+    /// Controllers start out as connected, as the drivers are created initialised.
+    pub fn new(controllers: [Option<&'a mut dyn Controller<E>>; N]) -> Self {
+        Self {
+            slots: controllers.map(|controller| Slot {
+                state: match controller {
+                    Some(_) => SlotState::Connected,
+                    None => SlotState::Empty,
+                },
+                controller,
+                latest: None,
+                disconnects: 0,
+            }),
+            next: 0,
+        }
+    }
+
+    /// Poll the next occupied slot, returning its number, or `None` if all slots are empty
+    pub fn poll(&mut self) -> Option<usize> {
+        for _ in 0..N {
+            let slot = self.next;
+            self.next = (self.next + 1) % N;
+            if self.slots[slot].controller.is_some() {
+                self.slots[slot].poll();
+                return Some(slot);
+            }
+        }
+        None
+    }
+
+    /// Poll every occupied slot once
+    pub fn poll_all(&mut self) {
+        for slot in self.slots.iter_mut() {
+            slot.poll();
+        }
+    }
+
+    /// The reading from the last successful poll of `slot`
+    ///
+    /// `None` if the slot is empty, hasn't been polled yet, or is disconnected.
+    pub fn latest(&self, slot: usize) -> Option<Reading> {
+        self.slots.get(slot).and_then(|slot| slot.latest)
+    }
+
+    /// Whether the controller in `slot` is responding
+    pub fn state(&self, slot: usize) -> SlotState {
+        self.slots
+            .get(slot)
+            .map_or(SlotState::Empty, |slot| slot.state)
+    }
+
+    /// The read statistics of the controller in `slot`
+    pub fn stats(&self, slot: usize) -> Option<Stats> {
+        self.slots
+            .get(slot)
+            .and_then(|slot| slot.controller.as_ref())
+            .map(|controller| controller.stats())
+    }
+
+    /// How many times the controller in `slot` has gone from connected to disconnected
+    pub fn disconnects(&self, slot: usize) -> u32 {
+        self.slots.get(slot).map_or(0, |slot| slot.disconnects)
+    }
+
+    /// Give the controllers back
+    pub fn into_inner(self) -> [Option<&'a mut dyn Controller<E>>; N] {
+        self.slots.map(|slot| slot.controller)
+    }
+}
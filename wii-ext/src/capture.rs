@@ -0,0 +1,175 @@
+//! Capture reports from a real controller, as source for `test_data`
+//!
+//! Adding support for a new controller starts with byte-level captures of its reports. A
+//! capture session prompts for each state in turn ("idle", "press A", "left stick up", ...),
+//! reads a raw report in each, and [`Captures::to_source()`] formats them the same way as
+//! [`crate::test_data`], ready to paste in:
+//!
+//! ```ignore
+//! let mut classic = Classic::new_uninit(i2c, delay).init_no_calibration()?;
+//! let captures = capture(&mut classic, CLASSIC_STEPS, true, |step, hires| {
+//!     println!("{}{}, then press enter", if hires { "[hi-res] " } else { "" }, step.prompt);
+//!     std::io::stdin().read_line(&mut String::new()).unwrap();
+//! })?;
+//! print!("{}", captures.to_source("Wii Classic controller", "CLASSIC"));
+//! ```
+//!
+//! The classic driver is used for every controller type, as only raw reports are read.
+
+use crate::blocking_impl::classic::Classic;
+use crate::core::{ExtHdReport, ExtReport};
+use crate::Error;
+use embedded_hal::delay::DelayNs;
+use embedded_hal::i2c::{I2c, SevenBitAddress};
+
+/// One controller state to capture
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CaptureStep {
+    /// Constant name suffix, eg: `BTN_A` for `CLASSIC_BTN_A`
+    pub name: &'static str,
+    /// What the user should do before the report is read
+    pub prompt: &'static str,
+}
+
+impl CaptureStep {
+    pub const fn new(name: &'static str, prompt: &'static str) -> Self {
+        Self { name, prompt }
+    }
+}
+
+/// States captured for classic controllers, in the order of the existing captures
+pub const CLASSIC_STEPS: &[CaptureStep] = &[
+    CaptureStep::new("IDLE", "Leave the controller at rest"),
+    CaptureStep::new("BTN_B", "Hold B"),
+    CaptureStep::new("BTN_A", "Hold A"),
+    CaptureStep::new("BTN_X", "Hold X"),
+    CaptureStep::new("BTN_Y", "Hold Y"),
+    CaptureStep::new("BTN_L", "Fully pull L"),
+    CaptureStep::new("BTN_R", "Fully pull R"),
+    CaptureStep::new("BTN_ZL", "Hold ZL"),
+    CaptureStep::new("BTN_ZR", "Hold ZR"),
+    CaptureStep::new("PAD_U", "Hold up on the d-pad"),
+    CaptureStep::new("PAD_D", "Hold down on the d-pad"),
+    CaptureStep::new("PAD_L", "Hold left on the d-pad"),
+    CaptureStep::new("PAD_R", "Hold right on the d-pad"),
+    CaptureStep::new("BTN_MINUS", "Hold minus (select)"),
+    CaptureStep::new("BTN_PLUS", "Hold plus (start)"),
+    CaptureStep::new("BTN_HOME", "Hold home"),
+    CaptureStep::new("LJOY_U", "Hold the left stick up"),
+    CaptureStep::new("LJOY_D", "Hold the left stick down"),
+    CaptureStep::new("LJOY_L", "Hold the left stick left"),
+    CaptureStep::new("LJOY_R", "Hold the left stick right"),
+    CaptureStep::new("RJOY_U", "Hold the right stick up"),
+    CaptureStep::new("RJOY_D", "Hold the right stick down"),
+    CaptureStep::new("RJOY_L", "Hold the right stick left"),
+    CaptureStep::new("RJOY_R", "Hold the right stick right"),
+    CaptureStep::new("LTRIG", "Pull L halfway, without clicking it"),
+    CaptureStep::new("RTRIG", "Pull R halfway, without clicking it"),
+    CaptureStep::new("LJOY_UR", "Hold the left stick up and right"),
+    CaptureStep::new("LJOY_DR", "Hold the left stick down and right"),
+    CaptureStep::new("LJOY_DL", "Hold the left stick down and left"),
+    CaptureStep::new("LJOY_UL", "Hold the left stick up and left"),
+    CaptureStep::new("RJOY_UR", "Hold the right stick up and right"),
+    CaptureStep::new("RJOY_DR", "Hold the right stick down and right"),
+    CaptureStep::new("RJOY_DL", "Hold the right stick down and left"),
+    CaptureStep::new("RJOY_UL", "Hold the right stick up and left"),
+];
+
+/// States captured for nunchuks, in the order of the existing captures
+pub const NUNCHUK_STEPS: &[CaptureStep] = &[
+    CaptureStep::new("IDLE", "Leave the nunchuk at rest"),
+    CaptureStep::new("JOY_U", "Hold the stick up"),
+    CaptureStep::new("JOY_D", "Hold the stick down"),
+    CaptureStep::new("JOY_L", "Hold the stick left"),
+    CaptureStep::new("JOY_R", "Hold the stick right"),
+    CaptureStep::new("BTN_C", "Hold C"),
+    CaptureStep::new("BTN_Z", "Hold Z"),
+];
+
+/// Reports captured from one controller
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Captures {
+    /// The controller's ID, from the registers at 0xFA
+    pub id: ExtReport,
+    /// Value of the report mode register before hi-resolution mode was set, if it was
+    pub hires_default: Option<u8>,
+    /// Standard reports, by constant name suffix
+    pub reports: Vec<(&'static str, ExtReport)>,
+    /// Hi-resolution reports, by constant name suffix
+    pub hd_reports: Vec<(&'static str, ExtHdReport)>,
+}
+
+impl Captures {
+    /// Format the captures as `test_data` constants named `{prefix}_...`, under a `// {label}`
+    /// comment
+    ///
+    /// Hi-resolution reports follow the standard ones after a blank line, named
+    /// `{prefix}_HD_...`.
+    pub fn to_source(&self, label: &str, prefix: &str) -> String {
+        fn array(bytes: &[u8]) -> String {
+            let values: Vec<String> = bytes.iter().map(|b| b.to_string()).collect();
+            format!("[{}]", values.join(", "))
+        }
+        let mut out = format!("// {label}\n");
+        out += &format!("pub const {prefix}_ID: [u8; 6] = {};\n", array(&self.id));
+        if let Some(value) = self.hires_default {
+            out += &format!("pub const {prefix}_HIRES_DEFAULT: u8 = {value};\n");
+        }
+        for (name, report) in &self.reports {
+            out += &format!("pub const {prefix}_{name}: [u8; 6] = {};\n", array(report));
+        }
+        if !self.hd_reports.is_empty() {
+            out.push('\n');
+        }
+        for (name, report) in &self.hd_reports {
+            out += &format!(
+                "pub const {prefix}_HD_{name}: [u8; 8] = {};\n",
+                array(report)
+            );
+        }
+        out
+    }
+}
+
+/// Walk the user through `steps`, reading a raw report in each state
+///
+/// `prompt` is called before each read, and should return once the user has the controller in
+/// the state described, eg: after they press enter. Its second argument is true for the
+/// hi-resolution pass.
+///
+/// The ID is read first, then a standard report for each step. With `hires`, the report mode
+/// register is saved, hi-resolution mode is set and every step is prompted for again, then
+/// the register is restored. The driver should be initialised but doesn't need calibrating.
+pub fn capture<I2C, E, DELAY>(
+    classic: &mut Classic<I2C, DELAY>,
+    steps: &[CaptureStep],
+    hires: bool,
+    mut prompt: impl FnMut(&CaptureStep, bool),
+) -> Result<Captures, Error<E>>
+where
+    I2C: I2c<SevenBitAddress, Error = E>,
+    DELAY: DelayNs,
+{
+    let mut captures = Captures::default();
+    classic.read_registers(0xFA, &mut captures.id)?;
+    for step in steps {
+        prompt(step, false);
+        let mut buf = ExtHdReport::default();
+        classic.read_raw_into(&mut buf)?;
+        let [report @ .., _, _] = buf;
+        captures.reports.push((step.name, report));
+    }
+    if hires {
+        let default = classic.read_register(0xFE)?;
+        captures.hires_default = Some(default);
+        classic.write_register(0xFE, 0x03)?;
+        for step in steps {
+            prompt(step, true);
+            let mut report = ExtHdReport::default();
+            classic.read_raw_into(&mut report)?;
+            captures.hd_reports.push((step.name, report));
+        }
+        classic.write_register(0xFE, default)?;
+    }
+    Ok(captures)
+}
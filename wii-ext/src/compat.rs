@@ -0,0 +1,97 @@
+//! Module paths from before the `blocking_impl`/`async_impl` split
+//!
+//! These let code written against the old layout keep building while it is moved over to the
+//! new paths. Everything in here is deprecated and only points at the new types.
+#![allow(deprecated)]
+
+/// Classic controller data types
+#[deprecated(note = "use `wii_ext::core::classic` instead")]
+pub mod classic {
+    #[deprecated(note = "use `wii_ext::core::classic::ClassicReading` instead")]
+    pub type ClassicReading = crate::core::classic::ClassicReading;
+    #[deprecated(note = "use `wii_ext::core::classic::ClassicReadingCalibrated` instead")]
+    pub type ClassicReadingCalibrated = crate::core::classic::ClassicReadingCalibrated;
+    #[deprecated(note = "use `wii_ext::core::classic::CalibrationData` instead")]
+    pub type CalibrationData = crate::core::classic::CalibrationData;
+}
+
+/// Blocking classic controller driver
+///
+/// The driver now owns its delay. Passing `&mut delay` to `Classic::new` still works, since a
+/// mutable reference to a delay is a delay too, but the driver keeps the borrow until it is
+/// destroyed and the methods no longer take a delay argument.
+#[deprecated(note = "use `wii_ext::blocking_impl::classic` instead")]
+pub mod classic_sync {
+    #[deprecated(note = "use `wii_ext::blocking_impl::classic::Classic` instead")]
+    pub type Classic<I2C, DELAY> = crate::blocking_impl::classic::Classic<I2C, DELAY>;
+    #[deprecated(note = "use `wii_ext::Error` instead")]
+    pub type ClassicError<E> = crate::blocking_impl::classic::ClassicError<E>;
+}
+
+/// Async classic controller driver
+///
+/// Delays use the `embedded_hal_async::delay::DelayNs` passed to `new()`, so this works on any
+/// executor, not just embassy.
+#[deprecated(note = "use `wii_ext::async_impl::classic` instead")]
+pub mod classic_async {
+    use crate::async_impl::classic;
+    use embedded_hal_async::i2c::{I2c, SevenBitAddress};
+
+    #[deprecated(note = "use `wii_ext::Error` instead")]
+    pub type AsyncImplError = crate::async_impl::interface::AsyncImplError;
+
+    /// The async classic driver, created without talking to the controller
+    ///
+    /// The new driver's `new()` initialises the controller. This wrapper keeps the old
+    /// behaviour, where `init()` has to be called separately, and derefs to the new driver
+    /// for everything else.
+    #[deprecated(note = "use `wii_ext::async_impl::classic::Classic` instead")]
+    pub struct Classic<I2C, DELAY>(classic::Classic<I2C, DELAY>);
+
+    impl<I2C, E, DELAY> Classic<I2C, DELAY>
+    where
+        I2C: I2c<SevenBitAddress, Error = E>,
+        DELAY: embedded_hal_async::delay::DelayNs,
+    {
+        /// Create a driver without initialising the controller
+        pub fn new(i2cdev: I2C, delay: DELAY) -> Self {
+            Self(classic::Classic::new_unchecked(i2cdev, delay))
+        }
+
+        /// Unwrap the new driver
+        pub fn into_inner(self) -> classic::Classic<I2C, DELAY> {
+            self.0
+        }
+    }
+
+    impl<I2C, DELAY> core::ops::Deref for Classic<I2C, DELAY> {
+        type Target = classic::Classic<I2C, DELAY>;
+
+        fn deref(&self) -> &Self::Target {
+            &self.0
+        }
+    }
+
+    impl<I2C, DELAY> core::ops::DerefMut for Classic<I2C, DELAY> {
+        fn deref_mut(&mut self) -> &mut Self::Target {
+            &mut self.0
+        }
+    }
+}
+
+/// Blocking nunchuk driver and data types
+///
+/// As with `classic_sync`, the driver now owns its delay.
+#[deprecated(note = "use `wii_ext::blocking_impl::nunchuk` and `wii_ext::core::nunchuk` instead")]
+pub mod nunchuk {
+    #[deprecated(note = "use `wii_ext::blocking_impl::nunchuk::Nunchuk` instead")]
+    pub type Nunchuk<I2C, DELAY> = crate::blocking_impl::nunchuk::Nunchuk<I2C, DELAY>;
+    #[deprecated(note = "use `wii_ext::Error` instead")]
+    pub type NunchukError<E> = crate::blocking_impl::nunchuk::NunchukError<E>;
+    #[deprecated(note = "use `wii_ext::core::nunchuk::NunchukReading` instead")]
+    pub type NunchukReading = crate::core::nunchuk::NunchukReading;
+    #[deprecated(note = "use `wii_ext::core::nunchuk::NunchukReadingCalibrated` instead")]
+    pub type NunchukReadingCalibrated = crate::core::nunchuk::NunchukReadingCalibrated;
+    #[deprecated(note = "use `wii_ext::core::nunchuk::CalibrationData` instead")]
+    pub type CalibrationData = crate::core::nunchuk::CalibrationData;
+}
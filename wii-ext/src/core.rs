@@ -1,44 +1,791 @@
+/// Add an accessor and a `with_*` method for each field of a reading
+///
+/// Code that builds readings field by field and reads them through the accessors keeps
+/// compiling when fields are added.
+macro_rules! with_fields {
+    ($reading:ident { $($field:ident: $ty:ty => $with:ident),* $(,)? }) => {
+        impl $reading {
+            $(
+                #[doc = concat!("Get `", stringify!($field), "`")]
+                pub const fn $field(&self) -> $ty {
+                    self.$field
+                }
+
+                #[doc = concat!("Set `", stringify!($field), "`, for building readings field by field")]
+                #[must_use]
+                pub const fn $with(mut self, $field: $ty) -> Self {
+                    self.$field = $field;
+                    self
+                }
+            )*
+        }
+    };
+}
+
 pub mod classic;
+pub mod clock;
+pub(crate) mod driver;
+pub mod events;
+pub mod keymap;
+pub mod motion_plus;
 pub mod nunchuk;
+#[cfg(feature = "fixed")]
+pub mod q15;
+pub mod quirk_db;
+pub mod raw;
+pub mod scale;
+pub mod standard_gamepad;
+pub mod tilt;
+pub mod wire;
 
 /// Standard input report
 pub type ExtReport = [u8; 6];
 /// HD input report
 pub type ExtHdReport = [u8; 8];
+/// Classic controller input report in data format 0x02
+pub type ExtAltReport = [u8; 9];
 /// Controller ID report
 pub type ControllerIdReport = [u8; 6];
 
 #[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ControllerType {
     Nunchuk,
     Classic,
     ClassicPro,
 }
 
+/// Driver state: created, but the controller has not been initialised yet
+///
+/// A driver in this state can only be initialised (or destroyed), so it can't be read by mistake:
+///
+/// ```compile_fail,E0599
+/// # use embedded_hal_mock::eh1::{delay::NoopDelay, i2c::Mock};
+/// use wii_ext::blocking_impl::classic::Classic;
+///
+/// let mut classic = Classic::new_uninit(Mock::new(&[]), NoopDelay::new());
+/// let input = classic.read();
+/// ```
+///
+/// The same goes for the async drivers:
+///
+/// ```compile_fail,E0599
+/// # use embedded_hal_mock::eh1::{delay::NoopDelay, i2c::Mock};
+/// use wii_ext::async_impl::nunchuk::Nunchuk;
+///
+/// # async fn run() {
+/// let mut nunchuk = Nunchuk::new_uninit(Mock::new(&[]), NoopDelay::new());
+/// let input = nunchuk.read().await;
+/// # }
+/// ```
+///
+/// `init()` returns a driver in the [`Ready`] state, which can be read from.
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Uninit;
+
+/// Driver state: the controller has been initialised and can be read from
+///
+/// This is the default state, and the state returned by the drivers' `new()` and builders.
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Ready;
+
 /// All Wii extension controllers use i2c address 52
 pub const EXT_I2C_ADDR: u16 = 0x52;
 
+/// Number of addressable registers on a Wii extension controller
+pub const REGISTER_SPACE_SIZE: usize = 0x100;
+
 /// There needs to be some time between i2c messages or the
 /// wii ext device will abort the i2c transaction
 /// 200 microseconds works in my tests - need to test with more devices
 pub const INTERMESSAGE_DELAY_MICROSEC_U32: u32 = 200;
 
+/// Reads of the controller ID made before giving up on a valid one, by default
+///
+/// Some clones NACK or return garbage for the first ID read after init.
+pub const ID_READ_ATTEMPTS: u8 = 3;
+
+/// Delays used when talking to a controller
+///
+/// The blocking and async drivers share the same conservative default, which waits at least as
+/// long as either of them used to at every step, and works with every controller tested so far.
+/// [`Timing::fast()`] brings a genuine controller up in a few milliseconds instead of a few
+/// hundred, but may be too quick for some clones.
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timing {
+    /// Delay between the steps of the init sequence, and before a report mode change
+    pub init_step_us: u32,
+    /// Delay before the init sequence, and after it or a report mode change, so the
+    /// controller can settle before it is read
+    pub settle_us: u32,
+    /// Delay between setting the read cursor and reading
+    pub read_delay_us: u32,
+}
+
+impl Timing {
+    /// The default timing
+    pub const CONSERVATIVE: Timing = Timing {
+        init_step_us: INTERMESSAGE_DELAY_MICROSEC_U32 * 2,
+        settle_us: 100_000,
+        read_delay_us: INTERMESSAGE_DELAY_MICROSEC_U32,
+    };
+
+    /// Shorter init delays, enough for genuine controllers
+    pub const fn fast() -> Timing {
+        Timing {
+            init_step_us: INTERMESSAGE_DELAY_MICROSEC_U32,
+            settle_us: 1_000,
+            read_delay_us: INTERMESSAGE_DELAY_MICROSEC_U32,
+        }
+    }
+}
+
+impl Default for Timing {
+    fn default() -> Self {
+        Self::CONSERVATIVE
+    }
+}
+
+/// How many times each bus transaction is tried before giving up
+///
+/// A transaction that fails with a bus error is retried after `backoff_us`, up to
+/// `max_attempts` attempts in total. If they all fail, the error is
+/// [`Error::RetriesExhausted`](crate::Error::RetriesExhausted), holding the last bus error.
+/// The default makes a single attempt, and reports a failure as `Error::I2C`.
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    pub max_attempts: u8,
+    /// Delay before each retry
+    pub backoff_us: u32,
+}
+
+impl RetryPolicy {
+    /// Don't retry failed transactions
+    pub const NONE: RetryPolicy = RetryPolicy {
+        max_attempts: 1,
+        backoff_us: 0,
+    };
+
+    pub const fn new(max_attempts: u8, backoff_us: u32) -> Self {
+        Self {
+            max_attempts,
+            backoff_us,
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::NONE
+    }
+}
+
+/// Identify a controller from the 6 byte ID read from register 0xFA
+///
+/// Bytes 2 and 3 are always `A4 20` for an extension controller. The rest identify the type:
+///
+/// | ID                  | Controller |
+/// |---------------------|------------|
+/// | `00 00 A4 20 00 00` | Nunchuk |
+/// | `00 00 A4 20 01 01` | Wii classic controller |
+/// | `00 00 A4 20 02 01` | Wii classic controller, in data format 0x02 |
+/// | `00 00 A4 20 03 01` | Wii classic controller, in high resolution mode |
+/// | `01 00 A4 20 01 01` | Classic controller pro, and compatibles: NES and SNES classic controllers, third party controllers |
+/// | `01 00 A4 20 02 01` | Classic controller pro, in data format 0x02 |
+/// | `01 00 A4 20 03 01` | Classic controller pro, in high resolution mode |
+///
+/// Byte 4 of a classic controller's ID follows its report format register, so it changes
+/// with the [`ReportMode`](classic::ReportMode) and says nothing about which modes it supports.
 pub fn identify_controller(id: ControllerIdReport) -> Option<ControllerType> {
-    if id[2] != 0xA4 || id[3] != 0x20 {
+    if !id_has_signature(&id) {
         // Not an extension controller
-        None
-    } else if id[0] == 0 && id[1] == 0 && id[4] == 0 && id[5] == 0 {
-        // It's a nunchuck
-        Some(ControllerType::Nunchuk)
-    } else if id[0] == 0 && id[1] == 0 && id[4] == 3 && id[5] == 1 {
-        // It's a wii classic controller
-        Some(ControllerType::Classic)
-    } else if id[0] == 1 && id[1] == 0 && id[4] == 1 && id[5] == 1 {
-        // It's a wii classic pro (or compatible) controller
-        // This is most wii classic extension controllers (NES/SNES/Clones)
-        Some(ControllerType::ClassicPro)
+        return None;
+    }
+    match (id[0], id[1], id[4], id[5]) {
+        (0, 0, 0, 0) => Some(ControllerType::Nunchuk),
+        // The report format is 1 by default, 2 or 3 once changed
+        (0, 0, 1..=3, 1) => Some(ControllerType::Classic),
+        (1, 0, 1..=3, 1) => Some(ControllerType::ClassicPro),
+        _ => None,
+    }
+}
+
+/// Whether an ID has the `A4 20` that every extension controller's ID has
+///
+/// An ID without it (eg: all 0xFF) is a failed read, and is worth reading again.
+pub(crate) fn id_has_signature(id: &ControllerIdReport) -> bool {
+    id[2] == 0xA4 && id[3] == 0x20
+}
+
+/// Report an axis deflection as centered if it is within `deadzone` of center
+pub(crate) fn apply_deadzone(value: i8, deadzone: u8) -> i8 {
+    if value.unsigned_abs() <= deadzone {
+        0
     } else {
-        None
+        value
+    }
+}
+
+/// What calibration does with an axis that ends up outside the `i8` range
+///
+/// A reading far from its calibration point usually means the calibration was taken with
+/// a stick or trigger held, or that the report was corrupted.
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum RangePolicy {
+    /// Clamp the value to -128..=127
+    #[default]
+    Saturate,
+    /// Clamp values up to `margin` past the `i8` range, and fail the read with
+    /// `Error::OutOfRange` for values further out
+    Strict { margin: u8 },
+}
+
+impl RangePolicy {
+    /// Fit a calibrated value into an `i8`, or `None` if this policy rejects it
+    pub(crate) fn fit(self, value: i16) -> Option<i8> {
+        if let Self::Strict { margin } = self {
+            let margin = margin as i16;
+            if value < i8::MIN as i16 - margin || value > i8::MAX as i16 + margin {
+                return None;
+            }
+        }
+        Some(value.clamp(i8::MIN as i16, i8::MAX as i16) as i8)
+    }
+}
+
+/// Response curve applied to a stick axis in calibrated reads
+///
+/// Curves are symmetric about center and map full deflection (±127) to itself. Apart from
+/// `Linear`, they limit the axis to -127..=127.
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum AxisCurve {
+    /// Report the deflection unchanged
+    #[default]
+    Linear,
+    /// Soften the response near center, like the expo setting on an RC transmitter
+    ///
+    /// The strength is a percentage from 0 (linear) to 100 (cubic), higher values are
+    /// treated as 100.
+    Expo(u8),
+    /// Interpolate between 17 evenly spaced points, from center to full deflection
+    ///
+    /// The first point is the output at center and the last at full deflection, so a table
+    /// ending in 127 keeps the endpoints. Negative deflections are mirrored. Outputs are
+    /// limited to 127.
+    Table([u8; 17]),
+}
+
+impl AxisCurve {
+    /// Apply the curve to a calibrated axis value
+    pub const fn apply(&self, value: i8) -> i8 {
+        const FULL: i32 = i8::MAX as i32;
+        let magnitude = match value.unsigned_abs() {
+            128 => FULL,
+            m => m as i32,
+        };
+        let curved = match self {
+            AxisCurve::Linear => return value,
+            AxisCurve::Expo(strength) => {
+                let k = if *strength > 100 {
+                    100
+                } else {
+                    *strength as i32
+                };
+                // (1 - k) * x + k * x^3, with x scaled to 0..=1
+                (magnitude * (100 - k) * FULL * FULL + magnitude * magnitude * magnitude * k)
+                    / (100 * FULL * FULL)
+            }
+            AxisCurve::Table(points) => {
+                let position = magnitude * 16;
+                let i = (position / FULL) as usize;
+                let fraction = position % FULL;
+                if i == 16 {
+                    points[16] as i32
+                } else {
+                    (points[i] as i32 * (FULL - fraction) + points[i + 1] as i32 * fraction) / FULL
+                }
+            }
+        };
+        let curved = if curved > FULL { FULL } else { curved } as i8;
+        if value < 0 {
+            -curved
+        } else {
+            curved
+        }
+    }
+}
+
+/// Curves for the stick axes, in [`events::Axis`] order
+pub(crate) type StickCurves = [AxisCurve; 4];
+
+/// Where a stick axis' curve is kept in [`StickCurves`], or `None` for trigger axes
+pub(crate) fn stick_curve_index(axis: events::Axis) -> Option<usize> {
+    match axis {
+        events::Axis::LeftX => Some(0),
+        events::Axis::LeftY => Some(1),
+        events::Axis::RightX => Some(2),
+        events::Axis::RightY => Some(3),
+        events::Axis::TriggerLeft | events::Axis::TriggerRight => None,
+    }
+}
+
+/// Position of a calibrated stick, relative to center
+///
+/// Up and right are positive, as in the calibrated readings' flat fields.
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StickPosition {
+    pub x: i8,
+    pub y: i8,
+}
+
+impl StickPosition {
+    pub const CENTER: StickPosition = StickPosition { x: 0, y: 0 };
+
+    pub const fn new(x: i8, y: i8) -> Self {
+        Self { x, y }
+    }
+
+    /// Squared distance from center
+    pub const fn magnitude_sq(&self) -> u32 {
+        let (x, y) = (self.x as i32, self.y as i32);
+        (x * x + y * y) as u32
+    }
+
+    /// Distance from center, rounded down
+    pub const fn magnitude(&self) -> u8 {
+        isqrt(self.magnitude_sq()) as u8
+    }
+
+    /// Whether both axes are within `slop` of center
+    pub const fn is_centered(&self, slop: u8) -> bool {
+        self.x.unsigned_abs() <= slop && self.y.unsigned_abs() <= slop
+    }
+
+    /// Scale the position toward center so it is no further than `radius` away
+    ///
+    /// The direction is kept, to within rounding. Positions already inside the circle are
+    /// unchanged. Sticks reach further on the diagonals than along the axes, so a radius of
+    /// 127 makes their range round.
+    pub const fn clamped_to(&self, radius: u8) -> Self {
+        let magnitude_sq = self.magnitude_sq();
+        let radius = radius as i32;
+        if magnitude_sq <= (radius * radius) as u32 {
+            return *self;
+        }
+        // Round the magnitude up, so the result is never outside the circle
+        let mut magnitude = isqrt(magnitude_sq) as i32;
+        if (magnitude * magnitude) as u32 != magnitude_sq {
+            magnitude += 1;
+        }
+        Self {
+            x: (self.x as i32 * radius / magnitude) as i8,
+            y: (self.y as i32 * radius / magnitude) as i8,
+        }
+    }
+
+    /// Apply `f` to both axes
+    pub fn map(self, mut f: impl FnMut(i8) -> i8) -> Self {
+        Self {
+            x: f(self.x),
+            y: f(self.y),
+        }
+    }
+}
+
+impl core::ops::Neg for StickPosition {
+    type Output = Self;
+
+    /// Mirror the position through center. -128 becomes 127, as it has no positive counterpart
+    fn neg(self) -> Self {
+        self.map(i8::saturating_neg)
+    }
+}
+
+impl From<(i8, i8)> for StickPosition {
+    fn from((x, y): (i8, i8)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl From<StickPosition> for (i8, i8) {
+    fn from(position: StickPosition) -> Self {
+        (position.x, position.y)
+    }
+}
+
+/// Integer square root, rounded down
+pub(crate) const fn isqrt(value: u32) -> u16 {
+    let mut remainder = value;
+    let mut root = 0u32;
+    let mut bit = 1u32 << 30;
+    while bit > value {
+        bit >>= 2;
+    }
+    while bit != 0 {
+        if remainder >= root + bit {
+            remainder -= root + bit;
+            root = (root >> 1) + bit;
+        } else {
+            root >>= 1;
+        }
+        bit >>= 2;
+    }
+    root as u16
+}
+
+/// What `read_throttled()` does when it is called before the minimum poll interval has passed
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EarlyRead {
+    /// Wait out the rest of the interval with the driver's delay, then read
+    Wait,
+    /// Return the previous reading again, without touching the bus
+    Cached,
+}
+
+/// A minimum time between reads of the controller
+///
+/// Some controllers misbehave when they are read too often. The drivers have no clock of their
+/// own, so the interval is kept by `read_throttled()`, which takes the time from a
+/// [`Clock`](clock::Clock).
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PollInterval {
+    pub min_us: u32,
+    pub early_read: EarlyRead,
+}
+
+/// What a read should do to keep to the poll interval
+pub(crate) enum Throttle<R> {
+    Read,
+    /// Wait this many microseconds, then read
+    Wait(u32),
+    /// Return this reading instead
+    Cached(R),
+}
+
+/// Enforces a [`PollInterval`], remembering when the last reading was taken
+#[derive(Debug, Default, Clone)]
+pub(crate) struct PollLimiter<R> {
+    interval: Option<PollInterval>,
+    last: Option<(u64, R)>,
+}
+
+impl<R: Copy> PollLimiter<R> {
+    pub(crate) fn set_interval(&mut self, interval: Option<PollInterval>) {
+        self.interval = interval;
+        self.last = None;
+    }
+
+    /// What a read starting at `now` should do
+    pub(crate) fn throttle(&self, now: u64) -> Throttle<R> {
+        let (Some(interval), Some((taken_at, reading))) = (self.interval, self.last) else {
+            return Throttle::Read;
+        };
+        let elapsed = now.saturating_sub(taken_at);
+        let remaining = u64::from(interval.min_us).saturating_sub(elapsed);
+        match interval.early_read {
+            _ if remaining == 0 => Throttle::Read,
+            // Less than min_us, so it fits
+            EarlyRead::Wait => Throttle::Wait(remaining as u32),
+            EarlyRead::Cached => Throttle::Cached(reading),
+        }
+    }
+
+    /// Record a reading taken from the controller at `now`
+    pub(crate) fn record(&mut self, now: u64, reading: R) {
+        if self.interval.is_some() {
+            self.last = Some((now, reading));
+        }
+    }
+}
+
+#[cfg(feature = "defmt_print")]
+impl<R> defmt::Format for PollLimiter<R> {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(
+            f,
+            "PollLimiter {{ min_us: {} }}",
+            self.interval.map(|interval| interval.min_us)
+        )
+    }
+}
+
+/// Counters describing how reads from a controller have gone
+///
+/// Maintained by the drivers for field diagnostics. Counters saturate rather than wrap.
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    /// Reads that failed because of an i2c bus error (eg: the controller NAKed), including
+    /// reads that ran out of retries or timed out
+    pub i2c_errors: u32,
+    /// Reads that returned data that could not be decoded
+    pub invalid_data_errors: u32,
+    /// Times the init sequence was re-sent to recover from errors
+    pub reinits: u32,
+    /// Reads that returned valid data
+    pub successful_reads: u32,
+    /// Streamed reads that returned an invalid report, and were read again after a seek
+    pub resyncs: u32,
+}
+
+impl Stats {
+    pub(crate) fn count_i2c_error(&mut self) {
+        self.i2c_errors = self.i2c_errors.saturating_add(1);
+    }
+
+    pub(crate) fn count_invalid_data(&mut self) {
+        self.invalid_data_errors = self.invalid_data_errors.saturating_add(1);
+    }
+
+    pub(crate) fn count_reinit(&mut self) {
+        self.reinits = self.reinits.saturating_add(1);
+    }
+
+    pub(crate) fn count_success(&mut self) {
+        self.successful_reads = self.successful_reads.saturating_add(1);
+    }
+
+    pub(crate) fn count_resync(&mut self) {
+        self.resyncs = self.resyncs.saturating_add(1);
+    }
+}
+
+/// A check that strict mode makes on every report before decoding it
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportCheck {
+    /// Bits that are the same in every report from this kind of controller were wrong
+    ConstantBits,
+    /// Every byte was 0xFF
+    ///
+    /// All-zero reports fail with [`Error::NotResponding`](crate::Error::NotResponding), in
+    /// strict mode or not.
+    Blank,
+    /// The last two bytes of a high-resolution report repeated the first two
+    ///
+    /// Controllers that ignored the switch to high-resolution mode wrap around after 6 bytes.
+    Wraparound,
+}
+
+impl ReportCheck {
+    /// Short description, for logs and error messages
+    pub const fn description(&self) -> &'static str {
+        match self {
+            Self::ConstantBits => "constant bits are wrong",
+            Self::Blank => "report is blank",
+            Self::Wraparound => "hi-resolution report wraps around",
+        }
+    }
+
+    /// The first check that `data` fails, given whether its constant bits look right
+    pub(crate) fn first_failed(data: &[u8], sane: bool) -> Option<ReportCheck> {
+        if data.iter().all(|&b| b == 0xFF) {
+            Some(Self::Blank)
+        } else if data.len() == 8 && data[6..] == data[..2] {
+            Some(Self::Wraparound)
+        } else if !sane {
+            Some(Self::ConstantBits)
+        } else {
+            None
+        }
+    }
+}
+
+/// Which of the strict mode checks have failed since the driver was created
+///
+/// Kept by the drivers whether or not strict mode is on, but the report checks are only
+/// made in strict mode. Flags are never cleared.
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct CheckFailures {
+    /// A report failed [`ReportCheck::ConstantBits`]
+    pub constant_bits: bool,
+    /// A report failed [`ReportCheck::Blank`]
+    pub blank_report: bool,
+    /// A report failed [`ReportCheck::Wraparound`]
+    pub hires_wraparound: bool,
+    /// A register read back a different value than was written to it
+    pub register_write: bool,
+    /// Reads of the same sample kept returning different reports
+    pub inconsistent_reads: bool,
+    /// The controller returned the same non-idle report too many times in a row
+    pub stuck: bool,
+    /// The controller ID changed
+    pub controller_changed: bool,
+    /// The controller returned an all-zero report
+    pub not_responding: bool,
+}
+
+impl CheckFailures {
+    /// Whether any check has failed
+    pub fn any(&self) -> bool {
+        self.constant_bits
+            || self.blank_report
+            || self.hires_wraparound
+            || self.register_write
+            || self.inconsistent_reads
+            || self.stuck
+            || self.controller_changed
+            || self.not_responding
+    }
+
+    pub(crate) fn record_report(&mut self, check: ReportCheck) {
+        match check {
+            ReportCheck::ConstantBits => self.constant_bits = true,
+            ReportCheck::Blank => self.blank_report = true,
+            ReportCheck::Wraparound => self.hires_wraparound = true,
+        }
+    }
+}
+
+/// Statistics of each analogue axis over a calibration window
+///
+/// Each axis' calibration value is its mean with the lowest and highest samples dropped,
+/// so a single twitch doesn't shift the center. With fewer than 3 samples nothing is dropped.
+#[derive(Debug)]
+pub(crate) struct AxisWindow<const N: usize> {
+    sum: [u32; N],
+    min: [u16; N],
+    max: [u16; N],
+    count: u32,
+}
+
+impl<const N: usize> Default for AxisWindow<N> {
+    fn default() -> Self {
+        Self {
+            sum: [0; N],
+            min: [u16::MAX; N],
+            max: [0; N],
+            count: 0,
+        }
+    }
+}
+
+impl<const N: usize> AxisWindow<N> {
+    pub(crate) fn add(&mut self, axes: [u16; N]) {
+        for (i, axis) in axes.into_iter().enumerate() {
+            self.sum[i] += u32::from(axis);
+            self.min[i] = self.min[i].min(axis);
+            self.max[i] = self.max[i].max(axis);
+        }
+        self.count += 1;
+    }
+
+    /// The mean of each axis without its extremes, rounded to the nearest value
+    pub(crate) fn trimmed_mean(&self) -> [u16; N] {
+        core::array::from_fn(|i| {
+            let (sum, count) = if self.count >= 3 {
+                let extremes = u32::from(self.min[i]) + u32::from(self.max[i]);
+                (self.sum[i] - extremes, self.count - 2)
+            } else {
+                (self.sum[i], self.count.max(1))
+            };
+            ((sum + count / 2) / count) as u16
+        })
+    }
+
+    /// The difference between the highest and lowest sample of each axis
+    pub(crate) fn spread(&self) -> [u16; N] {
+        core::array::from_fn(|i| self.max[i].saturating_sub(self.min[i]))
+    }
+}
+
+/// Calibrated axes within this distance of center count as idle for stuck-report detection
+pub(crate) const STUCK_IDLE_TOLERANCE: u8 = 2;
+
+/// Identical reports in a row that strict mode treats as a stuck controller
+pub(crate) const STRICT_STUCK_FRAMES: u16 = 50;
+
+/// Detection of controllers that keep returning the same report
+///
+/// Some clone controllers wedge in a state where they still ACK on the bus but return
+/// the same bytes until re-initialised. Idle reports are exempt, as a controller that
+/// nobody is touching legitimately repeats itself.
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug)]
+pub(crate) struct StuckCheck {
+    threshold: u16,
+    last: [u8; 8],
+    last_len: usize,
+    identical_frames: u16,
+}
+
+impl StuckCheck {
+    pub(crate) fn new(threshold: u16) -> Self {
+        Self {
+            threshold,
+            last: [0; 8],
+            last_len: 0,
+            identical_frames: 0,
+        }
+    }
+
+    /// Record a raw report, returning true once `threshold` consecutive identical
+    /// non-idle reports have been seen
+    pub(crate) fn is_stuck(&mut self, data: &[u8], idle: bool) -> bool {
+        let len = data.len().min(self.last.len());
+        if idle || self.last[..self.last_len] != data[..len] {
+            self.last[..len].copy_from_slice(&data[..len]);
+            self.last_len = len;
+            self.identical_frames = 1;
+            return false;
+        }
+        self.identical_frames = self.identical_frames.saturating_add(1);
+        if self.identical_frames >= self.threshold {
+            self.identical_frames = 0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Rate-limited re-identification of the connected controller
+///
+/// The drivers use this to notice that a controller has been swapped for a different
+/// one (eg: a classic controller replaced by a nunchuk) without adding an ID read to every poll.
+/// An ID read is only requested when a report looks anomalous, and then at most once per
+/// `min_reads_between_checks` reads.
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug)]
+pub(crate) struct IdentityCheck {
+    expected: Option<ControllerType>,
+    min_reads_between_checks: u16,
+    reads_since_check: u16,
+}
+
+impl IdentityCheck {
+    pub(crate) fn new(expected: Option<ControllerType>, min_reads_between_checks: u16) -> Self {
+        Self {
+            expected,
+            min_reads_between_checks,
+            reads_since_check: 0,
+        }
+    }
+
+    /// The controller type that was identified when the check was enabled
+    pub(crate) fn expected(&self) -> Option<ControllerType> {
+        self.expected
+    }
+
+    /// Record a read, returning true if the controller ID should be read again now
+    pub(crate) fn should_check(&mut self, anomalous: bool) -> bool {
+        self.reads_since_check = self.reads_since_check.saturating_add(1);
+        if anomalous && self.reads_since_check > self.min_reads_between_checks {
+            self.reads_since_check = 0;
+            true
+        } else {
+            false
+        }
     }
 }
@@ -1,10 +1,29 @@
+use crate::core::driver::Model;
+use crate::core::events::{Axis, Button};
+use crate::core::{
+    apply_deadzone, AxisCurve, AxisWindow, ExtAltReport, ExtHdReport, ExtReport, RangePolicy,
+    StickCurves, StickPosition,
+};
+
 /// Data from a classic controller after it has been deserialized
 ///
 /// In low-res mode, axes with less than 8 bits of range will be
-/// scaled to approximate an 8 bit range.
+/// scaled to approximate an 8 bit range. Stick axes keep their nominal
+/// center at 128, so an untouched stick isn't biased before calibration.
 /// in hi-res mode, all axes arleady have 8 bits of range
+///
+/// Fields may be added in future releases. Struct literals still build, but code that starts
+/// from [`CENTERED`](Self::CENTERED), uses the `with_*` methods and reads fields through their
+/// accessors keeps compiling when they are:
+///
+/// ```
+/// # use wii_ext::core::classic::ClassicReading;
+/// let reading = ClassicReading::CENTERED
+///     .with_joystick_left_x(0xFF)
+///     .with_button_a(true);
+/// ```
 #[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub struct ClassicReading {
     pub joystick_left_x: u8,
     pub joystick_left_y: u8,
@@ -34,8 +53,11 @@ pub struct ClassicReading {
 /// Calibration is done by subtracting the resting values from the current
 /// values, which means that going lower on the axis will go negative.
 /// Due to this, we now store analog values as signed integers
+///
+/// As with [`ClassicReading`], readings can also be built with `default()` and the `with_*`
+/// methods.
 #[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub struct ClassicReadingCalibrated {
     pub joystick_left_x: i8,
     pub joystick_left_y: i8,
@@ -61,21 +83,36 @@ pub struct ClassicReadingCalibrated {
 }
 
 impl ClassicReadingCalibrated {
+    /// Subtract the calibration from a reading, clamping axes to the `i8` range
     pub fn new(r: ClassicReading, c: &CalibrationData) -> ClassicReadingCalibrated {
-        /// Just in case `data` minus `calibration data` is out of range, perform all operations
-        /// on i16 and clamp to i8 limits before returning
-        fn ext_u8_sub(a: u8, b: u8) -> i8 {
-            let res = (a as i16) - (b as i16);
-            res.clamp(i8::MIN as i16, i8::MAX as i16) as i8
-        }
-
-        ClassicReadingCalibrated {
-            joystick_left_x: ext_u8_sub(r.joystick_left_x, c.joystick_left_x),
-            joystick_left_y: ext_u8_sub(r.joystick_left_y, c.joystick_left_y),
-            joystick_right_x: ext_u8_sub(r.joystick_right_x, c.joystick_right_x),
-            joystick_right_y: ext_u8_sub(r.joystick_right_y, c.joystick_right_y),
-            trigger_left: ext_u8_sub(r.trigger_left, c.trigger_left),
-            trigger_right: ext_u8_sub(r.trigger_right, c.trigger_right),
+        match Self::with_policy(r, c, RangePolicy::Saturate) {
+            Ok(reading) => reading,
+            Err(_) => unreachable!("saturating never fails"),
+        }
+    }
+
+    /// Subtract the calibration from a reading, handling axes outside the `i8` range as
+    /// `policy` says
+    ///
+    /// Returns the first axis that `policy` rejected.
+    pub fn with_policy(
+        r: ClassicReading,
+        c: &CalibrationData,
+        policy: RangePolicy,
+    ) -> Result<ClassicReadingCalibrated, Axis> {
+        // `data` minus `calibration data` can be out of range, so subtract on i16 and let
+        // the policy fit the result to i8
+        let ext_u8_sub = |axis: Axis, a: u8, b: u8| -> Result<i8, Axis> {
+            policy.fit((a as i16) - (b as i16)).ok_or(axis)
+        };
+
+        Ok(ClassicReadingCalibrated {
+            joystick_left_x: ext_u8_sub(Axis::LeftX, r.joystick_left_x, c.joystick_left_x)?,
+            joystick_left_y: ext_u8_sub(Axis::LeftY, r.joystick_left_y, c.joystick_left_y)?,
+            joystick_right_x: ext_u8_sub(Axis::RightX, r.joystick_right_x, c.joystick_right_x)?,
+            joystick_right_y: ext_u8_sub(Axis::RightY, r.joystick_right_y, c.joystick_right_y)?,
+            trigger_left: ext_u8_sub(Axis::TriggerLeft, r.trigger_left, c.trigger_left)?,
+            trigger_right: ext_u8_sub(Axis::TriggerRight, r.trigger_right, c.trigger_right)?,
             dpad_up: r.dpad_up,
             dpad_down: r.dpad_down,
             dpad_left: r.dpad_left,
@@ -91,13 +128,371 @@ impl ClassicReadingCalibrated {
             button_minus: r.button_minus,
             button_plus: r.button_plus,
             button_home: r.button_home,
+        })
+    }
+}
+
+impl ClassicReadingCalibrated {
+    /// Report joystick deflections within `deadzone` of center as 0
+    ///
+    /// Triggers are left unchanged
+    pub fn apply_deadzone(&mut self, deadzone: u8) {
+        self.joystick_left_x = apply_deadzone(self.joystick_left_x, deadzone);
+        self.joystick_left_y = apply_deadzone(self.joystick_left_y, deadzone);
+        self.joystick_right_x = apply_deadzone(self.joystick_right_x, deadzone);
+        self.joystick_right_y = apply_deadzone(self.joystick_right_y, deadzone);
+    }
+
+    /// Apply a response curve to each joystick axis, in `LeftX`, `LeftY`, `RightX`, `RightY` order
+    ///
+    /// Triggers are left unchanged
+    pub fn apply_curves(&mut self, curves: &[AxisCurve; 4]) {
+        self.joystick_left_x = curves[0].apply(self.joystick_left_x);
+        self.joystick_left_y = curves[1].apply(self.joystick_left_y);
+        self.joystick_right_x = curves[2].apply(self.joystick_right_x);
+        self.joystick_right_y = curves[3].apply(self.joystick_right_y);
+    }
+
+    /// Position of the left stick
+    pub const fn left_stick(&self) -> StickPosition {
+        StickPosition::new(self.joystick_left_x, self.joystick_left_y)
+    }
+
+    /// Position of the right stick
+    pub const fn right_stick(&self) -> StickPosition {
+        StickPosition::new(self.joystick_right_x, self.joystick_right_y)
+    }
+}
+
+/// Corrections for controllers that don't follow the usual report format
+///
+/// Quirks are applied to each reading as soon as it is decoded, before calibration, so
+/// everything built on the driver sees corrected data. Buttons and axes are selected by
+/// bitmask: bit n stands for the [`Button`] or [`Axis`] with discriminant n, as in
+/// [`crate::core::wire`].
+///
+/// ```ignore
+/// // A pad with active-high ZL/ZR bits and an upside down right stick
+/// let quirks = Quirks::NONE
+///     .invert_button(Button::ZL)
+///     .invert_button(Button::ZR)
+///     .invert_axis(Axis::RightY);
+/// ```
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Quirks {
+    /// Buttons whose bit is set when released instead of when pressed
+    pub inverted_buttons: u16,
+    /// Axes that read from high to low instead of low to high
+    pub inverted_axes: u8,
+    /// Report the analogue triggers as 0, for controllers whose triggers are digital only
+    pub ignore_analog_triggers: bool,
+}
+
+impl Quirks {
+    /// No corrections
+    pub const NONE: Self = Self {
+        inverted_buttons: 0,
+        inverted_axes: 0,
+        ignore_analog_triggers: false,
+    };
+
+    /// Also invert this button's bit
+    ///
+    /// The classic controller has no C or Z button, so these leave the quirks unchanged.
+    pub const fn invert_button(mut self, button: Button) -> Self {
+        if let Some(bit) = button_bit(button) {
+            self.inverted_buttons |= bit;
+        }
+        self
+    }
+
+    /// Also invert this axis
+    pub const fn invert_axis(mut self, axis: Axis) -> Self {
+        self.inverted_axes |= 1 << axis as u8;
+        self
+    }
+
+    /// Correct a decoded reading
+    pub fn apply(&self, r: &mut ClassicReading) {
+        if self.inverted_buttons != 0 {
+            let buttons = [
+                (Button::A, &mut r.button_a),
+                (Button::B, &mut r.button_b),
+                (Button::X, &mut r.button_x),
+                (Button::Y, &mut r.button_y),
+                (Button::L, &mut r.button_trigger_l),
+                (Button::R, &mut r.button_trigger_r),
+                (Button::ZL, &mut r.button_zl),
+                (Button::ZR, &mut r.button_zr),
+                (Button::Minus, &mut r.button_minus),
+                (Button::Plus, &mut r.button_plus),
+                (Button::Home, &mut r.button_home),
+                (Button::DpadUp, &mut r.dpad_up),
+                (Button::DpadDown, &mut r.dpad_down),
+                (Button::DpadLeft, &mut r.dpad_left),
+                (Button::DpadRight, &mut r.dpad_right),
+            ];
+            for (button, pressed) in buttons {
+                *pressed ^= button_bit(button).is_some_and(|bit| self.inverted_buttons & bit != 0);
+            }
+        }
+        if self.inverted_axes != 0 {
+            let axes = [
+                (Axis::LeftX, &mut r.joystick_left_x),
+                (Axis::LeftY, &mut r.joystick_left_y),
+                (Axis::RightX, &mut r.joystick_right_x),
+                (Axis::RightY, &mut r.joystick_right_y),
+                (Axis::TriggerLeft, &mut r.trigger_left),
+                (Axis::TriggerRight, &mut r.trigger_right),
+            ];
+            for (axis, value) in axes {
+                if self.inverted_axes & (1 << axis as u8) != 0 {
+                    *value = u8::MAX - *value;
+                }
+            }
+        }
+        if self.ignore_analog_triggers {
+            r.trigger_left = 0;
+            r.trigger_right = 0;
+        }
+    }
+}
+
+/// The bit standing for a classic controller button in [`Quirks::inverted_buttons`]
+const fn button_bit(button: Button) -> Option<u16> {
+    match button {
+        Button::C | Button::Z => None,
+        _ => Some(1 << button as u16),
+    }
+}
+
+/// Where an analogue trigger counts as pulled, in the calibrated 0..=127 range
+///
+/// A trigger engages when it reaches `engage`, and releases when it drops below `release`.
+/// Keeping `release` under `engage` stops the state chattering when the trigger is held near
+/// the threshold.
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TriggerThresholds {
+    pub engage: i8,
+    pub release: i8,
+}
+
+impl TriggerThresholds {
+    /// Engage at half travel, release a little below that
+    pub const DEFAULT: Self = Self {
+        engage: 64,
+        release: 48,
+    };
+}
+
+impl Default for TriggerThresholds {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Derives a digital state for each trigger from its analogue value
+///
+/// Controllers without analogue triggers (eg: the Classic Pro and the NES/SNES Classic
+/// controllers) report a resting analogue value, so a trigger also counts as engaged while its
+/// click bit (`button_trigger_l`/`button_trigger_r`) is set.
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TriggerTracker {
+    pub left: TriggerThresholds,
+    pub right: TriggerThresholds,
+    left_pulled: bool,
+    right_pulled: bool,
+    left_clicked: bool,
+    right_clicked: bool,
+}
+
+impl TriggerTracker {
+    /// Track triggers with these thresholds, starting released
+    pub const fn new(left: TriggerThresholds, right: TriggerThresholds) -> Self {
+        Self {
+            left,
+            right,
+            left_pulled: false,
+            right_pulled: false,
+            left_clicked: false,
+            right_clicked: false,
+        }
+    }
+
+    /// Update the trigger states from a new reading
+    pub fn update(&mut self, r: &ClassicReadingCalibrated) {
+        fn pulled(was_pulled: bool, value: i8, thresholds: TriggerThresholds) -> bool {
+            if was_pulled {
+                value >= thresholds.release
+            } else {
+                value >= thresholds.engage
+            }
+        }
+        self.left_pulled = pulled(self.left_pulled, r.trigger_left, self.left);
+        self.right_pulled = pulled(self.right_pulled, r.trigger_right, self.right);
+        self.left_clicked = r.button_trigger_l;
+        self.right_clicked = r.button_trigger_r;
+    }
+
+    /// Whether the left trigger was engaged in the last reading
+    pub fn left_engaged(&self) -> bool {
+        self.left_pulled || self.left_clicked
+    }
+
+    /// Whether the right trigger was engaged in the last reading
+    pub fn right_engaged(&self) -> bool {
+        self.right_pulled || self.right_clicked
+    }
+}
+
+/// Raw value each analogue trigger reports when fully pulled
+///
+/// Calibrated triggers report their travel from rest, which reaches 127 partway through the
+/// pull on some controllers and never reaches it on others. With a full-press point, a
+/// trigger's travel is scaled so that a full pull reports 127. `None` leaves that trigger
+/// unscaled.
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TriggerRange {
+    pub left_full: Option<u8>,
+    pub right_full: Option<u8>,
+}
+
+impl TriggerRange {
+    /// No full-press points, triggers are unscaled
+    pub const NONE: Self = Self {
+        left_full: None,
+        right_full: None,
+    };
+
+    /// How far a trigger must move from rest for a full press to be recorded
+    ///
+    /// Controllers without analogue triggers stay close to rest, so they are left unscaled.
+    pub const MIN_TRAVEL: u8 = 32;
+
+    /// Record full-press points from an uncalibrated reading taken with the triggers pulled
+    ///
+    /// Only triggers that moved at least [`Self::MIN_TRAVEL`] from their rest in `c` are
+    /// recorded, the other keeps its existing point. This allows the triggers to be pulled
+    /// one at a time.
+    pub fn record(&mut self, r: &ClassicReading, c: &CalibrationData) {
+        fn full(raw: u8, rest: u8) -> Option<u8> {
+            (raw >= rest.saturating_add(TriggerRange::MIN_TRAVEL)).then_some(raw)
+        }
+        self.left_full = full(r.trigger_left, c.trigger_left).or(self.left_full);
+        self.right_full = full(r.trigger_right, c.trigger_right).or(self.right_full);
+    }
+
+    /// Scale the triggers of `calibrated`, which was calibrated from `raw` with `c`
+    pub fn apply(
+        &self,
+        raw: &ClassicReading,
+        c: &CalibrationData,
+        calibrated: &mut ClassicReadingCalibrated,
+    ) {
+        fn scale(value: &mut i8, raw: u8, rest: u8, full: Option<u8>) {
+            let Some(full) = full.filter(|&full| full > rest) else {
+                return;
+            };
+            let travel = (raw as i32 - rest as i32) * i8::MAX as i32 / (full - rest) as i32;
+            *value = travel.clamp(i8::MIN as i32, i8::MAX as i32) as i8;
+        }
+        scale(
+            &mut calibrated.trigger_left,
+            raw.trigger_left,
+            c.trigger_left,
+            self.left_full,
+        );
+        scale(
+            &mut calibrated.trigger_right,
+            raw.trigger_right,
+            c.trigger_right,
+            self.right_full,
+        );
+    }
+}
+
+/// How [`SocdCleaner`] resolves opposing d-pad directions held at the same time
+///
+/// Controllers with a button per direction, such as leverless fight sticks, can report left
+/// and right, or up and down, together.
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SocdPolicy {
+    /// Report both directions, as the controller does
+    #[default]
+    PassThrough,
+    /// Report neither direction
+    Neutral,
+    /// Report the direction pressed most recently. If both were pressed in the same reading,
+    /// report neither
+    LastInput,
+    /// Up wins over down, and left with right is neutral
+    UpPriority,
+}
+
+/// Applies a [`SocdPolicy`] to the d-pad of successive readings
+///
+/// [`SocdPolicy::LastInput`] remembers which directions were held in the previous reading, so
+/// feed every reading through the same cleaner, in order.
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SocdCleaner {
+    pub policy: SocdPolicy,
+    /// Directions reported by the controller in the previous reading: up, down, left, right
+    previous: [bool; 4],
+    /// Directions reported after cleaning the previous reading
+    resolved: [bool; 4],
+}
+
+impl SocdCleaner {
+    pub const fn new(policy: SocdPolicy) -> Self {
+        Self {
+            policy,
+            previous: [false; 4],
+            resolved: [false; 4],
+        }
+    }
+
+    /// Clean the d-pad of the next reading
+    pub fn apply(&mut self, r: &mut ClassicReadingCalibrated) {
+        let held = [r.dpad_up, r.dpad_down, r.dpad_left, r.dpad_right];
+        let (up, down) = self.resolve(held, 0, true);
+        let (left, right) = self.resolve(held, 2, false);
+        self.previous = held;
+        self.resolved = [up, down, left, right];
+        [r.dpad_up, r.dpad_down, r.dpad_left, r.dpad_right] = self.resolved;
+    }
+
+    /// Resolve the opposing pair starting at `i` in `held`
+    fn resolve(&self, held: [bool; 4], i: usize, vertical: bool) -> (bool, bool) {
+        let (a, b) = (held[i], held[i + 1]);
+        if !(a && b) {
+            return (a, b);
+        }
+        match self.policy {
+            SocdPolicy::PassThrough => (true, true),
+            SocdPolicy::Neutral => (false, false),
+            SocdPolicy::UpPriority if vertical => (true, false),
+            SocdPolicy::UpPriority => (false, false),
+            SocdPolicy::LastInput => match (self.previous[i], self.previous[i + 1]) {
+                // b was held first, so a is the newer press
+                (false, true) => (true, false),
+                (true, false) => (false, true),
+                // Neither is new, keep what was reported last time
+                (true, true) => (self.resolved[i], self.resolved[i + 1]),
+                (false, false) => (false, false),
+            },
         }
     }
 }
 
 /// Convert raw data as returned from controller via i2c into buttons and axis fields
 #[rustfmt::skip]
-pub(crate) fn decode_classic_report(data: &[u8]) -> ClassicReading {
+pub(crate) const fn decode_classic_report(data: &ExtReport) -> ClassicReading {
     // Classic mode:
     //  Bit	7	6	5	4	3	2	1	0
     // 	Byte
@@ -108,14 +503,14 @@ pub(crate) fn decode_classic_report(data: &[u8]) -> ClassicReading {
     // 	4	BDR	BDD	BLT	B-	BH	B+	BRT	1
     // 	5	BZL	BB	BY	BA	BX	BZR	BDL	BDU
     ClassicReading {
-        joystick_left_x:   ClassicReading::scale_6bit_8bit(data[0] & 0b0011_1111),
-        joystick_left_y:   ClassicReading::scale_6bit_8bit(data[1] & 0b0011_1111),
-        joystick_right_x:  ClassicReading::scale_5bit_8bit(
+        joystick_left_x:   ClassicReading::scale_6bit_8bit_centered(data[0] & 0b0011_1111),
+        joystick_left_y:   ClassicReading::scale_6bit_8bit_centered(data[1] & 0b0011_1111),
+        joystick_right_x:  ClassicReading::scale_5bit_8bit_centered(
             ((data[2] & 0b1000_0000) >> 7) |
             ((data[1] & 0b1100_0000) >> 5) |
             ((data[0] & 0b1100_0000) >> 3)
         ),
-        joystick_right_y:  ClassicReading::scale_5bit_8bit(data[2] & 0b0001_1111),
+        joystick_right_y:  ClassicReading::scale_5bit_8bit_centered(data[2] & 0b0001_1111),
         trigger_left:     ClassicReading::scale_5bit_8bit(
             ((data[2] & 0b0110_0000) >> 2) |
             ((data[3] & 0b1110_0000) >> 5)
@@ -139,9 +534,79 @@ pub(crate) fn decode_classic_report(data: &[u8]) -> ClassicReading {
     }
 }
 
+/// Scale a stick axis from `0..=max` to u8, mapping its nominal center exactly to 128
+///
+/// `max` is one less than a power of two, so the center is `(max + 1) / 2`. Each side of the
+/// center is scaled separately: 0 maps to 0, the center to 128 and `max` to 255. Values above
+/// `max` are clamped to it.
+const fn scale_centered(reading: u8, max: u8) -> u8 {
+    let max = max as u32;
+    let center = max.div_ceil(2);
+    let reading = if reading as u32 > max {
+        max
+    } else {
+        reading as u32
+    };
+    if reading < center {
+        ((reading * 128 + center / 2) / center) as u8
+    } else {
+        let above = max - center;
+        (128 + ((reading - center) * 127 + above / 2) / above) as u8
+    }
+}
+
+/// Reverse [`scale_centered()`], rounding to the nearest value in `0..=max`
+const fn unscale_centered(value: u8, max: u8) -> u8 {
+    let max = max as u32;
+    let center = max.div_ceil(2);
+    let value = value as u32;
+    if value < 128 {
+        ((value * center + 64) / 128) as u8
+    } else {
+        (center + ((value - 128) * (max - center) + 63) / 127) as u8
+    }
+}
+
+/// Pack 8 buttons into a byte, most significant bit first, active low
+const fn encode_buttons(buttons: [bool; 8]) -> u8 {
+    let mut byte = 0;
+    let mut i = 0;
+    while i < buttons.len() {
+        byte = byte << 1 | !buttons[i] as u8;
+        i += 1;
+    }
+    byte
+}
+
+/// Pack the button bits shared by both report formats, active low
+const fn encode_classic_buttons(r: &ClassicReading) -> [u8; 2] {
+    [
+        encode_buttons([
+            r.dpad_right,
+            r.dpad_down,
+            r.button_trigger_l,
+            r.button_minus,
+            r.button_home,
+            r.button_plus,
+            r.button_trigger_r,
+            false,
+        ]),
+        encode_buttons([
+            r.button_zl,
+            r.button_b,
+            r.button_y,
+            r.button_a,
+            r.button_x,
+            r.button_zr,
+            r.dpad_left,
+            r.dpad_up,
+        ]),
+    ]
+}
+
 /// Convert high-resolution raw data as returned from controller via i2c into buttons and axis fields
 #[rustfmt::skip]
-pub(crate) fn decode_classic_hd_report(data: &[u8]) -> ClassicReading {
+pub(crate) const fn decode_classic_hd_report(data: &ExtHdReport) -> ClassicReading {
     // High precision mode:
     // Bit    7    6    5    4    3    2    1    0
     // Byte
@@ -178,11 +643,132 @@ pub(crate) fn decode_classic_hd_report(data: &[u8]) -> ClassicReading {
     }
 }
 
+/// Convert data format 0x02 data as returned from controller via i2c into buttons and axis fields
+///
+/// The sticks have 10 bits of range in this format, only the top 8 are kept.
+#[rustfmt::skip]
+pub(crate) const fn decode_classic_alt_report(data: &ExtAltReport) -> ClassicReading {
+    // Data format 0x02:
+    // Bit    7    6    5    4    3    2    1    0
+    // Byte
+    // 0      LX<9:2>
+    // 1      RX<9:2>
+    // 2      LY<9:2>
+    // 3      RY<9:2>
+    // 4      LX<1:0>   RX<1:0>   LY<1:0>   RY<1:0>
+    // 5      LT<7:0>
+    // 6      RT<7:0>
+    // 7      BDR  BDD  BLT  B-   BH   B+   BRT  1
+    // 8      BZL  BB   BY   BA   BX   BZR  BDL  BDU
+    // The same as a high-resolution report, with the low stick bits inserted at byte 4
+    decode_classic_hd_report(&[
+        data[0], data[1], data[2], data[3], data[5], data[6], data[7], data[8],
+    ])
+}
+
+/// Check the bits that are constant in every classic controller report
+///
+/// Bit 0 of the first button byte is always set, in both standard and high-resolution mode.
+/// If it is clear, the report is corrupt or did not come from a classic controller.
+pub(crate) fn report_is_sane(data: &[u8]) -> bool {
+    match data.len() {
+        6 => data[4] & 0b0000_0001 != 0,
+        8 => data[6] & 0b0000_0001 != 0,
+        9 => data[7] & 0b0000_0001 != 0,
+        _ => false,
+    }
+}
+
+/// Whether no buttons are pressed and all analogue inputs are within `tolerance` of calibration
+pub(crate) fn report_is_idle(r: &ClassicReading, c: &CalibrationData, tolerance: u8) -> bool {
+    let centered = |value: u8, center: u8| value.abs_diff(center) <= tolerance;
+    let any_button = r.dpad_up
+        || r.dpad_down
+        || r.dpad_left
+        || r.dpad_right
+        || r.button_b
+        || r.button_a
+        || r.button_x
+        || r.button_y
+        || r.button_trigger_l
+        || r.button_trigger_r
+        || r.button_zl
+        || r.button_zr
+        || r.button_minus
+        || r.button_plus
+        || r.button_home;
+    !any_button
+        && centered(r.joystick_left_x, c.joystick_left_x)
+        && centered(r.joystick_left_y, c.joystick_left_y)
+        && centered(r.joystick_right_x, c.joystick_right_x)
+        && centered(r.joystick_right_y, c.joystick_right_y)
+        && centered(r.trigger_left, c.trigger_left)
+        && centered(r.trigger_right, c.trigger_right)
+}
+
+/// Running total of several readings, for averaging out jitter on the analogue axes
+///
+/// Axes are averaged, buttons are reported as pressed if they were pressed in any reading.
+#[derive(Debug, Default)]
+pub(crate) struct ClassicReadingSum {
+    axes: [u16; 6],
+    buttons: ClassicReading,
+    count: u16,
+}
+
+impl ClassicReadingSum {
+    pub(crate) fn add(&mut self, r: ClassicReading) {
+        let axes = [
+            r.joystick_left_x,
+            r.joystick_left_y,
+            r.joystick_right_x,
+            r.joystick_right_y,
+            r.trigger_left,
+            r.trigger_right,
+        ];
+        for (sum, axis) in self.axes.iter_mut().zip(axes) {
+            *sum += u16::from(axis);
+        }
+        let b = &mut self.buttons;
+        b.dpad_up |= r.dpad_up;
+        b.dpad_down |= r.dpad_down;
+        b.dpad_left |= r.dpad_left;
+        b.dpad_right |= r.dpad_right;
+        b.button_b |= r.button_b;
+        b.button_a |= r.button_a;
+        b.button_x |= r.button_x;
+        b.button_y |= r.button_y;
+        b.button_trigger_l |= r.button_trigger_l;
+        b.button_trigger_r |= r.button_trigger_r;
+        b.button_zl |= r.button_zl;
+        b.button_zr |= r.button_zr;
+        b.button_minus |= r.button_minus;
+        b.button_plus |= r.button_plus;
+        b.button_home |= r.button_home;
+        self.count += 1;
+    }
+
+    /// Get the average of the readings added so far, rounding to the nearest value
+    pub(crate) fn average(self) -> ClassicReading {
+        let count = self.count.max(1);
+        let avg = |sum: u16| ((sum + count / 2) / count) as u8;
+        ClassicReading {
+            joystick_left_x: avg(self.axes[0]),
+            joystick_left_y: avg(self.axes[1]),
+            joystick_right_x: avg(self.axes[2]),
+            joystick_right_y: avg(self.axes[3]),
+            trigger_left: avg(self.axes[4]),
+            trigger_right: avg(self.axes[5]),
+            ..self.buttons
+        }
+    }
+}
+
 /// Relaxed/Center positions for each axis
 ///
 /// These are used to calculate the relative deflection of each access from their center point
 #[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub struct CalibrationData {
     pub joystick_left_x: u8,
     pub joystick_left_y: u8,
@@ -192,7 +778,347 @@ pub struct CalibrationData {
     pub trigger_right: u8,
 }
 
+impl CalibrationData {
+    /// Calibrate from a reading taken with the sticks centred and the triggers released
+    pub fn from_resting(r: &ClassicReading) -> Self {
+        CalibrationData {
+            joystick_left_x: r.joystick_left_x,
+            joystick_left_y: r.joystick_left_y,
+            joystick_right_x: r.joystick_right_x,
+            joystick_right_y: r.joystick_right_y,
+            trigger_left: r.trigger_left,
+            trigger_right: r.trigger_right,
+        }
+    }
+}
+
+/// How far each analogue input moved while calibrating, from its lowest to highest sample
+///
+/// Returned by `calibrate_for()`. Large values mean the controller wasn't at rest, so the
+/// calibration may be off.
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct CalibrationSpread {
+    pub joystick_left_x: u8,
+    pub joystick_left_y: u8,
+    pub joystick_right_x: u8,
+    pub joystick_right_y: u8,
+    pub trigger_left: u8,
+    pub trigger_right: u8,
+}
+
+/// Calibration window for the six analogue inputs of a classic controller
+#[derive(Debug, Default)]
+pub(crate) struct CalibrationWindow(AxisWindow<6>);
+
+impl CalibrationWindow {
+    pub(crate) fn add(&mut self, r: &ClassicReading) {
+        self.0.add(
+            [
+                r.joystick_left_x,
+                r.joystick_left_y,
+                r.joystick_right_x,
+                r.joystick_right_y,
+                r.trigger_left,
+                r.trigger_right,
+            ]
+            .map(u16::from),
+        );
+    }
+
+    /// A resting reading made of the trimmed mean of each input
+    pub(crate) fn rest(&self) -> ClassicReading {
+        let [lx, ly, rx, ry, lt, rt] = self.0.trimmed_mean().map(|axis| axis as u8);
+        ClassicReading {
+            joystick_left_x: lx,
+            joystick_left_y: ly,
+            joystick_right_x: rx,
+            joystick_right_y: ry,
+            trigger_left: lt,
+            trigger_right: rt,
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn spread(&self) -> CalibrationSpread {
+        let [lx, ly, rx, ry, lt, rt] = self.0.spread().map(|axis| axis as u8);
+        CalibrationSpread {
+            joystick_left_x: lx,
+            joystick_left_y: ly,
+            joystick_right_x: rx,
+            joystick_right_y: ry,
+            trigger_left: lt,
+            trigger_right: rt,
+        }
+    }
+}
+
+impl Model for CalibrationData {
+    type Reading = ClassicReading;
+    type Calibrated = ClassicReadingCalibrated;
+
+    fn decode(data: &[u8]) -> Option<ClassicReading> {
+        ClassicReading::from_data(data)
+    }
+
+    fn report_is_sane(data: &[u8]) -> bool {
+        report_is_sane(data)
+    }
+
+    fn report_is_idle(&self, reading: &ClassicReading, tolerance: u8) -> bool {
+        report_is_idle(reading, self, tolerance)
+    }
+
+    fn from_reading(data: &ClassicReading) -> Self {
+        CalibrationData::from_resting(data)
+    }
+
+    fn calibrate(
+        &self,
+        reading: ClassicReading,
+        policy: RangePolicy,
+        deadzone: u8,
+        curves: &StickCurves,
+    ) -> Result<ClassicReadingCalibrated, Axis> {
+        let mut reading = ClassicReadingCalibrated::with_policy(reading, self, policy)?;
+        reading.apply_deadzone(deadzone);
+        reading.apply_curves(curves);
+        Ok(reading)
+    }
+}
+
+/// Classic controller report formats, selected by writing to register 0xFE
+///
+/// The current format can be read back from 0xFE, which is also byte 4 of the controller ID.
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReportMode {
+    /// 6 byte reports, with 5 and 6 bit axes. The power-on format (0x01)
+    #[default]
+    Standard,
+    /// 9 byte reports, with 10 bit sticks and 8 bit triggers (0x02)
+    ///
+    /// Supported by genuine controllers and some adapters. Sticks are decoded to 8 bits.
+    Alt,
+    /// 8 byte reports, with 8 bit axes (0x03)
+    HiRes,
+    /// Any other value of 0xFE. Reports are decoded as standard reports
+    Unknown(u8),
+}
+
+impl ReportMode {
+    /// The mode selected by writing `value` to 0xFE
+    pub const fn from_register(value: u8) -> Self {
+        match value {
+            0x01 => Self::Standard,
+            0x02 => Self::Alt,
+            0x03 => Self::HiRes,
+            other => Self::Unknown(other),
+        }
+    }
+
+    /// The value to write to 0xFE to select this mode
+    pub const fn register_value(self) -> u8 {
+        match self {
+            Self::Standard => 0x01,
+            Self::Alt => 0x02,
+            Self::HiRes => 0x03,
+            Self::Unknown(value) => value,
+        }
+    }
+
+    /// Length of the reports sent in this mode, or `None` if it isn't known
+    pub const fn report_len(self) -> Option<usize> {
+        match self {
+            Self::Standard => Some(6),
+            Self::Alt => Some(9),
+            Self::HiRes => Some(8),
+            Self::Unknown(_) => None,
+        }
+    }
+}
+
+/// How `enable_hires_with()` changes the report format
+///
+/// The default skips the change if the driver already believes the controller is in the
+/// requested format, and takes a new calibration after changing it.
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ModeChangeOptions {
+    /// Write 0xFE even if the driver believes the controller is already in the format
+    pub force: bool,
+    /// Take a new calibration from a report in the new format
+    ///
+    /// Turn this off when a stored calibration will be set straight afterwards, to save
+    /// the report read.
+    pub recalibrate: bool,
+}
+
+impl ModeChangeOptions {
+    /// Skip changes that aren't needed, and recalibrate after a change
+    pub const DEFAULT: ModeChangeOptions = ModeChangeOptions {
+        force: false,
+        recalibrate: true,
+    };
+}
+
+impl Default for ModeChangeOptions {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+with_fields!(ModeChangeOptions {
+    force: bool => with_force,
+    recalibrate: bool => with_recalibrate,
+});
+
+/// Classic driver mode: the report format is chosen at runtime
+///
+/// `enable_hires()` and `assume_hires()` switch the format, and reads decode whichever
+/// format the driver last selected.
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Dynamic;
+
+/// Classic driver mode: standard reports only
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Standard;
+
+/// Classic driver mode: hi-resolution reports only
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct HiRes;
+
+mod sealed {
+    pub trait Sealed {}
+    impl Sealed for super::Dynamic {}
+    impl Sealed for super::Standard {}
+    impl Sealed for super::HiRes {}
+}
+
+/// The report format modes a classic driver can be in: [`Dynamic`], [`Standard`] or [`HiRes`]
+pub trait Resolution: sealed::Sealed {
+    /// Whether reads decode hi-resolution reports, or `None` if that is decided at runtime
+    const HIRES: Option<bool>;
+}
+
+impl Resolution for Dynamic {
+    const HIRES: Option<bool> = None;
+}
+
+impl Resolution for Standard {
+    const HIRES: Option<bool> = Some(false);
+}
+
+impl Resolution for HiRes {
+    const HIRES: Option<bool> = Some(true);
+}
+
+/// Tracks which report format a classic controller is using
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct ReportFormat {
+    mode: ReportMode,
+    /// The 0xFE value read before the driver first changed the format
+    power_on: Option<u8>,
+}
+
+impl ReportFormat {
+    pub(crate) fn mode(&self) -> ReportMode {
+        self.mode
+    }
+
+    pub(crate) fn hires(&self) -> bool {
+        self.mode == ReportMode::HiRes
+    }
+
+    /// The controller is already using this format, without the driver changing it
+    pub(crate) fn assume_hires(&mut self, hires: bool) {
+        self.mode = if hires {
+            ReportMode::HiRes
+        } else {
+            ReportMode::Standard
+        };
+    }
+
+    /// The driver has switched the controller to this format
+    pub(crate) fn set_mode(&mut self, mode: ReportMode) {
+        self.mode = mode;
+    }
+
+    /// Keep track of register writes that change the format
+    pub(crate) fn register_written(&mut self, addr: u8, value: u8) {
+        if addr == 0xFE {
+            self.set_mode(ReportMode::from_register(value));
+        }
+    }
+
+    /// Whether the power-on format still needs to be saved before changing the format
+    pub(crate) fn needs_save(&self) -> bool {
+        self.power_on.is_none()
+    }
+
+    /// Keep the 0xFE value the controller had before the driver changed it
+    pub(crate) fn save_power_on(&mut self, value: u8) {
+        self.power_on.get_or_insert(value);
+    }
+
+    /// The 0xFE value to restore, if the driver has changed the format
+    pub(crate) fn power_on(&self) -> Option<u8> {
+        self.power_on
+    }
+}
+
 impl ClassicReading {
+    /// Sticks centred, triggers and buttons released
+    pub const CENTERED: ClassicReading = ClassicReading {
+        joystick_left_x: 0x80,
+        joystick_left_y: 0x80,
+        joystick_right_x: 0x80,
+        joystick_right_y: 0x80,
+        trigger_left: 0,
+        trigger_right: 0,
+        dpad_up: false,
+        dpad_down: false,
+        dpad_left: false,
+        dpad_right: false,
+        button_b: false,
+        button_a: false,
+        button_x: false,
+        button_y: false,
+        button_trigger_l: false,
+        button_trigger_r: false,
+        button_zl: false,
+        button_zr: false,
+        button_minus: false,
+        button_plus: false,
+        button_home: false,
+    };
+
+    /// Whether two reports of the same input agree: buttons exactly, and axes to within 1
+    pub(crate) fn agrees_with(&self, other: &ClassicReading) -> bool {
+        let close = |a: u8, b: u8| a.abs_diff(b) <= 1;
+        close(self.joystick_left_x, other.joystick_left_x)
+            && close(self.joystick_left_y, other.joystick_left_y)
+            && close(self.joystick_right_x, other.joystick_right_x)
+            && close(self.joystick_right_y, other.joystick_right_y)
+            && close(self.trigger_left, other.trigger_left)
+            && close(self.trigger_right, other.trigger_right)
+            && ClassicReading {
+                joystick_left_x: other.joystick_left_x,
+                joystick_left_y: other.joystick_left_y,
+                joystick_right_x: other.joystick_right_x,
+                joystick_right_y: other.joystick_right_y,
+                trigger_left: other.trigger_left,
+                trigger_right: other.trigger_right,
+                ..*self
+            } == *other
+    }
+
     #[cfg(test)]
     /// Helper function for testing digital pin status
     /// This should work for all different classic controllers
@@ -211,28 +1137,285 @@ impl ClassicReading {
         assert_eq!(self.button_minus, other.button_minus);
     }
 
-    /// Some axis' data is u5, scale it to u8 for convenience
-    pub(crate) fn scale_5bit_8bit(reading: u8) -> u8 {
-        // TODO: better math here, move this somewhere common
+    /// Triggers are u5 in standard reports, scale them to u8 for convenience
+    pub(crate) const fn scale_5bit_8bit(reading: u8) -> u8 {
         ((reading as u32 * u8::MAX as u32) / 31) as u8
     }
 
-    /// Some axis' data is u6, scale it to u8 for convenience
-    pub(crate) fn scale_6bit_8bit(reading: u8) -> u8 {
-        // TODO: better math here, move this somewhere common
-        ((reading as u32 * u8::MAX as u32) / 63) as u8
+    /// The right stick is u5 in standard reports, scale it to u8 keeping its center at 128
+    pub(crate) const fn scale_5bit_8bit_centered(reading: u8) -> u8 {
+        scale_centered(reading, 31)
+    }
+
+    /// The left stick is u6 in standard reports, scale it to u8 keeping its center at 128
+    pub(crate) const fn scale_6bit_8bit_centered(reading: u8) -> u8 {
+        scale_centered(reading, 63)
     }
 
     /// Convert from a wii-ext report into controller data
-    pub fn from_data(data: &[u8]) -> Option<ClassicReading> {
+    ///
+    /// 6 byte reports are decoded as standard reports, 8 byte reports as high-resolution
+    /// reports and 9 byte reports as data format 0x02 reports. Other lengths return `None`.
+    pub const fn from_data(data: &[u8]) -> Option<ClassicReading> {
         if data.len() == 6 {
             // Classic mode:
-            Some(decode_classic_report(data))
+            match data.first_chunk() {
+                Some(report) => Some(Self::from_report(report)),
+                None => None,
+            }
         } else if data.len() == 8 {
             // High precision mode:
-            Some(decode_classic_hd_report(data))
+            match data.first_chunk() {
+                Some(report) => Some(Self::from_hd_report(report)),
+                None => None,
+            }
+        } else if data.len() == 9 {
+            // Data format 0x02:
+            match data.first_chunk() {
+                Some(report) => Some(Self::from_alt_report(report)),
+                None => None,
+            }
         } else {
             None
         }
     }
+
+    /// Decode a standard report
+    pub const fn from_report(report: &ExtReport) -> ClassicReading {
+        decode_classic_report(report)
+    }
+
+    /// Decode a high-resolution report
+    pub const fn from_hd_report(report: &ExtHdReport) -> ClassicReading {
+        decode_classic_hd_report(report)
+    }
+
+    /// Decode a data format 0x02 report, see [`ReportMode::Alt`]
+    pub const fn from_alt_report(report: &ExtAltReport) -> ClassicReading {
+        decode_classic_alt_report(report)
+    }
+
+    /// Encode as a standard report, as the controller would send it
+    ///
+    /// Axes are reduced to the report's 5 and 6 bit precision, rounding to the nearest value.
+    /// Useful for emulating a controller.
+    pub const fn to_report(&self) -> ExtReport {
+        const fn scale(value: u8, max: u32) -> u8 {
+            ((value as u32 * max + 127) / 255) as u8
+        }
+        let lx = unscale_centered(self.joystick_left_x, 63);
+        let ly = unscale_centered(self.joystick_left_y, 63);
+        let rx = unscale_centered(self.joystick_right_x, 31);
+        let ry = unscale_centered(self.joystick_right_y, 31);
+        let lt = scale(self.trigger_left, 31);
+        let rt = scale(self.trigger_right, 31);
+        let [buttons_0, buttons_1] = encode_classic_buttons(self);
+        [
+            (rx & 0b1_1000) << 3 | lx,
+            (rx & 0b0_0110) << 5 | ly,
+            (rx & 0b0_0001) << 7 | (lt & 0b1_1000) << 2 | ry,
+            (lt & 0b0_0111) << 5 | rt,
+            buttons_0,
+            buttons_1,
+        ]
+    }
+
+    /// Encode as a high-resolution report, as the controller would send it
+    pub const fn to_hd_report(&self) -> ExtHdReport {
+        let [buttons_0, buttons_1] = encode_classic_buttons(self);
+        [
+            self.joystick_left_x,
+            self.joystick_right_x,
+            self.joystick_left_y,
+            self.joystick_right_y,
+            self.trigger_left,
+            self.trigger_right,
+            buttons_0,
+            buttons_1,
+        ]
+    }
+
+    /// Encode as a data format 0x02 report, as the controller would send it
+    ///
+    /// The low 2 bits of each stick axis are 0.
+    pub const fn to_alt_report(&self) -> ExtAltReport {
+        let [buttons_0, buttons_1] = encode_classic_buttons(self);
+        [
+            self.joystick_left_x,
+            self.joystick_right_x,
+            self.joystick_left_y,
+            self.joystick_right_y,
+            0,
+            self.trigger_left,
+            self.trigger_right,
+            buttons_0,
+            buttons_1,
+        ]
+    }
+}
+
+with_fields!(ClassicReading {
+    joystick_left_x: u8 => with_joystick_left_x,
+    joystick_left_y: u8 => with_joystick_left_y,
+    joystick_right_x: u8 => with_joystick_right_x,
+    joystick_right_y: u8 => with_joystick_right_y,
+    trigger_left: u8 => with_trigger_left,
+    trigger_right: u8 => with_trigger_right,
+    dpad_up: bool => with_dpad_up,
+    dpad_down: bool => with_dpad_down,
+    dpad_left: bool => with_dpad_left,
+    dpad_right: bool => with_dpad_right,
+    button_b: bool => with_button_b,
+    button_a: bool => with_button_a,
+    button_x: bool => with_button_x,
+    button_y: bool => with_button_y,
+    button_trigger_l: bool => with_button_trigger_l,
+    button_trigger_r: bool => with_button_trigger_r,
+    button_zl: bool => with_button_zl,
+    button_zr: bool => with_button_zr,
+    button_minus: bool => with_button_minus,
+    button_plus: bool => with_button_plus,
+    button_home: bool => with_button_home,
+});
+
+with_fields!(ClassicReadingCalibrated {
+    joystick_left_x: i8 => with_joystick_left_x,
+    joystick_left_y: i8 => with_joystick_left_y,
+    joystick_right_x: i8 => with_joystick_right_x,
+    joystick_right_y: i8 => with_joystick_right_y,
+    trigger_left: i8 => with_trigger_left,
+    trigger_right: i8 => with_trigger_right,
+    dpad_up: bool => with_dpad_up,
+    dpad_down: bool => with_dpad_down,
+    dpad_left: bool => with_dpad_left,
+    dpad_right: bool => with_dpad_right,
+    button_b: bool => with_button_b,
+    button_a: bool => with_button_a,
+    button_x: bool => with_button_x,
+    button_y: bool => with_button_y,
+    button_trigger_l: bool => with_button_trigger_l,
+    button_trigger_r: bool => with_button_trigger_r,
+    button_zl: bool => with_button_zl,
+    button_zr: bool => with_button_zr,
+    button_minus: bool => with_button_minus,
+    button_plus: bool => with_button_plus,
+    button_home: bool => with_button_home,
+});
+
+/// Pack the buttons into one word, one bit per button
+///
+/// Bit n is set when the [`Button`] with discriminant n is pressed, the same order as the
+/// [`wire`](crate::core::wire) format:
+///
+/// | Bit | Button | Bit | Button  | Bit | Button      |
+/// |-----|--------|-----|---------|-----|-------------|
+/// | 0   | A      | 5   | R       | 10  | Home        |
+/// | 1   | B      | 6   | ZL      | 11  | D-pad up    |
+/// | 2   | X      | 7   | ZR      | 12  | D-pad down  |
+/// | 3   | Y      | 8   | Minus   | 13  | D-pad left  |
+/// | 4   | L      | 9   | Plus    | 14  | D-pad right |
+///
+/// Bit 15 is always clear.
+macro_rules! button_word {
+    ($reading:ident) => {
+        impl $reading {
+            /// Buttons packed into one word, see [`ClassicReading::buttons_u16()`]
+            pub const fn buttons_u16(&self) -> u16 {
+                (self.button_a as u16) << Button::A as u16
+                    | (self.button_b as u16) << Button::B as u16
+                    | (self.button_x as u16) << Button::X as u16
+                    | (self.button_y as u16) << Button::Y as u16
+                    | (self.button_trigger_l as u16) << Button::L as u16
+                    | (self.button_trigger_r as u16) << Button::R as u16
+                    | (self.button_zl as u16) << Button::ZL as u16
+                    | (self.button_zr as u16) << Button::ZR as u16
+                    | (self.button_minus as u16) << Button::Minus as u16
+                    | (self.button_plus as u16) << Button::Plus as u16
+                    | (self.button_home as u16) << Button::Home as u16
+                    | (self.dpad_up as u16) << Button::DpadUp as u16
+                    | (self.dpad_down as u16) << Button::DpadDown as u16
+                    | (self.dpad_left as u16) << Button::DpadLeft as u16
+                    | (self.dpad_right as u16) << Button::DpadRight as u16
+            }
+
+            /// Set every button from a word packed by [`buttons_u16()`](Self::buttons_u16)
+            ///
+            /// Bit 15 is ignored, and the axes are left as they are.
+            pub fn apply_buttons_u16(&mut self, buttons: u16) {
+                let pressed = |button: Button| buttons & 1 << button as u16 != 0;
+                self.button_a = pressed(Button::A);
+                self.button_b = pressed(Button::B);
+                self.button_x = pressed(Button::X);
+                self.button_y = pressed(Button::Y);
+                self.button_trigger_l = pressed(Button::L);
+                self.button_trigger_r = pressed(Button::R);
+                self.button_zl = pressed(Button::ZL);
+                self.button_zr = pressed(Button::ZR);
+                self.button_minus = pressed(Button::Minus);
+                self.button_plus = pressed(Button::Plus);
+                self.button_home = pressed(Button::Home);
+                self.dpad_up = pressed(Button::DpadUp);
+                self.dpad_down = pressed(Button::DpadDown);
+                self.dpad_left = pressed(Button::DpadLeft);
+                self.dpad_right = pressed(Button::DpadRight);
+            }
+        }
+    };
+}
+
+button_word!(ClassicReading);
+button_word!(ClassicReadingCalibrated);
+
+/// Build a standard classic controller report from a description of its fields
+///
+/// Starts from [`ClassicReading::CENTERED`], or from the report after `..`, then sets each
+/// [`ClassicReading`] field listed. A field without a value is set to `true`, for buttons.
+/// The result is encoded with [`ClassicReading::to_report()`], and can be used in constants:
+///
+/// ```
+/// # use wii_ext::classic_report;
+/// const IDLE: [u8; 6] = classic_report! { joystick_left_x: 0x90 };
+/// const BTN_A: [u8; 6] = classic_report! { ..IDLE, button_a };
+/// ```
+#[macro_export]
+macro_rules! classic_report {
+    (@value) => { true };
+    (@value $value:expr) => { $value };
+    (..$base:expr $(, $field:ident $(: $value:expr)?)* $(,)?) => {{
+        #[allow(unused_mut)]
+        let mut reading = match $crate::core::classic::ClassicReading::from_data(&$base) {
+            ::core::option::Option::Some(reading) => reading,
+            ::core::option::Option::None => panic!("not a classic controller report"),
+        };
+        $(reading.$field = $crate::classic_report!(@value $($value)?);)*
+        reading.to_report()
+    }};
+    ($($field:ident $(: $value:expr)?),* $(,)?) => {{
+        #[allow(unused_mut)]
+        let mut reading = $crate::core::classic::ClassicReading::CENTERED;
+        $(reading.$field = $crate::classic_report!(@value $($value)?);)*
+        reading.to_report()
+    }};
+}
+
+/// Build a high-resolution classic controller report from a description of its fields
+///
+/// The same as [`classic_report!`], but encodes with [`ClassicReading::to_hd_report()`].
+#[macro_export]
+macro_rules! classic_hd_report {
+    (..$base:expr $(, $field:ident $(: $value:expr)?)* $(,)?) => {{
+        #[allow(unused_mut)]
+        let mut reading = match $crate::core::classic::ClassicReading::from_data(&$base) {
+            ::core::option::Option::Some(reading) => reading,
+            ::core::option::Option::None => panic!("not a classic controller report"),
+        };
+        $(reading.$field = $crate::classic_report!(@value $($value)?);)*
+        reading.to_hd_report()
+    }};
+    ($($field:ident $(: $value:expr)?),* $(,)?) => {{
+        #[allow(unused_mut)]
+        let mut reading = $crate::core::classic::ClassicReading::CENTERED;
+        $(reading.$field = $crate::classic_report!(@value $($value)?);)*
+        reading.to_hd_report()
+    }};
 }
@@ -0,0 +1,69 @@
+//! Timestamps for readings, from a clock the application provides
+//!
+//! The drivers only have a delay, which can't tell the time. Anything that counts
+//! microseconds can implement [`Clock`], and `read_timestamped()` on the drivers stamps each
+//! reading with it:
+//!
+//! ```ignore
+//! let first = nunchuk.read_timestamped(&mut clock)?;
+//! let second = nunchuk.read_timestamped(&mut clock)?;
+//! let elapsed_us = second.micros - first.micros;
+//! ```
+//!
+//! On bare metal, a free-running 32-bit timer can be extended to 64 bits, as long as it is
+//! read more often than it wraps:
+//!
+//! ```ignore
+//! struct TimerClock {
+//!     timer: Timer,
+//!     last: u32,
+//!     high: u64,
+//! }
+//!
+//! impl Clock for TimerClock {
+//!     fn now_micros(&mut self) -> u64 {
+//!         let now = self.timer.counter_us();
+//!         if now < self.last {
+//!             self.high += 1 << 32;
+//!         }
+//!         self.last = now;
+//!         self.high | u64::from(now)
+//!     }
+//! }
+//! ```
+//!
+//! With the `embassy` feature, [`EmbassyClock`] uses `embassy_time::Instant`.
+
+/// A source of monotonic time, in microseconds
+pub trait Clock {
+    /// The current time. Only differences between times matter, so it can start anywhere
+    fn now_micros(&mut self) -> u64;
+}
+
+impl<C: Clock + ?Sized> Clock for &mut C {
+    fn now_micros(&mut self) -> u64 {
+        (**self).now_micros()
+    }
+}
+
+/// A reading, and the time it was taken
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Timestamped<T> {
+    /// When the read started, from [`Clock::now_micros()`]
+    pub micros: u64,
+    pub reading: T,
+}
+
+/// A clock reading `embassy_time::Instant`
+#[cfg(feature = "embassy")]
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct EmbassyClock;
+
+#[cfg(feature = "embassy")]
+impl Clock for EmbassyClock {
+    fn now_micros(&mut self) -> u64 {
+        embassy_time::Instant::now().as_micros()
+    }
+}
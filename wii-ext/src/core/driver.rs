@@ -0,0 +1,225 @@
+//! Driver logic shared by the blocking and async implementations
+//!
+//! The blocking and async drivers only differ in how they talk to the bus. Calibration,
+//! deadzones, stick curves, report checks and read statistics live here, so they are only written once.
+//! The drivers do the bus operations and hand the raw reports to [`DriverState`].
+
+use crate::core::events::Axis;
+use crate::core::{
+    CheckFailures, ControllerType, ExtAltReport, IdentityCheck, RangePolicy, ReportCheck, Stats,
+    StickCurves, StuckCheck, STUCK_IDLE_TOLERANCE,
+};
+use crate::logging;
+use crate::Error;
+
+/// How a controller's reports are decoded and calibrated
+///
+/// Implemented by each controller's calibration data type.
+pub(crate) trait Model: Copy + Default {
+    /// Decoded report, before calibration
+    type Reading;
+    /// Decoded report, relative to calibration
+    type Calibrated;
+
+    /// Decode a raw report
+    fn decode(data: &[u8]) -> Option<Self::Reading>;
+    /// Whether a raw report looks like it came from this kind of controller
+    fn report_is_sane(data: &[u8]) -> bool;
+    /// Whether a reading has no inputs active, relative to this calibration
+    fn report_is_idle(&self, reading: &Self::Reading, tolerance: u8) -> bool;
+    /// Take the calibration from a reading of the controller at rest
+    fn from_reading(reading: &Self::Reading) -> Self;
+    /// Apply this calibration, a joystick deadzone and the stick curves to a reading
+    ///
+    /// Returns the first axis that `policy` rejected.
+    fn calibrate(
+        &self,
+        reading: Self::Reading,
+        policy: RangePolicy,
+        deadzone: u8,
+        curves: &StickCurves,
+    ) -> Result<Self::Calibrated, Axis>;
+}
+
+/// The most recent report handed to [`DriverState::decode()`], kept for logging
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct LastReport {
+    bytes: ExtAltReport,
+    len: u8,
+}
+
+impl LastReport {
+    fn set(&mut self, data: &[u8]) {
+        let len = data.len().min(self.bytes.len());
+        self.bytes[..len].copy_from_slice(&data[..len]);
+        self.len = len as u8;
+    }
+
+    /// The report's bytes, or `None` if no report has been decoded yet
+    pub(crate) fn get(&self) -> Option<&[u8]> {
+        (self.len > 0).then(|| &self.bytes[..self.len as usize])
+    }
+}
+
+/// Decides when `read_with_recovery_hook()` calls its hook
+///
+/// Counts consecutive bus errors, including reads that ran out of retries, and fires once when
+/// the count reaches `after`. Other errors reset the count, but don't let the hook fire a
+/// second time.
+#[derive(Debug)]
+pub(crate) struct HookTrigger {
+    after: u8,
+    bus_errors: u8,
+    fired: bool,
+}
+
+impl HookTrigger {
+    /// A trigger that fires after `after` bus errors in a row, or never if `after` is 0
+    pub(crate) fn new(after: u8) -> Self {
+        Self {
+            after,
+            bus_errors: 0,
+            fired: false,
+        }
+    }
+
+    /// Note a failed attempt, returning the bus error count if the hook should be called now
+    pub(crate) fn on_error<E>(&mut self, error: &Error<E>) -> Option<u8> {
+        self.bus_errors = if matches!(error, Error::I2C(_) | Error::RetriesExhausted(_)) {
+            self.bus_errors.saturating_add(1)
+        } else {
+            0
+        };
+        if self.fired || self.after == 0 || self.bus_errors < self.after {
+            return None;
+        }
+        self.fired = true;
+        Some(self.bus_errors)
+    }
+}
+
+/// Everything a driver knows about its controller, apart from the bus
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Default)]
+pub(crate) struct DriverState<M> {
+    pub(crate) calibration: M,
+    pub(crate) deadzone: u8,
+    pub(crate) range_policy: RangePolicy,
+    pub(crate) curves: StickCurves,
+    pub(crate) identity_check: Option<IdentityCheck>,
+    pub(crate) stuck_check: Option<StuckCheck>,
+    /// Read each sample twice, and only accept it if the reports agree
+    pub(crate) verify_reads: bool,
+    /// Reject reports that fail any [`ReportCheck`]
+    pub(crate) strict: bool,
+    pub(crate) check_failures: CheckFailures,
+    pub(crate) stats: Stats,
+    pub(crate) last_report: LastReport,
+}
+
+impl<M: Model> DriverState<M> {
+    /// Whether the controller should be identified again before `data` is decoded
+    pub(crate) fn reidentify_needed(&mut self, data: &[u8]) -> bool {
+        match self.identity_check.as_mut() {
+            Some(check) => check.should_check(!M::report_is_sane(data)),
+            None => false,
+        }
+    }
+
+    /// Check and decode a raw report
+    ///
+    /// `current_id` is the result of identifying the controller, if `reidentify_needed()`
+    /// asked for that.
+    pub(crate) fn decode<E>(
+        &mut self,
+        data: &[u8],
+        current_id: Option<Option<ControllerType>>,
+    ) -> Result<M::Reading, Error<E>> {
+        self.last_report.set(data);
+        if let Some(check) = &self.identity_check {
+            let previous = check.expected();
+            if let Some(current) = current_id {
+                if current != previous {
+                    self.check_failures.controller_changed = true;
+                    logging::anomaly("controller changed", data);
+                    return Err(Error::ControllerChanged { previous, current });
+                }
+            }
+        }
+        // Impossible for every controller, as each has a constant bit or button bits
+        // that are high at rest. Checked before strict mode, which would call it blank.
+        if data.iter().all(|&b| b == 0) {
+            self.check_failures.not_responding = true;
+            logging::anomaly("controller is not responding", data);
+            return Err(Error::NotResponding);
+        }
+        if self.strict {
+            if let Some(check) = ReportCheck::first_failed(data, M::report_is_sane(data)) {
+                self.check_failures.record_report(check);
+                logging::anomaly(check.description(), data);
+                return Err(Error::RejectedReport(check));
+            }
+        } else if self.identity_check.is_some() && !M::report_is_sane(data) {
+            return Err(Error::InvalidInputData);
+        }
+        let reading = M::decode(data).ok_or(Error::InvalidInputData)?;
+        if let Some(check) = self.stuck_check.as_mut() {
+            let idle = self
+                .calibration
+                .report_is_idle(&reading, STUCK_IDLE_TOLERANCE);
+            if check.is_stuck(data, idle) {
+                self.check_failures.stuck = true;
+                logging::anomaly("controller is stuck", data);
+                return Err(Error::Stuck);
+            }
+        }
+        Ok(reading)
+    }
+
+    /// Whether a report read without seeking first looks valid, counting a resync if not
+    pub(crate) fn check_streamed(&mut self, data: &[u8]) -> bool {
+        let sane = M::report_is_sane(data);
+        if !sane {
+            self.stats.count_resync();
+        }
+        sane
+    }
+
+    /// Update the read statistics with the outcome of a read
+    pub(crate) fn record<R, E>(&mut self, result: Result<R, Error<E>>) -> Result<R, Error<E>> {
+        match &result {
+            Ok(_) => self.stats.count_success(),
+            Err(Error::I2C(_) | Error::RetriesExhausted(_) | Error::Timeout) => {
+                self.stats.count_i2c_error()
+            }
+            Err(Error::InvalidInputData | Error::RejectedReport(_) | Error::NotResponding) => {
+                self.stats.count_invalid_data()
+            }
+            Err(_) => {}
+        }
+        result
+    }
+
+    /// Note that the reports of a sample disagreed, returning the error for it
+    pub(crate) fn inconsistent<E>(&mut self) -> Error<E> {
+        self.check_failures.inconsistent_reads = true;
+        logging::anomaly("reads of the same sample disagree", &[]);
+        Error::InconsistentData
+    }
+
+    /// Use a reading of the controller at rest as the new calibration
+    pub(crate) fn update_calibration(&mut self, reading: &M::Reading) {
+        self.calibration = M::from_reading(reading);
+    }
+
+    /// Apply the calibration, deadzone and stick curves to a reading
+    pub(crate) fn calibrated<E>(&self, reading: M::Reading) -> Result<M::Calibrated, Error<E>> {
+        self.calibration
+            .calibrate(reading, self.range_policy, self.deadzone, &self.curves)
+            .map_err(|axis| {
+                logging::anomaly("calibrated axis out of range", &[]);
+                Error::OutOfRange { axis }
+            })
+    }
+}
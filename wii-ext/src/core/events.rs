@@ -0,0 +1,566 @@
+//! Button and axis events, generated by comparing successive readings
+//!
+//! There are two ways to get events:
+//!
+//! - [`InputEvents`] iterates over the differences between two readings, including the
+//!   controller being connected or disconnected. [`EventTracker`] keeps the previous reading
+//!   for you, so each poll produces the events since the last one:
+//!
+//! ```ignore
+//! let mut tracker = EventTracker::default();
+//! loop {
+//!     for event in tracker.update(controller.read().ok()) {
+//!         match event {
+//!             Event::ButtonPressed(Button::A) => jump(),
+//!             Event::Disconnected => pause(),
+//!             _ => {}
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! - An [`EventQueue`] buffers events so the main loop can drain them at its own pace, and only
+//!   reports axis movements larger than a threshold:
+//!
+//! ```ignore
+//! let mut events: EventQueue<16> = EventQueue::new(OverflowPolicy::DropOldest, 4);
+//! loop {
+//!     events.update(&controller.read()?);
+//!     for event in events.drain() {
+//!         match event {
+//!             InputEvent::ButtonPressed(Button::A) => jump(),
+//!             _ => {}
+//!         }
+//!     }
+//! }
+//! ```
+
+use crate::core::classic::{ClassicReading, ClassicReadingCalibrated};
+use crate::core::nunchuk::{NunchukReading, NunchukReadingCalibrated};
+use core::ops::Index;
+use heapless::Deque;
+
+/// A button on a classic controller or nunchuk
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Button {
+    A,
+    B,
+    X,
+    Y,
+    /// Digital part of the left shoulder button
+    L,
+    /// Digital part of the right shoulder button
+    R,
+    ZL,
+    ZR,
+    Minus,
+    Plus,
+    Home,
+    DpadUp,
+    DpadDown,
+    DpadLeft,
+    DpadRight,
+    /// Nunchuk C button
+    C,
+    /// Nunchuk Z button
+    Z,
+}
+
+impl Button {
+    /// Every button, in declaration order
+    pub const ALL: [Button; 17] = [
+        Button::A,
+        Button::B,
+        Button::X,
+        Button::Y,
+        Button::L,
+        Button::R,
+        Button::ZL,
+        Button::ZR,
+        Button::Minus,
+        Button::Plus,
+        Button::Home,
+        Button::DpadUp,
+        Button::DpadDown,
+        Button::DpadLeft,
+        Button::DpadRight,
+        Button::C,
+        Button::Z,
+    ];
+}
+
+/// An analogue axis on a classic controller or nunchuk
+///
+/// The nunchuk's stick is reported as the left stick.
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Axis {
+    LeftX,
+    LeftY,
+    RightX,
+    RightY,
+    /// Analogue part of the left shoulder button
+    TriggerLeft,
+    /// Analogue part of the right shoulder button
+    TriggerRight,
+}
+
+impl Axis {
+    /// Every axis, in declaration order
+    pub const ALL: [Axis; 6] = [
+        Axis::LeftX,
+        Axis::LeftY,
+        Axis::RightX,
+        Axis::RightY,
+        Axis::TriggerLeft,
+        Axis::TriggerRight,
+    ];
+}
+
+/// A calibrated reading whose buttons and axes can be looked up by name
+///
+/// Readings can also be indexed directly, eg: `reading[Button::A]` or `reading[Axis::LeftX]`,
+/// and changed with `set_button()` and `set_axis()`. Raw readings support the same, with
+/// their unsigned axis values.
+pub trait Inputs {
+    /// The buttons this controller has
+    const BUTTONS: &'static [Button];
+    /// The axes this controller has
+    const AXES: &'static [Axis];
+
+    /// Whether `button` is pressed. Buttons the controller doesn't have are never pressed
+    fn button(&self, button: Button) -> bool;
+    /// Value of `axis` relative to calibration. Axes the controller doesn't have are always 0
+    fn axis(&self, axis: Axis) -> i8;
+
+    /// Whether any of the controller's buttons is pressed
+    fn any_button_pressed(&self) -> bool {
+        Self::BUTTONS.iter().any(|&button| self.button(button))
+    }
+
+    /// Whether any axis is more than `threshold` from rest
+    ///
+    /// A nunchuk's accelerometer isn't an axis, so moving the nunchuk doesn't count.
+    fn any_axis_active(&self, threshold: u8) -> bool {
+        Self::AXES
+            .iter()
+            .any(|&axis| self.axis(axis).unsigned_abs() > threshold)
+    }
+
+    /// Whether nothing is pressed and every axis is within `threshold` of rest
+    fn is_idle(&self, threshold: u8) -> bool {
+        !self.any_button_pressed() && !self.any_axis_active(threshold)
+    }
+}
+
+impl Inputs for ClassicReadingCalibrated {
+    const BUTTONS: &'static [Button] = &[
+        Button::A,
+        Button::B,
+        Button::X,
+        Button::Y,
+        Button::L,
+        Button::R,
+        Button::ZL,
+        Button::ZR,
+        Button::Minus,
+        Button::Plus,
+        Button::Home,
+        Button::DpadUp,
+        Button::DpadDown,
+        Button::DpadLeft,
+        Button::DpadRight,
+    ];
+    const AXES: &'static [Axis] = &Axis::ALL;
+
+    fn button(&self, button: Button) -> bool {
+        self[button]
+    }
+
+    fn axis(&self, axis: Axis) -> i8 {
+        self[axis]
+    }
+}
+
+impl Inputs for NunchukReadingCalibrated {
+    const BUTTONS: &'static [Button] = &[Button::C, Button::Z];
+    const AXES: &'static [Axis] = &[Axis::LeftX, Axis::LeftY];
+
+    fn button(&self, button: Button) -> bool {
+        self[button]
+    }
+
+    fn axis(&self, axis: Axis) -> i8 {
+        self[axis]
+    }
+}
+
+/// Implement `Index<Button>` and `set_button()` for a reading, from its button fields
+macro_rules! buttons {
+    ($reading:ty { $($button:ident => $field:ident),* $(,)? }) => {
+        impl Index<Button> for $reading {
+            type Output = bool;
+
+            /// Whether `button` is pressed. Buttons the controller doesn't have are never pressed
+            fn index(&self, button: Button) -> &bool {
+                match button {
+                    $(Button::$button => &self.$field,)*
+                    _ => &false,
+                }
+            }
+        }
+
+        impl $reading {
+            /// Press or release `button`. Buttons the controller doesn't have are ignored
+            pub fn set_button(&mut self, button: Button, pressed: bool) {
+                match button {
+                    $(Button::$button => self.$field = pressed,)*
+                    _ => {}
+                }
+            }
+        }
+    };
+}
+
+/// Implement `Index<Axis>` and `set_axis()` for a reading, from its axis fields
+macro_rules! axes {
+    ($reading:ty: $value:ty { $($axis:ident => $field:ident),* $(,)? }) => {
+        impl Index<Axis> for $reading {
+            type Output = $value;
+
+            /// Value of `axis`. Axes the controller doesn't have are always 0
+            fn index(&self, axis: Axis) -> &$value {
+                #[allow(unreachable_patterns)]
+                match axis {
+                    $(Axis::$axis => &self.$field,)*
+                    _ => &0,
+                }
+            }
+        }
+
+        impl $reading {
+            /// Set the value of `axis`. Axes the controller doesn't have are ignored
+            pub fn set_axis(&mut self, axis: Axis, value: $value) {
+                #[allow(unreachable_patterns)]
+                match axis {
+                    $(Axis::$axis => self.$field = value,)*
+                    _ => {}
+                }
+            }
+        }
+    };
+}
+
+/// Implement the `Inputs` predicates for a raw reading, with the buttons and axes of its
+/// calibrated counterpart
+macro_rules! raw_predicates {
+    ($reading:ty, $calibrated:ty) => {
+        impl $reading {
+            /// Whether any of the controller's buttons is pressed
+            pub fn any_button_pressed(&self) -> bool {
+                <$calibrated>::BUTTONS.iter().any(|&button| self[button])
+            }
+
+            /// Whether any axis is more than `threshold` from its nominal rest position
+            ///
+            /// Without calibration, axes are compared with [`Self::CENTERED`], so use a
+            /// threshold large enough to cover the controller's own offset.
+            pub fn any_axis_active(&self, threshold: u8) -> bool {
+                <$calibrated>::AXES
+                    .iter()
+                    .any(|&axis| self[axis].abs_diff(Self::CENTERED[axis]) > threshold)
+            }
+
+            /// Whether nothing is pressed and every axis is within `threshold` of rest
+            pub fn is_idle(&self, threshold: u8) -> bool {
+                !self.any_button_pressed() && !self.any_axis_active(threshold)
+            }
+        }
+    };
+}
+
+raw_predicates!(ClassicReading, ClassicReadingCalibrated);
+raw_predicates!(NunchukReading, NunchukReadingCalibrated);
+
+buttons!(ClassicReading {
+    A => button_a,
+    B => button_b,
+    X => button_x,
+    Y => button_y,
+    L => button_trigger_l,
+    R => button_trigger_r,
+    ZL => button_zl,
+    ZR => button_zr,
+    Minus => button_minus,
+    Plus => button_plus,
+    Home => button_home,
+    DpadUp => dpad_up,
+    DpadDown => dpad_down,
+    DpadLeft => dpad_left,
+    DpadRight => dpad_right,
+});
+buttons!(ClassicReadingCalibrated {
+    A => button_a,
+    B => button_b,
+    X => button_x,
+    Y => button_y,
+    L => button_trigger_l,
+    R => button_trigger_r,
+    ZL => button_zl,
+    ZR => button_zr,
+    Minus => button_minus,
+    Plus => button_plus,
+    Home => button_home,
+    DpadUp => dpad_up,
+    DpadDown => dpad_down,
+    DpadLeft => dpad_left,
+    DpadRight => dpad_right,
+});
+buttons!(NunchukReading { C => button_c, Z => button_z });
+buttons!(NunchukReadingCalibrated { C => button_c, Z => button_z });
+
+axes!(ClassicReading: u8 {
+    LeftX => joystick_left_x,
+    LeftY => joystick_left_y,
+    RightX => joystick_right_x,
+    RightY => joystick_right_y,
+    TriggerLeft => trigger_left,
+    TriggerRight => trigger_right,
+});
+axes!(ClassicReadingCalibrated: i8 {
+    LeftX => joystick_left_x,
+    LeftY => joystick_left_y,
+    RightX => joystick_right_x,
+    RightY => joystick_right_y,
+    TriggerLeft => trigger_left,
+    TriggerRight => trigger_right,
+});
+axes!(NunchukReading: u8 { LeftX => joystick_x, LeftY => joystick_y });
+axes!(NunchukReadingCalibrated: i8 { LeftX => joystick_x, LeftY => joystick_y });
+
+/// A change in a controller's state, produced by [`InputEvents`]
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    ButtonPressed(Button),
+    ButtonReleased(Button),
+    /// An axis changed, to the value given
+    AxisChanged(Axis, i8),
+    /// A reading was taken after a failed one, or for the first time
+    Connected,
+    /// A reading failed. No release events are generated, so treat every button as released
+    Disconnected,
+}
+
+/// The events between two readings, where `None` means the controller could not be read
+///
+/// When the controller is connected, the [`Event::Connected`] event is followed by events for
+/// anything not at rest, compared with a default reading. Otherwise, button events come before
+/// axis events, each in the order of `R::BUTTONS` and `R::AXES`.
+#[derive(Debug, Clone)]
+pub struct InputEvents<R> {
+    connection: Option<Event>,
+    previous: R,
+    current: Option<R>,
+    /// Index into `R::BUTTONS`, then `R::AXES`
+    position: usize,
+}
+
+impl<R: Inputs + Default> InputEvents<R> {
+    pub fn new(previous: Option<R>, current: Option<R>) -> Self {
+        let connection = match (&previous, &current) {
+            (None, Some(_)) => Some(Event::Connected),
+            (Some(_), None) => Some(Event::Disconnected),
+            _ => None,
+        };
+        Self {
+            connection,
+            previous: previous.unwrap_or_default(),
+            current,
+            position: 0,
+        }
+    }
+}
+
+impl<R: Inputs + Default> Iterator for InputEvents<R> {
+    type Item = Event;
+
+    fn next(&mut self) -> Option<Event> {
+        if let Some(event) = self.connection.take() {
+            return Some(event);
+        }
+        let current = self.current.as_ref()?;
+        while let Some(&button) = R::BUTTONS.get(self.position) {
+            self.position += 1;
+            let pressed = current.button(button);
+            if pressed != self.previous.button(button) {
+                return Some(if pressed {
+                    Event::ButtonPressed(button)
+                } else {
+                    Event::ButtonReleased(button)
+                });
+            }
+        }
+        while let Some(&axis) = R::AXES.get(self.position - R::BUTTONS.len()) {
+            self.position += 1;
+            let value = current.axis(axis);
+            if value != self.previous.axis(axis) {
+                return Some(Event::AxisChanged(axis, value));
+            }
+        }
+        None
+    }
+}
+
+/// Remembers the last reading, so each update produces the [`InputEvents`] since the last one
+///
+/// The tracker starts out disconnected, so the first successful reading produces
+/// [`Event::Connected`].
+#[derive(Debug, Default, Clone)]
+pub struct EventTracker<R> {
+    last: Option<R>,
+}
+
+impl<R: Inputs + Default + Clone> EventTracker<R> {
+    /// Record a new reading, or `None` if the controller could not be read
+    pub fn update(&mut self, reading: Option<R>) -> InputEvents<R> {
+        let previous = core::mem::replace(&mut self.last, reading.clone());
+        InputEvents::new(previous, reading)
+    }
+
+    /// The last successful reading, if the controller is connected
+    pub fn last(&self) -> Option<&R> {
+        self.last.as_ref()
+    }
+}
+
+/// A change in a controller's state, queued by [`EventQueue`]
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputEvent {
+    ButtonPressed(Button),
+    ButtonReleased(Button),
+    /// An axis moved by more than the queue's threshold. `value` is its new position
+    AxisMoved {
+        axis: Axis,
+        value: i8,
+    },
+}
+
+/// What an [`EventQueue`] does with an event when it is full
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Discard the oldest queued event to make room, so the queue holds the latest events
+    DropOldest,
+    /// Discard the new event, so the queue holds the events that haven't been handled yet
+    DropNewest,
+}
+
+/// A fixed-capacity queue of [`InputEvent`]s, generated from successive readings
+///
+/// Works with any reading implementing [`Inputs`]. The queue starts out treating every button
+/// as released and every axis as centred, so buttons held during the first update are reported
+/// as pressed.
+#[derive(Debug)]
+pub struct EventQueue<const N: usize> {
+    events: Deque<InputEvent, N>,
+    policy: OverflowPolicy,
+    axis_threshold: u8,
+    overflows: u32,
+    /// Held buttons, one bit per `Button` discriminant
+    buttons: u32,
+    /// Axis positions as of their last `AxisMoved` event
+    axes: [i8; Axis::ALL.len()],
+}
+
+impl<const N: usize> EventQueue<N> {
+    /// Create an empty queue
+    ///
+    /// An `AxisMoved` event is only queued once an axis is more than `axis_threshold` away from
+    /// the position reported in its previous event, which filters out stick jitter.
+    pub const fn new(policy: OverflowPolicy, axis_threshold: u8) -> Self {
+        Self {
+            events: Deque::new(),
+            policy,
+            axis_threshold,
+            overflows: 0,
+            buttons: 0,
+            axes: [0; Axis::ALL.len()],
+        }
+    }
+
+    /// Compare `reading` against the previous state, queueing an event for each change
+    ///
+    /// Button events are queued before axis events, each in the order of `R::BUTTONS` and
+    /// `R::AXES`.
+    pub fn update<R: Inputs>(&mut self, reading: &R) {
+        for &button in R::BUTTONS {
+            let bit = 1 << button as u32;
+            let pressed = reading.button(button);
+            if pressed != (self.buttons & bit != 0) {
+                self.buttons ^= bit;
+                self.push(if pressed {
+                    InputEvent::ButtonPressed(button)
+                } else {
+                    InputEvent::ButtonReleased(button)
+                });
+            }
+        }
+        for &axis in R::AXES {
+            let value = reading.axis(axis);
+            let last = &mut self.axes[axis as usize];
+            if (i16::from(value) - i16::from(*last)).unsigned_abs() > u16::from(self.axis_threshold)
+            {
+                *last = value;
+                self.push(InputEvent::AxisMoved { axis, value });
+            }
+        }
+    }
+
+    /// Queue an event, applying the overflow policy if the queue is full
+    pub fn push(&mut self, event: InputEvent) {
+        if let Err(event) = self.events.push_back(event) {
+            self.overflows = self.overflows.saturating_add(1);
+            if self.policy == OverflowPolicy::DropOldest {
+                self.events.pop_front();
+                // Can't fail, there is space now
+                self.events.push_back(event).ok();
+            }
+        }
+    }
+
+    /// Take the oldest queued event
+    pub fn pop(&mut self) -> Option<InputEvent> {
+        self.events.pop_front()
+    }
+
+    /// Take queued events, oldest first, until the queue is empty
+    pub fn drain(&mut self) -> impl Iterator<Item = InputEvent> + '_ {
+        core::iter::from_fn(|| self.pop())
+    }
+
+    /// Number of queued events
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    pub fn policy(&self) -> OverflowPolicy {
+        self.policy
+    }
+
+    /// Number of events dropped because the queue was full
+    pub fn overflow_count(&self) -> u32 {
+        self.overflows
+    }
+
+    /// Reset the overflow count to 0
+    pub fn clear_overflow_count(&mut self) {
+        self.overflows = 0;
+    }
+}
@@ -0,0 +1,99 @@
+//! Mapping controller buttons to keyboard keys
+//!
+//! A [`KeyMap`] pairs buttons with keyboard usage codes (from the USB HID keyboard usage
+//! table), and turns successive readings into key presses and releases, for adapters that
+//! present a controller as a keyboard:
+//!
+//! ```ignore
+//! const MAP: KeyMap<4> = KeyMap::new([
+//!     (Button::DpadUp, 0x52),   // Up arrow
+//!     (Button::DpadDown, 0x51), // Down arrow
+//!     (Button::A, 0x1D),        // Z
+//!     (Button::B, 0x1B),        // X
+//! ]);
+//!
+//! let mut keys = MAP;
+//! loop {
+//!     for event in keys.update(&controller.read()?) {
+//!         // ...
+//!     }
+//!     hid.push_raw_input(&KeyboardReport::from(&keys).to_bytes()).ok();
+//! }
+//! ```
+//!
+//! With the `hid` feature, `wii_ext::hid::KeyboardReport` builds a boot protocol keyboard
+//! report from the keys held.
+
+use crate::core::events::{Button, Inputs};
+
+/// A key being pressed or released, by its usage code
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyEvent {
+    Pressed(u8),
+    Released(u8),
+}
+
+/// Buttons mapped to keyboard usage codes
+///
+/// A button can be mapped to more than one key. Buttons the controller doesn't have are
+/// never pressed, so one map can cover both classic controllers and nunchuks.
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyMap<const N: usize> {
+    keys: [(Button, u8); N],
+    held: [bool; N],
+}
+
+impl<const N: usize> KeyMap<N> {
+    /// Map each button to its key, starting with every key released
+    pub const fn new(keys: [(Button, u8); N]) -> Self {
+        Self {
+            keys,
+            held: [false; N],
+        }
+    }
+
+    /// The button and key pairs, in the order they were given
+    pub fn keys(&self) -> &[(Button, u8); N] {
+        &self.keys
+    }
+
+    /// Feed the next reading, returning the keys pressed and released since the last one
+    ///
+    /// Events are in map order. The held keys are updated straight away, whether or not the
+    /// events are used.
+    pub fn update<R: Inputs>(&mut self, reading: &R) -> impl Iterator<Item = KeyEvent> + '_ {
+        let previous = self.held;
+        for (held, (button, _)) in self.held.iter_mut().zip(&self.keys) {
+            *held = reading.button(*button);
+        }
+        self.keys
+            .iter()
+            .zip(previous.into_iter().zip(self.held))
+            .filter_map(|((_, key), change)| match change {
+                (false, true) => Some(KeyEvent::Pressed(*key)),
+                (true, false) => Some(KeyEvent::Released(*key)),
+                _ => None,
+            })
+    }
+
+    /// The usage codes of the keys held, in map order
+    pub fn held_keys(&self) -> impl Iterator<Item = u8> + '_ {
+        self.keys
+            .iter()
+            .zip(self.held)
+            .filter_map(|((_, key), held)| held.then_some(*key))
+    }
+
+    /// Release every key, eg: when the controller is disconnected
+    ///
+    /// Returns the keys that were held, as release events.
+    pub fn release_all(&mut self) -> impl Iterator<Item = KeyEvent> + '_ {
+        let previous = core::mem::replace(&mut self.held, [false; N]);
+        self.keys
+            .iter()
+            .zip(previous)
+            .filter_map(|((_, key), held)| held.then_some(KeyEvent::Released(*key)))
+    }
+}
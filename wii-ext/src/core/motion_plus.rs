@@ -0,0 +1,139 @@
+//! Decoding for Wii Motion Plus gyroscope reports
+//!
+//! There is no Motion Plus driver yet, so these reports have to be read from the bus by hand.
+//! Decoding is kept here so it can be shared by the blocking and async drivers once they exist.
+//!
+//! Each axis is a 14-bit rate, with [`GYRO_ZERO`] for no rotation. The gyro switches each axis
+//! between a slow and a fast range on its own, and flags which one it used in the report.
+//! The fast range covers about 2000/440 times as many degrees per second per count, so the
+//! raw value alone doesn't say how fast the remote is turning. Use the `*_dps()` methods,
+//! which take the flags into account.
+
+use crate::core::ExtReport;
+
+/// Gyro reading for no rotation, the middle of the 14-bit range
+pub const GYRO_ZERO: u16 = 0x2000;
+/// Largest value of the 14-bit gyro axes
+const GYRO_MAX: u16 = 0x3FFF;
+
+/// Rotation rate at [`GYRO_ZERO`] ± 8192 counts in the slow range, in centi-degrees per second
+const SLOW_FULL_SCALE_CENTI_DPS: i64 = 59_500;
+
+/// Data from a Wii Motion Plus after it has been deserialized
+///
+/// Fields may be added in future releases, so readings can't be built with a struct literal
+/// outside this crate. Start from [`CENTERED`](Self::CENTERED) and use the `with_*` methods.
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct MotionPlusReading {
+    pub yaw: u16,   // 14-bit
+    pub roll: u16,  // 14-bit
+    pub pitch: u16, // 14-bit
+    /// `yaw` is in the slow range
+    pub yaw_slow: bool,
+    /// `roll` is in the slow range
+    pub roll_slow: bool,
+    /// `pitch` is in the slow range
+    pub pitch_slow: bool,
+    /// Another controller is plugged into the Motion Plus extension port
+    pub extension_connected: bool,
+}
+
+impl MotionPlusReading {
+    /// Not rotating, all axes in the slow range and nothing plugged in
+    pub const CENTERED: MotionPlusReading = MotionPlusReading {
+        yaw: GYRO_ZERO,
+        roll: GYRO_ZERO,
+        pitch: GYRO_ZERO,
+        yaw_slow: true,
+        roll_slow: true,
+        pitch_slow: true,
+        extension_connected: false,
+    };
+
+    /// Decode a report
+    ///
+    /// Returns `None` if the report isn't Motion Plus data: bit 1 of the last byte is always
+    /// set in Motion Plus reports.
+    pub const fn from_report(data: &ExtReport) -> Option<MotionPlusReading> {
+        // Bit    7    6    5    4    3    2    1    0
+        // Byte
+        // 0      Yaw<7:0>
+        // 1      Roll<7:0>
+        // 2      Pitch<7:0>
+        // 3      Yaw<13:8>                     YS   PS
+        // 4      Roll<13:8>                    RS   EXT
+        // 5      Pitch<13:8>                   1    0
+        if data[5] & 0b10 == 0 {
+            return None;
+        }
+        const fn axis(low: u8, high: u8) -> u16 {
+            (high as u16 >> 2) << 8 | low as u16
+        }
+        Some(MotionPlusReading {
+            yaw: axis(data[0], data[3]),
+            roll: axis(data[1], data[4]),
+            pitch: axis(data[2], data[5]),
+            yaw_slow: data[3] & 0b10 != 0,
+            roll_slow: data[4] & 0b10 != 0,
+            pitch_slow: data[3] & 0b01 != 0,
+            extension_connected: data[4] & 0b01 != 0,
+        })
+    }
+
+    /// Encode as a report, as the Motion Plus would send it
+    ///
+    /// Axes are truncated to 14 bits. Useful for emulating a Motion Plus.
+    pub const fn to_report(&self) -> ExtReport {
+        const fn high(axis: u16) -> u8 {
+            ((axis & GYRO_MAX) >> 8 << 2) as u8
+        }
+        [
+            self.yaw as u8,
+            self.roll as u8,
+            self.pitch as u8,
+            high(self.yaw) | (self.yaw_slow as u8) << 1 | self.pitch_slow as u8,
+            high(self.roll) | (self.roll_slow as u8) << 1 | self.extension_connected as u8,
+            high(self.pitch) | 0b10,
+        ]
+    }
+
+    /// Yaw rate in hundredths of a degree per second, using the range flag
+    pub const fn yaw_dps(&self) -> i32 {
+        centi_dps(self.yaw, self.yaw_slow)
+    }
+
+    /// Roll rate in hundredths of a degree per second, using the range flag
+    pub const fn roll_dps(&self) -> i32 {
+        centi_dps(self.roll, self.roll_slow)
+    }
+
+    /// Pitch rate in hundredths of a degree per second, using the range flag
+    pub const fn pitch_dps(&self) -> i32 {
+        centi_dps(self.pitch, self.pitch_slow)
+    }
+}
+
+with_fields!(MotionPlusReading {
+    yaw: u16 => with_yaw,
+    roll: u16 => with_roll,
+    pitch: u16 => with_pitch,
+    yaw_slow: bool => with_yaw_slow,
+    roll_slow: bool => with_roll_slow,
+    pitch_slow: bool => with_pitch_slow,
+    extension_connected: bool => with_extension_connected,
+});
+
+/// Convert a raw gyro axis to hundredths of a degree per second
+///
+/// 8192 counts are 595°/s in the slow range, and the fast range is 2000/440 times that.
+const fn centi_dps(raw: u16, slow: bool) -> i32 {
+    let offset = raw as i64 - GYRO_ZERO as i64;
+    let centi_dps = if slow {
+        offset * SLOW_FULL_SCALE_CENTI_DPS / GYRO_ZERO as i64
+    } else {
+        offset * SLOW_FULL_SCALE_CENTI_DPS * 2000 / (GYRO_ZERO as i64 * 440)
+    };
+    centi_dps as i32
+}
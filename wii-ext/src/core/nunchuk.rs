@@ -1,8 +1,21 @@
+use crate::core::classic::ClassicReadingCalibrated;
+use crate::core::driver::Model;
+use crate::core::events::{Axis, Button};
+use crate::core::tilt::TiltStick;
+use crate::core::{
+    apply_deadzone, isqrt, AxisCurve, AxisWindow, ExtReport, RangePolicy, StickCurves,
+    StickPosition,
+};
 #[cfg(feature = "defmt_print")]
 use defmt;
 
+/// Data from a nunchuk after it has been deserialized
+///
+/// Fields may be added in future releases. Struct literals still build, but code that starts
+/// from [`CENTERED`](Self::CENTERED), uses the `with_*` methods and reads fields through their
+/// accessors keeps compiling when they are.
 #[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub struct NunchukReading {
     pub joystick_x: u8,
     pub joystick_y: u8,
@@ -14,19 +27,212 @@ pub struct NunchukReading {
 }
 
 impl NunchukReading {
-    pub fn from_data(data: &[u8]) -> Option<NunchukReading> {
-        if data.len() < 6 {
-            None
-        } else {
-            Some(NunchukReading {
-                joystick_x: data[0],
-                joystick_y: data[1],
-                accel_x: (u16::from(data[2]) << 2) | ((u16::from(data[5]) >> 6) & 0b11),
-                accel_y: (u16::from(data[3]) << 2) | ((u16::from(data[5]) >> 4) & 0b11),
-                accel_z: (u16::from(data[4]) << 2) | ((u16::from(data[5]) >> 2) & 0b11),
-                button_c: (data[5] & 0b10) == 0,
-                button_z: (data[5] & 0b01) == 0,
-            })
+    /// Stick centred, accelerometer at its midpoint and buttons released
+    pub const CENTERED: NunchukReading = NunchukReading {
+        joystick_x: 0x80,
+        joystick_y: 0x80,
+        accel_x: 0x200,
+        accel_y: 0x200,
+        accel_z: 0x200,
+        button_c: false,
+        button_z: false,
+    };
+
+    /// Whether two reports of the same input agree: buttons exactly, and axes to within 1
+    pub(crate) fn agrees_with(&self, other: &NunchukReading) -> bool {
+        self.joystick_x.abs_diff(other.joystick_x) <= 1
+            && self.joystick_y.abs_diff(other.joystick_y) <= 1
+            && self.accel_x.abs_diff(other.accel_x) <= 1
+            && self.accel_y.abs_diff(other.accel_y) <= 1
+            && self.accel_z.abs_diff(other.accel_z) <= 1
+            && self.button_c == other.button_c
+            && self.button_z == other.button_z
+    }
+
+    /// Decode the first 6 bytes of `data` as a report. Shorter slices return `None`
+    pub const fn from_data(data: &[u8]) -> Option<NunchukReading> {
+        match data.first_chunk() {
+            Some(report) => Some(Self::from_report(report)),
+            None => None,
+        }
+    }
+
+    /// Decode a report
+    pub const fn from_report(data: &ExtReport) -> NunchukReading {
+        NunchukReading {
+            joystick_x: data[0],
+            joystick_y: data[1],
+            accel_x: ((data[2] as u16) << 2) | ((data[5] as u16 >> 6) & 0b11),
+            accel_y: ((data[3] as u16) << 2) | ((data[5] as u16 >> 4) & 0b11),
+            accel_z: ((data[4] as u16) << 2) | ((data[5] as u16 >> 2) & 0b11),
+            button_c: (data[5] & 0b10) == 0,
+            button_z: (data[5] & 0b01) == 0,
+        }
+    }
+
+    /// Encode as a report, as the nunchuk would send it
+    ///
+    /// Accelerometer values are truncated to 10 bits. Useful for emulating a nunchuk.
+    pub const fn to_report(&self) -> ExtReport {
+        const fn high(accel: u16) -> u8 {
+            (accel >> 2) as u8
+        }
+        const fn low(accel: u16) -> u8 {
+            (accel & 0b11) as u8
+        }
+        [
+            self.joystick_x,
+            self.joystick_y,
+            high(self.accel_x),
+            high(self.accel_y),
+            high(self.accel_z),
+            low(self.accel_x) << 6
+                | low(self.accel_y) << 4
+                | low(self.accel_z) << 2
+                | (!self.button_c as u8) << 1
+                | !self.button_z as u8,
+        ]
+    }
+
+    /// Squared length of the acceleration vector, in counts relative to 0g ([`ACCEL_ZERO_G`])
+    ///
+    /// Cheaper than `accel_magnitude()`, for comparing against a squared threshold.
+    /// 1g is roughly 200 counts, so a nunchuk at rest gives about 40000.
+    pub const fn accel_magnitude_sq(&self) -> u32 {
+        accel_magnitude_sq(self.accel_x, self.accel_y, self.accel_z)
+    }
+
+    /// Length of the acceleration vector, in counts relative to 0g ([`ACCEL_ZERO_G`])
+    ///
+    /// 1g is roughly 200 counts, so a nunchuk at rest gives about 200 and a falling one
+    /// close to 0.
+    pub const fn accel_magnitude(&self) -> u16 {
+        isqrt(self.accel_magnitude_sq())
+    }
+}
+
+with_fields!(NunchukReading {
+    joystick_x: u8 => with_joystick_x,
+    joystick_y: u8 => with_joystick_y,
+    accel_x: u16 => with_accel_x,
+    accel_y: u16 => with_accel_y,
+    accel_z: u16 => with_accel_z,
+    button_c: bool => with_button_c,
+    button_z: bool => with_button_z,
+});
+
+/// Check for reports that a nunchuk should never produce
+///
+/// Nunchuk reports have no constant bits, but a report of all 0x00 or all 0xFF
+/// means the device is missing, uninitialised or is not a nunchuk.
+pub(crate) fn report_is_sane(data: &[u8]) -> bool {
+    data.len() >= 6 && !data.iter().all(|&b| b == 0x00) && !data.iter().all(|&b| b == 0xFF)
+}
+
+/// Whether no buttons are pressed and the joystick is within `tolerance` of calibration
+///
+/// The accelerometer is ignored, as it depends on how the nunchuk is lying.
+pub(crate) fn report_is_idle(r: &NunchukReading, c: &CalibrationData, tolerance: u8) -> bool {
+    !r.button_c
+        && !r.button_z
+        && r.joystick_x.abs_diff(c.joystick_x) <= tolerance
+        && r.joystick_y.abs_diff(c.joystick_y) <= tolerance
+}
+
+/// Running total of several readings, for averaging out jitter on the analogue axes
+///
+/// Axes are averaged, buttons are reported as pressed if they were pressed in any reading.
+#[derive(Debug, Default)]
+pub(crate) struct NunchukReadingSum {
+    joystick: [u16; 2],
+    accel: [u32; 3],
+    button_c: bool,
+    button_z: bool,
+    count: u16,
+}
+
+impl NunchukReadingSum {
+    pub(crate) fn add(&mut self, r: NunchukReading) {
+        self.joystick[0] += u16::from(r.joystick_x);
+        self.joystick[1] += u16::from(r.joystick_y);
+        self.accel[0] += u32::from(r.accel_x);
+        self.accel[1] += u32::from(r.accel_y);
+        self.accel[2] += u32::from(r.accel_z);
+        self.button_c |= r.button_c;
+        self.button_z |= r.button_z;
+        self.count += 1;
+    }
+
+    /// Get the average of the readings added so far, rounding to the nearest value
+    pub(crate) fn average(self) -> NunchukReading {
+        let count = self.count.max(1);
+        let avg = |sum: u16| ((sum + count / 2) / count) as u8;
+        let count = u32::from(count);
+        let avg_accel = |sum: u32| ((sum + count / 2) / count) as u16;
+        NunchukReading {
+            joystick_x: avg(self.joystick[0]),
+            joystick_y: avg(self.joystick[1]),
+            accel_x: avg_accel(self.accel[0]),
+            accel_y: avg_accel(self.accel[1]),
+            accel_z: avg_accel(self.accel[2]),
+            button_c: self.button_c,
+            button_z: self.button_z,
+        }
+    }
+}
+
+/// How far each analogue input moved while calibrating, from its lowest to highest sample
+///
+/// Returned by `calibrate_for()`. Large values mean the nunchuk wasn't at rest, so the
+/// calibration may be off.
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct CalibrationSpread {
+    pub joystick_x: u8,
+    pub joystick_y: u8,
+    pub accel_x: u16,
+    pub accel_y: u16,
+    pub accel_z: u16,
+}
+
+/// Calibration window for the stick and accelerometer of a nunchuk
+#[derive(Debug, Default)]
+pub(crate) struct CalibrationWindow(AxisWindow<5>);
+
+impl CalibrationWindow {
+    pub(crate) fn add(&mut self, r: &NunchukReading) {
+        self.0.add([
+            u16::from(r.joystick_x),
+            u16::from(r.joystick_y),
+            r.accel_x,
+            r.accel_y,
+            r.accel_z,
+        ]);
+    }
+
+    /// A resting reading made of the trimmed mean of each input
+    pub(crate) fn rest(&self) -> NunchukReading {
+        let [x, y, accel_x, accel_y, accel_z] = self.0.trimmed_mean();
+        NunchukReading {
+            joystick_x: x as u8,
+            joystick_y: y as u8,
+            accel_x,
+            accel_y,
+            accel_z,
+            button_c: false,
+            button_z: false,
+        }
+    }
+
+    pub(crate) fn spread(&self) -> CalibrationSpread {
+        let [x, y, accel_x, accel_y, accel_z] = self.0.spread();
+        CalibrationSpread {
+            joystick_x: x as u8,
+            joystick_y: y as u8,
+            accel_x,
+            accel_y,
+            accel_z,
         }
     }
 }
@@ -35,21 +241,107 @@ impl NunchukReading {
 ///
 /// These are used to calculate the relative deflection of each access from their center point
 #[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub struct CalibrationData {
     pub joystick_x: u8,
     pub joystick_y: u8,
 }
 
+/// Accelerometer readings for 0g on each axis
+///
+/// Captured with the nunchuk lying flat, where X and Y read 0g and Z reads +1g, so Z's zero
+/// point is its resting value minus [`ACCEL_ONE_G`]. Kept apart from [`CalibrationData`], which
+/// only holds the stick centers. The default is [`ACCEL_ZERO_G`] on every axis.
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AccelCalibration {
+    /// 0g reading of each axis, `[x, y, z]`
+    pub zero: [u16; 3],
+}
+
+impl AccelCalibration {
+    /// The nominal 0g point, [`ACCEL_ZERO_G`] on every axis
+    pub const NOMINAL: AccelCalibration = AccelCalibration {
+        zero: [ACCEL_ZERO_G; 3],
+    };
+
+    /// Calibrate from a reading taken with the nunchuk lying flat
+    pub fn from_resting(r: &NunchukReading) -> Self {
+        AccelCalibration {
+            zero: [r.accel_x, r.accel_y, r.accel_z.saturating_sub(ACCEL_ONE_G)],
+        }
+    }
+}
+
+impl Default for AccelCalibration {
+    fn default() -> Self {
+        Self::NOMINAL
+    }
+}
+
+/// Everything the nunchuk driver calibrates against: stick centers and accelerometer zero point
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct NunchukCalibration {
+    pub(crate) sticks: CalibrationData,
+    pub(crate) accel: AccelCalibration,
+}
+
+impl Model for NunchukCalibration {
+    type Reading = NunchukReading;
+    type Calibrated = NunchukReadingCalibrated;
+
+    fn decode(data: &[u8]) -> Option<NunchukReading> {
+        NunchukReading::from_data(data)
+    }
+
+    fn report_is_sane(data: &[u8]) -> bool {
+        report_is_sane(data)
+    }
+
+    fn report_is_idle(&self, reading: &NunchukReading, tolerance: u8) -> bool {
+        report_is_idle(reading, &self.sticks, tolerance)
+    }
+
+    fn from_reading(data: &NunchukReading) -> Self {
+        NunchukCalibration {
+            sticks: CalibrationData {
+                joystick_x: data.joystick_x,
+                joystick_y: data.joystick_y,
+            },
+            accel: AccelCalibration::from_resting(data),
+        }
+    }
+
+    fn calibrate(
+        &self,
+        reading: NunchukReading,
+        policy: RangePolicy,
+        deadzone: u8,
+        curves: &StickCurves,
+    ) -> Result<NunchukReadingCalibrated, Axis> {
+        let mut reading = NunchukReadingCalibrated::with_policy(reading, &self.sticks, policy)?;
+        reading.apply_accel_calibration(&self.accel);
+        reading.apply_deadzone(deadzone);
+        reading.apply_curves(curves);
+        Ok(reading)
+    }
+}
+
 /// Data from a Nunchuk after calibration data has been applied
 ///
 /// Calibration is done by subtracting the resting values from the current
 /// values, which means that going lower on the axis will go negative.
 /// Due to this, we now store analog values as signed integers
 ///
-/// We'll only calibrate the joystick axes, leave accelerometer readings as-is
+/// The accelerometer fields are the raw 10-bit values. `accel_offset` holds the same
+/// readings relative to the 0g point: the nominal one from `new()`, or the nunchuk's own once
+/// [`apply_accel_calibration()`](Self::apply_accel_calibration) is used. The driver does this.
+///
+/// As with [`NunchukReading`], readings can also be built with `default()` and the `with_*`
+/// methods.
 #[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub struct NunchukReadingCalibrated {
     pub joystick_x: i8,
     pub joystick_y: i8,
@@ -58,25 +350,492 @@ pub struct NunchukReadingCalibrated {
     pub accel_z: u16, // 10-bit
     pub button_c: bool,
     pub button_z: bool,
+    /// Acceleration on each axis relative to the 0g point, in counts, `[x, y, z]`
+    ///
+    /// With an [`AccelCalibration`] applied, a nunchuk lying flat reads about
+    /// `[0, 0, ACCEL_ONE_G]`, whichever nunchuk it is.
+    pub accel_offset: [i16; 3],
 }
 
 impl NunchukReadingCalibrated {
+    /// Subtract the calibration from a reading, clamping the stick to the `i8` range
     pub fn new(r: NunchukReading, c: &CalibrationData) -> NunchukReadingCalibrated {
-        /// Just in case `data` minus `calibration data` is out of range, perform all operations
-        /// on i16 and clamp to i8 limits before returning
-        fn ext_u8_sub(a: u8, b: u8) -> i8 {
-            let res = (a as i16) - (b as i16);
-            res.clamp(i8::MIN as i16, i8::MAX as i16) as i8
+        match Self::with_policy(r, c, RangePolicy::Saturate) {
+            Ok(reading) => reading,
+            Err(_) => unreachable!("saturating never fails"),
         }
+    }
 
-        NunchukReadingCalibrated {
-            joystick_x: ext_u8_sub(r.joystick_x, c.joystick_x),
-            joystick_y: ext_u8_sub(r.joystick_y, c.joystick_y),
+    /// Subtract the calibration from a reading, handling stick axes outside the `i8` range as
+    /// `policy` says
+    ///
+    /// Returns the first axis that `policy` rejected. The stick is reported as the left stick.
+    pub fn with_policy(
+        r: NunchukReading,
+        c: &CalibrationData,
+        policy: RangePolicy,
+    ) -> Result<NunchukReadingCalibrated, Axis> {
+        // `data` minus `calibration data` can be out of range, so subtract on i16 and let
+        // the policy fit the result to i8
+        let ext_u8_sub = |axis: Axis, a: u8, b: u8| -> Result<i8, Axis> {
+            policy.fit((a as i16) - (b as i16)).ok_or(axis)
+        };
+
+        Ok(NunchukReadingCalibrated {
+            joystick_x: ext_u8_sub(Axis::LeftX, r.joystick_x, c.joystick_x)?,
+            joystick_y: ext_u8_sub(Axis::LeftY, r.joystick_y, c.joystick_y)?,
             accel_x: r.accel_x,
             accel_y: r.accel_y, // 10-bit
             accel_z: r.accel_z, // 10-bit
             button_c: r.button_c,
             button_z: r.button_z,
+            accel_offset: accel_offset(
+                [r.accel_x, r.accel_y, r.accel_z],
+                AccelCalibration::NOMINAL.zero,
+            ),
+        })
+    }
+}
+
+impl NunchukReadingCalibrated {
+    /// Measure `accel_offset` from this accelerometer calibration instead of the nominal 0g point
+    pub fn apply_accel_calibration(&mut self, accel: &AccelCalibration) {
+        self.accel_offset = accel_offset([self.accel_x, self.accel_y, self.accel_z], accel.zero);
+    }
+
+    /// Report joystick deflections within `deadzone` of center as 0
+    pub fn apply_deadzone(&mut self, deadzone: u8) {
+        self.joystick_x = apply_deadzone(self.joystick_x, deadzone);
+        self.joystick_y = apply_deadzone(self.joystick_y, deadzone);
+    }
+
+    /// Apply a response curve to each joystick axis
+    ///
+    /// The stick uses the curves for the left stick, `LeftX` and `LeftY`, as in
+    /// [`crate::core::events::Axis`]. The other two are ignored.
+    pub fn apply_curves(&mut self, curves: &[AxisCurve; 4]) {
+        self.joystick_x = curves[0].apply(self.joystick_x);
+        self.joystick_y = curves[1].apply(self.joystick_y);
+    }
+
+    /// Position of the stick
+    pub const fn stick(&self) -> StickPosition {
+        StickPosition::new(self.joystick_x, self.joystick_y)
+    }
+
+    /// This reading as a classic controller would report it, with the default [`ClassicShape`]
+    ///
+    /// Lets code written for classic controllers accept a nunchuk: the stick becomes the left
+    /// stick, C becomes A and Z becomes ZL. Everything else is at rest, and the accelerometer
+    /// is dropped.
+    pub fn to_classic_shape(&self) -> ClassicReadingCalibrated {
+        ClassicShape::DEFAULT.convert(self)
+    }
+
+    /// Squared length of the acceleration vector, in counts relative to the calibrated 0g point
+    ///
+    /// This is measured from `accel_offset`, so it doesn't depend on which nunchuk is used.
+    /// Cheaper than `accel_magnitude()`, for comparing against a squared threshold.
+    /// 1g is roughly 200 counts, so a nunchuk at rest gives about 40000.
+    pub const fn accel_magnitude_sq(&self) -> u32 {
+        offset_magnitude_sq(self.accel_offset)
+    }
+
+    /// Length of the acceleration vector, in counts relative to the calibrated 0g point
+    ///
+    /// 1g is roughly 200 counts, so a nunchuk at rest gives about 200 and a falling one
+    /// close to 0.
+    pub const fn accel_magnitude(&self) -> u16 {
+        isqrt(self.accel_magnitude_sq())
+    }
+}
+
+with_fields!(NunchukReadingCalibrated {
+    joystick_x: i8 => with_joystick_x,
+    joystick_y: i8 => with_joystick_y,
+    accel_x: u16 => with_accel_x,
+    accel_y: u16 => with_accel_y,
+    accel_z: u16 => with_accel_z,
+    button_c: bool => with_button_c,
+    button_z: bool => with_button_z,
+    accel_offset: [i16; 3] => with_accel_offset,
+});
+
+/// Each accelerometer axis relative to its 0g reading
+pub(crate) fn accel_offset(accel: [u16; 3], zero: [u16; 3]) -> [i16; 3] {
+    core::array::from_fn(|axis| accel[axis] as i16 - zero[axis] as i16)
+}
+
+/// Accelerometer reading for 0g on each axis, the middle of the 10-bit range
+pub const ACCEL_ZERO_G: u16 = 512;
+/// Nominal change in an accelerometer reading for 1g
+pub const ACCEL_ONE_G: u16 = 200;
+
+/// Squared length of the acceleration vector, in accelerometer counts relative to 0g
+///
+/// Saturates rather than overflowing for values outside the 10-bit range.
+pub(crate) const fn accel_magnitude_sq(x: u16, y: u16, z: u16) -> u32 {
+    const fn square(axis: u16) -> u32 {
+        let offset = axis.abs_diff(ACCEL_ZERO_G) as u32;
+        offset * offset
+    }
+    square(x)
+        .saturating_add(square(y))
+        .saturating_add(square(z))
+}
+
+/// Squared length of a vector of accelerometer offsets from 0g
+///
+/// Saturates rather than overflowing.
+pub(crate) const fn offset_magnitude_sq(offset: [i16; 3]) -> u32 {
+    const fn square(axis: i16) -> u32 {
+        let offset = axis.unsigned_abs() as u32;
+        offset * offset
+    }
+    square(offset[0])
+        .saturating_add(square(offset[1]))
+        .saturating_add(square(offset[2]))
+}
+
+/// How [`NunchukReadingCalibrated::to_classic_shape()`] maps a nunchuk onto a classic controller
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClassicShape {
+    /// Classic controller button reported for C
+    pub c: Button,
+    /// Classic controller button reported for Z
+    pub z: Button,
+}
+
+impl ClassicShape {
+    /// C is A, Z is ZL
+    pub const DEFAULT: Self = Self::new(Button::A, Button::ZL);
+
+    pub const fn new(c: Button, z: Button) -> Self {
+        Self { c, z }
+    }
+
+    /// Convert a nunchuk reading, putting the stick on the left stick
+    ///
+    /// Buttons the classic controller doesn't have are dropped. If C and Z map to the same
+    /// button, it is pressed while either is.
+    pub fn convert(&self, r: &NunchukReadingCalibrated) -> ClassicReadingCalibrated {
+        let mut classic = ClassicReadingCalibrated {
+            joystick_left_x: r.joystick_x,
+            joystick_left_y: r.joystick_y,
+            ..Default::default()
+        };
+        classic.set_button(self.c, r.button_c);
+        classic.set_button(self.z, r.button_z || (self.z == self.c && r.button_c));
+        classic
+    }
+}
+
+impl Default for ClassicShape {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// A change in whether the nunchuk is falling
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FreeFallEvent {
+    Started,
+    Ended,
+}
+
+/// Detects the nunchuk being dropped, from its total acceleration
+///
+/// A falling nunchuk measures close to 0g, where one at rest measures 1g (about 200 counts).
+/// A fall starts once the magnitude has been below `threshold` for `duration` readings in a
+/// row, and ends once it has been back above it for `duration` readings, so single-sample
+/// glitches don't start or end a fall.
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FreeFallDetector {
+    /// Magnitude, in accelerometer counts relative to 0g, below which the nunchuk may be falling
+    pub threshold: u16,
+    /// Consecutive readings needed to start or end a fall
+    pub duration: u8,
+    falling: bool,
+    run: u8,
+}
+
+impl FreeFallDetector {
+    /// About 0.3g, for 3 readings
+    pub const DEFAULT: Self = Self::new(60, 3);
+
+    pub const fn new(threshold: u16, duration: u8) -> Self {
+        Self {
+            threshold,
+            duration,
+            falling: false,
+            run: 0,
+        }
+    }
+
+    /// Whether a fall is in progress
+    pub fn falling(&self) -> bool {
+        self.falling
+    }
+
+    /// Feed the next reading, returning an event if a fall started or ended
+    pub fn update(&mut self, r: &NunchukReadingCalibrated) -> Option<FreeFallEvent> {
+        let threshold = u32::from(self.threshold);
+        let low = r.accel_magnitude_sq() < threshold * threshold;
+        if low == self.falling {
+            self.run = 0;
+            return None;
+        }
+        self.run = self.run.saturating_add(1);
+        if self.run < self.duration.max(1) {
+            return None;
+        }
+        self.run = 0;
+        self.falling = low;
+        Some(if low {
+            FreeFallEvent::Started
+        } else {
+            FreeFallEvent::Ended
+        })
+    }
+}
+
+impl Default for FreeFallDetector {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// A change in whether the nunchuk is being held still
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StationaryEvent {
+    Settled,
+    Moved,
+}
+
+/// Detects the nunchuk being held still, from the spread of its last `N` accelerometer samples
+///
+/// The nunchuk is stationary once `N` readings have been seen and, on every axis, the largest
+/// and smallest of the last `N` differ by no more than `threshold` counts. Use it to gate
+/// operations that need the nunchuk at rest, such as taking a new zero point.
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StationaryDetector<const N: usize> {
+    /// Largest spread, in accelerometer counts, still considered stationary
+    pub threshold: u16,
+    window: [[u16; 3]; N],
+    len: usize,
+    next: usize,
+    stationary: bool,
+}
+
+impl<const N: usize> StationaryDetector<N> {
+    /// A spread of 4 counts, enough to ignore sensor noise
+    pub const DEFAULT: Self = Self::new(4);
+
+    pub const fn new(threshold: u16) -> Self {
+        Self {
+            threshold,
+            window: [[0; 3]; N],
+            len: 0,
+            next: 0,
+            stationary: false,
+        }
+    }
+
+    /// Whether the nunchuk has been still for the whole window
+    pub fn is_stationary(&self) -> bool {
+        self.stationary
+    }
+
+    /// Largest minus smallest value of each axis over the window, `[x, y, z]`
+    pub fn spread(&self) -> [u16; 3] {
+        let samples = &self.window[..self.len];
+        core::array::from_fn(|axis| {
+            let values = samples.iter().map(|sample| sample[axis]);
+            let max = values.clone().max().unwrap_or(0);
+            let min = values.min().unwrap_or(0);
+            max - min
+        })
+    }
+
+    /// Feed the next reading, returning an event if the nunchuk settled or started moving
+    pub fn update(&mut self, r: &NunchukReadingCalibrated) -> Option<StationaryEvent> {
+        if N == 0 {
+            return None;
+        }
+        self.window[self.next] = [r.accel_x, r.accel_y, r.accel_z];
+        self.next = (self.next + 1) % N;
+        self.len = (self.len + 1).min(N);
+        let stationary =
+            self.len == N && self.spread().iter().all(|&spread| spread <= self.threshold);
+        if stationary == self.stationary {
+            return None;
+        }
+        self.stationary = stationary;
+        Some(if stationary {
+            StationaryEvent::Settled
+        } else {
+            StationaryEvent::Moved
+        })
+    }
+
+    /// Forget the samples seen so far, eg: after the nunchuk is reconnected
+    ///
+    /// This doesn't generate a [`StationaryEvent::Moved`] event.
+    pub fn reset(&mut self) {
+        *self = Self::new(self.threshold);
+    }
+}
+
+impl<const N: usize> Default for StationaryDetector<N> {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Where [`PointerEmulator`] takes pointer movement from
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PointerSource {
+    Stick,
+    /// Tilting the nunchuk, measured by a [`TiltStick`]
+    Tilt(TiltStick),
+}
+
+/// Relative mouse movement and buttons, ready for a HID mouse report
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PointerReport {
+    /// Movement right
+    pub dx: i8,
+    /// Movement down
+    pub dy: i8,
+    /// Scrolling up
+    pub wheel: i8,
+    /// The C button
+    pub left: bool,
+    /// The Z button, unless it is held to scroll
+    pub right: bool,
+}
+
+/// Turns successive nunchuk readings into mouse movement
+///
+/// Each reading moves the pointer by up to `sensitivity` counts on each axis, in proportion to
+/// the deflection after `curve`. Fractions of a count carry over to the next reading, so small
+/// deflections still move the pointer slowly. C is the left button and Z the right.
+///
+/// With `scroll_with_z`, holding Z scrolls instead of moving the pointer: pushing up scrolls
+/// up, at up to `scroll_sensitivity` counts per reading, and Z isn't reported as a button.
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PointerEmulator {
+    pub source: PointerSource,
+    /// Counts per reading at full deflection
+    pub sensitivity: u8,
+    /// Acceleration curve. `AxisCurve::Expo` gives fine control near center and fast movement
+    /// at full deflection
+    pub curve: AxisCurve,
+    /// Deflections up to this are ignored, so a stick at rest doesn't drift the pointer
+    pub deadzone: u8,
+    pub scroll_with_z: bool,
+    /// Wheel counts per reading at full deflection
+    pub scroll_sensitivity: u8,
+    /// Fractions of a count carried over, for dx, dy and wheel
+    remainder: [i16; 3],
+}
+
+impl PointerEmulator {
+    /// Stick input at up to 10 counts per reading, with Z held to scroll
+    pub const DEFAULT: Self = Self::new(PointerSource::Stick);
+
+    pub const fn new(source: PointerSource) -> Self {
+        Self {
+            source,
+            sensitivity: 10,
+            curve: AxisCurve::Linear,
+            deadzone: 4,
+            scroll_with_z: true,
+            scroll_sensitivity: 2,
+            remainder: [0; 3],
         }
     }
+
+    /// Feed the next reading, returning the pointer movement since the last one
+    pub fn update(&mut self, r: &NunchukReadingCalibrated) -> PointerReport {
+        let (x, y) = match &self.source {
+            PointerSource::Stick => (r.joystick_x, r.joystick_y),
+            PointerSource::Tilt(tilt) => tilt.tilt(r),
+        };
+        let scrolling = self.scroll_with_z && r.button_z;
+        let mut report = PointerReport {
+            left: r.button_c,
+            right: r.button_z && !scrolling,
+            ..PointerReport::default()
+        };
+        if scrolling {
+            report.wheel = self.scale(2, y, self.scroll_sensitivity);
+            self.remainder[..2].fill(0);
+        } else {
+            report.dx = self.scale(0, x, self.sensitivity);
+            // Pushing the stick up moves the pointer up the screen, which is negative
+            report.dy = self.scale(1, y.saturating_neg(), self.sensitivity);
+            self.remainder[2] = 0;
+        }
+        report
+    }
+
+    /// Scale a deflection to counts, carrying the fraction over in `self.remainder[axis]`
+    fn scale(&mut self, axis: usize, value: i8, sensitivity: u8) -> i8 {
+        let value = apply_deadzone(value, self.deadzone);
+        if value == 0 {
+            self.remainder[axis] = 0;
+            return 0;
+        }
+        let full = i32::from(i8::MAX);
+        let scaled = i32::from(self.curve.apply(value)) * i32::from(sensitivity)
+            + i32::from(self.remainder[axis]);
+        self.remainder[axis] = (scaled % full) as i16;
+        (scaled / full).clamp(-full, full) as i8
+    }
+}
+
+impl Default for PointerEmulator {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Build a nunchuk report from a description of its fields
+///
+/// Starts from [`NunchukReading::CENTERED`], or from the report after `..`, then sets each
+/// [`NunchukReading`] field listed. A field without a value is set to `true`, for buttons.
+/// The result is encoded with [`NunchukReading::to_report()`], and can be used in constants:
+///
+/// ```
+/// # use wii_ext::nunchuk_report;
+/// const BTN_C: [u8; 6] = nunchuk_report! { button_c, accel_z: 0x2C0 };
+/// ```
+#[macro_export]
+macro_rules! nunchuk_report {
+    (@value) => { true };
+    (@value $value:expr) => { $value };
+    (..$base:expr $(, $field:ident $(: $value:expr)?)* $(,)?) => {{
+        #[allow(unused_mut)]
+        let mut reading = match $crate::core::nunchuk::NunchukReading::from_data(&$base) {
+            ::core::option::Option::Some(reading) => reading,
+            ::core::option::Option::None => panic!("not a nunchuk report"),
+        };
+        $(reading.$field = $crate::nunchuk_report!(@value $($value)?);)*
+        reading.to_report()
+    }};
+    ($($field:ident $(: $value:expr)?),* $(,)?) => {{
+        #[allow(unused_mut)]
+        let mut reading = $crate::core::nunchuk::NunchukReading::CENTERED;
+        $(reading.$field = $crate::nunchuk_report!(@value $($value)?);)*
+        reading.to_report()
+    }};
 }
@@ -0,0 +1,95 @@
+//! Q15 fixed-point axes, for control loops that work in fixed-point
+//!
+//! Sticks are converted to [`I1F15`], in -1..1, and triggers to [`U0F16`], in 0..1.
+//! The conversions use the same extents and scaling as [`crate::core::scale`]: the Q15 value
+//! of an axis is the result of `axis_to_i16()` divided by 32768, and a trigger's is the result
+//! of `trigger_to_u16()` divided by 65536. The two paths always agree, bit for bit.
+
+use crate::core::classic::ClassicReadingCalibrated;
+use crate::core::nunchuk::NunchukReadingCalibrated;
+use crate::core::scale::{
+    axis_to_i16, trigger_to_u16, AxisExtents, ClassicExtents, NunchukExtents,
+};
+pub use fixed::types::{I1F15, U0F16};
+
+/// Convert a calibrated stick axis to Q15
+pub const fn axis_to_q15(value: i8, extents: AxisExtents) -> I1F15 {
+    I1F15::from_bits(axis_to_i16(value, extents))
+}
+
+/// Convert a calibrated trigger to a 0..1 fraction
+pub const fn trigger_to_u0f16(value: i8, extent: u8) -> U0F16 {
+    U0F16::from_bits(trigger_to_u16(value, extent))
+}
+
+/// A classic controller's axes in fixed-point
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ClassicAxesQ15 {
+    pub joystick_left_x: I1F15,
+    pub joystick_left_y: I1F15,
+    pub joystick_right_x: I1F15,
+    pub joystick_right_y: I1F15,
+    pub trigger_left: U0F16,
+    pub trigger_right: U0F16,
+}
+
+/// A nunchuk's stick axes in fixed-point
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct NunchukAxesQ15 {
+    pub joystick_x: I1F15,
+    pub joystick_y: I1F15,
+}
+
+impl ClassicReadingCalibrated {
+    /// Convert the sticks to Q15 and the triggers to 0..1 fractions, using these extents
+    pub const fn to_q15(&self, extents: &ClassicExtents) -> ClassicAxesQ15 {
+        ClassicAxesQ15 {
+            joystick_left_x: axis_to_q15(self.joystick_left_x, extents.joystick_left_x),
+            joystick_left_y: axis_to_q15(self.joystick_left_y, extents.joystick_left_y),
+            joystick_right_x: axis_to_q15(self.joystick_right_x, extents.joystick_right_x),
+            joystick_right_y: axis_to_q15(self.joystick_right_y, extents.joystick_right_y),
+            trigger_left: trigger_to_u0f16(self.trigger_left, extents.trigger_left),
+            trigger_right: trigger_to_u0f16(self.trigger_right, extents.trigger_right),
+        }
+    }
+
+    /// The left stick's X axis in Q15, over the whole i8 range
+    pub const fn joystick_left_x_q15(&self) -> I1F15 {
+        axis_to_q15(self.joystick_left_x, AxisExtents::FULL)
+    }
+
+    /// The left stick's Y axis in Q15, over the whole i8 range
+    pub const fn joystick_left_y_q15(&self) -> I1F15 {
+        axis_to_q15(self.joystick_left_y, AxisExtents::FULL)
+    }
+
+    /// The right stick's X axis in Q15, over the whole i8 range
+    pub const fn joystick_right_x_q15(&self) -> I1F15 {
+        axis_to_q15(self.joystick_right_x, AxisExtents::FULL)
+    }
+
+    /// The right stick's Y axis in Q15, over the whole i8 range
+    pub const fn joystick_right_y_q15(&self) -> I1F15 {
+        axis_to_q15(self.joystick_right_y, AxisExtents::FULL)
+    }
+}
+
+impl NunchukReadingCalibrated {
+    /// Convert the stick to Q15, using these extents
+    pub const fn to_q15(&self, extents: &NunchukExtents) -> NunchukAxesQ15 {
+        NunchukAxesQ15 {
+            joystick_x: axis_to_q15(self.joystick_x, extents.joystick_x),
+            joystick_y: axis_to_q15(self.joystick_y, extents.joystick_y),
+        }
+    }
+
+    /// The stick's X axis in Q15, over the whole i8 range
+    pub const fn joystick_x_q15(&self) -> I1F15 {
+        axis_to_q15(self.joystick_x, AxisExtents::FULL)
+    }
+
+    /// The stick's Y axis in Q15, over the whole i8 range
+    pub const fn joystick_y_q15(&self) -> I1F15 {
+        axis_to_q15(self.joystick_y, AxisExtents::FULL)
+    }
+}
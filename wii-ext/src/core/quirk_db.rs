@@ -0,0 +1,164 @@
+//! Built-in table of controllers that need [`Quirks`], keyed by controller ID
+//!
+//! Many controllers share the Classic Pro ID, so entries can also match a fingerprint of the
+//! controller's first report: a byte mask and the values the masked bytes must have. The first
+//! matching entry wins. With `ClassicBuilder::known_quirks(true)`, the builder reads the ID and a
+//! report during `build()` and applies the matching entry's quirks.
+//!
+//! Entries for other controllers can be added at compile time, and are checked before the
+//! built-in ones:
+//!
+//! ```ignore
+//! const MY_QUIRKS: &[QuirkEntry] = &[QuirkEntry {
+//!     name: "Knock-off pad",
+//!     id: [0, 0, 0xA4, 0x20, 1, 1],
+//!     fingerprint: None,
+//!     quirks: Quirks::NONE.invert_button(Button::ZL),
+//!     capabilities: Capabilities::FULL,
+//! }];
+//! let classic = ClassicBuilder::new().quirk_table(MY_QUIRKS).build(i2c, delay)?;
+//! ```
+
+use crate::core::classic::Quirks;
+use crate::core::{ControllerIdReport, ExtReport};
+
+/// The inputs and modes a controller supports
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Capabilities {
+    /// The controller has analogue sticks, rather than reporting them fixed at center
+    pub sticks: bool,
+    /// The controller's shoulder buttons report analogue positions
+    pub analog_triggers: bool,
+    /// The controller supports the hi-resolution report format
+    pub hires: bool,
+}
+
+impl Capabilities {
+    /// Everything a genuine Classic controller supports
+    pub const FULL: Self = Self {
+        sticks: true,
+        analog_triggers: true,
+        hires: true,
+    };
+}
+
+impl Default for Capabilities {
+    fn default() -> Self {
+        Self::FULL
+    }
+}
+
+/// Bytes of a standard report that identify a controller: `report[i] & mask[i] == value[i]`
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fingerprint {
+    pub mask: ExtReport,
+    pub value: ExtReport,
+}
+
+impl Fingerprint {
+    pub const fn matches(&self, report: &ExtReport) -> bool {
+        let mut i = 0;
+        while i < report.len() {
+            if report[i] & self.mask[i] != self.value[i] {
+                return false;
+            }
+            i += 1;
+        }
+        true
+    }
+}
+
+/// A controller that needs quirks, and how to recognise it
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuirkEntry {
+    /// Description, for diagnostics
+    pub name: &'static str,
+    /// The controller ID, read from 0xFA
+    pub id: ControllerIdReport,
+    /// Report pattern to tell this controller apart from others with the same ID
+    pub fingerprint: Option<Fingerprint>,
+    pub quirks: Quirks,
+    pub capabilities: Capabilities,
+}
+
+impl QuirkEntry {
+    /// Whether this entry describes a controller with this ID and first report
+    pub const fn matches(&self, id: &ControllerIdReport, report: &ExtReport) -> bool {
+        let mut i = 0;
+        while i < id.len() {
+            if id[i] != self.id[i] {
+                return false;
+            }
+            i += 1;
+        }
+        match &self.fingerprint {
+            Some(fingerprint) => fingerprint.matches(report),
+            None => true,
+        }
+    }
+}
+
+const CLASSIC_PRO_ID: ControllerIdReport = [1, 0, 0xA4, 0x20, 1, 1];
+
+/// Controllers without analogue sticks or triggers
+const NO_STICKS: Capabilities = Capabilities {
+    sticks: false,
+    analog_triggers: false,
+    hires: true,
+};
+
+const IGNORE_TRIGGERS: Quirks = Quirks {
+    ignore_analog_triggers: true,
+    ..Quirks::NONE
+};
+
+/// Known controllers that need quirks
+pub const KNOWN_QUIRKS: &[QuirkEntry] = &[
+    QuirkEntry {
+        name: "NES Classic Mini controller",
+        id: CLASSIC_PRO_ID,
+        // The sticks and triggers are reported at fixed values
+        fingerprint: Some(Fingerprint {
+            mask: [0xFF, 0xFF, 0xFF, 0xFF, 0, 0],
+            value: [95, 223, 143, 0, 0, 0],
+        }),
+        quirks: IGNORE_TRIGGERS,
+        capabilities: NO_STICKS,
+    },
+    QuirkEntry {
+        name: "SNES Classic Mini controller",
+        id: CLASSIC_PRO_ID,
+        fingerprint: Some(Fingerprint {
+            mask: [0xFF, 0xFF, 0xFF, 0xFF, 0, 0],
+            value: [160, 33, 16, 0, 0, 0],
+        }),
+        quirks: IGNORE_TRIGGERS,
+        capabilities: NO_STICKS,
+    },
+    QuirkEntry {
+        name: "Classic Pro style controller (eg: PDP Link)",
+        id: CLASSIC_PRO_ID,
+        // Analogue triggers at 0. The shoulder buttons move them to full when clicked
+        fingerprint: Some(Fingerprint {
+            mask: [0, 0, 0x60, 0xFF, 0, 0],
+            value: [0; 6],
+        }),
+        quirks: IGNORE_TRIGGERS,
+        capabilities: Capabilities {
+            analog_triggers: false,
+            ..Capabilities::FULL
+        },
+    },
+];
+
+/// Find the first entry in `table` matching this ID and first report
+pub fn find_quirks<'a>(
+    table: &'a [QuirkEntry],
+    id: &ControllerIdReport,
+    report: &ExtReport,
+) -> Option<&'a QuirkEntry> {
+    table.iter().find(|entry| entry.matches(id, report))
+}
@@ -0,0 +1,179 @@
+//! Raw reports that format as fixed-width hex, for comparing with the tables on wiibrew
+//!
+//! Plain byte arrays format as decimal, which is hard to line up with bit tables:
+//!
+//! ```ignore
+//! let report = RawReport(test_data::CLASSIC_IDLE);
+//! assert_eq!(format!("{report}"), "[61 E0 91 63 FF FF]");
+//! // Button bits are active low
+//! assert!(report.bit(5, 4)); // A released
+//! ```
+//!
+//! [`RawReport`] is a transparent wrapper, so it costs nothing over the array it holds.
+
+use crate::core::{ExtAltReport, ExtHdReport, ExtReport};
+use core::fmt;
+
+/// Bytes of any length that format as `[61 E0 91 63 FF FF]`
+///
+/// Useful for printing register dumps and other buffers that aren't a whole report.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct HexBytes<'a>(pub &'a [u8]);
+
+impl fmt::Display for HexBytes<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("[")?;
+        for (i, byte) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{byte:02X}")?;
+        }
+        f.write_str("]")
+    }
+}
+
+impl fmt::Debug for HexBytes<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+#[cfg(feature = "defmt_print")]
+impl defmt::Format for HexBytes<'_> {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(f, "[");
+        for (i, byte) in self.0.iter().enumerate() {
+            if i > 0 {
+                defmt::write!(f, " ");
+            }
+            defmt::write!(f, "{=u8:02X}", byte);
+        }
+        defmt::write!(f, "]");
+    }
+}
+
+/// A raw report of `N` bytes, formatted as fixed-width hex
+///
+/// Debug and Display give `[61 E0 91 63 FF FF]`, as does defmt.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct RawReport<const N: usize>(pub [u8; N]);
+
+/// A raw standard report
+pub type RawExtReport = RawReport<6>;
+/// A raw high-resolution report
+pub type RawExtHdReport = RawReport<8>;
+/// A raw classic controller report in data format 0x02
+pub type RawExtAltReport = RawReport<9>;
+
+impl<const N: usize> RawReport<N> {
+    /// The report's bytes
+    pub const fn bytes(&self) -> &[u8; N] {
+        &self.0
+    }
+
+    /// Whether bit `bit` (0 is the least significant) of byte `byte` is set
+    ///
+    /// Panics if `byte` is past the end of the report or `bit` is over 7.
+    pub const fn bit(&self, byte: usize, bit: u8) -> bool {
+        assert!(bit < 8, "bit out of range");
+        self.0[byte] & (1 << bit) != 0
+    }
+
+    /// Whether every byte of the report is `value`, eg: 0x00 or 0xFF for a blank report
+    pub fn is_all(&self, value: u8) -> bool {
+        self.0.iter().all(|&b| b == value)
+    }
+}
+
+impl<const N: usize> From<[u8; N]> for RawReport<N> {
+    fn from(report: [u8; N]) -> Self {
+        Self(report)
+    }
+}
+
+impl<const N: usize> From<RawReport<N>> for [u8; N] {
+    fn from(report: RawReport<N>) -> Self {
+        report.0
+    }
+}
+
+impl<const N: usize> AsRef<[u8]> for RawReport<N> {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl<const N: usize> fmt::Display for RawReport<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&HexBytes(&self.0), f)
+    }
+}
+
+impl<const N: usize> fmt::Debug for RawReport<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&HexBytes(&self.0), f)
+    }
+}
+
+#[cfg(feature = "defmt_print")]
+impl<const N: usize> defmt::Format for RawReport<N> {
+    fn format(&self, f: defmt::Formatter) {
+        HexBytes(&self.0).format(f)
+    }
+}
+
+/// A raw report in whichever format the controller sent it
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RawFrame {
+    /// A 6 byte standard report
+    Standard(RawExtReport),
+    /// An 8 byte high-resolution report
+    HiRes(RawExtHdReport),
+    /// A 9 byte data format 0x02 report
+    Alt(RawExtAltReport),
+}
+
+impl RawFrame {
+    /// Wrap a report, or `None` if `data` isn't 6, 8 or 9 bytes long
+    pub fn from_slice(data: &[u8]) -> Option<Self> {
+        if let Ok(report) = ExtReport::try_from(data) {
+            Some(Self::Standard(RawReport(report)))
+        } else if let Ok(report) = ExtHdReport::try_from(data) {
+            Some(Self::HiRes(RawReport(report)))
+        } else if let Ok(report) = ExtAltReport::try_from(data) {
+            Some(Self::Alt(RawReport(report)))
+        } else {
+            None
+        }
+    }
+
+    /// The report's bytes
+    pub fn bytes(&self) -> &[u8] {
+        match self {
+            Self::Standard(report) => report.as_ref(),
+            Self::HiRes(report) => report.as_ref(),
+            Self::Alt(report) => report.as_ref(),
+        }
+    }
+}
+
+impl fmt::Display for RawFrame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&HexBytes(self.bytes()), f)
+    }
+}
+
+impl fmt::Debug for RawFrame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&HexBytes(self.bytes()), f)
+    }
+}
+
+#[cfg(feature = "defmt_print")]
+impl defmt::Format for RawFrame {
+    fn format(&self, f: defmt::Formatter) {
+        HexBytes(self.bytes()).format(f)
+    }
+}
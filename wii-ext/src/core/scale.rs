@@ -0,0 +1,168 @@
+//! Scaling calibrated axes to the full 16 bit range, for HID reports and game APIs
+//!
+//! Calibrated readings are relative to the resting position, and most controllers can't reach
+//! the same deflection in both directions. Scaling with the reachable extent of each side, as
+//! measured on the controller, maps the end of travel to the end of the 16 bit range in both
+//! directions:
+//!
+//! ```ignore
+//! let extents = ClassicExtents {
+//!     joystick_left_x: AxisExtents { negative: 92, positive: 101 },
+//!     ..Default::default()
+//! };
+//! let axes = classic.read()?.to_i16_full_scale(&extents);
+//! ```
+//!
+//! Values past the extents saturate, so the results never overflow.
+
+use crate::core::classic::ClassicReadingCalibrated;
+use crate::core::nunchuk::NunchukReadingCalibrated;
+
+/// How far a calibrated axis can move from center in each direction
+///
+/// Both are magnitudes, so an axis that reaches -90 and +100 is `{ negative: 90, positive: 100 }`.
+/// An extent of 0 means the axis can't move that way, and it scales to 0.
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AxisExtents {
+    pub negative: u8,
+    pub positive: u8,
+}
+
+impl AxisExtents {
+    /// The whole i8 range, so -128 and 127 map to the ends of the i16 range
+    pub const FULL: Self = Self {
+        negative: 128,
+        positive: 127,
+    };
+}
+
+impl Default for AxisExtents {
+    fn default() -> Self {
+        Self::FULL
+    }
+}
+
+/// Scale a calibrated stick axis to -32768..=32767
+///
+/// `-extents.negative` maps to -32768, 0 to 0 and `extents.positive` to 32767.
+pub const fn axis_to_i16(value: i8, extents: AxisExtents) -> i16 {
+    let (magnitude, extent, full) = if value < 0 {
+        (
+            value.unsigned_abs(),
+            extents.negative,
+            i16::MIN.unsigned_abs() as u32,
+        )
+    } else {
+        (value as u8, extents.positive, i16::MAX as u32)
+    };
+    if extent == 0 {
+        return 0;
+    }
+    let magnitude = if magnitude > extent {
+        extent
+    } else {
+        magnitude
+    };
+    let scaled = magnitude as u32 * full / extent as u32;
+    if value < 0 {
+        (-(scaled as i32)) as i16
+    } else {
+        scaled as i16
+    }
+}
+
+/// Scale a calibrated trigger to 0..=65535
+///
+/// 0 and below map to 0, and `extent` to 65535. An extent of 0 always gives 0.
+pub const fn trigger_to_u16(value: i8, extent: u8) -> u16 {
+    if value <= 0 || extent == 0 {
+        return 0;
+    }
+    let value = if value as u8 > extent {
+        extent
+    } else {
+        value as u8
+    };
+    (value as u32 * u16::MAX as u32 / extent as u32) as u16
+}
+
+/// Extents of each axis of a classic controller
+///
+/// Triggers only move one way, so they have a single extent.
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClassicExtents {
+    pub joystick_left_x: AxisExtents,
+    pub joystick_left_y: AxisExtents,
+    pub joystick_right_x: AxisExtents,
+    pub joystick_right_y: AxisExtents,
+    pub trigger_left: u8,
+    pub trigger_right: u8,
+}
+
+impl Default for ClassicExtents {
+    /// The whole i8 range for the sticks, and up to 127 for the triggers
+    fn default() -> Self {
+        Self {
+            joystick_left_x: AxisExtents::FULL,
+            joystick_left_y: AxisExtents::FULL,
+            joystick_right_x: AxisExtents::FULL,
+            joystick_right_y: AxisExtents::FULL,
+            trigger_left: i8::MAX as u8,
+            trigger_right: i8::MAX as u8,
+        }
+    }
+}
+
+/// Extents of each axis of a nunchuk's stick
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct NunchukExtents {
+    pub joystick_x: AxisExtents,
+    pub joystick_y: AxisExtents,
+}
+
+/// A classic controller's axes, scaled to the full 16 bit range
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ClassicAxesI16 {
+    pub joystick_left_x: i16,
+    pub joystick_left_y: i16,
+    pub joystick_right_x: i16,
+    pub joystick_right_y: i16,
+    pub trigger_left: u16,
+    pub trigger_right: u16,
+}
+
+/// A nunchuk's stick axes, scaled to the full 16 bit range
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct NunchukAxesI16 {
+    pub joystick_x: i16,
+    pub joystick_y: i16,
+}
+
+impl ClassicReadingCalibrated {
+    /// Scale the sticks to -32768..=32767 and the triggers to 0..=65535, using these extents
+    pub const fn to_i16_full_scale(&self, extents: &ClassicExtents) -> ClassicAxesI16 {
+        ClassicAxesI16 {
+            joystick_left_x: axis_to_i16(self.joystick_left_x, extents.joystick_left_x),
+            joystick_left_y: axis_to_i16(self.joystick_left_y, extents.joystick_left_y),
+            joystick_right_x: axis_to_i16(self.joystick_right_x, extents.joystick_right_x),
+            joystick_right_y: axis_to_i16(self.joystick_right_y, extents.joystick_right_y),
+            trigger_left: trigger_to_u16(self.trigger_left, extents.trigger_left),
+            trigger_right: trigger_to_u16(self.trigger_right, extents.trigger_right),
+        }
+    }
+}
+
+impl NunchukReadingCalibrated {
+    /// Scale the stick to -32768..=32767, using these extents
+    pub const fn to_i16_full_scale(&self, extents: &NunchukExtents) -> NunchukAxesI16 {
+        NunchukAxesI16 {
+            joystick_x: axis_to_i16(self.joystick_x, extents.joystick_x),
+            joystick_y: axis_to_i16(self.joystick_y, extents.joystick_y),
+        }
+    }
+}
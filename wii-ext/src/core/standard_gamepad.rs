@@ -0,0 +1,137 @@
+//! The "standard gamepad" layout used by SDL and the Web Gamepad API
+//!
+//! Buttons and axes are identified by their index in that layout, so readings can be handed
+//! to emulator frontends and browsers without each application inventing its own mapping.
+//!
+//! Classic controllers map their shoulder buttons to the bumpers and ZL/ZR to the triggers,
+//! as SDL does. Only the digital part of L and R is reported, and there are no stick buttons.
+//! Face buttons can be mapped by position or by label, see [`FaceButtons`].
+//!
+//! Nunchuks are mapped as if they were the left half of a gamepad: the stick is the left stick,
+//! C is the left bumper and Z is the left trigger.
+
+use crate::core::classic::ClassicReadingCalibrated;
+use crate::core::nunchuk::NunchukReadingCalibrated;
+
+/// Bottom face button
+pub const SOUTH: usize = 0;
+/// Right face button
+pub const EAST: usize = 1;
+/// Left face button
+pub const WEST: usize = 2;
+/// Top face button
+pub const NORTH: usize = 3;
+pub const LEFT_BUMPER: usize = 4;
+pub const RIGHT_BUMPER: usize = 5;
+pub const LEFT_TRIGGER: usize = 6;
+pub const RIGHT_TRIGGER: usize = 7;
+/// Select, back or minus
+pub const SELECT: usize = 8;
+/// Start, forward or plus
+pub const START: usize = 9;
+pub const LEFT_STICK: usize = 10;
+pub const RIGHT_STICK: usize = 11;
+pub const DPAD_UP: usize = 12;
+pub const DPAD_DOWN: usize = 13;
+pub const DPAD_LEFT: usize = 14;
+pub const DPAD_RIGHT: usize = 15;
+/// Home or guide
+pub const HOME: usize = 16;
+/// Number of buttons in the layout
+pub const BUTTON_COUNT: usize = 17;
+
+/// Left stick, positive is right
+pub const LEFT_X: usize = 0;
+/// Left stick, positive is down
+pub const LEFT_Y: usize = 1;
+/// Right stick, positive is right
+pub const RIGHT_X: usize = 2;
+/// Right stick, positive is down
+pub const RIGHT_Y: usize = 3;
+/// Number of axes in the layout
+pub const AXIS_COUNT: usize = 4;
+
+/// How the classic controller's A/B/X/Y buttons are mapped to the face button indices
+///
+/// Nintendo puts A on the right and B at the bottom, the opposite of the controllers the
+/// standard layout was modelled on, and the same goes for X and Y.
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum FaceButtons {
+    /// Map by where the buttons are: B is south, A is east, Y is west and X is north
+    ///
+    /// Games get the button in the position they expect, so "confirm on the bottom button"
+    /// works as designed. This is what SDL does by default.
+    #[default]
+    Position,
+    /// Map by what the buttons say: A is south, B is east, X is west and Y is north
+    ///
+    /// Prompts that name a button match the label on the controller, but the buttons are
+    /// in mirrored positions.
+    Label,
+}
+
+/// Buttons and axes in the standard gamepad layout
+///
+/// Index `buttons` and `axes` with the constants in this module. Axes are relative to
+/// calibration, with positive Y pointing down as in the standard layout.
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct StandardGamepad {
+    pub buttons: [bool; BUTTON_COUNT],
+    pub axes: [i8; AXIS_COUNT],
+}
+
+impl StandardGamepad {
+    /// Map a classic controller reading, choosing how the face buttons are mapped
+    pub fn from_classic(r: &ClassicReadingCalibrated, face_buttons: FaceButtons) -> Self {
+        let mut pad = Self::default();
+        let (a, b, x, y) = match face_buttons {
+            FaceButtons::Position => (EAST, SOUTH, NORTH, WEST),
+            FaceButtons::Label => (SOUTH, EAST, WEST, NORTH),
+        };
+        pad.buttons[a] = r.button_a;
+        pad.buttons[b] = r.button_b;
+        pad.buttons[x] = r.button_x;
+        pad.buttons[y] = r.button_y;
+        pad.buttons[LEFT_BUMPER] = r.button_trigger_l;
+        pad.buttons[RIGHT_BUMPER] = r.button_trigger_r;
+        pad.buttons[LEFT_TRIGGER] = r.button_zl;
+        pad.buttons[RIGHT_TRIGGER] = r.button_zr;
+        pad.buttons[SELECT] = r.button_minus;
+        pad.buttons[START] = r.button_plus;
+        pad.buttons[DPAD_UP] = r.dpad_up;
+        pad.buttons[DPAD_DOWN] = r.dpad_down;
+        pad.buttons[DPAD_LEFT] = r.dpad_left;
+        pad.buttons[DPAD_RIGHT] = r.dpad_right;
+        pad.buttons[HOME] = r.button_home;
+        pad.axes[LEFT_X] = r.joystick_left_x;
+        pad.axes[LEFT_Y] = r.joystick_left_y.saturating_neg();
+        pad.axes[RIGHT_X] = r.joystick_right_x;
+        pad.axes[RIGHT_Y] = r.joystick_right_y.saturating_neg();
+        pad
+    }
+
+    /// Whether the button at `index` is pressed. Out of range indices are never pressed
+    pub fn pressed(&self, index: usize) -> bool {
+        self.buttons.get(index).copied().unwrap_or(false)
+    }
+}
+
+impl From<&ClassicReadingCalibrated> for StandardGamepad {
+    /// Map a classic controller reading, with face buttons mapped by position
+    fn from(r: &ClassicReadingCalibrated) -> Self {
+        Self::from_classic(r, FaceButtons::Position)
+    }
+}
+
+impl From<&NunchukReadingCalibrated> for StandardGamepad {
+    fn from(r: &NunchukReadingCalibrated) -> Self {
+        let mut pad = Self::default();
+        pad.buttons[LEFT_BUMPER] = r.button_c;
+        pad.buttons[LEFT_TRIGGER] = r.button_z;
+        pad.axes[LEFT_X] = r.joystick_x;
+        pad.axes[LEFT_Y] = r.joystick_y.saturating_neg();
+        pad
+    }
+}
@@ -0,0 +1,93 @@
+//! Steering the nunchuk's joystick axes by tilting it
+//!
+//! [`TiltStick`] turns the accelerometer into a second joystick: rolling the nunchuk right
+//! moves X positive, and pitching it forward (tipping the front down) moves Y positive, like
+//! pushing the stick forward. Whichever of the tilt and the physical stick is deflected further
+//! is reported, in the normal joystick fields:
+//!
+//! ```ignore
+//! let tilt = TiltStick::default();
+//! let input = tilt.apply(nunchuk.read()?);
+//! move_cursor(input.joystick_x, input.joystick_y);
+//! ```
+//!
+//! Tilt is taken from how far the accelerometer's X and Y readings have moved from their
+//! values with the nunchuk held level. That offset follows the sine of the tilt angle, so
+//! no trigonometry is needed and the response is close to linear for moderate angles.
+
+use crate::core::nunchuk::NunchukReadingCalibrated;
+
+/// Converts nunchuk tilt into joystick deflection
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TiltStick {
+    /// Accelerometer X reading with the nunchuk level
+    pub level_x: u16,
+    /// Accelerometer Y reading with the nunchuk level
+    pub level_y: u16,
+    /// Accelerometer offset from level that gives full deflection. Lower is more sensitive
+    ///
+    /// The accelerometer reads roughly 200 counts per g, so the default of 100 is full
+    /// deflection at about 30 degrees of tilt.
+    pub full_scale: u16,
+    /// Offsets from level up to this are ignored, so a nunchuk held roughly level stays centered
+    pub deadband: u16,
+}
+
+impl TiltStick {
+    /// Level at the middle of the 10-bit range, full deflection at about 30 degrees
+    pub const DEFAULT: Self = Self {
+        level_x: 512,
+        level_y: 512,
+        full_scale: 100,
+        deadband: 10,
+    };
+
+    /// Take the level position from a reading of the nunchuk held level
+    pub fn set_level(&mut self, r: &NunchukReadingCalibrated) {
+        self.level_x = r.accel_x;
+        self.level_y = r.accel_y;
+    }
+
+    /// The joystick deflection from the tilt in this reading, as `(x, y)`
+    pub fn tilt(&self, r: &NunchukReadingCalibrated) -> (i8, i8) {
+        (
+            self.axis(i32::from(r.accel_x) - i32::from(self.level_x)),
+            // Tipping the front down lowers the Y reading
+            self.axis(i32::from(self.level_y) - i32::from(r.accel_y)),
+        )
+    }
+
+    /// Replace the joystick axes with the tilt, if the tilt is the larger deflection
+    ///
+    /// Deflection is compared by the larger of each stick's two axes, so touching the stick
+    /// takes over from the tilt as soon as it moves further.
+    pub fn apply(&self, mut r: NunchukReadingCalibrated) -> NunchukReadingCalibrated {
+        let (x, y) = self.tilt(&r);
+        let tilt = x.unsigned_abs().max(y.unsigned_abs());
+        let stick = r.joystick_x.unsigned_abs().max(r.joystick_y.unsigned_abs());
+        if tilt > stick {
+            r.joystick_x = x;
+            r.joystick_y = y;
+        }
+        r
+    }
+
+    fn axis(&self, offset: i32) -> i8 {
+        let deadband = i32::from(self.deadband);
+        let range = (i32::from(self.full_scale) - deadband).max(1);
+        let magnitude = (offset.abs() - deadband).max(0);
+        let value = (magnitude * i32::from(i8::MAX) / range).min(i32::from(i8::MAX)) as i8;
+        if offset < 0 {
+            -value
+        } else {
+            value
+        }
+    }
+}
+
+impl Default for TiltStick {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
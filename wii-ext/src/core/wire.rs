@@ -0,0 +1,199 @@
+//! A compact, versioned binary encoding of calibrated readings
+//!
+//! Intended for streaming controller state to a host, eg: over a serial port, without needing
+//! serde. Multi-byte fields are little-endian, and the first byte of every message is
+//! [`VERSION`], so decoders can reject data in a format they don't understand.
+//!
+//! Classic controller ([`CLASSIC_SIZE`] bytes):
+//!
+//! | Byte | Contents |
+//! |------|----------|
+//! | 0    | [`VERSION`] |
+//! | 1-2  | Buttons, one bit each, bit n set if the [`Button`](crate::core::events::Button) with discriminant n is pressed: A, B, X, Y, L, R, ZL, ZR, Minus, Plus, Home, DpadUp, DpadDown, DpadLeft, DpadRight. Bit 15 is reserved, and 0 |
+//! | 3    | `joystick_left_x` (i8) |
+//! | 4    | `joystick_left_y` (i8) |
+//! | 5    | `joystick_right_x` (i8) |
+//! | 6    | `joystick_right_y` (i8) |
+//! | 7    | `trigger_left` (i8) |
+//! | 8    | `trigger_right` (i8) |
+//!
+//! Nunchuk ([`NUNCHUK_SIZE`] bytes):
+//!
+//! | Byte | Contents |
+//! |------|----------|
+//! | 0    | [`VERSION`] |
+//! | 1    | Buttons: bit 0 is C, bit 1 is Z, the rest are reserved and 0 |
+//! | 2    | `joystick_x` (i8) |
+//! | 3    | `joystick_y` (i8) |
+//! | 4-7  | Accelerometer as a 32-bit word: `accel_x` in bits 0-9, `accel_y` in bits 10-19, `accel_z` in bits 20-29. Bits 30 and 31 are reserved, and 0 |
+//!
+//! [`CompactClassic`] and [`CompactNunchuk`] hold the same fields in memory, without the
+//! version byte, for keeping many readings in a RAM buffer.
+
+use crate::core::classic::ClassicReadingCalibrated;
+use crate::core::events::Inputs;
+use crate::core::nunchuk::{accel_offset, NunchukReadingCalibrated, ACCEL_ZERO_G};
+
+/// Format version written by `to_wire()`, and the only one accepted by `from_wire()`
+pub const VERSION: u8 = 1;
+/// Size of an encoded classic controller reading
+pub const CLASSIC_SIZE: usize = 9;
+/// Size of an encoded nunchuk reading
+pub const NUNCHUK_SIZE: usize = 8;
+
+/// Largest value of the nunchuk's 10-bit accelerometer axes
+const ACCEL_MAX: u16 = 0x3FF;
+
+/// A classic controller reading in 8 bytes
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[repr(C)]
+pub struct CompactClassic {
+    /// Buttons, in the same bit order as the wire format
+    pub buttons: u16,
+    /// `joystick_left_x`, `joystick_left_y`, `joystick_right_x`, `joystick_right_y`,
+    /// `trigger_left` and `trigger_right`
+    pub axes: [i8; 6],
+}
+
+impl CompactClassic {
+    /// Size in bytes, for sizing buffers
+    pub const SIZE: usize = core::mem::size_of::<Self>();
+}
+
+/// A nunchuk reading in 8 bytes
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[repr(C)]
+pub struct CompactNunchuk {
+    /// Buttons, in the same bit order as the wire format
+    pub buttons: u8,
+    /// `joystick_x` and `joystick_y`
+    pub stick: [i8; 2],
+    /// Accelerometer axes packed 10 bits each, as in the wire format
+    pub accel: u32,
+}
+
+impl CompactNunchuk {
+    /// Size in bytes, for sizing buffers
+    pub const SIZE: usize = core::mem::size_of::<Self>();
+}
+
+impl ClassicReadingCalibrated {
+    /// Encode this reading, see the [module docs](crate::core::wire) for the layout
+    pub fn to_wire(&self) -> [u8; CLASSIC_SIZE] {
+        let compact = self.to_compact();
+        let buttons = compact.buttons.to_le_bytes();
+        let axes = compact.axes.map(|axis| axis as u8);
+        [
+            VERSION, buttons[0], buttons[1], axes[0], axes[1], axes[2], axes[3], axes[4], axes[5],
+        ]
+    }
+
+    /// Decode a reading encoded by [`to_wire()`](Self::to_wire)
+    ///
+    /// Returns `None` if the data is in a different format version.
+    pub fn from_wire(data: &[u8; CLASSIC_SIZE]) -> Option<Self> {
+        if data[0] != VERSION {
+            return None;
+        }
+        let mut axes = [0; 6];
+        for (axis, &byte) in axes.iter_mut().zip(&data[3..]) {
+            *axis = byte as i8;
+        }
+        Some(Self::from_compact(&CompactClassic {
+            buttons: u16::from_le_bytes([data[1], data[2]]),
+            axes,
+        }))
+    }
+
+    /// Pack this reading for storage, see [`CompactClassic`]
+    pub fn to_compact(&self) -> CompactClassic {
+        CompactClassic {
+            buttons: self.buttons_u16(),
+            axes: [
+                self.joystick_left_x,
+                self.joystick_left_y,
+                self.joystick_right_x,
+                self.joystick_right_y,
+                self.trigger_left,
+                self.trigger_right,
+            ],
+        }
+    }
+
+    /// Unpack a reading packed by [`to_compact()`](Self::to_compact)
+    pub fn from_compact(compact: &CompactClassic) -> Self {
+        let mut reading = Self::default();
+        for (&axis, &value) in Self::AXES.iter().zip(&compact.axes) {
+            reading.set_axis(axis, value);
+        }
+        reading.apply_buttons_u16(compact.buttons);
+        reading
+    }
+}
+
+impl NunchukReadingCalibrated {
+    /// Encode this reading, see the [module docs](crate::core::wire) for the layout
+    ///
+    /// Accelerometer values above 10 bits are clamped to 1023.
+    pub fn to_wire(&self) -> [u8; NUNCHUK_SIZE] {
+        let compact = self.to_compact();
+        let accel = compact.accel.to_le_bytes();
+        [
+            VERSION,
+            compact.buttons,
+            compact.stick[0] as u8,
+            compact.stick[1] as u8,
+            accel[0],
+            accel[1],
+            accel[2],
+            accel[3],
+        ]
+    }
+
+    /// Decode a reading encoded by [`to_wire()`](Self::to_wire)
+    ///
+    /// Returns `None` if the data is in a different format version.
+    pub fn from_wire(data: &[u8; NUNCHUK_SIZE]) -> Option<Self> {
+        if data[0] != VERSION {
+            return None;
+        }
+        Some(Self::from_compact(&CompactNunchuk {
+            buttons: data[1],
+            stick: [data[2] as i8, data[3] as i8],
+            accel: u32::from_le_bytes([data[4], data[5], data[6], data[7]]),
+        }))
+    }
+
+    /// Pack this reading for storage, see [`CompactNunchuk`]
+    ///
+    /// Accelerometer values above 10 bits are clamped to 1023.
+    pub fn to_compact(&self) -> CompactNunchuk {
+        let accel = |value: u16| u32::from(value.min(ACCEL_MAX));
+        CompactNunchuk {
+            buttons: u8::from(self.button_c) | u8::from(self.button_z) << 1,
+            stick: [self.joystick_x, self.joystick_y],
+            accel: accel(self.accel_x) | accel(self.accel_y) << 10 | accel(self.accel_z) << 20,
+        }
+    }
+
+    /// Unpack a reading packed by [`to_compact()`](Self::to_compact)
+    ///
+    /// The packed reading doesn't carry the accelerometer calibration, so `accel_offset` is
+    /// relative to the nominal 0g point, [`ACCEL_ZERO_G`].
+    pub fn from_compact(compact: &CompactNunchuk) -> Self {
+        let accel = |shift: u32| (compact.accel >> shift) as u16 & ACCEL_MAX;
+        let (accel_x, accel_y, accel_z) = (accel(0), accel(10), accel(20));
+        Self {
+            joystick_x: compact.stick[0],
+            joystick_y: compact.stick[1],
+            accel_x,
+            accel_y,
+            accel_z,
+            button_c: compact.buttons & 1 != 0,
+            button_z: compact.buttons & 1 << 1 != 0,
+            accel_offset: accel_offset([accel_x, accel_y, accel_z], [ACCEL_ZERO_G; 3]),
+        }
+    }
+}
@@ -0,0 +1,46 @@
+//! Controller drivers, written once and generated for both the blocking and async interfaces
+//!
+//! Each driver is an async module annotated with `maybe_async_cfg::maybe`, which expands it into
+//! a `blocking` module (with the `async`/`.await` removed, using the blocking interface) and an
+//! `asynchronous` module. `blocking_impl` and `async_impl` re-export the generated items under
+//! their usual names, so a fix made here applies to both.
+//!
+//! Anything only one of the implementations has is marked with `maybe_async_cfg::only_if`.
+
+pub(crate) mod classic;
+pub(crate) mod nunchuk;
+
+use core::future::Future;
+
+/// The hook a blocking driver's `read_with_recovery_hook()` calls
+pub(crate) trait RecoveryHook {
+    fn call(&mut self, bus_errors: u8);
+}
+
+impl<F: FnMut(u8)> RecoveryHook for F {
+    fn call(&mut self, bus_errors: u8) {
+        self(bus_errors)
+    }
+}
+
+/// The hook an async driver's `read_with_recovery_hook()` calls and awaits
+pub(crate) trait RecoveryHookAsync {
+    async fn call(&mut self, bus_errors: u8);
+}
+
+impl<F: FnMut(u8) -> Fut, Fut: Future<Output = ()>> RecoveryHookAsync for F {
+    async fn call(&mut self, bus_errors: u8) {
+        self(bus_errors).await
+    }
+}
+
+/// No hook, for `read_with_recovery()`
+pub(crate) struct NoHook;
+
+impl RecoveryHook for NoHook {
+    fn call(&mut self, _bus_errors: u8) {}
+}
+
+impl RecoveryHookAsync for NoHook {
+    async fn call(&mut self, _bus_errors: u8) {}
+}
@@ -0,0 +1,1504 @@
+#[maybe_async_cfg::maybe(
+    idents(
+        blocking_impl(sync, async = "async_impl"),
+        Interface(sync, async = "InterfaceAsync"),
+        embedded_hal(sync, async = "embedded_hal_async"),
+        RecoveryHook(sync, async = "RecoveryHookAsync"),
+    ),
+    sync(self = "blocking"),
+    async(self = "asynchronous")
+)]
+pub(crate) mod classic_impl {
+    #[maybe_async_cfg::only_if(async)]
+    use crate::async_impl::interface::with_timeout;
+    use crate::blocking_impl::interface::Interface;
+    use crate::core::classic::{
+        CalibrationData, CalibrationSpread, CalibrationWindow, ClassicReading,
+        ClassicReadingCalibrated, ClassicReadingSum, Dynamic, HiRes, ModeChangeOptions, Quirks,
+        ReportFormat, ReportMode, Resolution, SocdCleaner, SocdPolicy, Standard, TriggerRange,
+        TriggerThresholds, TriggerTracker,
+    };
+    use crate::core::clock::{Clock, Timestamped};
+    use crate::core::driver::{DriverState, HookTrigger};
+    use crate::core::events::Axis;
+    use crate::core::quirk_db::{find_quirks, Capabilities, QuirkEntry, KNOWN_QUIRKS};
+    use crate::core::raw::RawFrame;
+    use crate::core::{
+        stick_curve_index, AxisCurve, CheckFailures, ControllerType, ExtAltReport, ExtHdReport,
+        ExtReport, IdentityCheck, PollInterval, PollLimiter, RangePolicy, Ready, RetryPolicy,
+        Stats, StickCurves, StuckCheck, Throttle, Timing, Uninit, INTERMESSAGE_DELAY_MICROSEC_U32,
+        STRICT_STUCK_FRAMES,
+    };
+    use crate::drivers::{NoHook, RecoveryHook};
+    use crate::{BatchError, Error};
+    #[maybe_async_cfg::only_if(async)]
+    use core::future::Future;
+    use core::marker::PhantomData;
+    use embedded_hal::i2c::{I2c, SevenBitAddress};
+
+    #[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+    #[derive(Debug, Default)]
+    pub struct Classic<I2C, DELAY, STATE = Ready, MODE = Dynamic> {
+        interface: Interface<I2C, DELAY>,
+        format: ReportFormat,
+        state: DriverState<CalibrationData>,
+        limiter: PollLimiter<ClassicReading>,
+        triggers: TriggerTracker,
+        trigger_range: TriggerRange,
+        socd: SocdCleaner,
+        quirks: Quirks,
+        capabilities: Capabilities,
+        init_state: PhantomData<STATE>,
+        mode: PhantomData<MODE>,
+    }
+
+    /// A classic controller driver whose report format is chosen at runtime
+    ///
+    /// This is what `Classic::new()` and the builder return. Use `into_standard()` or
+    /// `into_hires()` to get a driver with the report format fixed in its type.
+    pub type DynClassic<I2C, DELAY> = Classic<I2C, DELAY, Ready, Dynamic>;
+
+    impl<I2C, DELAY, STATE, MODE> Classic<I2C, DELAY, STATE, MODE> {
+        /// Move the driver to another state or mode, keeping everything else
+        fn into_state<S, M>(self) -> Classic<I2C, DELAY, S, M> {
+            Classic {
+                interface: self.interface,
+                format: self.format,
+                state: self.state,
+                limiter: self.limiter,
+                triggers: self.triggers,
+                trigger_range: self.trigger_range,
+                socd: self.socd,
+                quirks: self.quirks,
+                capabilities: self.capabilities,
+                init_state: PhantomData,
+                mode: PhantomData,
+            }
+        }
+    }
+
+    impl<I2C, E, DELAY> Classic<I2C, DELAY, Uninit>
+    where
+        I2C: I2c<SevenBitAddress, Error = E>,
+        DELAY: embedded_hal::delay::DelayNs,
+    {
+        /// Create a driver without talking to the controller
+        ///
+        /// The driver can't be read from until `init()` has been called, which returns a driver
+        /// in the [`Ready`] state.
+        pub fn new_uninit(i2cdev: I2C, delay: DELAY) -> Self {
+            Classic::new_unchecked(i2cdev, delay).into_state()
+        }
+
+        /// Send the init sequence to the controller and calibrate it
+        pub async fn init(self) -> Result<Classic<I2C, DELAY, Ready>, Error<E>> {
+            let mut classic = self.assume_ready();
+            classic.init().await?;
+            Ok(classic)
+        }
+
+        /// Send the init sequence to the controller without calibrating it
+        ///
+        /// The driver starts with default calibration. Call `update_calibration()` once the
+        /// controller is known to be at rest.
+        pub async fn init_no_calibration(self) -> Result<Classic<I2C, DELAY, Ready>, Error<E>> {
+            let mut classic = self.assume_ready();
+            classic.init_no_calibration().await?;
+            Ok(classic)
+        }
+
+        /// Treat the controller as already initialised, without talking to it
+        ///
+        /// This is for resuming after sleep when the controller is still powered and configured.
+        /// The same hazards as for `new_unchecked()` apply.
+        pub fn assume_ready(self) -> Classic<I2C, DELAY, Ready> {
+            self.into_state()
+        }
+
+        /// Destroy this driver, recovering the i2c bus and delay used to create it
+        pub fn destroy(self) -> (I2C, DELAY) {
+            self.interface.destroy()
+        }
+    }
+
+    impl<I2C, E, DELAY> Classic<I2C, DELAY, Ready>
+    where
+        I2C: I2c<SevenBitAddress, Error = E>,
+        DELAY: embedded_hal::delay::DelayNs,
+    {
+        /// Create a new Wii Classic Controller
+        ///
+        /// This sends the init sequence to the controller and calibrates it,
+        /// so the driver is ready to read from as soon as it is returned.
+        pub async fn new(i2cdev: I2C, delay: DELAY) -> Result<Self, Error<E>> {
+            ClassicBuilder::new().build(i2cdev, delay).await
+        }
+
+        /// Create a new driver, making up to `attempts` attempts if the classic isn't ready yet
+        ///
+        /// This is `new()` with the whole init sequence and calibration retried, waiting
+        /// `Timing::CONSERVATIVE.settle_us` between attempts. Use `init_retry_policy()` on the
+        /// builder to choose the wait. The error from the last attempt is returned.
+        pub async fn new_with_retries(
+            i2cdev: I2C,
+            delay: DELAY,
+            attempts: u8,
+        ) -> Result<Self, Error<E>> {
+            ClassicBuilder::new()
+                .init_retry_policy(RetryPolicy::new(attempts, Timing::CONSERVATIVE.settle_us))
+                .build(i2cdev, delay)
+                .await
+        }
+
+        /// Create a driver for an extension controller that has already been initialised
+        ///
+        /// No bus operations are performed, so this is suitable for resuming after sleep
+        /// when the controller is still powered and configured.
+        ///
+        /// Hazards: the driver trusts that the controller has already had the init sequence
+        /// sent, and starts with default calibration. If the controller was power-cycled or
+        /// swapped, reads will fail or return garbage. Use `set_calibration()` and `assume_hires()`
+        /// to restore the state the driver had before sleeping.
+        pub fn new_unchecked(i2cdev: I2C, delay: DELAY) -> Self {
+            Self {
+                interface: Interface::new(i2cdev, delay),
+                format: ReportFormat::default(),
+                state: DriverState::default(),
+                limiter: PollLimiter::default(),
+                triggers: TriggerTracker::default(),
+                trigger_range: TriggerRange::NONE,
+                socd: SocdCleaner::default(),
+                quirks: Quirks::NONE,
+                capabilities: Capabilities::FULL,
+                init_state: PhantomData,
+                mode: PhantomData,
+            }
+        }
+
+        /// Tell the driver which report format the controller is already using
+        ///
+        /// This does not touch the bus. It is intended for use with `new_unchecked()`, when the
+        /// controller was left in hi-resolution mode before the driver was created.
+        /// If this does not match the controller's actual mode, reads will return garbage.
+        pub fn assume_hires(&mut self, hires: bool) {
+            self.format.assume_hires(hires);
+        }
+
+        /// Switch the driver from standard to hi-resolution reporting
+        ///
+        /// This enables the controllers high-resolution report data mode, which returns each
+        /// analogue axis as a u8, rather than packing smaller integers in a structure.
+        /// If your controllers supports this mode, you should use it. It is much better.
+        ///
+        /// Nothing is done if the driver is already in hi-resolution mode, so this is safe to
+        /// call defensively. Use `enable_hires_with()` to force the write or skip recalibration.
+        pub async fn enable_hires(&mut self) -> Result<(), Error<E>> {
+            self.enable_hires_with(ModeChangeOptions::DEFAULT).await
+        }
+
+        /// Switch the driver to hi-resolution reporting, with the given options
+        ///
+        /// See [`ModeChangeOptions`]. Without `force`, nothing is done if the driver is already
+        /// in hi-resolution mode. Without `recalibrate`, the calibration is left as it was,
+        /// which is only right for hi-resolution reports if it is replaced with
+        /// `set_calibration()` before reading.
+        pub async fn enable_hires_with(
+            &mut self,
+            options: ModeChangeOptions,
+        ) -> Result<(), Error<E>> {
+            if self.format.hires() && !options.force {
+                return Ok(());
+            }
+            self.save_report_format().await;
+            self.interface.enable_hires().await?;
+            self.format.set_mode(ReportMode::HiRes);
+            if options.recalibrate {
+                self.update_calibration().await?;
+            }
+            Ok(())
+        }
+
+        /// Switch the controller to another report format
+        ///
+        /// 0xFE is read back to check the controller accepted the format, and calibration is
+        /// updated for the new format, as with `enable_hires()`. If the controller reads back a
+        /// different value, this returns `Error::WriteVerifyFailed` and the driver keeps
+        /// decoding the previous format. The format is written even if the driver is already
+        /// using it. Use `set_report_mode_with()` to skip the write or the recalibration.
+        ///
+        /// `ReportMode::Unknown` returns `Error::InvalidInputData` without touching the bus, as
+        /// the driver can't decode its reports. Use `write_register()` to try other formats.
+        pub async fn set_report_mode(&mut self, mode: ReportMode) -> Result<(), Error<E>> {
+            self.set_report_mode_with(mode, ModeChangeOptions::DEFAULT.with_force(true))
+                .await
+        }
+
+        /// Switch the controller to another report format, with the given options
+        ///
+        /// See [`ModeChangeOptions`]. Without `force`, nothing is done if the driver is already
+        /// using `mode`. Without `recalibrate`, the calibration is left as it was, which is only
+        /// right for the new format if it is replaced with `set_calibration()` before reading.
+        ///
+        /// When the driver was built with `verify_writes()`, the write is already read back as
+        /// it is made, so 0xFE isn't read a second time.
+        pub async fn set_report_mode_with(
+            &mut self,
+            mode: ReportMode,
+            options: ModeChangeOptions,
+        ) -> Result<(), Error<E>> {
+            if mode.report_len().is_none() {
+                return Err(Error::InvalidInputData);
+            }
+            if self.format.mode() == mode && !options.force {
+                return Ok(());
+            }
+            let expected = mode.register_value();
+            self.save_report_format().await;
+            self.interface.set_report_mode(expected).await?;
+            if !self.interface.verifies_writes() {
+                let got = self.read_register(0xFE).await?;
+                if got != expected {
+                    self.state.check_failures.register_write = true;
+                    return Err(Error::WriteVerifyFailed {
+                        addr: 0xFE,
+                        expected,
+                        got,
+                    });
+                }
+            }
+            self.format.set_mode(mode);
+            if options.recalibrate {
+                self.update_calibration().await?;
+            }
+            Ok(())
+        }
+
+        /// Switch the driver from hi-resolution to standard reporting reporting
+        ///
+        /// This disables the controllers high-resolution report data mode
+        /// It is assumed that all controllers use 0x01 as the 'standard' mode.
+        /// This has only been confirmed for classic and pro-classic controller.
+        ///
+        /// This function does not work.
+        /// TODO: work out why, make it public when it works
+        #[maybe_async_cfg::only_if(sync)]
+        #[allow(dead_code)]
+        fn disable_hires(&mut self, options: ModeChangeOptions) -> Result<(), Error<E>> {
+            if self.format.mode() == ReportMode::Standard && !options.force {
+                return Ok(());
+            }
+            self.save_report_format();
+            self.interface.disable_hires()?;
+            self.format.set_mode(ReportMode::Standard);
+            if options.recalibrate {
+                self.update_calibration()?;
+            }
+            Ok(())
+        }
+
+        /// Fix the report format in the driver's type, if the controller is using standard reports
+        ///
+        /// The driver is returned unchanged if it is in any other mode.
+        // Handing the driver back is the point of the error, and boxing it needs an allocator
+        #[allow(clippy::result_large_err)]
+        pub fn into_standard(self) -> Result<Classic<I2C, DELAY, Ready, Standard>, Self> {
+            if self.format.mode() == ReportMode::Standard {
+                Ok(self.into_state())
+            } else {
+                Err(self)
+            }
+        }
+
+        /// Fix the report format in the driver's type, if the controller is using hi-resolution reports
+        ///
+        /// The driver is returned unchanged if it is in any other mode.
+        #[allow(clippy::result_large_err)]
+        pub fn into_hires(self) -> Result<Classic<I2C, DELAY, Ready, HiRes>, Self> {
+            if self.format.hires() {
+                Ok(self.into_state())
+            } else {
+                Err(self)
+            }
+        }
+    }
+
+    impl<I2C, E, DELAY> Classic<I2C, DELAY, Ready, Standard>
+    where
+        I2C: I2c<SevenBitAddress, Error = E>,
+        DELAY: embedded_hal::delay::DelayNs,
+    {
+        /// Switch the controller to hi-resolution reporting
+        ///
+        /// This returns a driver that decodes hi-resolution reports, with calibration updated
+        /// for the new format.
+        pub async fn enable_hires(mut self) -> Result<Classic<I2C, DELAY, Ready, HiRes>, Error<E>> {
+            self.save_report_format().await;
+            self.interface.enable_hires().await?;
+            self.format.set_mode(ReportMode::HiRes);
+            let mut classic: Classic<I2C, DELAY, Ready, HiRes> = self.into_state();
+            classic.update_calibration().await?;
+            Ok(classic)
+        }
+
+        /// Let the report format be chosen at runtime again
+        pub fn into_dyn(self) -> DynClassic<I2C, DELAY> {
+            self.into_state()
+        }
+    }
+
+    impl<I2C, E, DELAY> Classic<I2C, DELAY, Ready, HiRes>
+    where
+        I2C: I2c<SevenBitAddress, Error = E>,
+        DELAY: embedded_hal::delay::DelayNs,
+    {
+        /// Let the report format be chosen at runtime again
+        pub fn into_dyn(self) -> DynClassic<I2C, DELAY> {
+            self.into_state()
+        }
+    }
+
+    impl<I2C, E, DELAY, MODE> Classic<I2C, DELAY, Ready, MODE>
+    where
+        I2C: I2c<SevenBitAddress, Error = E>,
+        DELAY: embedded_hal::delay::DelayNs,
+        MODE: Resolution,
+    {
+        /// Get the calibration currently used by this driver
+        ///
+        /// This can be persisted and restored with `set_calibration()`
+        pub fn calibration(&self) -> CalibrationData {
+            self.state.calibration
+        }
+
+        /// Replace the calibration used by this driver, eg: with one saved earlier
+        pub fn set_calibration(&mut self, calibration: CalibrationData) {
+            self.state.calibration = calibration;
+        }
+
+        /// Calibrate from a reading the caller already has, without touching the bus
+        ///
+        /// `reading` should be uncalibrated, taken with the controller at rest. This is what
+        /// `update_calibration()` does with the sample it reads.
+        pub fn update_calibration_from(&mut self, reading: &ClassicReading) {
+            self.state.update_calibration(reading);
+        }
+
+        /// Report joystick deflections within `deadzone` of center as 0 in calibrated reads
+        pub fn set_deadzone(&mut self, deadzone: u8) {
+            self.state.deadzone = deadzone;
+        }
+
+        /// Choose what calibrated reads do with axes that end up outside the `i8` range
+        ///
+        /// See [`RangePolicy`]. The default clamps them.
+        pub fn set_range_policy(&mut self, policy: RangePolicy) {
+            self.state.range_policy = policy;
+        }
+
+        /// Keep reads made with `read_throttled()` at least `interval.min_us` apart, or `None` for
+        /// no limit
+        pub fn set_min_poll_interval(&mut self, interval: Option<PollInterval>) {
+            self.limiter.set_interval(interval);
+        }
+
+        /// Read each sample twice, and only return it if both reports agree
+        ///
+        /// This catches corruption on long or noisy wiring that still produces a valid-looking
+        /// report, at the cost of a second read. Buttons must match exactly, while axes may
+        /// differ by 1 so that movement between the reads isn't rejected. If the reports
+        /// disagree, one more is read and compared with the second, and if that disagrees too
+        /// the read fails with `Error::InconsistentData`. Applies to `read()` and the reads
+        /// built on it, but not to `begin_read()`/`finish_read()`. Each report counts towards
+        /// stuck detection.
+        pub fn set_verify_reads(&mut self, verify_reads: bool) {
+            self.state.verify_reads = verify_reads;
+        }
+
+        /// Apply a response curve to a stick axis in calibrated reads
+        ///
+        /// Trigger axes have no curve, and setting one does nothing.
+        pub fn set_axis_curve(&mut self, axis: Axis, curve: AxisCurve) {
+            if let Some(i) = stick_curve_index(axis) {
+                self.state.curves[i] = curve;
+            }
+        }
+
+        /// Get the corrections applied to each reading
+        pub fn quirks(&self) -> Quirks {
+            self.quirks
+        }
+
+        /// Correct each reading for a controller that doesn't follow the usual report format
+        ///
+        /// This doesn't update the calibration, so call `update_calibration()` afterwards if
+        /// the quirks affect the sticks or triggers.
+        pub fn set_quirks(&mut self, quirks: Quirks) {
+            self.quirks = quirks;
+        }
+
+        /// What the controller supports, as far as the driver knows
+        ///
+        /// This is [`Capabilities::FULL`] unless the builder found the controller in a quirk table.
+        pub fn capabilities(&self) -> Capabilities {
+            self.capabilities
+        }
+
+        /// Set where each analogue trigger engages and releases, see [`TriggerThresholds`]
+        pub fn set_trigger_thresholds(
+            &mut self,
+            left: TriggerThresholds,
+            right: TriggerThresholds,
+        ) {
+            self.triggers.left = left;
+            self.triggers.right = right;
+        }
+
+        /// Get the full-press point of each analogue trigger
+        ///
+        /// This can be persisted and restored with `set_trigger_range()`
+        pub fn trigger_range(&self) -> TriggerRange {
+            self.trigger_range
+        }
+
+        /// Replace the full-press points used to scale the triggers, see [`TriggerRange`]
+        pub fn set_trigger_range(&mut self, range: TriggerRange) {
+            self.trigger_range = range;
+        }
+
+        /// Choose how opposing d-pad directions held together are reported in calibrated reads
+        ///
+        /// See [`SocdPolicy`]. The default reports them as the controller does.
+        pub fn set_socd_policy(&mut self, policy: SocdPolicy) {
+            self.socd = SocdCleaner::new(policy);
+        }
+
+        /// Whether the left trigger was engaged in the last calibrated read
+        ///
+        /// The analogue value is compared with the trigger thresholds. The click bit also
+        /// counts, for controllers without analogue triggers.
+        pub fn trigger_left_engaged(&self) -> bool {
+            self.triggers.left_engaged()
+        }
+
+        /// Whether the right trigger was engaged in the last calibrated read
+        ///
+        /// See `trigger_left_engaged()`.
+        pub fn trigger_right_engaged(&self) -> bool {
+            self.triggers.right_engaged()
+        }
+
+        /// The raw bytes of the most recent report, as received from the controller
+        ///
+        /// This is the report behind the last reading, or the one that made the last read fail,
+        /// for logging unexpected input. [`RawFrame`] tells which format it was, and formats as
+        /// hex. `None` until the first report is read. Raw reads with `read_raw_into()` aren't
+        /// kept.
+        pub fn last_raw(&self) -> Option<RawFrame> {
+            self.state.last_report.get().and_then(RawFrame::from_slice)
+        }
+
+        /// Get the read statistics collected since the driver was created or `reset_stats()` was called
+        pub fn stats(&self) -> Stats {
+            self.state.stats
+        }
+
+        /// Clear the read statistics
+        pub fn reset_stats(&mut self) {
+            self.state.stats = Stats::default();
+        }
+
+        /// Get the validation checks that have failed since the driver was created
+        ///
+        /// Report checks are only made in strict mode, see `strict()` on the builder. The
+        /// other checks are recorded whenever they are enabled.
+        pub fn check_failures(&self) -> CheckFailures {
+            let mut failures = self.state.check_failures;
+            failures.register_write |= self.interface.write_verify_failed();
+            failures
+        }
+
+        /// Destroy this driver, recovering the i2c bus and delay used to create it
+        pub fn destroy(self) -> (I2C, DELAY) {
+            self.interface.destroy()
+        }
+
+        /// Return the controller to its power-on report format, then release the i2c bus and delay
+        ///
+        /// If the driver changed the report format (eg: with `enable_hires()`), the 0xFE value read
+        /// before the first change is written back so that a Wiimote or console can use the
+        /// controller again (0x01, standard reports, if 0xFE couldn't be read). Otherwise this is
+        /// the same as `destroy()`.
+        /// Encryption is not re-enabled, as the handshake can't be reversed. A Wiimote sends its
+        /// own init sequence when the controller is connected to it, so this is not a problem.
+        pub async fn shutdown(mut self) -> Result<(I2C, DELAY), Error<E>> {
+            if let Some(value) = self.format.power_on() {
+                self.interface.write_register(0xFE, value).await?;
+            }
+            Ok(self.destroy())
+        }
+
+        /// Update the stored calibration for this controller
+        ///
+        /// Since each device will have different tolerances, we take a snapshot of some analog data
+        /// to use as the "baseline" center.
+        pub async fn update_calibration(&mut self) -> Result<(), Error<E>> {
+            let data = self.read_sample().await?;
+            self.update_calibration_from(&data);
+            Ok(())
+        }
+
+        /// Calibrate from `samples` reads spaced `spacing_us` apart, eg: 50 reads 5ms apart
+        ///
+        /// Each input is calibrated to its mean over the reads, leaving out its lowest and
+        /// highest sample. This is less sensitive to the moment it is taken than
+        /// `update_calibration()`. The returned spread of each input can be checked to warn
+        /// that the controller wasn't at rest. At least one read is always made. If a read fails,
+        /// the calibration is left unchanged.
+        pub async fn calibrate_for(
+            &mut self,
+            samples: u16,
+            spacing_us: u32,
+        ) -> Result<CalibrationSpread, Error<E>> {
+            let mut window = CalibrationWindow::default();
+            for i in 0..samples.max(1) {
+                if i > 0 {
+                    self.interface.delay_us(spacing_us).await;
+                }
+                window.add(&self.read_sample().await?);
+            }
+            self.update_calibration_from(&window.rest());
+            Ok(window.spread())
+        }
+
+        /// Record the full-press point of each analogue trigger, see [`TriggerRange`]
+        ///
+        /// Call this while the user holds the triggers fully pulled, after calibrating at rest.
+        /// A trigger that hasn't moved far from rest keeps its existing point, so the triggers
+        /// can be pulled one at a time, and controllers without analogue triggers are left
+        /// unscaled. Returns the points now in use.
+        pub async fn calibrate_trigger_full_press(&mut self) -> Result<TriggerRange, Error<E>> {
+            let data = self.read_sample().await?;
+            self.trigger_range.record(&data, &self.state.calibration);
+            Ok(self.trigger_range)
+        }
+
+        /// Send the init sequence to the controller and calibrate it
+        pub async fn init(&mut self) -> Result<(), Error<E>> {
+            self.init_no_calibration().await?;
+            self.update_calibration().await?;
+            Ok(())
+        }
+
+        /// Send the init sequence to the controller without calibrating it
+        ///
+        /// The existing calibration is kept. Call `update_calibration()` once the
+        /// controller is known to be at rest (eg: after prompting the user).
+        pub async fn init_no_calibration(&mut self) -> Result<(), Error<E>> {
+            self.interface.init().await
+        }
+
+        /// Determine the controller type based on the type ID of the extension controller
+        pub async fn identify_controller(&mut self) -> Result<Option<ControllerType>, Error<E>> {
+            self.interface.identify_controller().await
+        }
+
+        /// Read the controller's current report format from 0xFE
+        ///
+        /// This doesn't change which format the driver decodes.
+        pub async fn report_mode(&mut self) -> Result<ReportMode, Error<E>> {
+            let value = self.read_register(0xFE).await?;
+            Ok(ReportMode::from_register(value))
+        }
+
+        /// Read a single register from the extension controller
+        ///
+        /// The read cursor is reset to 0 afterwards, so normal polling is unaffected.
+        pub async fn read_register(&mut self, addr: u8) -> Result<u8, Error<E>> {
+            let mut buf = [0u8; 1];
+            self.read_registers(addr, &mut buf).await?;
+            Ok(buf[0])
+        }
+
+        /// Read a block of registers from the extension controller, starting at `addr`
+        ///
+        /// The read cursor is reset to 0 afterwards, so normal polling is unaffected.
+        pub async fn read_registers(&mut self, addr: u8, buf: &mut [u8]) -> Result<(), Error<E>> {
+            self.interface.read_registers(addr, buf).await
+        }
+
+        /// Dump the extension controller's register space, starting at `start`
+        ///
+        /// This is intended for diagnostics, such as working out how a clone controller differs
+        /// from the original. Registers are read in report-sized chunks until `buf` is full or the
+        /// end of the register space is reached, and the number of bytes read is returned.
+        /// The read cursor is reset to 0 afterwards, so normal polling is unaffected.
+        /// Print the dump with [`HexBytes`](crate::core::raw::HexBytes) to get fixed-width hex.
+        pub async fn dump_registers(
+            &mut self,
+            start: u8,
+            buf: &mut [u8],
+        ) -> Result<usize, Error<E>> {
+            self.interface.dump_registers(start, buf).await
+        }
+
+        /// Write a single register on the extension controller
+        ///
+        /// The read cursor is reset to 0 afterwards, so normal polling is unaffected.
+        ///
+        /// Be careful: some registers change the behaviour of the controller underneath the driver.
+        /// Writing to 0xFE changes the report format. The driver keeps track of writes made through
+        /// this function, and decodes reports in the [`ReportMode`] written. Calibration is not
+        /// updated, call `update_calibration()` after changing the report format.
+        /// The first time the format is changed, 0xFE is read first so `shutdown()` can restore it.
+        ///
+        /// Drivers with a [`Standard`] or [`HiRes`] format type always decode that format, so
+        /// they return `Error::InvalidInputData` for writes to 0xFE without touching the bus.
+        pub async fn write_register(&mut self, addr: u8, value: u8) -> Result<(), Error<E>> {
+            if addr == 0xFE && MODE::HIRES.is_some() {
+                return Err(Error::InvalidInputData);
+            }
+            if addr == 0xFE {
+                self.save_report_format().await;
+            }
+            self.interface.write_register(addr, value).await?;
+            self.format.register_written(addr, value);
+            Ok(())
+        }
+
+        /// Read 0xFE before the driver first changes the report format, so `shutdown()` can
+        /// restore it
+        ///
+        /// Not every controller answers short reads, so if this one doesn't, standard reports
+        /// are assumed rather than failing the mode change.
+        async fn save_report_format(&mut self) {
+            if self.format.needs_save() {
+                let value = match self.read_register(0xFE).await {
+                    Ok(value) => value,
+                    Err(_) => ReportMode::Standard.register_value(),
+                };
+                self.format.save_power_on(value);
+            }
+        }
+
+        /// Detect the controller being swapped for a different one
+        ///
+        /// The controller is identified now, and the result is cached. After this, any report that
+        /// fails sanity checks causes the controller ID to be read again (at most once every
+        /// `min_reads_between_checks` reads). If the ID no longer matches, reads return
+        /// `Error::ControllerChanged` so the application can create the right driver.
+        pub async fn enable_identity_check(
+            &mut self,
+            min_reads_between_checks: u16,
+        ) -> Result<(), Error<E>> {
+            let expected = self.interface.identify_controller().await?;
+            self.state.identity_check =
+                Some(IdentityCheck::new(expected, min_reads_between_checks));
+            Ok(())
+        }
+
+        /// Stop checking the controller identity on anomalous reads
+        pub fn disable_identity_check(&mut self) {
+            self.state.identity_check = None;
+        }
+
+        /// Detect a controller that keeps returning the same report
+        ///
+        /// After `frames` consecutive identical reports, the read fails with `Error::Stuck`.
+        /// `read_with_recovery()` treats this as transient and re-initialises the controller.
+        /// Idle reports (no buttons pressed, analogue inputs centered) are exempt, so a controller
+        /// that is not being touched is not reported as stuck.
+        pub fn enable_stuck_detection(&mut self, frames: u16) {
+            self.state.stuck_check = Some(StuckCheck::new(frames));
+        }
+
+        /// Stop checking for repeated reports
+        pub fn disable_stuck_detection(&mut self) {
+            self.state.stuck_check = None;
+        }
+
+        /// The report format reads decode
+        fn decode_mode(&self) -> ReportMode {
+            match MODE::HIRES {
+                Some(true) => ReportMode::HiRes,
+                Some(false) => ReportMode::Standard,
+                None => self.format.mode(),
+            }
+        }
+
+        /// Check and decode a raw report, re-identifying the controller if the report looks wrong
+        async fn decode_report(&mut self, data: &[u8]) -> Result<ClassicReading, Error<E>> {
+            let current_id = if self.state.reidentify_needed(data) {
+                Some(self.interface.identify_controller().await?)
+            } else {
+                None
+            };
+            let mut reading = self.state.decode(data, current_id)?;
+            self.quirks.apply(&mut reading);
+            Ok(reading)
+        }
+
+        /// Do a read, and return button and axis values without applying calibration
+        #[maybe_async_cfg::only_if(sync)]
+        pub fn read_uncalibrated(&mut self) -> Result<ClassicReading, Error<E>> {
+            self.read_sample()
+        }
+
+        /// Start a read without waiting for the controller to prepare the sample
+        ///
+        /// This sets the controller's read cursor, then returns immediately. Call `finish_read()`
+        /// (or `finish_read_uncalibrated()`) at least `INTERMESSAGE_DELAY_MICROSEC_U32` later to
+        /// collect the data. This lets a superloop do other work instead of waiting on the delay.
+        /// Other driver calls in between may move the read cursor, so avoid them
+        /// or call `begin_read()` again.
+        #[maybe_async_cfg::only_if(sync)]
+        pub fn begin_read(&mut self) -> Result<(), Error<E>> {
+            if let Err(e) = self.interface.start_sample() {
+                return self.state.record(Err(e));
+            }
+            Ok(())
+        }
+
+        /// Complete a read started with `begin_read()`, returning values without applying calibration
+        ///
+        /// Returns `Error::ReadNotStarted` if there is no read in progress
+        #[maybe_async_cfg::only_if(sync)]
+        pub fn finish_read_uncalibrated(&mut self) -> Result<ClassicReading, Error<E>> {
+            if !self.interface.take_sample_started() {
+                return Err(Error::ReadNotStarted);
+            }
+            let result = self.read_report();
+            self.state.record(result)
+        }
+
+        /// Complete a read started with `begin_read()`, returning values relative to calibration
+        ///
+        /// Returns `Error::ReadNotStarted` if there is no read in progress
+        #[maybe_async_cfg::only_if(sync)]
+        pub fn finish_read(&mut self) -> Result<ClassicReadingCalibrated, Error<E>> {
+            let reading = self.finish_read_uncalibrated()?;
+            self.calibrated(reading)
+        }
+
+        /// Read and decode the report for a read that has been started
+        #[maybe_async_cfg::only_if(sync)]
+        fn read_report(&mut self) -> Result<ClassicReading, Error<E>> {
+            match self.decode_mode() {
+                ReportMode::HiRes => {
+                    let buf = self.interface.read_hd_report()?;
+                    self.decode_report(&buf)
+                }
+                ReportMode::Alt => {
+                    let mut buf = ExtAltReport::default();
+                    self.interface.read_alt_report_into(&mut buf)?;
+                    self.decode_report(&buf)
+                }
+                _ => {
+                    let buf = self.interface.read_report()?;
+                    self.decode_report(&buf)
+                }
+            }
+        }
+
+        /// Read uncalibrated data from the controller
+        #[maybe_async_cfg::only_if(async)]
+        async fn read_report(&mut self) -> Result<ClassicReading, Error<E>> {
+            self.interface.start_sample().await?;
+            self.interface.wait_for_sample().await;
+            match self.decode_mode() {
+                ReportMode::HiRes => {
+                    let buf = self.interface.read_hd_report().await?;
+                    self.decode_report(&buf).await
+                }
+                ReportMode::Alt => {
+                    let mut buf = ExtAltReport::default();
+                    self.interface.read_alt_report_into(&mut buf).await?;
+                    self.decode_report(&buf).await
+                }
+                _ => {
+                    let buf = self.interface.read_report().await?;
+                    self.decode_report(&buf).await
+                }
+            }
+        }
+
+        /// Read a single report, updating the read statistics
+        #[maybe_async_cfg::only_if(sync)]
+        fn read_frame(&mut self) -> Result<ClassicReading, Error<E>> {
+            self.begin_read()?;
+            self.interface.wait_for_sample();
+            self.finish_read_uncalibrated()
+        }
+
+        /// Read a single report, updating the read statistics
+        #[maybe_async_cfg::only_if(async)]
+        async fn read_frame(&mut self) -> Result<ClassicReading, Error<E>> {
+            let result = self.read_report().await;
+            self.state.record(result)
+        }
+
+        /// Do a complete read, checking it against a second read if verification is on
+        ///
+        /// If the first two reports disagree, one more is read and checked against the second.
+        async fn read_sample(&mut self) -> Result<ClassicReading, Error<E>> {
+            let mut previous = self.read_frame().await?;
+            if !self.state.verify_reads {
+                return Ok(previous);
+            }
+            for _ in 0..2 {
+                let reading = self.read_frame().await?;
+                if reading.agrees_with(&previous) {
+                    return Ok(reading);
+                }
+                previous = reading;
+            }
+            Err(self.state.inconsistent())
+        }
+
+        /// Apply the calibration and d-pad cleaning to a reading, and update the trigger states
+        fn calibrated(
+            &mut self,
+            raw: ClassicReading,
+        ) -> Result<ClassicReadingCalibrated, Error<E>> {
+            let mut reading = self.state.calibrated(raw)?;
+            self.trigger_range
+                .apply(&raw, &self.state.calibration, &mut reading);
+            self.socd.apply(&mut reading);
+            self.triggers.update(&reading);
+            Ok(reading)
+        }
+
+        /// Do a read, and return button and axis values relative to calibration
+        pub async fn read(&mut self) -> Result<ClassicReadingCalibrated, Error<E>> {
+            let reading = self.read_sample().await?;
+            self.calibrated(reading)
+        }
+
+        /// Do a read, keeping to the minimum poll interval with the time from `clock`
+        ///
+        /// A read that comes too soon after the last one made this way waits or returns the last
+        /// reading again, as configured. With no interval set, this is the same as `read()`.
+        pub async fn read_throttled(
+            &mut self,
+            clock: &mut impl Clock,
+        ) -> Result<ClassicReadingCalibrated, Error<E>> {
+            let mut now = clock.now_micros();
+            match self.limiter.throttle(now) {
+                Throttle::Read => {}
+                Throttle::Wait(micros) => {
+                    self.interface.delay_us(micros).await;
+                    now = clock.now_micros();
+                }
+                Throttle::Cached(reading) => return self.calibrated(reading),
+            }
+            let reading = self.read_sample().await?;
+            self.limiter.record(now, reading);
+            self.calibrated(reading)
+        }
+
+        /// Do a read, stamped with the time from `clock`
+        ///
+        /// The time is taken as the read starts, just before the read cursor is set, which is
+        /// when the controller prepares the sample.
+        pub async fn read_timestamped(
+            &mut self,
+            clock: &mut impl Clock,
+        ) -> Result<Timestamped<ClassicReadingCalibrated>, Error<E>> {
+            let micros = clock.now_micros();
+            let reading = self.read().await?;
+            Ok(Timestamped { micros, reading })
+        }
+
+        /// Do a read, waiting `delay_us` for the controller to prepare the sample
+        ///
+        /// This is the same as `read()`, but overrides the wait between setting the read cursor
+        /// and reading the report for this read only, eg: while the bus is busy with other
+        /// devices. The configured timing is unchanged.
+        pub async fn read_with_delay(
+            &mut self,
+            delay_us: u32,
+        ) -> Result<ClassicReadingCalibrated, Error<E>> {
+            self.interface.override_sample_delay(Some(delay_us));
+            let result = self.read().await;
+            self.interface.override_sample_delay(None);
+            result
+        }
+
+        /// Do a read without setting the read cursor first, if it should already be in place
+        ///
+        /// Genuine controllers return the cursor to the start of the report after each report
+        /// read, so this saves the seek and the wait for the controller to prepare the sample.
+        /// Reports that don't look valid are discarded, counted in [`Stats::resyncs`], and
+        /// replaced by a normal read. Calls after any other driver call also start with a normal
+        /// read.
+        pub async fn read_streamed(&mut self) -> Result<ClassicReadingCalibrated, Error<E>> {
+            if self.interface.cursor_at_report() {
+                let mut buf = ExtAltReport::default();
+                let len = self.decode_mode().report_len().unwrap_or(6);
+                let report = &mut buf[..len];
+                match self.interface.read_at_cursor(report).await {
+                    Ok(()) if self.state.check_streamed(report) => {
+                        let result = self.decode_report(report).await;
+                        let reading = self.state.record(result)?;
+                        return self.calibrated(reading);
+                    }
+                    Ok(()) => {}
+                    Err(e) => return self.state.record(Err(e)),
+                }
+            }
+            self.read().await
+        }
+
+        /// Read a report into `buf` without checking or decoding it
+        ///
+        /// Hi-resolution reports fill `buf`, standard reports fill its first 6 bytes. Returns the
+        /// number of bytes read. Decode with `ClassicReading::from_hd_report()` or
+        /// `ClassicReading::from_report()`, and apply calibration yourself if needed.
+        /// Data format 0x02 reports don't fit in `buf`, so this returns `Error::InvalidInputData`
+        /// in [`ReportMode::Alt`].
+        pub async fn read_raw_into(&mut self, buf: &mut ExtHdReport) -> Result<usize, Error<E>> {
+            match self.decode_mode() {
+                ReportMode::Alt => Err(Error::InvalidInputData),
+                ReportMode::HiRes => {
+                    self.interface.read_sample_into(buf).await?;
+                    Ok(buf.len())
+                }
+                _ => {
+                    let [report @ .., _, _] = buf;
+                    self.interface.read_sample_into(report).await?;
+                    Ok(report.len())
+                }
+            }
+        }
+
+        /// Do `samples` reads and return their average, relative to calibration
+        ///
+        /// Reads are spaced by the inter-message delay. Analogue axes are averaged, which smooths
+        /// out jitter at the cost of latency. Buttons are reported as pressed if they were pressed
+        /// in any of the reads. At least one read is always made.
+        pub async fn read_averaged(
+            &mut self,
+            samples: u8,
+        ) -> Result<ClassicReadingCalibrated, Error<E>> {
+            let mut sum = ClassicReadingSum::default();
+            for i in 0..samples.max(1) {
+                if i > 0 {
+                    self.interface
+                        .delay_us(INTERMESSAGE_DELAY_MICROSEC_U32)
+                        .await;
+                }
+                sum.add(self.read_sample().await?);
+            }
+            self.calibrated(sum.average())
+        }
+
+        /// Fill `out` with back-to-back reads, relative to calibration
+        ///
+        /// Each element gets its own complete read, spaced by the inter-message delay, for
+        /// capturing quick movements at the fastest rate the controller allows. The minimum poll
+        /// interval isn't applied between elements. If a read fails, the elements before it keep
+        /// their readings and the error reports its index.
+        ///
+        /// In the async driver, each element is stored as soon as its read completes, so
+        /// dropping the future between elements leaves the earlier ones filled. Dropping it
+        /// mid-read is safe too, as the next read sets the read cursor again.
+        pub async fn read_n(
+            &mut self,
+            out: &mut [ClassicReadingCalibrated],
+        ) -> Result<(), BatchError<E>> {
+            for (index, slot) in out.iter_mut().enumerate() {
+                if index > 0 {
+                    self.interface
+                        .delay_us(INTERMESSAGE_DELAY_MICROSEC_U32)
+                        .await;
+                }
+                let reading = self
+                    .read_sample()
+                    .await
+                    .map_err(|error| BatchError { index, error })?;
+                *slot = self
+                    .calibrated(reading)
+                    .map_err(|error| BatchError { index, error })?;
+            }
+            Ok(())
+        }
+
+        /// Do a read, giving up with `Error::Timeout` if `timeout` completes first
+        ///
+        /// `timeout` can be any future, eg: `embassy_time::Timer::after_millis(5)`.
+        /// This protects against i2c implementations that stall when the controller is unplugged
+        /// mid-transaction. The next read sets the read cursor again, so the driver can be used
+        /// as normal after a timeout.
+        #[maybe_async_cfg::only_if(async)]
+        pub async fn read_with_timeout(
+            &mut self,
+            timeout: impl Future,
+        ) -> Result<ClassicReadingCalibrated, Error<E>> {
+            match with_timeout(self.read(), timeout).await {
+                // The read was dropped before it could count the failure
+                Err(Error::Timeout) => self.state.record(Err(Error::Timeout)),
+                result => result,
+            }
+        }
+
+        /// Do a read, re-initialising the controller and retrying if it fails
+        ///
+        /// Up to `max_attempts` reads are made. After each failed read the init sequence is sent
+        /// again (keeping the current calibration and report mode) before the next attempt.
+        /// Errors that a retry can't fix, such as a different controller being connected, are
+        /// returned immediately. Otherwise the error from the last attempt is returned.
+        pub async fn read_with_recovery(
+            &mut self,
+            max_attempts: u8,
+        ) -> Result<ClassicReadingCalibrated, Error<E>> {
+            self.read_recovering(max_attempts, 0, &mut NoHook).await
+        }
+
+        /// Do a read with recovery, calling `hook` once the bus has failed `hook_after` times in a row
+        ///
+        /// This is `read_with_recovery()`, but after `hook_after` consecutive bus errors `hook` is
+        /// called with the error count, before the controller is re-initialised. The hook can
+        /// do what only the application can, such as clocking SCL by hand or power-cycling the
+        /// controller. It is called at most once per call, and never if `hook_after` is 0.
+        #[maybe_async_cfg::only_if(sync)]
+        pub fn read_with_recovery_hook(
+            &mut self,
+            max_attempts: u8,
+            hook_after: u8,
+            mut hook: impl FnMut(u8),
+        ) -> Result<ClassicReadingCalibrated, Error<E>> {
+            self.read_recovering(max_attempts, hook_after, &mut hook)
+        }
+
+        /// Do a read with recovery, calling `hook` once the bus has failed `hook_after` times in a row
+        ///
+        /// This is `read_with_recovery()`, but after `hook_after` consecutive bus errors `hook` is
+        /// called with the error count and awaited, before the controller is re-initialised. The
+        /// hook can do what only the application can, such as clocking SCL by hand or
+        /// power-cycling the controller. It is called at most once per call, and never if
+        /// `hook_after` is 0.
+        #[maybe_async_cfg::only_if(async)]
+        pub async fn read_with_recovery_hook<F: Future<Output = ()>>(
+            &mut self,
+            max_attempts: u8,
+            hook_after: u8,
+            mut hook: impl FnMut(u8) -> F,
+        ) -> Result<ClassicReadingCalibrated, Error<E>> {
+            self.read_recovering(max_attempts, hook_after, &mut hook)
+                .await
+        }
+
+        /// Read, re-initialising the controller after transient errors, and call `hook` as
+        /// [`HookTrigger`] decides
+        async fn read_recovering(
+            &mut self,
+            max_attempts: u8,
+            hook_after: u8,
+            hook: &mut impl RecoveryHook,
+        ) -> Result<ClassicReadingCalibrated, Error<E>> {
+            let mut trigger = HookTrigger::new(hook_after);
+            let mut attempt = 1;
+            let mut result = self.read().await;
+            while let Err(e) = &result {
+                if !e.is_transient() || attempt >= max_attempts {
+                    break;
+                }
+                if let Some(bus_errors) = trigger.on_error(e) {
+                    hook.call(bus_errors).await;
+                }
+                attempt += 1;
+                result = match self.reinit().await {
+                    Ok(()) => self.read().await,
+                    Err(e) => Err(e),
+                };
+            }
+            result
+        }
+
+        /// Read the controller repeatedly, waiting `interval_us` microseconds between reads
+        ///
+        /// The first reading is taken straight away, and the driver's delay is used before each
+        /// later one. Errors are returned as items and the iterator carries on, so it never ends:
+        /// stop with `break`, or adapters such as `take_while()`. The driver is only borrowed,
+        /// so it can be recalibrated or reconfigured after leaving the loop.
+        #[maybe_async_cfg::only_if(sync)]
+        pub fn iter_readings(
+            &mut self,
+            interval_us: u32,
+        ) -> impl Iterator<Item = Result<ClassicReadingCalibrated, Error<E>>> + '_ {
+            let mut wait = false;
+            core::iter::from_fn(move || {
+                if wait {
+                    self.interface.delay_us(interval_us);
+                }
+                wait = true;
+                Some(self.read())
+            })
+        }
+
+        /// Read the controller repeatedly, as a [`Stream`](futures_core::Stream) of readings
+        ///
+        /// The first reading is taken when the stream is first polled, and each later one
+        /// `interval_us` microseconds after the previous one was returned, using the driver's delay.
+        /// Errors are returned as items and the stream carries on, so it never ends.
+        ///
+        /// Dropping the stream between items leaves the driver ready for use. Dropping it
+        /// mid-read is also fine, as the next read sets the read cursor again.
+        #[maybe_async_cfg::only_if(async)]
+        #[cfg(feature = "futures")]
+        pub fn readings_stream(
+            &mut self,
+            interval_us: u32,
+        ) -> impl futures_core::Stream<Item = Result<ClassicReadingCalibrated, Error<E>>> + '_
+        {
+            crate::async_impl::stream::unfold((self, false), move |(driver, wait)| async move {
+                if wait {
+                    driver.interface.delay_us(interval_us).await;
+                }
+                (driver.read().await, (driver, true))
+            })
+        }
+
+        /// Send the init sequence again, keeping the current calibration and report mode
+        pub(crate) async fn reinit(&mut self) -> Result<(), Error<E>> {
+            self.state.stats.count_reinit();
+            self.init_no_calibration().await?;
+            let mode = self.decode_mode();
+            if mode != ReportMode::Standard {
+                self.interface
+                    .set_report_mode(mode.register_value())
+                    .await?;
+            }
+            Ok(())
+        }
+    }
+
+    /// Configure a [`Classic`] driver before it first talks to the controller
+    ///
+    /// `build()` performs only the bus operations needed for the chosen options, in this order:
+    /// init sequence, ID check, quirk lookup, report mode change, calibration read.
+    /// The default configuration behaves the same as `Classic::new()`.
+    #[derive(Debug, Default, Clone)]
+    pub struct ClassicBuilder {
+        hires: bool,
+        calibration: Option<CalibrationData>,
+        deadzone: u8,
+        range_policy: RangePolicy,
+        verify_reads: bool,
+        curves: StickCurves,
+        trigger_thresholds: (TriggerThresholds, TriggerThresholds),
+        trigger_range: TriggerRange,
+        socd: SocdPolicy,
+        quirks: Option<Quirks>,
+        known_quirks: bool,
+        quirk_table: &'static [QuirkEntry],
+        verify_id: bool,
+        skip_init: bool,
+        fast_polling: bool,
+        combined_reads: bool,
+        verify_writes: bool,
+        strict: bool,
+        timing: Timing,
+        id_attempts: Option<u8>,
+        retry: RetryPolicy,
+        init_retry: RetryPolicy,
+        poll_interval: Option<PollInterval>,
+    }
+
+    impl ClassicBuilder {
+        /// Create a builder with the default configuration
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Use the controller's high-resolution report mode
+        ///
+        /// If `skip_init()` is also used, the controller is assumed to already be in this mode
+        pub fn hires(mut self, hires: bool) -> Self {
+            self.hires = hires;
+            self
+        }
+
+        /// Use this calibration instead of reading it from the controller
+        pub fn calibration(mut self, calibration: CalibrationData) -> Self {
+            self.calibration = Some(calibration);
+            self
+        }
+
+        /// Report joystick deflections within `deadzone` of center as 0
+        pub fn deadzone(mut self, deadzone: u8) -> Self {
+            self.deadzone = deadzone;
+            self
+        }
+
+        /// Choose what calibrated reads do with axes outside the `i8` range, see [`RangePolicy`]
+        pub fn range_policy(mut self, policy: RangePolicy) -> Self {
+            self.range_policy = policy;
+            self
+        }
+
+        /// Read each sample twice, and only accept it if both reports agree
+        ///
+        /// See `set_verify_reads()` on the driver.
+        pub fn verify_reads(mut self, verify_reads: bool) -> Self {
+            self.verify_reads = verify_reads;
+            self
+        }
+
+        /// Apply a response curve to a stick axis. Trigger axes have no curve
+        pub fn axis_curve(mut self, axis: Axis, curve: AxisCurve) -> Self {
+            if let Some(i) = stick_curve_index(axis) {
+                self.curves[i] = curve;
+            }
+            self
+        }
+
+        /// Set where the left and right analogue triggers engage and release
+        pub fn trigger_thresholds(
+            mut self,
+            left: TriggerThresholds,
+            right: TriggerThresholds,
+        ) -> Self {
+            self.trigger_thresholds = (left, right);
+            self
+        }
+
+        /// Scale the analogue triggers with full-press points saved earlier, see [`TriggerRange`]
+        pub fn trigger_range(mut self, range: TriggerRange) -> Self {
+            self.trigger_range = range;
+            self
+        }
+
+        /// Choose how opposing d-pad directions held together are reported, see [`SocdPolicy`]
+        pub fn socd_policy(mut self, policy: SocdPolicy) -> Self {
+            self.socd = policy;
+            self
+        }
+
+        /// Correct each reading for a controller that doesn't follow the usual report format
+        ///
+        /// The quirks are applied to the calibration read too, and replace any found by
+        /// `known_quirks()`.
+        pub fn quirks(mut self, quirks: Quirks) -> Self {
+            self.quirks = Some(quirks);
+            self
+        }
+
+        /// Look the controller up in [`KNOWN_QUIRKS`], and apply the quirks of a matching entry
+        ///
+        /// This reads the controller ID and a report. If the entry says the controller can't
+        /// do hi-resolution reports, `hires()` is ignored. See [`crate::core::quirk_db`].
+        pub fn known_quirks(mut self, known_quirks: bool) -> Self {
+            self.known_quirks = known_quirks;
+            self
+        }
+
+        /// Look the controller up in `table` before [`KNOWN_QUIRKS`], as with `known_quirks(true)`
+        pub fn quirk_table(mut self, table: &'static [QuirkEntry]) -> Self {
+            self.known_quirks = true;
+            self.quirk_table = table;
+            self
+        }
+
+        /// Read the controller ID and fail with `Error::UnexpectedController`
+        /// if it is not a classic controller
+        pub fn verify_id(mut self, verify_id: bool) -> Self {
+            self.verify_id = verify_id;
+            self
+        }
+
+        /// Skip the seek write before each report read when the read cursor is known to
+        /// already be at 0
+        ///
+        /// This saves a bus transaction per read on genuine controllers, which return the
+        /// cursor to 0 after a report read. Some clones don't, and will return stale or
+        /// shifted data with this enabled, so it is off by default.
+        pub fn fast_polling(mut self, fast_polling: bool) -> Self {
+            self.fast_polling = fast_polling;
+            self
+        }
+
+        /// Send the seek and the report read as a single `write_read` transaction
+        ///
+        /// This uses a repeated start instead of a delay between the two, halving the bus
+        /// overhead of each read. Some controllers need the delay to prepare the sample,
+        /// so this is off by default.
+        pub fn combined_reads(mut self, combined_reads: bool) -> Self {
+            self.combined_reads = combined_reads;
+            self
+        }
+
+        /// Read back every register written, including during init
+        ///
+        /// Some clones silently ignore writes, so the driver carries on in the wrong mode.
+        /// With this, a register that reads back a different value fails with
+        /// `Error::WriteVerifyFailed`. Each write costs an extra seek and report read, so this
+        /// is off by default.
+        pub fn verify_writes(mut self, verify_writes: bool) -> Self {
+            self.verify_writes = verify_writes;
+            self
+        }
+
+        /// Enable every validation the driver can do, for debugging a new integration
+        ///
+        /// This turns on `verify_reads()`, `verify_writes()` and `verify_id()`, the identity
+        /// check and stuck detection. It also checks each report before decoding it, and fails
+        /// with `Error::RejectedReport` instead of returning a best-effort reading, see
+        /// [`ReportCheck`](crate::core::ReportCheck). Failed checks are logged and recorded in `check_failures()`.
+        ///
+        /// This costs a lot of extra bus traffic, so it is off by default.
+        pub fn strict(mut self, strict: bool) -> Self {
+            self.strict = strict;
+            self
+        }
+
+        /// Use these delays for init and reads, instead of the conservative defaults
+        pub fn timing(mut self, timing: Timing) -> Self {
+            self.timing = timing;
+            self
+        }
+
+        /// Read the controller ID up to `attempts` times, until it has a valid signature
+        ///
+        /// Some clones NACK or return garbage (eg: all 0xFF) for the first ID read after init.
+        /// The default is [`ID_READ_ATTEMPTS`](crate::core::ID_READ_ATTEMPTS). This applies
+        /// to every ID read the driver makes, including `identify_controller()`.
+        pub fn id_read_attempts(mut self, attempts: u8) -> Self {
+            self.id_attempts = Some(attempts);
+            self
+        }
+
+        /// Keep reads made with `read_throttled()` at least `interval.min_us` apart
+        ///
+        /// Off by default.
+        pub fn min_poll_interval(mut self, interval: PollInterval) -> Self {
+            self.poll_interval = Some(interval);
+            self
+        }
+
+        /// Retry bus transactions that fail, instead of returning the first error
+        ///
+        /// This covers every transaction the driver makes, including those made by `build()`.
+        pub fn retry_policy(mut self, retry: RetryPolicy) -> Self {
+            self.retry = retry;
+            self
+        }
+
+        /// Try the whole of `build()` again if it fails, for controllers that aren't ready
+        /// straight after power-on
+        ///
+        /// `build()` makes up to `retry.max_attempts` attempts, waiting `retry.backoff_us`
+        /// before each retry. Only errors that are [transient](Error::is_transient) are
+        /// retried; the error from the last attempt is returned. This is separate from
+        /// `retry_policy()`, which retries single bus transactions.
+        pub fn init_retry_policy(mut self, retry: RetryPolicy) -> Self {
+            self.init_retry = retry;
+            self
+        }
+
+        /// Don't send the init sequence, for controllers that are already initialised
+        ///
+        /// Unless a calibration is provided, the driver starts with default calibration.
+        pub fn skip_init(mut self) -> Self {
+            self.skip_init = true;
+            self
+        }
+
+        /// Create the driver, performing the configured bus operations
+        pub async fn build<I2C, E, DELAY>(
+            self,
+            i2cdev: I2C,
+            delay: DELAY,
+        ) -> Result<Classic<I2C, DELAY>, Error<E>>
+        where
+            I2C: I2c<SevenBitAddress, Error = E>,
+            DELAY: embedded_hal::delay::DelayNs,
+        {
+            let mut classic = Classic::new_unchecked(i2cdev, delay);
+            classic.set_deadzone(self.deadzone);
+            classic.set_range_policy(self.range_policy);
+            classic.set_verify_reads(self.verify_reads || self.strict);
+            classic.set_min_poll_interval(self.poll_interval);
+            classic.state.curves = self.curves;
+            let (left, right) = self.trigger_thresholds;
+            classic.set_trigger_thresholds(left, right);
+            classic.set_trigger_range(self.trigger_range);
+            classic.set_socd_policy(self.socd);
+            classic.set_quirks(self.quirks.unwrap_or(Quirks::NONE));
+            classic.interface.set_fast_polling(self.fast_polling);
+            classic.interface.set_combined_reads(self.combined_reads);
+            classic
+                .interface
+                .set_verify_writes(self.verify_writes || self.strict);
+            classic.interface.set_timing(self.timing);
+            if let Some(attempts) = self.id_attempts {
+                classic.interface.set_id_attempts(attempts);
+            }
+            classic.interface.set_retry_policy(self.retry);
+            let mut attempt = 1;
+            loop {
+                match self.setup(&mut classic).await {
+                    Ok(()) => return Ok(classic),
+                    Err(e) if e.is_transient() && attempt < self.init_retry.max_attempts => {
+                        attempt += 1;
+                        classic.interface.delay_us(self.init_retry.backoff_us).await;
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+        }
+
+        /// The bus operations of `build()`, repeated on each attempt
+        async fn setup<I2C, E, DELAY>(
+            &self,
+            classic: &mut Classic<I2C, DELAY>,
+        ) -> Result<(), Error<E>>
+        where
+            I2C: I2c<SevenBitAddress, Error = E>,
+            DELAY: embedded_hal::delay::DelayNs,
+        {
+            if !self.skip_init {
+                classic.init_no_calibration().await?;
+            }
+            if self.verify_id || self.strict {
+                let found = classic.identify_controller().await?;
+                match found {
+                    Some(ControllerType::Classic | ControllerType::ClassicPro) => {}
+                    other => return Err(Error::UnexpectedController(other)),
+                }
+                if self.strict {
+                    classic.state.strict = true;
+                    classic.state.identity_check = Some(IdentityCheck::new(found, 0));
+                    classic.enable_stuck_detection(STRICT_STUCK_FRAMES);
+                }
+            }
+            if self.known_quirks {
+                let id = classic.interface.read_id().await?;
+                let mut report = ExtReport::default();
+                classic.read_registers(0, &mut report).await?;
+                let entry = find_quirks(self.quirk_table, &id, &report)
+                    .or_else(|| find_quirks(KNOWN_QUIRKS, &id, &report));
+                if let Some(entry) = entry {
+                    classic.capabilities = entry.capabilities;
+                    if self.quirks.is_none() {
+                        classic.set_quirks(entry.quirks);
+                    }
+                }
+            }
+            if self.hires && classic.capabilities.hires {
+                if !self.skip_init {
+                    classic.save_report_format().await;
+                    classic.interface.enable_hires().await?;
+                    classic.format.set_mode(ReportMode::HiRes);
+                } else {
+                    classic.format.assume_hires(true);
+                }
+            }
+            match self.calibration {
+                Some(calibration) => classic.set_calibration(calibration),
+                None if !self.skip_init => classic.update_calibration().await?,
+                None => {}
+            }
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,1009 @@
+#[maybe_async_cfg::maybe(
+    idents(
+        blocking_impl(sync, async = "async_impl"),
+        Interface(sync, async = "InterfaceAsync"),
+        embedded_hal(sync, async = "embedded_hal_async"),
+        RecoveryHook(sync, async = "RecoveryHookAsync"),
+    ),
+    sync(self = "blocking"),
+    async(self = "asynchronous")
+)]
+pub(crate) mod nunchuk_impl {
+    #[maybe_async_cfg::only_if(async)]
+    use crate::async_impl::interface::with_timeout;
+    use crate::blocking_impl::interface::Interface;
+    use crate::core::clock::{Clock, Timestamped};
+    use crate::core::driver::{DriverState, HookTrigger};
+    use crate::core::events::Axis;
+    use crate::core::nunchuk::{
+        AccelCalibration, CalibrationData, CalibrationSpread, CalibrationWindow,
+        NunchukCalibration, NunchukReading, NunchukReadingCalibrated, NunchukReadingSum,
+    };
+    use crate::core::raw::RawFrame;
+    use crate::core::{
+        stick_curve_index, AxisCurve, CheckFailures, ControllerType, ExtReport, IdentityCheck,
+        PollInterval, PollLimiter, RangePolicy, Ready, RetryPolicy, Stats, StickCurves, StuckCheck,
+        Throttle, Timing, Uninit, INTERMESSAGE_DELAY_MICROSEC_U32, STRICT_STUCK_FRAMES,
+    };
+    use crate::drivers::{NoHook, RecoveryHook};
+    use crate::{BatchError, Error};
+    #[maybe_async_cfg::only_if(async)]
+    use core::future::Future;
+    use core::marker::PhantomData;
+    use embedded_hal::i2c::{I2c, SevenBitAddress};
+
+    pub struct Nunchuk<I2C, DELAY, STATE = Ready> {
+        interface: Interface<I2C, DELAY>,
+        state: DriverState<NunchukCalibration>,
+        limiter: PollLimiter<NunchukReading>,
+        init_state: PhantomData<STATE>,
+    }
+
+    impl<I2C, DELAY, STATE> Nunchuk<I2C, DELAY, STATE> {
+        /// Move the driver to another state, keeping everything else
+        fn into_state<S>(self) -> Nunchuk<I2C, DELAY, S> {
+            Nunchuk {
+                interface: self.interface,
+                state: self.state,
+                limiter: self.limiter,
+                init_state: PhantomData,
+            }
+        }
+    }
+
+    impl<I2C, E, DELAY> Nunchuk<I2C, DELAY, Uninit>
+    where
+        I2C: I2c<SevenBitAddress, Error = E>,
+        DELAY: embedded_hal::delay::DelayNs,
+    {
+        /// Create a driver without talking to the controller
+        ///
+        /// The driver can't be read from until `init()` has been called, which returns a driver
+        /// in the [`Ready`] state.
+        pub fn new_uninit(i2cdev: I2C, delay: DELAY) -> Self {
+            Nunchuk::new_unchecked(i2cdev, delay).into_state()
+        }
+
+        /// Send the init sequence to the controller and calibrate it
+        pub async fn init(self) -> Result<Nunchuk<I2C, DELAY, Ready>, Error<E>> {
+            let mut nunchuk = self.assume_ready();
+            nunchuk.init().await?;
+            Ok(nunchuk)
+        }
+
+        /// Send the init sequence to the controller without calibrating it
+        ///
+        /// The driver starts with default calibration. Call `update_calibration()` once the
+        /// controller is known to be at rest.
+        pub async fn init_no_calibration(self) -> Result<Nunchuk<I2C, DELAY, Ready>, Error<E>> {
+            let mut nunchuk = self.assume_ready();
+            nunchuk.init_no_calibration().await?;
+            Ok(nunchuk)
+        }
+
+        /// Treat the controller as already initialised, without talking to it
+        ///
+        /// This is for resuming after sleep when the controller is still powered and configured.
+        /// The same hazards as for `new_unchecked()` apply.
+        pub fn assume_ready(self) -> Nunchuk<I2C, DELAY, Ready> {
+            self.into_state()
+        }
+
+        /// Destroy this driver, recovering the i2c bus and delay used to create it
+        pub fn destroy(self) -> (I2C, DELAY) {
+            self.interface.destroy()
+        }
+    }
+
+    impl<I2C, E, DELAY> Nunchuk<I2C, DELAY, Ready>
+    where
+        I2C: I2c<SevenBitAddress, Error = E>,
+        DELAY: embedded_hal::delay::DelayNs,
+    {
+        /// Create a new Wii Nunchuk
+        ///
+        /// This sends the init sequence to the controller and calibrates it,
+        /// so the driver is ready to read from as soon as it is returned.
+        pub async fn new(i2cdev: I2C, delay: DELAY) -> Result<Self, Error<E>> {
+            NunchukBuilder::new().build(i2cdev, delay).await
+        }
+
+        /// Create a new driver, making up to `attempts` attempts if the nunchuk isn't ready yet
+        ///
+        /// This is `new()` with the whole init sequence and calibration retried, waiting
+        /// `Timing::CONSERVATIVE.settle_us` between attempts. Use `init_retry_policy()` on the
+        /// builder to choose the wait. The error from the last attempt is returned.
+        pub async fn new_with_retries(
+            i2cdev: I2C,
+            delay: DELAY,
+            attempts: u8,
+        ) -> Result<Self, Error<E>> {
+            NunchukBuilder::new()
+                .init_retry_policy(RetryPolicy::new(attempts, Timing::CONSERVATIVE.settle_us))
+                .build(i2cdev, delay)
+                .await
+        }
+
+        /// Create a driver for an extension controller that has already been initialised
+        ///
+        /// No bus operations are performed, so this is suitable for resuming after sleep
+        /// when the controller is still powered and configured.
+        ///
+        /// Hazards: the driver trusts that the controller has already had the init sequence
+        /// sent, and starts with default calibration. If the controller was power-cycled or
+        /// swapped, reads will fail or return garbage. Use `set_calibration()`
+        /// to restore the state the driver had before sleeping.
+        pub fn new_unchecked(i2cdev: I2C, delay: DELAY) -> Self {
+            Self {
+                interface: Interface::new(i2cdev, delay),
+                state: DriverState::default(),
+                limiter: PollLimiter::default(),
+                init_state: PhantomData,
+            }
+        }
+
+        /// Get the calibration currently used by this driver
+        ///
+        /// This can be persisted and restored with `set_calibration()`
+        pub fn calibration(&self) -> CalibrationData {
+            self.state.calibration.sticks
+        }
+
+        /// Replace the calibration used by this driver, eg: with one saved earlier
+        pub fn set_calibration(&mut self, calibration: CalibrationData) {
+            self.state.calibration.sticks = calibration;
+        }
+
+        /// Get the accelerometer calibration currently used by this driver
+        ///
+        /// `update_calibration()` sets this along with the stick calibration. It can be persisted
+        /// and restored with `set_accel_calibration()`
+        pub fn accel_calibration(&self) -> AccelCalibration {
+            self.state.calibration.accel
+        }
+
+        /// Replace the accelerometer calibration used by this driver, eg: with one saved earlier
+        pub fn set_accel_calibration(&mut self, accel: AccelCalibration) {
+            self.state.calibration.accel = accel;
+        }
+
+        /// Calibrate from a reading the caller already has, without touching the bus
+        ///
+        /// `reading` should be uncalibrated, taken with the controller at rest. This is what
+        /// `update_calibration()` does with the sample it reads.
+        pub fn update_calibration_from(&mut self, reading: &NunchukReading) {
+            self.state.update_calibration(reading);
+        }
+
+        /// Report joystick deflections within `deadzone` of center as 0 in calibrated reads
+        pub fn set_deadzone(&mut self, deadzone: u8) {
+            self.state.deadzone = deadzone;
+        }
+
+        /// Choose what calibrated reads do with axes that end up outside the `i8` range
+        ///
+        /// See [`RangePolicy`]. The default clamps them.
+        pub fn set_range_policy(&mut self, policy: RangePolicy) {
+            self.state.range_policy = policy;
+        }
+
+        /// Keep reads made with `read_throttled()` at least `interval.min_us` apart, or `None` for
+        /// no limit
+        pub fn set_min_poll_interval(&mut self, interval: Option<PollInterval>) {
+            self.limiter.set_interval(interval);
+        }
+
+        /// Read each sample twice, and only return it if both reports agree
+        ///
+        /// This catches corruption on long or noisy wiring that still produces a valid-looking
+        /// report, at the cost of a second read. Buttons must match exactly, while axes may
+        /// differ by 1 so that movement between the reads isn't rejected. If the reports
+        /// disagree, one more is read and compared with the second, and if that disagrees too
+        /// the read fails with `Error::InconsistentData`. Applies to `read()` and the reads
+        /// built on it, but not to `begin_read()`/`finish_read()`. Each report counts towards
+        /// stuck detection.
+        pub fn set_verify_reads(&mut self, verify_reads: bool) {
+            self.state.verify_reads = verify_reads;
+        }
+
+        /// Apply a response curve to a stick axis in calibrated reads
+        ///
+        /// Trigger axes have no curve, and setting one does nothing.
+        pub fn set_axis_curve(&mut self, axis: Axis, curve: AxisCurve) {
+            if let Some(i) = stick_curve_index(axis) {
+                self.state.curves[i] = curve;
+            }
+        }
+
+        /// The raw bytes of the most recent report, as received from the controller
+        ///
+        /// This is the report behind the last reading, or the one that made the last read fail,
+        /// for logging unexpected input. [`RawFrame`] tells which format it was, and formats as
+        /// hex. `None` until the first report is read. Raw reads with `read_raw_into()` aren't
+        /// kept.
+        pub fn last_raw(&self) -> Option<RawFrame> {
+            self.state.last_report.get().and_then(RawFrame::from_slice)
+        }
+
+        /// Get the read statistics collected since the driver was created or `reset_stats()` was called
+        pub fn stats(&self) -> Stats {
+            self.state.stats
+        }
+
+        /// Clear the read statistics
+        pub fn reset_stats(&mut self) {
+            self.state.stats = Stats::default();
+        }
+
+        /// Get the validation checks that have failed since the driver was created
+        ///
+        /// Report checks are only made in strict mode, see `strict()` on the builder. The
+        /// other checks are recorded whenever they are enabled.
+        pub fn check_failures(&self) -> CheckFailures {
+            let mut failures = self.state.check_failures;
+            failures.register_write |= self.interface.write_verify_failed();
+            failures
+        }
+
+        /// Destroy this driver, recovering the i2c bus and delay used to create it
+        pub fn destroy(self) -> (I2C, DELAY) {
+            self.interface.destroy()
+        }
+
+        /// Update the stored calibration for this controller
+        ///
+        /// Since each device will have different tolerances, we take a snapshot of some analog data
+        /// to use as the "baseline" center. The accelerometer zero point is taken from the same
+        /// snapshot, so the nunchuk should be lying flat.
+        pub async fn update_calibration(&mut self) -> Result<(), Error<E>> {
+            let data = self.read_sample().await?;
+            self.update_calibration_from(&data);
+            Ok(())
+        }
+
+        /// Calibrate from `samples` reads spaced `spacing_us` apart, eg: 50 reads 5ms apart
+        ///
+        /// Each input is calibrated to its mean over the reads, leaving out its lowest and
+        /// highest sample. This is less sensitive to the moment it is taken than
+        /// `update_calibration()`. The returned spread of each input can be checked to warn
+        /// that the nunchuk wasn't at rest. At least one read is always made. If a read fails,
+        /// the calibration is left unchanged.
+        pub async fn calibrate_for(
+            &mut self,
+            samples: u16,
+            spacing_us: u32,
+        ) -> Result<CalibrationSpread, Error<E>> {
+            let mut window = CalibrationWindow::default();
+            for i in 0..samples.max(1) {
+                if i > 0 {
+                    self.interface.delay_us(spacing_us).await;
+                }
+                window.add(&self.read_sample().await?);
+            }
+            self.update_calibration_from(&window.rest());
+            Ok(window.spread())
+        }
+
+        /// Send the init sequence to the controller and calibrate it
+        pub async fn init(&mut self) -> Result<(), Error<E>> {
+            self.init_no_calibration().await?;
+            self.update_calibration().await
+        }
+
+        /// Send the init sequence to the controller without calibrating it
+        ///
+        /// The existing calibration is kept. Call `update_calibration()` once the
+        /// controller is known to be at rest (eg: after prompting the user).
+        pub async fn init_no_calibration(&mut self) -> Result<(), Error<E>> {
+            self.interface.init().await
+        }
+
+        /// Determine the controller type based on the type ID of the extension controller
+        pub async fn identify_controller(&mut self) -> Result<Option<ControllerType>, Error<E>> {
+            self.interface.identify_controller().await
+        }
+
+        /// Read a single register from the extension controller
+        ///
+        /// The read cursor is reset to 0 afterwards, so normal polling is unaffected.
+        pub async fn read_register(&mut self, addr: u8) -> Result<u8, Error<E>> {
+            let mut buf = [0u8; 1];
+            self.read_registers(addr, &mut buf).await?;
+            Ok(buf[0])
+        }
+
+        /// Read a block of registers from the extension controller, starting at `addr`
+        ///
+        /// The read cursor is reset to 0 afterwards, so normal polling is unaffected.
+        pub async fn read_registers(&mut self, addr: u8, buf: &mut [u8]) -> Result<(), Error<E>> {
+            self.interface.read_registers(addr, buf).await
+        }
+
+        /// Dump the extension controller's register space, starting at `start`
+        ///
+        /// This is intended for diagnostics, such as working out how a clone controller differs
+        /// from the original. Registers are read in report-sized chunks until `buf` is full or the
+        /// end of the register space is reached, and the number of bytes read is returned.
+        /// The read cursor is reset to 0 afterwards, so normal polling is unaffected.
+        /// Print the dump with [`HexBytes`](crate::core::raw::HexBytes) to get fixed-width hex.
+        pub async fn dump_registers(
+            &mut self,
+            start: u8,
+            buf: &mut [u8],
+        ) -> Result<usize, Error<E>> {
+            self.interface.dump_registers(start, buf).await
+        }
+
+        /// Write a single register on the extension controller
+        ///
+        /// The read cursor is reset to 0 afterwards, so normal polling is unaffected.
+        ///
+        /// Be careful: some registers change the behaviour of the controller underneath the driver.
+        /// Writing to 0xFE changes the report format. The nunchuk driver only understands the
+        /// standard format, so changing it will make `read()` return garbage until it is restored.
+        pub async fn write_register(&mut self, addr: u8, value: u8) -> Result<(), Error<E>> {
+            self.interface.write_register(addr, value).await?;
+            Ok(())
+        }
+
+        /// Detect the controller being swapped for a different one
+        ///
+        /// The controller is identified now, and the result is cached. After this, any report that
+        /// fails sanity checks causes the controller ID to be read again (at most once every
+        /// `min_reads_between_checks` reads). If the ID no longer matches, reads return
+        /// `Error::ControllerChanged` so the application can create the right driver.
+        pub async fn enable_identity_check(
+            &mut self,
+            min_reads_between_checks: u16,
+        ) -> Result<(), Error<E>> {
+            let expected = self.interface.identify_controller().await?;
+            self.state.identity_check =
+                Some(IdentityCheck::new(expected, min_reads_between_checks));
+            Ok(())
+        }
+
+        /// Stop checking the controller identity on anomalous reads
+        pub fn disable_identity_check(&mut self) {
+            self.state.identity_check = None;
+        }
+
+        /// Detect a controller that keeps returning the same report
+        ///
+        /// After `frames` consecutive identical reports, the read fails with `Error::Stuck`.
+        /// `read_with_recovery()` treats this as transient and re-initialises the controller.
+        /// Idle reports (no buttons pressed, analogue inputs centered) are exempt, so a controller
+        /// that is not being touched is not reported as stuck.
+        pub fn enable_stuck_detection(&mut self, frames: u16) {
+            self.state.stuck_check = Some(StuckCheck::new(frames));
+        }
+
+        /// Stop checking for repeated reports
+        pub fn disable_stuck_detection(&mut self) {
+            self.state.stuck_check = None;
+        }
+
+        /// Check and decode a raw report, re-identifying the controller if the report looks wrong
+        async fn decode_report(&mut self, data: &[u8]) -> Result<NunchukReading, Error<E>> {
+            let current_id = if self.state.reidentify_needed(data) {
+                Some(self.interface.identify_controller().await?)
+            } else {
+                None
+            };
+            self.state.decode(data, current_id)
+        }
+
+        /// Do a read, and return button and axis values without applying calibration
+        #[maybe_async_cfg::only_if(sync)]
+        pub fn read_uncalibrated(&mut self) -> Result<NunchukReading, Error<E>> {
+            self.read_sample()
+        }
+
+        /// Start a read without waiting for the controller to prepare the sample
+        ///
+        /// This sets the controller's read cursor, then returns immediately. Call `finish_read()`
+        /// (or `finish_read_uncalibrated()`) at least `INTERMESSAGE_DELAY_MICROSEC_U32` later to
+        /// collect the data. This lets a superloop do other work instead of waiting on the delay.
+        /// Other driver calls in between may move the read cursor, so avoid them
+        /// or call `begin_read()` again.
+        #[maybe_async_cfg::only_if(sync)]
+        pub fn begin_read(&mut self) -> Result<(), Error<E>> {
+            if let Err(e) = self.interface.start_sample() {
+                return self.state.record(Err(e));
+            }
+            Ok(())
+        }
+
+        /// Complete a read started with `begin_read()`, returning values without applying calibration
+        ///
+        /// Returns `Error::ReadNotStarted` if there is no read in progress
+        #[maybe_async_cfg::only_if(sync)]
+        pub fn finish_read_uncalibrated(&mut self) -> Result<NunchukReading, Error<E>> {
+            if !self.interface.take_sample_started() {
+                return Err(Error::ReadNotStarted);
+            }
+            let result = self.read_report();
+            self.state.record(result)
+        }
+
+        /// Complete a read started with `begin_read()`, returning values relative to calibration
+        ///
+        /// Returns `Error::ReadNotStarted` if there is no read in progress
+        #[maybe_async_cfg::only_if(sync)]
+        pub fn finish_read(&mut self) -> Result<NunchukReadingCalibrated, Error<E>> {
+            let reading = self.finish_read_uncalibrated()?;
+            self.state.calibrated(reading)
+        }
+
+        /// Read and decode the report for a read that has been started
+        #[maybe_async_cfg::only_if(sync)]
+        fn read_report(&mut self) -> Result<NunchukReading, Error<E>> {
+            let buf = self.interface.read_report()?;
+            self.decode_report(&buf)
+        }
+
+        /// Read uncalibrated data from the controller
+        #[maybe_async_cfg::only_if(async)]
+        async fn read_report(&mut self) -> Result<NunchukReading, Error<E>> {
+            self.interface.start_sample().await?;
+            self.interface.wait_for_sample().await;
+            let buf = self.interface.read_report().await?;
+            self.decode_report(&buf).await
+        }
+
+        /// Read a single report, updating the read statistics
+        #[maybe_async_cfg::only_if(sync)]
+        fn read_frame(&mut self) -> Result<NunchukReading, Error<E>> {
+            self.begin_read()?;
+            self.interface.wait_for_sample();
+            self.finish_read_uncalibrated()
+        }
+
+        /// Read a single report, updating the read statistics
+        #[maybe_async_cfg::only_if(async)]
+        async fn read_frame(&mut self) -> Result<NunchukReading, Error<E>> {
+            let result = self.read_report().await;
+            self.state.record(result)
+        }
+
+        /// Do a complete read, checking it against a second read if verification is on
+        ///
+        /// If the first two reports disagree, one more is read and checked against the second.
+        async fn read_sample(&mut self) -> Result<NunchukReading, Error<E>> {
+            let mut previous = self.read_frame().await?;
+            if !self.state.verify_reads {
+                return Ok(previous);
+            }
+            for _ in 0..2 {
+                let reading = self.read_frame().await?;
+                if reading.agrees_with(&previous) {
+                    return Ok(reading);
+                }
+                previous = reading;
+            }
+            Err(self.state.inconsistent())
+        }
+
+        /// Do a read, and return button and axis values relative to calibration
+        pub async fn read(&mut self) -> Result<NunchukReadingCalibrated, Error<E>> {
+            let reading = self.read_sample().await?;
+            self.state.calibrated(reading)
+        }
+
+        /// Do a read, keeping to the minimum poll interval with the time from `clock`
+        ///
+        /// A read that comes too soon after the last one made this way waits or returns the last
+        /// reading again, as configured. With no interval set, this is the same as `read()`.
+        pub async fn read_throttled(
+            &mut self,
+            clock: &mut impl Clock,
+        ) -> Result<NunchukReadingCalibrated, Error<E>> {
+            let mut now = clock.now_micros();
+            match self.limiter.throttle(now) {
+                Throttle::Read => {}
+                Throttle::Wait(micros) => {
+                    self.interface.delay_us(micros).await;
+                    now = clock.now_micros();
+                }
+                Throttle::Cached(reading) => return self.state.calibrated(reading),
+            }
+            let reading = self.read_sample().await?;
+            self.limiter.record(now, reading);
+            self.state.calibrated(reading)
+        }
+
+        /// Do a read, stamped with the time from `clock`
+        ///
+        /// The time is taken as the read starts, just before the read cursor is set, which is
+        /// when the controller prepares the sample.
+        pub async fn read_timestamped(
+            &mut self,
+            clock: &mut impl Clock,
+        ) -> Result<Timestamped<NunchukReadingCalibrated>, Error<E>> {
+            let micros = clock.now_micros();
+            let reading = self.read().await?;
+            Ok(Timestamped { micros, reading })
+        }
+
+        /// Do a read, waiting `delay_us` for the controller to prepare the sample
+        ///
+        /// This is the same as `read()`, but overrides the wait between setting the read cursor
+        /// and reading the report for this read only, eg: while the bus is busy with other
+        /// devices. The configured timing is unchanged.
+        pub async fn read_with_delay(
+            &mut self,
+            delay_us: u32,
+        ) -> Result<NunchukReadingCalibrated, Error<E>> {
+            self.interface.override_sample_delay(Some(delay_us));
+            let result = self.read().await;
+            self.interface.override_sample_delay(None);
+            result
+        }
+
+        /// Do a read without setting the read cursor first, if it should already be in place
+        ///
+        /// Genuine controllers return the cursor to the start of the report after each report
+        /// read, so this saves the seek and the wait for the controller to prepare the sample.
+        /// Reports that don't look valid are discarded, counted in [`Stats::resyncs`], and
+        /// replaced by a normal read. Calls after any other driver call also start with a normal
+        /// read.
+        pub async fn read_streamed(&mut self) -> Result<NunchukReadingCalibrated, Error<E>> {
+            if self.interface.cursor_at_report() {
+                let mut buf = ExtReport::default();
+                let report = &mut buf[..];
+                match self.interface.read_at_cursor(report).await {
+                    Ok(()) if self.state.check_streamed(report) => {
+                        let result = self.decode_report(report).await;
+                        let reading = self.state.record(result)?;
+                        return self.state.calibrated(reading);
+                    }
+                    Ok(()) => {}
+                    Err(e) => return self.state.record(Err(e)),
+                }
+            }
+            self.read().await
+        }
+
+        /// Read a report into `buf` without checking or decoding it
+        ///
+        /// Decode with `NunchukReading::from_report()`, and apply calibration yourself if needed.
+        pub async fn read_raw_into(&mut self, buf: &mut ExtReport) -> Result<(), Error<E>> {
+            self.interface.read_sample_into(buf).await
+        }
+
+        /// Do `samples` reads and return their average, relative to calibration
+        ///
+        /// Reads are spaced by the inter-message delay. Analogue axes are averaged, which smooths
+        /// out jitter at the cost of latency. Buttons are reported as pressed if they were pressed
+        /// in any of the reads. At least one read is always made.
+        pub async fn read_averaged(
+            &mut self,
+            samples: u8,
+        ) -> Result<NunchukReadingCalibrated, Error<E>> {
+            let mut sum = NunchukReadingSum::default();
+            for i in 0..samples.max(1) {
+                if i > 0 {
+                    self.interface
+                        .delay_us(INTERMESSAGE_DELAY_MICROSEC_U32)
+                        .await;
+                }
+                sum.add(self.read_sample().await?);
+            }
+            self.state.calibrated(sum.average())
+        }
+
+        /// Fill `out` with back-to-back reads, relative to calibration
+        ///
+        /// Each element gets its own complete read, spaced by the inter-message delay, for
+        /// capturing quick movements at the fastest rate the controller allows. The minimum poll
+        /// interval isn't applied between elements. If a read fails, the elements before it keep
+        /// their readings and the error reports its index.
+        ///
+        /// In the async driver, each element is stored as soon as its read completes, so
+        /// dropping the future between elements leaves the earlier ones filled. Dropping it
+        /// mid-read is safe too, as the next read sets the read cursor again.
+        pub async fn read_n(
+            &mut self,
+            out: &mut [NunchukReadingCalibrated],
+        ) -> Result<(), BatchError<E>> {
+            for (index, slot) in out.iter_mut().enumerate() {
+                if index > 0 {
+                    self.interface
+                        .delay_us(INTERMESSAGE_DELAY_MICROSEC_U32)
+                        .await;
+                }
+                let reading = self
+                    .read_sample()
+                    .await
+                    .map_err(|error| BatchError { index, error })?;
+                *slot = self
+                    .state
+                    .calibrated(reading)
+                    .map_err(|error| BatchError { index, error })?;
+            }
+            Ok(())
+        }
+
+        /// Do a read, giving up with `Error::Timeout` if `timeout` completes first
+        ///
+        /// `timeout` can be any future, eg: `embassy_time::Timer::after_millis(5)`.
+        /// This protects against i2c implementations that stall when the controller is unplugged
+        /// mid-transaction. The next read sets the read cursor again, so the driver can be used
+        /// as normal after a timeout.
+        #[maybe_async_cfg::only_if(async)]
+        pub async fn read_with_timeout(
+            &mut self,
+            timeout: impl Future,
+        ) -> Result<NunchukReadingCalibrated, Error<E>> {
+            match with_timeout(self.read(), timeout).await {
+                // The read was dropped before it could count the failure
+                Err(Error::Timeout) => self.state.record(Err(Error::Timeout)),
+                result => result,
+            }
+        }
+
+        /// Do a read, re-initialising the controller and retrying if it fails
+        ///
+        /// Up to `max_attempts` reads are made. After each failed read the init sequence is sent
+        /// again (keeping the current calibration) before the next attempt.
+        /// Errors that a retry can't fix, such as a different controller being connected, are
+        /// returned immediately. Otherwise the error from the last attempt is returned.
+        pub async fn read_with_recovery(
+            &mut self,
+            max_attempts: u8,
+        ) -> Result<NunchukReadingCalibrated, Error<E>> {
+            self.read_recovering(max_attempts, 0, &mut NoHook).await
+        }
+
+        /// Do a read with recovery, calling `hook` once the bus has failed `hook_after` times in a row
+        ///
+        /// This is `read_with_recovery()`, but after `hook_after` consecutive bus errors `hook` is
+        /// called with the error count, before the controller is re-initialised. The hook can
+        /// do what only the application can, such as clocking SCL by hand or power-cycling the
+        /// controller. It is called at most once per call, and never if `hook_after` is 0.
+        #[maybe_async_cfg::only_if(sync)]
+        pub fn read_with_recovery_hook(
+            &mut self,
+            max_attempts: u8,
+            hook_after: u8,
+            mut hook: impl FnMut(u8),
+        ) -> Result<NunchukReadingCalibrated, Error<E>> {
+            self.read_recovering(max_attempts, hook_after, &mut hook)
+        }
+
+        /// Do a read with recovery, calling `hook` once the bus has failed `hook_after` times in a row
+        ///
+        /// This is `read_with_recovery()`, but after `hook_after` consecutive bus errors `hook` is
+        /// called with the error count and awaited, before the controller is re-initialised. The
+        /// hook can do what only the application can, such as clocking SCL by hand or
+        /// power-cycling the controller. It is called at most once per call, and never if
+        /// `hook_after` is 0.
+        #[maybe_async_cfg::only_if(async)]
+        pub async fn read_with_recovery_hook<F: Future<Output = ()>>(
+            &mut self,
+            max_attempts: u8,
+            hook_after: u8,
+            mut hook: impl FnMut(u8) -> F,
+        ) -> Result<NunchukReadingCalibrated, Error<E>> {
+            self.read_recovering(max_attempts, hook_after, &mut hook)
+                .await
+        }
+
+        /// Read, re-initialising the controller after transient errors, and call `hook` as
+        /// [`HookTrigger`] decides
+        async fn read_recovering(
+            &mut self,
+            max_attempts: u8,
+            hook_after: u8,
+            hook: &mut impl RecoveryHook,
+        ) -> Result<NunchukReadingCalibrated, Error<E>> {
+            let mut trigger = HookTrigger::new(hook_after);
+            let mut attempt = 1;
+            let mut result = self.read().await;
+            while let Err(e) = &result {
+                if !e.is_transient() || attempt >= max_attempts {
+                    break;
+                }
+                if let Some(bus_errors) = trigger.on_error(e) {
+                    hook.call(bus_errors).await;
+                }
+                attempt += 1;
+                result = match self.reinit().await {
+                    Ok(()) => self.read().await,
+                    Err(e) => Err(e),
+                };
+            }
+            result
+        }
+
+        /// Read the controller repeatedly, waiting `interval_us` microseconds between reads
+        ///
+        /// The first reading is taken straight away, and the driver's delay is used before each
+        /// later one. Errors are returned as items and the iterator carries on, so it never ends:
+        /// stop with `break`, or adapters such as `take_while()`. The driver is only borrowed,
+        /// so it can be recalibrated or reconfigured after leaving the loop.
+        #[maybe_async_cfg::only_if(sync)]
+        pub fn iter_readings(
+            &mut self,
+            interval_us: u32,
+        ) -> impl Iterator<Item = Result<NunchukReadingCalibrated, Error<E>>> + '_ {
+            let mut wait = false;
+            core::iter::from_fn(move || {
+                if wait {
+                    self.interface.delay_us(interval_us);
+                }
+                wait = true;
+                Some(self.read())
+            })
+        }
+
+        /// Read the controller repeatedly, as a [`Stream`](futures_core::Stream) of readings
+        ///
+        /// The first reading is taken when the stream is first polled, and each later one
+        /// `interval_us` microseconds after the previous one was returned, using the driver's delay.
+        /// Errors are returned as items and the stream carries on, so it never ends.
+        ///
+        /// Dropping the stream between items leaves the driver ready for use. Dropping it
+        /// mid-read is also fine, as the next read sets the read cursor again.
+        #[maybe_async_cfg::only_if(async)]
+        #[cfg(feature = "futures")]
+        pub fn readings_stream(
+            &mut self,
+            interval_us: u32,
+        ) -> impl futures_core::Stream<Item = Result<NunchukReadingCalibrated, Error<E>>> + '_
+        {
+            crate::async_impl::stream::unfold((self, false), move |(driver, wait)| async move {
+                if wait {
+                    driver.interface.delay_us(interval_us).await;
+                }
+                (driver.read().await, (driver, true))
+            })
+        }
+
+        /// Send the init sequence again, keeping the current calibration
+        pub(crate) async fn reinit(&mut self) -> Result<(), Error<E>> {
+            self.state.stats.count_reinit();
+            self.init_no_calibration().await
+        }
+    }
+
+    /// Configure a [`Nunchuk`] driver before it first talks to the controller
+    ///
+    /// `build()` performs only the bus operations needed for the chosen options, in this order:
+    /// init sequence, ID check, calibration read.
+    /// The default configuration behaves the same as `Nunchuk::new()`.
+    #[derive(Debug, Default, Clone)]
+    pub struct NunchukBuilder {
+        calibration: Option<CalibrationData>,
+        deadzone: u8,
+        range_policy: RangePolicy,
+        verify_reads: bool,
+        curves: StickCurves,
+        verify_id: bool,
+        skip_init: bool,
+        fast_polling: bool,
+        combined_reads: bool,
+        verify_writes: bool,
+        strict: bool,
+        timing: Timing,
+        id_attempts: Option<u8>,
+        retry: RetryPolicy,
+        init_retry: RetryPolicy,
+        poll_interval: Option<PollInterval>,
+    }
+
+    impl NunchukBuilder {
+        /// Create a builder with the default configuration
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Use this calibration instead of reading it from the controller
+        pub fn calibration(mut self, calibration: CalibrationData) -> Self {
+            self.calibration = Some(calibration);
+            self
+        }
+
+        /// Report joystick deflections within `deadzone` of center as 0
+        pub fn deadzone(mut self, deadzone: u8) -> Self {
+            self.deadzone = deadzone;
+            self
+        }
+
+        /// Choose what calibrated reads do with axes outside the `i8` range, see [`RangePolicy`]
+        pub fn range_policy(mut self, policy: RangePolicy) -> Self {
+            self.range_policy = policy;
+            self
+        }
+
+        /// Read each sample twice, and only accept it if both reports agree
+        ///
+        /// See `set_verify_reads()` on the driver.
+        pub fn verify_reads(mut self, verify_reads: bool) -> Self {
+            self.verify_reads = verify_reads;
+            self
+        }
+
+        /// Apply a response curve to a stick axis. Trigger axes have no curve
+        pub fn axis_curve(mut self, axis: Axis, curve: AxisCurve) -> Self {
+            if let Some(i) = stick_curve_index(axis) {
+                self.curves[i] = curve;
+            }
+            self
+        }
+
+        /// Read the controller ID and fail with `Error::UnexpectedController`
+        /// if it is not a nunchuk
+        pub fn verify_id(mut self, verify_id: bool) -> Self {
+            self.verify_id = verify_id;
+            self
+        }
+
+        /// Skip the seek write before each report read when the read cursor is known to
+        /// already be at 0
+        ///
+        /// This saves a bus transaction per read on genuine controllers, which return the
+        /// cursor to 0 after a report read. Some clones don't, and will return stale or
+        /// shifted data with this enabled, so it is off by default.
+        pub fn fast_polling(mut self, fast_polling: bool) -> Self {
+            self.fast_polling = fast_polling;
+            self
+        }
+
+        /// Send the seek and the report read as a single `write_read` transaction
+        ///
+        /// This uses a repeated start instead of a delay between the two, halving the bus
+        /// overhead of each read. Some controllers need the delay to prepare the sample,
+        /// so this is off by default.
+        pub fn combined_reads(mut self, combined_reads: bool) -> Self {
+            self.combined_reads = combined_reads;
+            self
+        }
+
+        /// Read back every register written, including during init
+        ///
+        /// Some clones silently ignore writes, so the driver carries on in the wrong mode.
+        /// With this, a register that reads back a different value fails with
+        /// `Error::WriteVerifyFailed`. Each write costs an extra seek and report read, so this
+        /// is off by default.
+        pub fn verify_writes(mut self, verify_writes: bool) -> Self {
+            self.verify_writes = verify_writes;
+            self
+        }
+
+        /// Enable every validation the driver can do, for debugging a new integration
+        ///
+        /// This turns on `verify_reads()`, `verify_writes()` and `verify_id()`, the identity
+        /// check and stuck detection. It also checks each report before decoding it, and fails
+        /// with `Error::RejectedReport` instead of returning a best-effort reading, see
+        /// [`ReportCheck`](crate::core::ReportCheck). Failed checks are logged and recorded in `check_failures()`.
+        ///
+        /// This costs a lot of extra bus traffic, so it is off by default.
+        pub fn strict(mut self, strict: bool) -> Self {
+            self.strict = strict;
+            self
+        }
+
+        /// Use these delays for init and reads, instead of the conservative defaults
+        pub fn timing(mut self, timing: Timing) -> Self {
+            self.timing = timing;
+            self
+        }
+
+        /// Read the controller ID up to `attempts` times, until it has a valid signature
+        ///
+        /// Some clones NACK or return garbage (eg: all 0xFF) for the first ID read after init.
+        /// The default is [`ID_READ_ATTEMPTS`](crate::core::ID_READ_ATTEMPTS). This applies
+        /// to every ID read the driver makes, including `identify_controller()`.
+        pub fn id_read_attempts(mut self, attempts: u8) -> Self {
+            self.id_attempts = Some(attempts);
+            self
+        }
+
+        /// Keep reads made with `read_throttled()` at least `interval.min_us` apart
+        ///
+        /// Off by default.
+        pub fn min_poll_interval(mut self, interval: PollInterval) -> Self {
+            self.poll_interval = Some(interval);
+            self
+        }
+
+        /// Retry bus transactions that fail, instead of returning the first error
+        ///
+        /// This covers every transaction the driver makes, including those made by `build()`.
+        pub fn retry_policy(mut self, retry: RetryPolicy) -> Self {
+            self.retry = retry;
+            self
+        }
+
+        /// Try the whole of `build()` again if it fails, for controllers that aren't ready
+        /// straight after power-on
+        ///
+        /// `build()` makes up to `retry.max_attempts` attempts, waiting `retry.backoff_us`
+        /// before each retry. Only errors that are [transient](Error::is_transient) are
+        /// retried; the error from the last attempt is returned. This is separate from
+        /// `retry_policy()`, which retries single bus transactions.
+        pub fn init_retry_policy(mut self, retry: RetryPolicy) -> Self {
+            self.init_retry = retry;
+            self
+        }
+
+        /// Don't send the init sequence, for controllers that are already initialised
+        ///
+        /// Unless a calibration is provided, the driver starts with default calibration.
+        pub fn skip_init(mut self) -> Self {
+            self.skip_init = true;
+            self
+        }
+
+        /// Create the driver, performing the configured bus operations
+        pub async fn build<I2C, E, DELAY>(
+            self,
+            i2cdev: I2C,
+            delay: DELAY,
+        ) -> Result<Nunchuk<I2C, DELAY>, Error<E>>
+        where
+            I2C: I2c<SevenBitAddress, Error = E>,
+            DELAY: embedded_hal::delay::DelayNs,
+        {
+            let mut nunchuk = Nunchuk::new_unchecked(i2cdev, delay);
+            nunchuk.set_deadzone(self.deadzone);
+            nunchuk.set_range_policy(self.range_policy);
+            nunchuk.set_verify_reads(self.verify_reads || self.strict);
+            nunchuk.set_min_poll_interval(self.poll_interval);
+            nunchuk.state.curves = self.curves;
+            nunchuk.interface.set_fast_polling(self.fast_polling);
+            nunchuk.interface.set_combined_reads(self.combined_reads);
+            nunchuk
+                .interface
+                .set_verify_writes(self.verify_writes || self.strict);
+            nunchuk.interface.set_timing(self.timing);
+            if let Some(attempts) = self.id_attempts {
+                nunchuk.interface.set_id_attempts(attempts);
+            }
+            nunchuk.interface.set_retry_policy(self.retry);
+            let mut attempt = 1;
+            loop {
+                match self.setup(&mut nunchuk).await {
+                    Ok(()) => return Ok(nunchuk),
+                    Err(e) if e.is_transient() && attempt < self.init_retry.max_attempts => {
+                        attempt += 1;
+                        nunchuk.interface.delay_us(self.init_retry.backoff_us).await;
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+        }
+
+        /// The bus operations of `build()`, repeated on each attempt
+        async fn setup<I2C, E, DELAY>(
+            &self,
+            nunchuk: &mut Nunchuk<I2C, DELAY>,
+        ) -> Result<(), Error<E>>
+        where
+            I2C: I2c<SevenBitAddress, Error = E>,
+            DELAY: embedded_hal::delay::DelayNs,
+        {
+            if !self.skip_init {
+                nunchuk.init_no_calibration().await?;
+            }
+            if self.verify_id || self.strict {
+                let found = nunchuk.identify_controller().await?;
+                match found {
+                    Some(ControllerType::Nunchuk) => {}
+                    other => return Err(Error::UnexpectedController(other)),
+                }
+                if self.strict {
+                    nunchuk.state.strict = true;
+                    nunchuk.state.identity_check = Some(IdentityCheck::new(found, 0));
+                    nunchuk.enable_stuck_detection(STRICT_STUCK_FRAMES);
+                }
+            }
+            match self.calibration {
+                Some(calibration) => nunchuk.set_calibration(calibration),
+                None if !self.skip_init => nunchuk.update_calibration().await?,
+                None => {}
+            }
+            Ok(())
+        }
+    }
+}
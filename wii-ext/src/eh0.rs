@@ -0,0 +1,120 @@
+//! Adapters for i2c buses and delays that only implement the embedded-hal 0.2 traits
+//!
+//! Wrap the bus in [`Eh0I2c`] and the delay in [`Eh0Delay`], then create the blocking drivers
+//! as normal:
+//!
+//! ```ignore
+//! use wii_ext::blocking_impl::classic::Classic;
+//! use wii_ext::eh0::{Eh0Delay, Eh0I2c};
+//!
+//! let mut classic = Classic::new(Eh0I2c::new(i2c), Eh0Delay::new(delay))?;
+//! // ...
+//! let (i2c, delay) = classic.destroy();
+//! let (i2c, delay) = (i2c.into_inner(), delay.into_inner());
+//! ```
+
+use embedded_hal::i2c::{ErrorKind, ErrorType, I2c, Operation, SevenBitAddress};
+use embedded_hal_0_2::blocking::delay::DelayUs;
+use embedded_hal_0_2::blocking::i2c::{Read, Write, WriteRead};
+
+/// An embedded-hal 0.2 bus error
+///
+/// embedded-hal 0.2 errors don't say what went wrong, so the kind is always `ErrorKind::Other`.
+#[derive(Debug)]
+pub struct Eh0Error<E>(pub E);
+
+impl<E: core::fmt::Debug> core::fmt::Display for Eh0Error<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "embedded-hal 0.2 bus error: {:?}", self.0)
+    }
+}
+
+impl<E: core::error::Error + 'static> core::error::Error for Eh0Error<E> {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+impl<E: core::fmt::Debug> embedded_hal::i2c::Error for Eh0Error<E> {
+    fn kind(&self) -> ErrorKind {
+        ErrorKind::Other
+    }
+}
+
+/// Use an embedded-hal 0.2 i2c bus as an embedded-hal 1.0 one
+///
+/// A write followed by a read is sent as a `WriteRead`. Other multi-operation transactions
+/// are sent as separate writes and reads, which the drivers don't rely on.
+#[derive(Debug)]
+pub struct Eh0I2c<T>(T);
+
+impl<T> Eh0I2c<T> {
+    /// Wrap an embedded-hal 0.2 i2c bus
+    pub fn new(i2c: T) -> Self {
+        Self(i2c)
+    }
+
+    /// Recover the wrapped bus
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T, E> ErrorType for Eh0I2c<T>
+where
+    T: Read<Error = E> + Write<Error = E> + WriteRead<Error = E>,
+    E: core::fmt::Debug,
+{
+    type Error = Eh0Error<E>;
+}
+
+impl<T, E> I2c<SevenBitAddress> for Eh0I2c<T>
+where
+    T: Read<Error = E> + Write<Error = E> + WriteRead<Error = E>,
+    E: core::fmt::Debug,
+{
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        match operations {
+            [Operation::Write(write), Operation::Read(read)] => {
+                self.0.write_read(address, write, read)
+            }
+            _ => operations
+                .iter_mut()
+                .try_for_each(|operation| match operation {
+                    Operation::Write(write) => self.0.write(address, write),
+                    Operation::Read(read) => self.0.read(address, read),
+                }),
+        }
+        .map_err(Eh0Error)
+    }
+}
+
+/// Use an embedded-hal 0.2 microsecond delay as an embedded-hal 1.0 one
+#[derive(Debug)]
+pub struct Eh0Delay<T>(T);
+
+impl<T> Eh0Delay<T> {
+    /// Wrap an embedded-hal 0.2 delay
+    pub fn new(delay: T) -> Self {
+        Self(delay)
+    }
+
+    /// Recover the wrapped delay
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: DelayUs<u32>> embedded_hal::delay::DelayNs for Eh0Delay<T> {
+    fn delay_ns(&mut self, ns: u32) {
+        self.0.delay_us(ns.div_ceil(1000));
+    }
+
+    fn delay_us(&mut self, us: u32) {
+        self.0.delay_us(us);
+    }
+}
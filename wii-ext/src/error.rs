@@ -0,0 +1,148 @@
+use crate::core::events::Axis;
+use crate::core::{ControllerType, ReportCheck};
+
+/// Errors returned by the blocking and async drivers
+///
+/// `E` is the error type of the i2c bus the driver was created with.
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug)]
+pub enum Error<E> {
+    /// I²C bus communication error
+    I2C(E),
+    /// A bus transaction failed on every attempt allowed by the
+    /// [`RetryPolicy`](crate::core::RetryPolicy), with this as the last error
+    RetriesExhausted(E),
+    /// Invalid input data provided
+    InvalidInputData,
+    /// Reads of the same sample kept returning different reports
+    InconsistentData,
+    /// `finish_read()` was called without a matching `begin_read()`
+    ReadNotStarted,
+    /// The operation did not complete before the timeout future resolved
+    Timeout,
+    /// The controller has returned the same non-idle report too many times in a row
+    Stuck,
+    /// The connected controller is not the type this driver expects
+    UnexpectedController(Option<ControllerType>),
+    /// The connected controller is no longer the one that was identified earlier
+    ControllerChanged {
+        previous: Option<ControllerType>,
+        current: Option<ControllerType>,
+    },
+    /// A register read back a different value than was written to it
+    WriteVerifyFailed { addr: u8, expected: u8, got: u8 },
+    /// Strict mode rejected a report that failed this check
+    RejectedReport(ReportCheck),
+    /// The controller returned an all-zero report
+    ///
+    /// This happens when the init handshake only partly worked: the ID reads back, but
+    /// reports stay blank until the controller is initialised again.
+    NotResponding,
+    /// A calibrated axis was further outside the `i8` range than the
+    /// [`RangePolicy`](crate::core::RangePolicy) allows
+    ///
+    /// Usually the calibration was taken with this axis held, or the report was corrupted.
+    OutOfRange { axis: Axis },
+}
+
+impl<E> Error<E> {
+    /// Whether re-initialising the controller and trying again might fix this error
+    ///
+    /// Bus errors and corrupt data are usually transient. A different controller
+    /// being connected, or a calibration taken with an axis held, is not something a retry
+    /// can fix.
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            Self::I2C(_)
+                | Self::RetriesExhausted(_)
+                | Self::InvalidInputData
+                | Self::InconsistentData
+                | Self::Timeout
+                | Self::Stuck
+                | Self::RejectedReport(_)
+                | Self::NotResponding
+        )
+    }
+}
+
+impl<E> From<E> for Error<E> {
+    fn from(e: E) -> Self {
+        Self::I2C(e)
+    }
+}
+
+impl<E> core::fmt::Display for Error<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::I2C(_) => f.write_str("i2c bus error"),
+            Self::RetriesExhausted(_) => f.write_str("i2c bus error, retries exhausted"),
+            Self::InvalidInputData => f.write_str("invalid report data from controller"),
+            Self::InconsistentData => f.write_str("controller returned inconsistent reports"),
+            Self::ReadNotStarted => f.write_str("read finished without being started"),
+            Self::Timeout => f.write_str("timed out waiting for controller"),
+            Self::Stuck => f.write_str("controller keeps returning the same report"),
+            Self::UnexpectedController(found) => {
+                write!(f, "unexpected controller type: {:?}", found)
+            }
+            Self::ControllerChanged { previous, current } => {
+                write!(f, "controller changed from {:?} to {:?}", previous, current)
+            }
+            Self::WriteVerifyFailed {
+                addr,
+                expected,
+                got,
+            } => write!(
+                f,
+                "wrote {:#04x} to register {:#04x}, read back {:#04x}",
+                expected, addr, got
+            ),
+            Self::RejectedReport(check) => write!(f, "report rejected: {}", check.description()),
+            Self::NotResponding => f.write_str("controller returned an all-zero report"),
+            Self::OutOfRange { axis } => write!(f, "calibrated {:?} axis out of range", axis),
+        }
+    }
+}
+
+impl<E: core::error::Error + 'static> core::error::Error for Error<E> {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::I2C(e) | Self::RetriesExhausted(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// A batch read failed partway through
+///
+/// Elements before `index` were filled with good readings, `index` and the elements after
+/// it were left as they were.
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug)]
+pub struct BatchError<E> {
+    /// Index of the element whose read failed
+    pub index: usize,
+    pub error: Error<E>,
+}
+
+impl<E> From<BatchError<E>> for Error<E> {
+    fn from(e: BatchError<E>) -> Self {
+        e.error
+    }
+}
+
+impl<E> core::fmt::Display for BatchError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "batch read failed at element {}: {}",
+            self.index, self.error
+        )
+    }
+}
+
+impl<E: core::error::Error + 'static> core::error::Error for BatchError<E> {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
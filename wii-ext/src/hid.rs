@@ -0,0 +1,592 @@
+//! USB HID joystick reports
+//!
+//! [`JoystickReport`] is a 9 byte input report matching [`JOYSTICK_DESCRIPTOR`]: six signed
+//! axes, a hat switch and 16 buttons. Nothing here depends on a USB stack. With `usbd-hid`,
+//! pass the descriptor to `HIDClass::new()` and send reports with
+//! `hid.push_raw_input(&report.to_bytes())`.
+//!
+//! ```ignore
+//! let mut hid = HIDClass::new(&usb_bus, wii_ext::hid::JOYSTICK_DESCRIPTOR, 10);
+//! let input = controller.read().unwrap();
+//! hid.push_raw_input(&JoystickReport::from(&input).to_bytes()).ok();
+//! ```
+//!
+//! USB classes that want unsigned axes centered on 0x80 can use [`UnsignedJoystickReport`]
+//! instead. It has the same axes and button order, so its fields can be copied across one for
+//! one.
+//!
+//! With the `usbd-human-interface-device` feature, calibrated readings also convert into that
+//! crate's `device::joystick::JoystickReport`, to send with its `Joystick` device:
+//!
+//! ```ignore
+//! let input = controller.read().unwrap();
+//! joystick.device().write_report(&(&input).into()).ok();
+//! ```
+//!
+//! [`CLASSIC_DESCRIPTOR`] and [`NUNCHUK_DESCRIPTOR`] describe each controller more closely:
+//! the classic controller's triggers are reported as unsigned analogue values and only its
+//! 11 buttons are declared, and the nunchuk's accelerometer keeps its full 10-bit range.
+//! [`pack()`] and [`pack_nunchuk()`] fill in the matching [`ClassicHidReport`] and
+//! [`NunchukHidReport`].
+//!
+//! [`KeyboardReport`] is the boot protocol keyboard report described by
+//! [`KEYBOARD_DESCRIPTOR`], for presenting a controller as a keyboard through a
+//! [`KeyMap`].
+
+use crate::core::classic::ClassicReadingCalibrated;
+use crate::core::keymap::KeyMap;
+use crate::core::nunchuk::NunchukReadingCalibrated;
+
+/// HID report descriptor for [`JoystickReport`]
+#[rustfmt::skip]
+pub const JOYSTICK_DESCRIPTOR: &[u8] = &[
+    0x05, 0x01,       // Usage Page (Generic Desktop)
+    0x09, 0x05,       // Usage (Game Pad)
+    0xA1, 0x01,       // Collection (Application)
+    0x09, 0x30,       //   Usage (X)
+    0x09, 0x31,       //   Usage (Y)
+    0x09, 0x33,       //   Usage (Rx)
+    0x09, 0x34,       //   Usage (Ry)
+    0x09, 0x32,       //   Usage (Z)
+    0x09, 0x35,       //   Usage (Rz)
+    0x15, 0x81,       //   Logical Minimum (-127)
+    0x25, 0x7F,       //   Logical Maximum (127)
+    0x75, 0x08,       //   Report Size (8)
+    0x95, 0x06,       //   Report Count (6)
+    0x81, 0x02,       //   Input (Data, Variable, Absolute)
+    0x09, 0x39,       //   Usage (Hat Switch)
+    0x15, 0x00,       //   Logical Minimum (0)
+    0x25, 0x07,       //   Logical Maximum (7)
+    0x35, 0x00,       //   Physical Minimum (0)
+    0x46, 0x3B, 0x01, //   Physical Maximum (315)
+    0x65, 0x14,       //   Unit (Degrees)
+    0x75, 0x04,       //   Report Size (4)
+    0x95, 0x01,       //   Report Count (1)
+    0x81, 0x42,       //   Input (Data, Variable, Absolute, Null State)
+    0x65, 0x00,       //   Unit (None)
+    0x81, 0x03,       //   Input (Constant): 4 bits padding
+    0x05, 0x09,       //   Usage Page (Button)
+    0x19, 0x01,       //   Usage Minimum (1)
+    0x29, 0x10,       //   Usage Maximum (16)
+    0x15, 0x00,       //   Logical Minimum (0)
+    0x25, 0x01,       //   Logical Maximum (1)
+    0x75, 0x01,       //   Report Size (1)
+    0x95, 0x10,       //   Report Count (16)
+    0x81, 0x02,       //   Input (Data, Variable, Absolute)
+    0xC0,             // End Collection
+];
+
+/// Hat switch value when the d-pad is not pressed
+pub const HAT_CENTERED: u8 = 8;
+
+/// Input report for [`JOYSTICK_DESCRIPTOR`]
+///
+/// Axes follow HID conventions: positive X is right and positive Y is down, so the
+/// controller's Y axes are inverted. Axes are limited to -127..=127.
+///
+/// Classic controllers map the left stick to X/Y, the right stick to Rx/Ry and the
+/// triggers to Z/Rz. The d-pad is the hat switch. Buttons, from bit 0:
+/// A, B, X, Y, L, R, ZL, ZR, Minus, Plus, Home.
+///
+/// Nunchuks map the stick to X/Y and the accelerometer to Rx/Ry/Rz, relative to the
+/// middle of its range and scaled down to 8 bits. Z is unused and the hat is centered.
+/// Buttons, from bit 0: C, Z.
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct JoystickReport {
+    pub x: i8,
+    pub y: i8,
+    pub rx: i8,
+    pub ry: i8,
+    pub z: i8,
+    pub rz: i8,
+    /// 0 is up, counting clockwise in 45 degree steps. [`HAT_CENTERED`] when released
+    pub hat: u8,
+    pub buttons: u16,
+}
+
+impl JoystickReport {
+    /// Size of the report in bytes
+    pub const SIZE: usize = 9;
+
+    /// Serialize the report in the layout declared by [`JOYSTICK_DESCRIPTOR`]
+    pub fn to_bytes(&self) -> [u8; Self::SIZE] {
+        let buttons = self.buttons.to_le_bytes();
+        [
+            self.x as u8,
+            self.y as u8,
+            self.rx as u8,
+            self.ry as u8,
+            self.z as u8,
+            self.rz as u8,
+            self.hat & 0x0F,
+            buttons[0],
+            buttons[1],
+        ]
+    }
+}
+
+/// Limit an axis to the descriptor's logical range
+fn axis(value: i8) -> i8 {
+    value.max(-127)
+}
+
+/// Flip an axis so that positive is down
+fn inverted_axis(value: i8) -> i8 {
+    value.saturating_neg().max(-127)
+}
+
+/// Convert a 10-bit accelerometer reading to a signed 8-bit axis
+fn accel_axis(value: u16) -> i8 {
+    ((value as i16 - 512) / 4).clamp(-127, 127) as i8
+}
+
+/// Encode a d-pad as a hat switch value
+///
+/// Opposite directions pressed together (which a worn d-pad can report) cancel out.
+fn hat(up: bool, down: bool, left: bool, right: bool) -> u8 {
+    match (up && !down, down && !up, left && !right, right && !left) {
+        (true, _, false, false) => 0,
+        (true, _, _, true) => 1,
+        (false, false, _, true) => 2,
+        (_, true, _, true) => 3,
+        (_, true, false, false) => 4,
+        (_, true, true, _) => 5,
+        (false, false, true, _) => 6,
+        (true, _, true, _) => 7,
+        _ => HAT_CENTERED,
+    }
+}
+
+/// Pack buttons into a bitfield, with the first button in bit 0
+fn buttons(pressed: &[bool]) -> u16 {
+    pressed
+        .iter()
+        .enumerate()
+        .fold(0, |bits, (i, &pressed)| bits | ((pressed as u16) << i))
+}
+
+/// Pack a classic controller's buttons into a bitfield, in the documented order
+fn classic_buttons(r: &ClassicReadingCalibrated) -> u16 {
+    buttons(&[
+        r.button_a,
+        r.button_b,
+        r.button_x,
+        r.button_y,
+        r.button_trigger_l,
+        r.button_trigger_r,
+        r.button_zl,
+        r.button_zr,
+        r.button_minus,
+        r.button_plus,
+        r.button_home,
+    ])
+}
+
+impl From<&ClassicReadingCalibrated> for JoystickReport {
+    fn from(r: &ClassicReadingCalibrated) -> Self {
+        Self {
+            x: axis(r.joystick_left_x),
+            y: inverted_axis(r.joystick_left_y),
+            rx: axis(r.joystick_right_x),
+            ry: inverted_axis(r.joystick_right_y),
+            z: axis(r.trigger_left),
+            rz: axis(r.trigger_right),
+            hat: hat(r.dpad_up, r.dpad_down, r.dpad_left, r.dpad_right),
+            buttons: classic_buttons(r),
+        }
+    }
+}
+
+impl From<&NunchukReadingCalibrated> for JoystickReport {
+    fn from(r: &NunchukReadingCalibrated) -> Self {
+        Self {
+            x: axis(r.joystick_x),
+            y: inverted_axis(r.joystick_y),
+            rx: accel_axis(r.accel_x),
+            ry: accel_axis(r.accel_y),
+            z: 0,
+            rz: accel_axis(r.accel_z),
+            hat: HAT_CENTERED,
+            buttons: buttons(&[r.button_c, r.button_z]),
+        }
+    }
+}
+
+/// [`JoystickReport`] with unsigned axes, centered on 0x80
+///
+/// Axes, hat and buttons are in the same order, with the same meaning, as [`JoystickReport`].
+/// The full range of the calibrated readings is used: -128 maps to 0x00 and 127 to 0xFF.
+/// Y axes are inverted before the shift, so up is at most 0x01.
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct UnsignedJoystickReport {
+    pub x: u8,
+    pub y: u8,
+    pub rx: u8,
+    pub ry: u8,
+    pub z: u8,
+    pub rz: u8,
+    /// 0 is up, counting clockwise in 45 degree steps. [`HAT_CENTERED`] when released
+    pub hat: u8,
+    pub buttons: u16,
+}
+
+/// Shift a signed axis so that its center is 0x80
+fn unsigned_axis(value: i8) -> u8 {
+    (value as u8) ^ 0x80
+}
+
+/// Shift a signed axis so that its center is 0x80, and flip it so that positive is down
+fn unsigned_inverted_axis(value: i8) -> u8 {
+    unsigned_axis(value.saturating_neg())
+}
+
+impl From<&ClassicReadingCalibrated> for UnsignedJoystickReport {
+    fn from(r: &ClassicReadingCalibrated) -> Self {
+        let report = JoystickReport::from(r);
+        Self {
+            x: unsigned_axis(r.joystick_left_x),
+            y: unsigned_inverted_axis(r.joystick_left_y),
+            rx: unsigned_axis(r.joystick_right_x),
+            ry: unsigned_inverted_axis(r.joystick_right_y),
+            z: unsigned_axis(r.trigger_left),
+            rz: unsigned_axis(r.trigger_right),
+            hat: report.hat,
+            buttons: report.buttons,
+        }
+    }
+}
+
+impl From<&NunchukReadingCalibrated> for UnsignedJoystickReport {
+    fn from(r: &NunchukReadingCalibrated) -> Self {
+        let report = JoystickReport::from(r);
+        Self {
+            x: unsigned_axis(r.joystick_x),
+            y: unsigned_inverted_axis(r.joystick_y),
+            rx: unsigned_axis(report.rx),
+            ry: unsigned_axis(report.ry),
+            z: 0x80,
+            rz: unsigned_axis(report.rz),
+            hat: report.hat,
+            buttons: report.buttons,
+        }
+    }
+}
+
+/// The `usbd-human-interface-device` joystick has one stick and 8 buttons
+///
+/// The stick is the left stick, mapped as for [`JoystickReport`]: up is negative Y and -128 is
+/// clamped to -127. Buttons are the first 8 of the [`JoystickReport`] order, from bit 0: A, B,
+/// X, Y, L, R, ZL, ZR. Minus, Plus, Home, the d-pad, the right stick and the triggers aren't
+/// reported.
+#[cfg(feature = "usbd-human-interface-device")]
+impl From<&ClassicReadingCalibrated>
+    for usbd_human_interface_device::device::joystick::JoystickReport
+{
+    fn from(r: &ClassicReadingCalibrated) -> Self {
+        Self {
+            x: axis(r.joystick_left_x),
+            y: inverted_axis(r.joystick_left_y),
+            buttons: classic_buttons(r) as u8,
+        }
+    }
+}
+
+/// The stick is mapped as for [`JoystickReport`], and buttons from bit 0 are C, Z
+#[cfg(feature = "usbd-human-interface-device")]
+impl From<&NunchukReadingCalibrated>
+    for usbd_human_interface_device::device::joystick::JoystickReport
+{
+    fn from(r: &NunchukReadingCalibrated) -> Self {
+        Self {
+            x: axis(r.joystick_x),
+            y: inverted_axis(r.joystick_y),
+            buttons: buttons(&[r.button_c, r.button_z]) as u8,
+        }
+    }
+}
+
+/// HID report descriptor for [`ClassicHidReport`]
+#[rustfmt::skip]
+pub const CLASSIC_DESCRIPTOR: &[u8] = &[
+    0x05, 0x01,       // Usage Page (Generic Desktop)
+    0x09, 0x05,       // Usage (Game Pad)
+    0xA1, 0x01,       // Collection (Application)
+    0x09, 0x30,       //   Usage (X)
+    0x09, 0x31,       //   Usage (Y)
+    0x09, 0x33,       //   Usage (Rx)
+    0x09, 0x34,       //   Usage (Ry)
+    0x15, 0x81,       //   Logical Minimum (-127)
+    0x25, 0x7F,       //   Logical Maximum (127)
+    0x75, 0x08,       //   Report Size (8)
+    0x95, 0x04,       //   Report Count (4)
+    0x81, 0x02,       //   Input (Data, Variable, Absolute)
+    0x09, 0x32,       //   Usage (Z)
+    0x09, 0x35,       //   Usage (Rz)
+    0x15, 0x00,       //   Logical Minimum (0)
+    0x25, 0x7F,       //   Logical Maximum (127)
+    0x95, 0x02,       //   Report Count (2)
+    0x81, 0x02,       //   Input (Data, Variable, Absolute)
+    0x09, 0x39,       //   Usage (Hat Switch)
+    0x15, 0x00,       //   Logical Minimum (0)
+    0x25, 0x07,       //   Logical Maximum (7)
+    0x35, 0x00,       //   Physical Minimum (0)
+    0x46, 0x3B, 0x01, //   Physical Maximum (315)
+    0x65, 0x14,       //   Unit (Degrees)
+    0x75, 0x04,       //   Report Size (4)
+    0x95, 0x01,       //   Report Count (1)
+    0x81, 0x42,       //   Input (Data, Variable, Absolute, Null State)
+    0x65, 0x00,       //   Unit (None)
+    0x81, 0x03,       //   Input (Constant): 4 bits padding
+    0x05, 0x09,       //   Usage Page (Button)
+    0x19, 0x01,       //   Usage Minimum (1)
+    0x29, 0x0B,       //   Usage Maximum (11)
+    0x15, 0x00,       //   Logical Minimum (0)
+    0x25, 0x01,       //   Logical Maximum (1)
+    0x75, 0x01,       //   Report Size (1)
+    0x95, 0x0B,       //   Report Count (11)
+    0x81, 0x02,       //   Input (Data, Variable, Absolute)
+    0x75, 0x05,       //   Report Size (5)
+    0x95, 0x01,       //   Report Count (1)
+    0x81, 0x03,       //   Input (Constant): 5 bits padding
+    0xC0,             // End Collection
+];
+
+/// Input report for [`CLASSIC_DESCRIPTOR`]
+///
+/// The fields are laid out in the order the descriptor declares them, with no padding, so
+/// the struct can be sent as is on a little-endian target. Sticks and buttons are mapped as
+/// for [`JoystickReport`]. Triggers are 0 at rest, and negative trigger readings are reported
+/// as 0.
+#[repr(C, packed)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ClassicHidReport {
+    pub left_x: i8,
+    pub left_y: i8,
+    pub right_x: i8,
+    pub right_y: i8,
+    pub trigger_left: u8,
+    pub trigger_right: u8,
+    /// 0 is up, counting clockwise in 45 degree steps. [`HAT_CENTERED`] when released
+    pub hat: u8,
+    /// Buttons from bit 0: A, B, X, Y, L, R, ZL, ZR, Minus, Plus, Home
+    pub buttons: u16,
+}
+
+impl ClassicHidReport {
+    /// Serialize the report in the layout declared by [`CLASSIC_DESCRIPTOR`]
+    pub fn to_bytes(&self) -> [u8; core::mem::size_of::<Self>()] {
+        let buttons = self.buttons.to_le_bytes();
+        [
+            self.left_x as u8,
+            self.left_y as u8,
+            self.right_x as u8,
+            self.right_y as u8,
+            self.trigger_left,
+            self.trigger_right,
+            self.hat & 0x0F,
+            buttons[0],
+            buttons[1],
+        ]
+    }
+}
+
+/// Convert a classic controller reading into a [`ClassicHidReport`]
+pub fn pack(r: &ClassicReadingCalibrated) -> ClassicHidReport {
+    ClassicHidReport {
+        left_x: axis(r.joystick_left_x),
+        left_y: inverted_axis(r.joystick_left_y),
+        right_x: axis(r.joystick_right_x),
+        right_y: inverted_axis(r.joystick_right_y),
+        trigger_left: r.trigger_left.max(0) as u8,
+        trigger_right: r.trigger_right.max(0) as u8,
+        hat: hat(r.dpad_up, r.dpad_down, r.dpad_left, r.dpad_right),
+        buttons: classic_buttons(r),
+    }
+}
+
+/// HID report descriptor for [`NunchukHidReport`]
+#[rustfmt::skip]
+pub const NUNCHUK_DESCRIPTOR: &[u8] = &[
+    0x05, 0x01,       // Usage Page (Generic Desktop)
+    0x09, 0x04,       // Usage (Joystick)
+    0xA1, 0x01,       // Collection (Application)
+    0x09, 0x30,       //   Usage (X)
+    0x09, 0x31,       //   Usage (Y)
+    0x15, 0x81,       //   Logical Minimum (-127)
+    0x25, 0x7F,       //   Logical Maximum (127)
+    0x75, 0x08,       //   Report Size (8)
+    0x95, 0x02,       //   Report Count (2)
+    0x81, 0x02,       //   Input (Data, Variable, Absolute)
+    0x09, 0x33,       //   Usage (Rx)
+    0x09, 0x34,       //   Usage (Ry)
+    0x09, 0x35,       //   Usage (Rz)
+    0x15, 0x00,       //   Logical Minimum (0)
+    0x26, 0xFF, 0x03, //   Logical Maximum (1023)
+    0x75, 0x10,       //   Report Size (16)
+    0x95, 0x03,       //   Report Count (3)
+    0x81, 0x02,       //   Input (Data, Variable, Absolute)
+    0x05, 0x09,       //   Usage Page (Button)
+    0x19, 0x01,       //   Usage Minimum (1)
+    0x29, 0x02,       //   Usage Maximum (2)
+    0x15, 0x00,       //   Logical Minimum (0)
+    0x25, 0x01,       //   Logical Maximum (1)
+    0x75, 0x01,       //   Report Size (1)
+    0x95, 0x02,       //   Report Count (2)
+    0x81, 0x02,       //   Input (Data, Variable, Absolute)
+    0x75, 0x06,       //   Report Size (6)
+    0x95, 0x01,       //   Report Count (1)
+    0x81, 0x03,       //   Input (Constant): 6 bits padding
+    0xC0,             // End Collection
+];
+
+/// Input report for [`NUNCHUK_DESCRIPTOR`]
+///
+/// The fields are laid out in the order the descriptor declares them, with no padding, so
+/// the struct can be sent as is on a little-endian target. The stick is mapped as for
+/// [`JoystickReport`], and the accelerometer axes are the raw 10-bit readings.
+#[repr(C, packed)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct NunchukHidReport {
+    pub x: i8,
+    pub y: i8,
+    pub accel_x: u16,
+    pub accel_y: u16,
+    pub accel_z: u16,
+    /// Buttons from bit 0: C, Z
+    pub buttons: u8,
+}
+
+impl NunchukHidReport {
+    /// Serialize the report in the layout declared by [`NUNCHUK_DESCRIPTOR`]
+    pub fn to_bytes(&self) -> [u8; core::mem::size_of::<Self>()] {
+        let (x, y, z) = (
+            self.accel_x.to_le_bytes(),
+            self.accel_y.to_le_bytes(),
+            self.accel_z.to_le_bytes(),
+        );
+        [
+            self.x as u8,
+            self.y as u8,
+            x[0],
+            x[1],
+            y[0],
+            y[1],
+            z[0],
+            z[1],
+            self.buttons,
+        ]
+    }
+}
+
+/// Convert a nunchuk reading into a [`NunchukHidReport`]
+pub fn pack_nunchuk(r: &NunchukReadingCalibrated) -> NunchukHidReport {
+    NunchukHidReport {
+        x: axis(r.joystick_x),
+        y: inverted_axis(r.joystick_y),
+        accel_x: r.accel_x.min(1023),
+        accel_y: r.accel_y.min(1023),
+        accel_z: r.accel_z.min(1023),
+        buttons: buttons(&[r.button_c, r.button_z]) as u8,
+    }
+}
+
+/// HID report descriptor for [`KeyboardReport`], the boot protocol keyboard from the HID spec
+///
+/// This also declares the host's LED output report, which can be ignored.
+#[rustfmt::skip]
+pub const KEYBOARD_DESCRIPTOR: &[u8] = &[
+    0x05, 0x01,       // Usage Page (Generic Desktop)
+    0x09, 0x06,       // Usage (Keyboard)
+    0xA1, 0x01,       // Collection (Application)
+    0x05, 0x07,       //   Usage Page (Keyboard)
+    0x19, 0xE0,       //   Usage Minimum (Left Control)
+    0x29, 0xE7,       //   Usage Maximum (Right GUI)
+    0x15, 0x00,       //   Logical Minimum (0)
+    0x25, 0x01,       //   Logical Maximum (1)
+    0x75, 0x01,       //   Report Size (1)
+    0x95, 0x08,       //   Report Count (8)
+    0x81, 0x02,       //   Input (Data, Variable, Absolute): modifiers
+    0x95, 0x01,       //   Report Count (1)
+    0x75, 0x08,       //   Report Size (8)
+    0x81, 0x01,       //   Input (Constant): reserved
+    0x95, 0x05,       //   Report Count (5)
+    0x75, 0x01,       //   Report Size (1)
+    0x05, 0x08,       //   Usage Page (LEDs)
+    0x19, 0x01,       //   Usage Minimum (Num Lock)
+    0x29, 0x05,       //   Usage Maximum (Kana)
+    0x91, 0x02,       //   Output (Data, Variable, Absolute)
+    0x95, 0x01,       //   Report Count (1)
+    0x75, 0x03,       //   Report Size (3)
+    0x91, 0x01,       //   Output (Constant): 3 bits padding
+    0x95, 0x06,       //   Report Count (6)
+    0x75, 0x08,       //   Report Size (8)
+    0x15, 0x00,       //   Logical Minimum (0)
+    0x25, 0x65,       //   Logical Maximum (101)
+    0x05, 0x07,       //   Usage Page (Keyboard)
+    0x19, 0x00,       //   Usage Minimum (0)
+    0x29, 0x65,       //   Usage Maximum (101)
+    0x81, 0x00,       //   Input (Data, Array): keys
+    0xC0,             // End Collection
+];
+
+/// Usage code reported in every key slot when more keys are held than a report can hold
+pub const KEY_ERROR_ROLL_OVER: u8 = 0x01;
+
+/// Boot protocol keyboard input report, matching [`KEYBOARD_DESCRIPTOR`]
+///
+/// Up to 6 keys can be reported at once, plus the 8 modifier keys.
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct KeyboardReport {
+    /// Modifier keys, from bit 0: left control, shift, alt and GUI, then the right ones
+    pub modifiers: u8,
+    /// Usage codes of the keys held, unused slots are 0
+    pub keys: [u8; 6],
+}
+
+impl KeyboardReport {
+    /// Size of the report in bytes
+    pub const SIZE: usize = 8;
+
+    /// Build a report from the usage codes of the keys held
+    ///
+    /// Modifier keys (0xE0 to 0xE7) set their bit instead of taking a slot, and repeated
+    /// keys are only reported once. If more than 6 other keys are held, every slot is
+    /// [`KEY_ERROR_ROLL_OVER`], as the boot protocol requires.
+    pub fn from_keys(keys: impl IntoIterator<Item = u8>) -> Self {
+        let mut report = Self::default();
+        let mut count = 0;
+        let mut overflow = false;
+        for key in keys {
+            match key {
+                0 => {}
+                0xE0..=0xE7 => report.modifiers |= 1 << (key - 0xE0),
+                _ if report.keys[..count].contains(&key) => {}
+                _ if count == report.keys.len() => overflow = true,
+                _ => {
+                    report.keys[count] = key;
+                    count += 1;
+                }
+            }
+        }
+        if overflow {
+            report.keys = [KEY_ERROR_ROLL_OVER; 6];
+        }
+        report
+    }
+
+    /// Serialize the report in the layout declared by [`KEYBOARD_DESCRIPTOR`]
+    pub fn to_bytes(&self) -> [u8; Self::SIZE] {
+        let k = self.keys;
+        [self.modifiers, 0, k[0], k[1], k[2], k[3], k[4], k[5]]
+    }
+}
+
+impl<const N: usize> From<&KeyMap<N>> for KeyboardReport {
+    /// The keys currently held in `map`
+    fn from(map: &KeyMap<N>) -> Self {
+        Self::from_keys(map.held_keys())
+    }
+}
@@ -14,12 +14,39 @@
 // The nunchuk portion of this crate is derived from
 // https://github.com/rust-embedded/rust-i2cdev/blob/master/examples/nunchuck.rs
 // which is Copyright 2015, Paul Osborne <osbpau@gmail.com>
-#![cfg_attr(not(test), no_std)]
+#![cfg_attr(not(any(test, feature = "std")), no_std)]
+
+// Lets `wii_ext::` paths resolve in files shared with the integration tests
+extern crate self as wii_ext;
 
 /// Async I2C implementations
 pub mod async_impl;
 
 /// Blocking I2C implementations
 pub mod blocking_impl;
+#[cfg(feature = "capture")]
+pub mod capture;
+mod compat;
 /// Types + data decoding
 pub mod core;
+mod drivers;
+#[cfg(feature = "eh0")]
+pub mod eh0;
+mod error;
+#[cfg(feature = "hid")]
+pub mod hid;
+#[cfg(feature = "linux")]
+pub mod linux;
+mod logging;
+#[cfg(feature = "mux")]
+pub mod mux;
+#[cfg(feature = "test-support")]
+pub mod test_data;
+#[cfg(feature = "test-support")]
+pub mod testing;
+
+pub use crate::error::{BatchError, Error};
+
+// Old module paths, kept so downstream crates can upgrade incrementally
+#[allow(deprecated)]
+pub use crate::compat::{classic, classic_async, classic_sync, nunchuk};
@@ -0,0 +1,57 @@
+//! Helpers for using the blocking drivers on Linux, over `/dev/i2c-*`
+//!
+//! This is intended for prototyping on a single board computer such as a Raspberry Pi:
+//!
+//! ```ignore
+//! let mut classic = wii_ext::linux::classic("/dev/i2c-1")?;
+//! loop {
+//!     println!("{:?}", classic.read()?);
+//! }
+//! ```
+//!
+//! The bus is opened with `linux-embedded-hal`, and delays use `std::thread::sleep`.
+
+use crate::blocking_impl::classic::Classic;
+use crate::blocking_impl::nunchuk::Nunchuk;
+use crate::eh0::{Eh0Error, Eh0I2c};
+use crate::Error;
+use linux_embedded_hal::i2cdev::linux::LinuxI2CError;
+use linux_embedded_hal::I2cdev;
+use std::path::Path;
+use std::time::Duration;
+
+/// A Linux i2c bus device, usable with the drivers
+pub type LinuxI2c = Eh0I2c<I2cdev>;
+
+/// Errors from the drivers when using a Linux i2c bus
+pub type LinuxError = Error<Eh0Error<LinuxI2CError>>;
+
+/// A delay that puts the current thread to sleep
+///
+/// Sleeps are at least as long as requested, but can be much longer on a busy system.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct StdDelay;
+
+impl embedded_hal::delay::DelayNs for StdDelay {
+    fn delay_ns(&mut self, ns: u32) {
+        std::thread::sleep(Duration::from_nanos(ns.into()));
+    }
+}
+
+/// Open an i2c bus, eg: `/dev/i2c-1`
+///
+/// A failure to open the device is reported as a bus error.
+pub fn open(path: impl AsRef<Path>) -> Result<LinuxI2c, LinuxError> {
+    let i2c = I2cdev::new(path).map_err(|e| Error::I2C(Eh0Error(e)))?;
+    Ok(Eh0I2c::new(i2c))
+}
+
+/// Open an i2c bus, then create, initialise and calibrate a classic controller on it
+pub fn classic(path: impl AsRef<Path>) -> Result<Classic<LinuxI2c, StdDelay>, LinuxError> {
+    Classic::new(open(path)?, StdDelay)
+}
+
+/// Open an i2c bus, then create, initialise and calibrate a nunchuk on it
+pub fn nunchuk(path: impl AsRef<Path>) -> Result<Nunchuk<LinuxI2c, StdDelay>, LinuxError> {
+    Nunchuk::new(open(path)?, StdDelay)
+}
@@ -0,0 +1,97 @@
+//! Bus-level diagnostics, sent to `log` and/or `defmt` depending on the enabled features
+//!
+//! Every i2c transaction made by the interfaces goes through one of these functions, which
+//! log it and convert its error. `B` is the bus type, named at the call site since the
+//! interfaces only know its error type as a type parameter. With neither feature enabled they only do the conversion.
+//! Successful transactions are logged at trace level and failures at debug level.
+
+#[cfg(any(feature = "log", feature = "defmt_print"))]
+use crate::core::raw::HexBytes;
+use crate::Error;
+use embedded_hal::i2c::ErrorType;
+
+/// Log a write (a seek if it is a single byte, a register write otherwise)
+#[inline(always)]
+pub(crate) fn write<B: ErrorType>(
+    result: Result<(), B::Error>,
+    bytes: &[u8],
+) -> Result<(), Error<B::Error>> {
+    if let Err(e) = &result {
+        failed("write", e);
+    } else if let [cursor] = bytes {
+        #[cfg(feature = "log")]
+        log::trace!("seek to {cursor:#04x}");
+        #[cfg(feature = "defmt_print")]
+        defmt::trace!("seek to {=u8:#04x}", *cursor);
+        let _ = cursor;
+    } else {
+        #[cfg(feature = "log")]
+        log::trace!("write {}", HexBytes(bytes));
+        #[cfg(feature = "defmt_print")]
+        defmt::trace!("write {}", HexBytes(bytes));
+    }
+    result.map_err(Error::I2C)
+}
+
+/// Log a read and the data it returned
+#[inline(always)]
+pub(crate) fn read<B: ErrorType>(
+    result: Result<(), B::Error>,
+    bytes: &[u8],
+) -> Result<(), Error<B::Error>> {
+    if let Err(e) = &result {
+        failed("read", e);
+    } else {
+        #[cfg(feature = "log")]
+        log::trace!("read {}", HexBytes(bytes));
+        #[cfg(feature = "defmt_print")]
+        defmt::trace!("read {}", HexBytes(bytes));
+        let _ = bytes;
+    }
+    result.map_err(Error::I2C)
+}
+
+/// Log a combined write and read, and the data it returned
+#[inline(always)]
+pub(crate) fn write_read<B: ErrorType>(
+    result: Result<(), B::Error>,
+    write: &[u8],
+    read: &[u8],
+) -> Result<(), Error<B::Error>> {
+    if let Err(e) = &result {
+        failed("write_read", e);
+    } else {
+        #[cfg(feature = "log")]
+        log::trace!("write {}, read {}", HexBytes(write), HexBytes(read));
+        #[cfg(feature = "defmt_print")]
+        defmt::trace!("write {}, read {}", HexBytes(write), HexBytes(read));
+        let _ = (write, read);
+    }
+    result.map_err(Error::I2C)
+}
+
+/// Log a failed validation check, with the data that failed it if there is any
+#[inline(always)]
+pub(crate) fn anomaly(description: &str, bytes: &[u8]) {
+    if bytes.is_empty() {
+        #[cfg(feature = "log")]
+        log::warn!("{description}");
+        #[cfg(feature = "defmt_print")]
+        defmt::warn!("{=str}", description);
+    } else {
+        #[cfg(feature = "log")]
+        log::warn!("{description}: {}", HexBytes(bytes));
+        #[cfg(feature = "defmt_print")]
+        defmt::warn!("{=str}: {}", description, HexBytes(bytes));
+    }
+    let _ = (description, bytes);
+}
+
+#[inline(always)]
+fn failed<E: core::fmt::Debug>(operation: &str, e: &E) {
+    #[cfg(feature = "log")]
+    log::debug!("{operation} failed: {e:?}");
+    #[cfg(feature = "defmt_print")]
+    defmt::debug!("{=str} failed: {}", operation, defmt::Debug2Format(e));
+    let _ = (operation, e);
+}
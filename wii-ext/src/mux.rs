@@ -0,0 +1,203 @@
+//! Running several controllers through a TCA9548A i2c multiplexer
+//!
+//! Every extension controller uses address 0x52, so more than one needs a mux. [`MuxedBus`]
+//! selects its channel before every transaction, so each driver can be given its own bus and
+//! used without any channel bookkeeping. The buses need to share the underlying i2c
+//! peripheral, eg: with `embedded-hal-bus`:
+//!
+//! ```ignore
+//! use core::cell::RefCell;
+//! use embedded_hal_bus::i2c::RefCellDevice;
+//! use wii_ext::mux::{MuxedBus, TCA9548A_ADDR};
+//!
+//! let bus = RefCell::new(i2c);
+//! let left = MuxedBus::new(RefCellDevice::new(&bus), TCA9548A_ADDR, 0).unwrap();
+//! let right = MuxedBus::new(RefCellDevice::new(&bus), TCA9548A_ADDR, 1).unwrap();
+//! let mut left = Classic::new(left, &mut delay)?;
+//! let mut right = Classic::new(right, &mut delay)?;
+//! ```
+//!
+//! Nothing else may change the mux channel during a transaction, so if other tasks use the
+//! mux, share the bus with a mutex rather than a `RefCell`.
+
+use embedded_hal::i2c::{ErrorKind, ErrorType, Operation, SevenBitAddress};
+
+/// Default address of a TCA9548A, with A0-A2 low
+pub const TCA9548A_ADDR: u8 = 0x70;
+
+/// An error from a [`MuxedBus`]
+#[cfg_attr(feature = "defmt_print", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MuxError<E> {
+    /// Selecting the channel failed, so the device was not contacted
+    Select(E),
+    /// The transaction with the device on the channel failed
+    Device(E),
+}
+
+impl<E> MuxError<E> {
+    /// The bus error, wherever it happened
+    pub fn into_inner(self) -> E {
+        match self {
+            Self::Select(e) | Self::Device(e) => e,
+        }
+    }
+}
+
+impl<E: core::fmt::Debug> core::fmt::Display for MuxError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Select(e) => write!(f, "i2c mux channel select failed: {:?}", e),
+            Self::Device(e) => write!(f, "i2c error behind mux: {:?}", e),
+        }
+    }
+}
+
+impl<E: core::error::Error + 'static> core::error::Error for MuxError<E> {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::Select(e) | Self::Device(e) => Some(e),
+        }
+    }
+}
+
+impl<E: embedded_hal::i2c::Error> embedded_hal::i2c::Error for MuxError<E> {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Select(e) | Self::Device(e) => e.kind(),
+        }
+    }
+}
+
+/// One channel of a TCA9548A, used as an i2c bus
+///
+/// Every transaction first writes the channel mask to the mux, then talks to the device.
+#[derive(Debug)]
+pub struct MuxedBus<I2C> {
+    i2c: I2C,
+    mux_address: u8,
+    channel: u8,
+}
+
+impl<I2C> MuxedBus<I2C> {
+    /// Use `channel` (0 to 7) of the mux at `mux_address`
+    ///
+    /// Returns `None` if the channel is out of range.
+    pub fn new(i2c: I2C, mux_address: u8, channel: u8) -> Option<Self> {
+        (channel < 8).then_some(Self {
+            i2c,
+            mux_address,
+            channel,
+        })
+    }
+
+    /// The channel this bus selects
+    pub fn channel(&self) -> u8 {
+        self.channel
+    }
+
+    /// Recover the underlying bus
+    pub fn into_inner(self) -> I2C {
+        self.i2c
+    }
+}
+
+impl<I2C: ErrorType> ErrorType for MuxedBus<I2C> {
+    type Error = MuxError<I2C::Error>;
+}
+
+impl<I2C: embedded_hal::i2c::I2c> MuxedBus<I2C> {
+    fn select(&mut self) -> Result<(), MuxError<I2C::Error>> {
+        self.i2c
+            .write(self.mux_address, &[1 << self.channel])
+            .map_err(MuxError::Select)
+    }
+}
+
+// Each method is forwarded as the same kind of operation, so the underlying bus can use
+// its own implementation of it
+impl<I2C: embedded_hal::i2c::I2c> embedded_hal::i2c::I2c<SevenBitAddress> for MuxedBus<I2C> {
+    fn read(&mut self, address: u8, read: &mut [u8]) -> Result<(), Self::Error> {
+        self.select()?;
+        self.i2c.read(address, read).map_err(MuxError::Device)
+    }
+
+    fn write(&mut self, address: u8, write: &[u8]) -> Result<(), Self::Error> {
+        self.select()?;
+        self.i2c.write(address, write).map_err(MuxError::Device)
+    }
+
+    fn write_read(
+        &mut self,
+        address: u8,
+        write: &[u8],
+        read: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        self.select()?;
+        self.i2c
+            .write_read(address, write, read)
+            .map_err(MuxError::Device)
+    }
+
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        self.select()?;
+        self.i2c
+            .transaction(address, operations)
+            .map_err(MuxError::Device)
+    }
+}
+
+impl<I2C: embedded_hal_async::i2c::I2c> MuxedBus<I2C> {
+    async fn select_async(&mut self) -> Result<(), MuxError<I2C::Error>> {
+        self.i2c
+            .write(self.mux_address, &[1 << self.channel])
+            .await
+            .map_err(MuxError::Select)
+    }
+}
+
+impl<I2C: embedded_hal_async::i2c::I2c> embedded_hal_async::i2c::I2c<SevenBitAddress>
+    for MuxedBus<I2C>
+{
+    async fn read(&mut self, address: u8, read: &mut [u8]) -> Result<(), Self::Error> {
+        self.select_async().await?;
+        self.i2c.read(address, read).await.map_err(MuxError::Device)
+    }
+
+    async fn write(&mut self, address: u8, write: &[u8]) -> Result<(), Self::Error> {
+        self.select_async().await?;
+        self.i2c
+            .write(address, write)
+            .await
+            .map_err(MuxError::Device)
+    }
+
+    async fn write_read(
+        &mut self,
+        address: u8,
+        write: &[u8],
+        read: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        self.select_async().await?;
+        self.i2c
+            .write_read(address, write, read)
+            .await
+            .map_err(MuxError::Device)
+    }
+
+    async fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        self.select_async().await?;
+        self.i2c
+            .transaction(address, operations)
+            .await
+            .map_err(MuxError::Device)
+    }
+}
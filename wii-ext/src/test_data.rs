@@ -0,0 +1,233 @@
+//! Reports captured from real controllers, for testing code that decodes them
+//!
+//! Each controller has its ID (the registers at 0xFA), and reports in certain states.
+//! Reports are 6 bytes ([`ExtReport`](crate::core::ExtReport)), or 8 bytes
+//! ([`ExtHdReport`](crate::core::ExtHdReport)) for the `_HD_` constants.
+//! `*_HIRES_DEFAULT` is the value of the hi-res mode register *before* setting it,
+//! since we'll need that to restore to regular mode.
+//!
+//! Single button presses that only change the button bits are built from the idle report with
+//! [`classic_report!`](crate::classic_report), the other reports are raw captures.
+//!
+//! This file is also used by the crate's own integration tests, so it spells out its types
+//! and names the crate as `wii_ext`.
+
+use wii_ext::classic_report;
+
+// Nunchuck
+pub const NUNCHUCK_ID: [u8; 6] = [0, 0, 164, 32, 0, 0];
+pub const NUNCHUCK_IDLE: [u8; 6] = [126, 129, 125, 139, 170, 95];
+pub const NUNCHUCK_JOY_U: [u8; 6] = [130, 221, 125, 118, 172, 191];
+pub const NUNCHUCK_JOY_D: [u8; 6] = [126, 35, 130, 131, 173, 7];
+pub const NUNCHUCK_JOY_L: [u8; 6] = [25, 130, 117, 126, 172, 191];
+pub const NUNCHUCK_JOY_R: [u8; 6] = [225, 130, 122, 132, 173, 27];
+pub const NUNCHUCK_BTN_C: [u8; 6] = [127, 128, 122, 138, 171, 181];
+pub const NUNCHUCK_BTN_Z: [u8; 6] = [127, 127, 122, 134, 172, 122];
+pub const NUNCHUCK_HD_IDLE: [u8; 8] = [126, 128, 148, 119, 160, 211, 0, 0];
+
+// NES classic controller
+pub const NES_ID: [u8; 6] = [1, 0, 164, 32, 1, 1];
+pub const NES_IDLE: [u8; 6] = [95, 223, 143, 0, 255, 255];
+pub const NES_BTN_B: [u8; 6] = [95, 223, 143, 0, 255, 191];
+pub const NES_BTN_A: [u8; 6] = [95, 223, 143, 0, 255, 239];
+pub const NES_BTN_SELECT: [u8; 6] = [95, 223, 143, 0, 239, 255];
+pub const NES_BTN_START: [u8; 6] = [95, 223, 143, 0, 251, 255];
+pub const NES_PAD_U: [u8; 6] = [95, 223, 143, 0, 255, 254];
+pub const NES_PAD_D: [u8; 6] = [95, 223, 143, 0, 191, 255];
+pub const NES_PAD_L: [u8; 6] = [95, 223, 143, 0, 255, 253];
+pub const NES_PAD_R: [u8; 6] = [95, 223, 143, 0, 127, 255];
+pub const NES_HD_IDLE: [u8; 8] = [127, 127, 127, 127, 000, 000, 255, 255];
+
+// SNES classic
+pub const SNES_ID: [u8; 6] = [1, 0, 164, 32, 1, 1];
+pub const SNES_IDLE: [u8; 6] = [160, 33, 16, 0, 255, 255];
+pub const SNES_BTN_B: [u8; 6] = [160, 33, 16, 0, 255, 191];
+pub const SNES_BTN_A: [u8; 6] = [95, 223, 143, 0, 255, 239];
+pub const SNES_BTN_X: [u8; 6] = [160, 33, 16, 0, 255, 247];
+pub const SNES_BTN_Y: [u8; 6] = [160, 33, 16, 0, 255, 223];
+pub const SNES_BTN_L: [u8; 6] = [160, 33, 112, 224, 223, 255];
+pub const SNES_BTN_R: [u8; 6] = [160, 33, 16, 31, 253, 255];
+pub const SNES_PAD_U: [u8; 6] = [95, 223, 143, 0, 255, 254];
+pub const SNES_PAD_D: [u8; 6] = [95, 223, 143, 0, 191, 255];
+pub const SNES_PAD_L: [u8; 6] = [95, 223, 143, 0, 255, 253];
+pub const SNES_PAD_R: [u8; 6] = [95, 223, 143, 0, 127, 255];
+pub const SNES_BTN_SELECT: [u8; 6] = [95, 223, 143, 0, 239, 255];
+pub const SNES_BTN_START: [u8; 6] = [95, 223, 143, 0, 251, 255];
+pub const SNES_HD_IDLE: [u8; 8] = [128, 132, 132, 132, 0, 0, 255, 255];
+
+// Wii Classic controller
+pub const CLASSIC_ID: [u8; 6] = [0, 0, 164, 32, 1, 1];
+pub const CLASSIC_HIRES_DEFAULT: u8 = 1;
+pub const CLASSIC_IDLE: [u8; 6] = [97, 224, 145, 99, 255, 255];
+pub const CLASSIC_BTN_B: [u8; 6] = [97, 224, 145, 99, 255, 191];
+pub const CLASSIC_BTN_A: [u8; 6] = [97, 224, 145, 99, 255, 239];
+pub const CLASSIC_BTN_X: [u8; 6] = [97, 224, 145, 99, 255, 247];
+pub const CLASSIC_BTN_Y: [u8; 6] = [97, 224, 145, 99, 255, 223];
+pub const CLASSIC_BTN_L: [u8; 6] = [97, 224, 241, 163, 223, 255];
+pub const CLASSIC_BTN_R: [u8; 6] = [97, 224, 145, 124, 253, 255];
+pub const CLASSIC_BTN_ZL: [u8; 6] = [97, 224, 145, 99, 255, 127];
+pub const CLASSIC_BTN_ZR: [u8; 6] = [97, 224, 145, 99, 255, 251];
+pub const CLASSIC_PAD_U: [u8; 6] = [97, 224, 145, 99, 255, 254];
+pub const CLASSIC_PAD_D: [u8; 6] = [97, 224, 145, 99, 191, 255];
+pub const CLASSIC_PAD_L: [u8; 6] = [97, 224, 145, 99, 255, 253];
+pub const CLASSIC_PAD_R: [u8; 6] = [97, 224, 145, 99, 127, 255];
+pub const CLASSIC_BTN_MINUS: [u8; 6] = [97, 224, 145, 99, 239, 255];
+pub const CLASSIC_BTN_PLUS: [u8; 6] = [97, 224, 145, 99, 251, 255];
+pub const CLASSIC_BTN_HOME: [u8; 6] = [97, 224, 145, 99, 247, 255];
+pub const CLASSIC_LJOY_U: [u8; 6] = [97, 251, 145, 99, 255, 255];
+pub const CLASSIC_LJOY_D: [u8; 6] = [97, 200, 145, 99, 255, 255];
+pub const CLASSIC_LJOY_L: [u8; 6] = [72, 226, 145, 99, 255, 255];
+pub const CLASSIC_LJOY_R: [u8; 6] = [121, 225, 145, 99, 255, 255];
+pub const CLASSIC_RJOY_U: [u8; 6] = [161, 32, 29, 99, 255, 255];
+pub const CLASSIC_RJOY_D: [u8; 6] = [161, 32, 3, 99, 255, 255];
+pub const CLASSIC_RJOY_L: [u8; 6] = [33, 96, 144, 99, 255, 255];
+pub const CLASSIC_RJOY_R: [u8; 6] = [225, 160, 16, 99, 255, 255];
+pub const CLASSIC_LTRIG: [u8; 6] = [97, 224, 241, 195, 255, 255];
+pub const CLASSIC_RTRIG: [u8; 6] = [97, 224, 145, 126, 255, 255];
+pub const CLASSIC_LTRIG_W_BUTTON: [u8; 6] = [97, 224, 241, 195, 223, 255];
+pub const CLASSIC_RTRIG_W_BUTTON: [u8; 6] = [97, 224, 145, 126, 253, 255];
+pub const CLASSIC_LJOY_UR: [u8; 6] = [114, 243, 145, 99, 255, 255];
+pub const CLASSIC_LJOY_DR: [u8; 6] = [114, 206, 145, 99, 255, 255];
+pub const CLASSIC_LJOY_DL: [u8; 6] = [79, 205, 145, 99, 255, 255];
+pub const CLASSIC_LJOY_UL: [u8; 6] = [79, 245, 145, 99, 255, 255];
+pub const CLASSIC_RJOY_UR: [u8; 6] = [225, 32, 154, 99, 255, 255];
+pub const CLASSIC_RJOY_DR: [u8; 6] = [225, 32, 134, 99, 255, 255];
+pub const CLASSIC_RJOY_DL: [u8; 6] = [33, 224, 7, 99, 255, 255];
+pub const CLASSIC_RJOY_UL: [u8; 6] = [33, 224, 25, 99, 255, 255];
+
+// Wii Classic in High_Def mode (subset of all data, only really care about axis diffs)
+pub const CLASSIC_HD_IDLE: [u8; 8] = [132, 127, 130, 136, 31, 26, 255, 255];
+pub const CLASSIC_HD_LJOY_U: [u8; 8] = [134, 128, 238, 137, 31, 26, 255, 255];
+pub const CLASSIC_HD_LJOY_D: [u8; 8] = [130, 128, 34, 138, 31, 26, 255, 255];
+pub const CLASSIC_HD_LJOY_L: [u8; 8] = [36, 127, 135, 137, 31, 26, 255, 255];
+pub const CLASSIC_HD_LJOY_R: [u8; 8] = [229, 127, 134, 138, 31, 26, 255, 255];
+pub const CLASSIC_HD_RJOY_U: [u8; 8] = [132, 131, 130, 239, 31, 24, 255, 255];
+pub const CLASSIC_HD_RJOY_D: [u8; 8] = [132, 130, 131, 30, 31, 24, 255, 255];
+pub const CLASSIC_HD_RJOY_L: [u8; 8] = [133, 29, 130, 135, 31, 24, 255, 255];
+pub const CLASSIC_HD_RJOY_R: [u8; 8] = [133, 226, 131, 132, 31, 24, 255, 255];
+pub const CLASSIC_HD_LTRIG: [u8; 8] = [133, 128, 131, 137, 245, 22, 255, 255];
+pub const CLASSIC_HD_RTRIG: [u8; 8] = [131, 128, 131, 137, 31, 230, 255, 255];
+pub const CLASSIC_HD_BTN_X: [u8; 8] = [132, 128, 131, 137, 31, 26, 255, 247];
+
+// wii classic pro joystick
+pub const PRO_ID: [u8; 6] = [1, 0, 164, 32, 1, 1];
+pub const PRO_HIRES_DEFAULT: u8 = 1;
+pub const PRO_IDLE: [u8; 6] = [160, 31, 17, 0, 255, 255];
+// Presses that only change the button bits are built from PRO_IDLE.
+// L and R also move the analogue triggers, so they are raw captures
+pub const PRO_BTN_B: [u8; 6] = classic_report! { ..PRO_IDLE, button_b };
+pub const PRO_BTN_A: [u8; 6] = classic_report! { ..PRO_IDLE, button_a };
+pub const PRO_BTN_X: [u8; 6] = classic_report! { ..PRO_IDLE, button_x };
+pub const PRO_BTN_Y: [u8; 6] = classic_report! { ..PRO_IDLE, button_y };
+pub const PRO_BTN_L: [u8; 6] = [159, 31, 113, 224, 223, 255];
+pub const PRO_BTN_R: [u8; 6] = [160, 31, 17, 31, 253, 255];
+pub const PRO_BTN_ZL: [u8; 6] = classic_report! { ..PRO_IDLE, button_zl };
+pub const PRO_BTN_ZR: [u8; 6] = classic_report! { ..PRO_IDLE, button_zr };
+pub const PRO_PAD_U: [u8; 6] = classic_report! { ..PRO_IDLE, dpad_up };
+pub const PRO_PAD_D: [u8; 6] = classic_report! { ..PRO_IDLE, dpad_down };
+pub const PRO_PAD_L: [u8; 6] = classic_report! { ..PRO_IDLE, dpad_left };
+pub const PRO_PAD_R: [u8; 6] = classic_report! { ..PRO_IDLE, dpad_right };
+pub const PRO_BTN_MINUS: [u8; 6] = classic_report! { ..PRO_IDLE, button_minus };
+pub const PRO_BTN_PLUS: [u8; 6] = classic_report! { ..PRO_IDLE, button_plus };
+pub const PRO_BTN_HOME: [u8; 6] = classic_report! { ..PRO_IDLE, button_home };
+pub const PRO_LJOY_U: [u8; 6] = [160, 57, 17, 0, 255, 255];
+pub const PRO_LJOY_D: [u8; 6] = [160, 4, 17, 0, 255, 255];
+pub const PRO_LJOY_L: [u8; 6] = [133, 30, 17, 0, 255, 255];
+pub const PRO_LJOY_R: [u8; 6] = [185, 31, 17, 0, 255, 255];
+pub const PRO_RJOY_U: [u8; 6] = [160, 31, 30, 0, 255, 255];
+pub const PRO_RJOY_D: [u8; 6] = [160, 31, 4, 0, 255, 255];
+pub const PRO_RJOY_L: [u8; 6] = [32, 95, 17, 0, 255, 255];
+pub const PRO_RJOY_R: [u8; 6] = [224, 159, 145, 0, 255, 255];
+pub const PRO_LJOY_UR: [u8; 6] = [180, 48, 17, 0, 255, 255];
+pub const PRO_LJOY_DR: [u8; 6] = [179, 12, 17, 0, 255, 255];
+pub const PRO_LJOY_DL: [u8; 6] = [139, 11, 17, 0, 255, 255];
+pub const PRO_LJOY_UL: [u8; 6] = [139, 50, 17, 0, 255, 255];
+pub const PRO_RJOY_UR: [u8; 6] = [224, 95, 27, 0, 255, 255];
+pub const PRO_RJOY_DR: [u8; 6] = [224, 95, 7, 0, 255, 255];
+pub const PRO_RJOY_DL: [u8; 6] = [32, 223, 7, 0, 255, 255];
+pub const PRO_RJOY_UL: [u8; 6] = [32, 223, 27, 0, 255, 255];
+
+// No analog triggers on pro controller
+//pub const PRO_LTRIG: [u8; 6] = [];
+//pub const PRO_RTRIG: [u8; 6] = [];
+
+pub const PRO_HD_IDLE: [u8; 8] = [128, 129, 125, 139, 0, 0, 255, 255];
+pub const PRO_HD_LJOY_U: [u8; 8] = [129, 129, 228, 139, 0, 0, 255, 255];
+pub const PRO_HD_LJOY_D: [u8; 8] = [127, 129, 18, 139, 0, 0, 255, 255];
+pub const PRO_HD_LJOY_L: [u8; 8] = [22, 129, 121, 139, 0, 0, 255, 255];
+pub const PRO_HD_LJOY_R: [u8; 8] = [230, 129, 125, 139, 0, 0, 255, 255];
+pub const PRO_HD_RJOY_U: [u8; 8] = [128, 130, 126, 245, 0, 0, 255, 255];
+pub const PRO_HD_RJOY_D: [u8; 8] = [128, 130, 126, 33, 0, 0, 255, 255];
+pub const PRO_HD_RJOY_L: [u8; 8] = [128, 23, 126, 142, 0, 0, 255, 255];
+pub const PRO_HD_RJOY_R: [u8; 8] = [128, 235, 126, 139, 0, 0, 255, 255];
+pub const PRO_HD_LJOY_UR: [u8; 8] = [207, 130, 197, 138, 0, 0, 255, 255];
+pub const PRO_HD_LJOY_DR: [u8; 8] = [207, 130, 50, 138, 0, 0, 255, 255];
+pub const PRO_HD_LJOY_DL: [u8; 8] = [45, 130, 43, 138, 0, 0, 255, 255];
+pub const PRO_HD_LJOY_UL: [u8; 8] = [47, 130, 203, 138, 0, 0, 255, 255];
+pub const PRO_HD_RJOY_UR: [u8; 8] = [128, 210, 125, 215, 0, 0, 255, 255];
+pub const PRO_HD_RJOY_DR: [u8; 8] = [128, 210, 125, 62, 0, 0, 255, 255];
+pub const PRO_HD_RJOY_DL: [u8; 8] = [128, 48, 125, 58, 0, 0, 255, 255];
+pub const PRO_HD_RJOY_UL: [u8; 8] = [128, 50, 125, 220, 0, 0, 255, 255];
+
+// No analog triggers on pro controller
+//pub const PRO_HD_LTRIG: [u8; 6] = [];
+//pub const PRO_HD_RTRIG: [u8; 6] = [];
+
+// PDP "Link" gamecube clone controller
+pub const PDP_LINK_ID: [u8; 6] = [1, 0, 164, 32, 1, 1];
+pub const PDP_LINK_HIRES_DEFAULT: u8 = 1;
+pub const PDP_LINK_IDLE: [u8; 6] = [160, 29, 15, 0, 255, 255];
+pub const PDP_LINK_BTN_B: [u8; 6] = [160, 30, 15, 0, 255, 191];
+pub const PDP_LINK_BTN_A: [u8; 6] = [160, 30, 15, 0, 255, 239];
+pub const PDP_LINK_BTN_X: [u8; 6] = [160, 30, 15, 0, 255, 247];
+pub const PDP_LINK_BTN_Y: [u8; 6] = [160, 30, 15, 0, 255, 223];
+pub const PDP_LINK_BTN_L: [u8; 6] = [160, 30, 111, 224, 223, 255];
+pub const PDP_LINK_BTN_R: [u8; 6] = [160, 30, 15, 31, 253, 255];
+pub const PDP_LINK_BTN_ZL: [u8; 6] = [160, 30, 15, 0, 255, 127];
+pub const PDP_LINK_BTN_ZR: [u8; 6] = [160, 30, 15, 0, 255, 251];
+pub const PDP_LINK_PAD_U: [u8; 6] = [160, 30, 15, 0, 255, 254];
+pub const PDP_LINK_PAD_D: [u8; 6] = [160, 30, 15, 0, 191, 255];
+pub const PDP_LINK_PAD_L: [u8; 6] = [160, 30, 15, 0, 255, 253];
+pub const PDP_LINK_PAD_R: [u8; 6] = [160, 30, 15, 0, 127, 255];
+pub const PDP_LINK_BTN_MINUS: [u8; 6] = [160, 30, 15, 0, 239, 255];
+pub const PDP_LINK_BTN_PLUS: [u8; 6] = [160, 30, 15, 0, 251, 255];
+pub const PDP_LINK_BTN_HOME: [u8; 6] = [160, 30, 15, 0, 247, 255];
+pub const PDP_LINK_LJOY_U: [u8; 6] = [159, 63, 15, 0, 255, 255];
+pub const PDP_LINK_LJOY_D: [u8; 6] = [159, 0, 15, 0, 255, 255];
+pub const PDP_LINK_LJOY_L: [u8; 6] = [128, 30, 15, 0, 255, 255];
+pub const PDP_LINK_LJOY_R: [u8; 6] = [189, 30, 15, 0, 255, 255];
+pub const PDP_LINK_RJOY_U: [u8; 6] = [160, 30, 31, 0, 255, 255];
+pub const PDP_LINK_RJOY_D: [u8; 6] = [160, 30, 0, 0, 255, 255];
+pub const PDP_LINK_RJOY_L: [u8; 6] = [32, 30, 143, 0, 255, 255];
+pub const PDP_LINK_RJOY_R: [u8; 6] = [224, 222, 143, 0, 255, 255];
+// No analog triggers on PDP controller
+// pub const PDP_LINK_LTRIG: [u8; 6] = [];
+// pub const PDP_LINK_RTRIG: [u8; 6] = [];
+pub const PDP_LINK_LJOY_UR: [u8; 6] = [180, 55, 15, 0, 255, 255];
+pub const PDP_LINK_LJOY_DR: [u8; 6] = [179, 6, 15, 0, 255, 255];
+pub const PDP_LINK_LJOY_DL: [u8; 6] = [131, 5, 15, 0, 255, 255];
+pub const PDP_LINK_LJOY_UL: [u8; 6] = [131, 54, 15, 0, 255, 255];
+pub const PDP_LINK_RJOY_UR: [u8; 6] = [223, 222, 27, 0, 255, 255];
+pub const PDP_LINK_RJOY_DR: [u8; 6] = [223, 222, 2, 0, 255, 255];
+pub const PDP_LINK_RJOY_DL: [u8; 6] = [31, 222, 2, 0, 255, 255];
+pub const PDP_LINK_RJOY_UL: [u8; 6] = [31, 222, 27, 0, 255, 255];
+
+pub const PDP_LINK_HD_IDLE: [u8; 8] = [126, 130, 120, 124, 0, 0, 255, 255];
+pub const PDP_LINK_HD_LJOY_U: [u8; 8] = [125, 130, 253, 124, 0, 0, 255, 255];
+pub const PDP_LINK_HD_LJOY_D: [u8; 8] = [124, 130, 0, 124, 0, 0, 255, 255];
+pub const PDP_LINK_HD_LJOY_L: [u8; 8] = [0, 130, 120, 124, 0, 0, 255, 255];
+pub const PDP_LINK_HD_LJOY_R: [u8; 8] = [245, 130, 123, 124, 0, 0, 255, 255];
+pub const PDP_LINK_HD_RJOY_U: [u8; 8] = [129, 132, 120, 253, 0, 0, 255, 255];
+pub const PDP_LINK_HD_RJOY_D: [u8; 8] = [129, 132, 120, 0, 0, 0, 255, 255];
+pub const PDP_LINK_HD_RJOY_L: [u8; 8] = [129, 10, 120, 124, 0, 0, 255, 255];
+pub const PDP_LINK_HD_RJOY_R: [u8; 8] = [129, 255, 120, 124, 0, 0, 255, 255];
+pub const PDP_LINK_HD_LTRIG: [u8; 8] = [129, 131, 120, 125, 0, 0, 255, 127];
+pub const PDP_LINK_HD_RTRIG: [u8; 8] = [129, 131, 120, 125, 0, 0, 255, 251];
+pub const PDP_LINK_HD_LJOY_UR: [u8; 8] = [209, 130, 222, 124, 0, 0, 255, 255];
+pub const PDP_LINK_HD_LJOY_DR: [u8; 8] = [207, 131, 23, 125, 0, 0, 255, 255];
+pub const PDP_LINK_HD_LJOY_DL: [u8; 8] = [18, 131, 20, 125, 0, 0, 255, 255];
+pub const PDP_LINK_HD_LJOY_UL: [u8; 8] = [15, 131, 216, 125, 0, 0, 255, 255];
+pub const PDP_LINK_HD_RJOY_UR: [u8; 8] = [127, 242, 120, 218, 0, 0, 255, 255];
+pub const PDP_LINK_HD_RJOY_DR: [u8; 8] = [127, 242, 120, 19, 0, 0, 255, 255];
+pub const PDP_LINK_HD_RJOY_DL: [u8; 8] = [127, 50, 120, 22, 0, 0, 255, 255];
+pub const PDP_LINK_HD_RJOY_UL: [u8; 8] = [127, 50, 120, 219, 0, 0, 255, 255];
@@ -0,0 +1,308 @@
+//! An emulated extension controller, for testing code that uses the drivers
+//!
+//! [`FakeExtension`] implements the blocking and async i2c traits and behaves like a controller
+//! on the bus, so tests can drive the real [`Classic`](crate::blocking_impl::classic::Classic)
+//! and [`Nunchuk`](crate::blocking_impl::nunchuk::Nunchuk) drivers without scripting the
+//! crate's bus traffic:
+//!
+//! ```ignore
+//! let fake = FakeExtension::classic();
+//! let mut classic = Classic::new(fake.clone(), NoopDelay::new())?;
+//! fake.press(Button::A);
+//! assert!(classic.read()?.button_a);
+//! ```
+//!
+//! Clones of a `FakeExtension` share the same device, so keep one to control the controller
+//! while the driver owns another.
+
+use crate::core::classic::ClassicReading;
+use crate::core::events::Button;
+use crate::core::nunchuk::NunchukReading;
+use crate::core::{ControllerType, EXT_I2C_ADDR, REGISTER_SPACE_SIZE};
+use embedded_hal::i2c::{ErrorKind, ErrorType, NoAcknowledgeSource, Operation, SevenBitAddress};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// Register holding the report format, 3 for high-resolution classic controller reports
+const FORMAT_REGISTER: u8 = 0xFE;
+/// Report format that selects 9 byte classic controller reports
+const FORMAT_ALT: u8 = 0x02;
+/// Report format that selects 8 byte classic controller reports
+const FORMAT_HIRES: u8 = 0x03;
+
+/// What the fake reports from register 0
+#[derive(Debug, Clone, Copy)]
+enum Reading {
+    Classic(ClassicReading),
+    Nunchuk(NunchukReading),
+}
+
+#[derive(Debug)]
+struct Device {
+    registers: [u8; REGISTER_SPACE_SIZE],
+    cursor: u8,
+    /// The first step of the unencrypted init handshake, 0x55 to 0xF0, has been done
+    handshake_started: bool,
+    /// The init handshake is complete, so reports are sent unencrypted
+    initialised: bool,
+    connected: bool,
+    reading: Reading,
+    queue: VecDeque<Reading>,
+    report_reads: usize,
+}
+
+impl Device {
+    fn write(&mut self, bytes: &[u8]) {
+        let Some((&cursor, data)) = bytes.split_first() else {
+            return;
+        };
+        self.cursor = cursor;
+        for &value in data {
+            match (self.cursor, value) {
+                (0xF0, 0x55) => self.handshake_started = true,
+                (0xFB, 0x00) if self.handshake_started => self.initialised = true,
+                _ => {}
+            }
+            self.registers[usize::from(self.cursor)] = value;
+            self.cursor = self.cursor.wrapping_add(1);
+        }
+    }
+
+    fn read(&mut self, buf: &mut [u8]) {
+        if self.cursor != 0 {
+            for byte in buf {
+                *byte = self.registers[usize::from(self.cursor)];
+                self.cursor = self.cursor.wrapping_add(1);
+            }
+            return;
+        }
+        self.report_reads += 1;
+        if !self.initialised {
+            // Without the handshake, reports are encrypted
+            buf.fill(0xFF);
+            return;
+        }
+        if let Some(reading) = self.queue.pop_front() {
+            self.reading = reading;
+        }
+        let report = match (self.reading, self.registers[usize::from(FORMAT_REGISTER)]) {
+            (Reading::Classic(r), FORMAT_HIRES) => r.to_hd_report().to_vec(),
+            (Reading::Classic(r), FORMAT_ALT) => r.to_alt_report().to_vec(),
+            (Reading::Classic(r), _) => r.to_report().to_vec(),
+            (Reading::Nunchuk(r), _) => r.to_report().to_vec(),
+        };
+        for (i, byte) in buf.iter_mut().enumerate() {
+            *byte = report.get(i).copied().unwrap_or(self.registers[i]);
+        }
+        // Like genuine controllers, a report read leaves the cursor back at 0
+    }
+}
+
+/// An emulated Wii extension controller on an i2c bus
+///
+/// Answers the init handshake and ID read, honours the report format register (0xFE) and
+/// serves reports from a programmable reading. Until the handshake is done, reports read as
+/// all 0xFF, like the encrypted reports a real controller sends.
+#[derive(Debug, Clone)]
+pub struct FakeExtension(Arc<Mutex<Device>>);
+
+impl FakeExtension {
+    /// Emulate a controller of type `controller`, at rest and connected
+    pub fn new(controller: ControllerType) -> Self {
+        let (id, reading) = match controller {
+            ControllerType::Nunchuk => (
+                [0x00, 0x00, 0xA4, 0x20, 0x00, 0x00],
+                Reading::Nunchuk(NunchukReading::CENTERED),
+            ),
+            ControllerType::Classic | ControllerType::ClassicPro => (
+                if controller == ControllerType::Classic {
+                    [0x00, 0x00, 0xA4, 0x20, 0x01, 0x01]
+                } else {
+                    [0x01, 0x00, 0xA4, 0x20, 0x01, 0x01]
+                },
+                Reading::Classic(ClassicReading::CENTERED),
+            ),
+        };
+        let mut registers = [0; REGISTER_SPACE_SIZE];
+        registers[0xFA..].copy_from_slice(&id);
+        Self(Arc::new(Mutex::new(Device {
+            registers,
+            cursor: 0,
+            handshake_started: false,
+            initialised: false,
+            connected: true,
+            reading,
+            queue: VecDeque::new(),
+            report_reads: 0,
+        })))
+    }
+
+    /// Emulate a Wii classic controller
+    pub fn classic() -> Self {
+        Self::new(ControllerType::Classic)
+    }
+
+    /// Emulate a classic controller pro, or one of the many controllers that identify as one
+    pub fn classic_pro() -> Self {
+        Self::new(ControllerType::ClassicPro)
+    }
+
+    /// Emulate a nunchuk
+    pub fn nunchuk() -> Self {
+        Self::new(ControllerType::Nunchuk)
+    }
+
+    fn device(&self) -> MutexGuard<'_, Device> {
+        // A test that panicked while holding the lock can't leave the device half-updated
+        self.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Press a button. Buttons the controller doesn't have are ignored
+    pub fn press(&self, button: Button) {
+        self.set_button(button, true);
+    }
+
+    /// Release a button. Buttons the controller doesn't have are ignored
+    pub fn release(&self, button: Button) {
+        self.set_button(button, false);
+    }
+
+    fn set_button(&self, button: Button, pressed: bool) {
+        match &mut self.device().reading {
+            Reading::Classic(r) => r.set_button(button, pressed),
+            Reading::Nunchuk(r) => r.set_button(button, pressed),
+        }
+    }
+
+    /// Report `reading` from now on. Ignored unless this is a classic controller
+    pub fn set_classic(&self, reading: ClassicReading) {
+        let mut device = self.device();
+        if let Reading::Classic(r) = &mut device.reading {
+            *r = reading;
+        }
+    }
+
+    /// Report `reading` from now on. Ignored unless this is a nunchuk
+    pub fn set_nunchuk(&self, reading: NunchukReading) {
+        let mut device = self.device();
+        if let Reading::Nunchuk(r) = &mut device.reading {
+            *r = reading;
+        }
+    }
+
+    /// The reading currently reported, if this is a classic controller
+    pub fn classic_reading(&self) -> Option<ClassicReading> {
+        match self.device().reading {
+            Reading::Classic(r) => Some(r),
+            Reading::Nunchuk(_) => None,
+        }
+    }
+
+    /// The reading currently reported, if this is a nunchuk
+    pub fn nunchuk_reading(&self) -> Option<NunchukReading> {
+        match self.device().reading {
+            Reading::Nunchuk(r) => Some(r),
+            Reading::Classic(_) => None,
+        }
+    }
+
+    /// Queue readings to report, one per report read
+    ///
+    /// The last one stays in place once the queue is empty. Ignored unless this is a classic
+    /// controller.
+    pub fn queue_classic(&self, readings: impl IntoIterator<Item = ClassicReading>) {
+        let mut device = self.device();
+        if let Reading::Classic(_) = device.reading {
+            device
+                .queue
+                .extend(readings.into_iter().map(Reading::Classic));
+        }
+    }
+
+    /// Queue readings to report, one per report read
+    ///
+    /// The last one stays in place once the queue is empty. Ignored unless this is a nunchuk.
+    pub fn queue_nunchuk(&self, readings: impl IntoIterator<Item = NunchukReading>) {
+        let mut device = self.device();
+        if let Reading::Nunchuk(_) = device.reading {
+            device
+                .queue
+                .extend(readings.into_iter().map(Reading::Nunchuk));
+        }
+    }
+
+    /// Plug or unplug the controller
+    ///
+    /// An unplugged controller doesn't acknowledge its address, and forgets that it was
+    /// initialised, like a real controller losing power.
+    pub fn set_connected(&self, connected: bool) {
+        let mut device = self.device();
+        device.connected = connected;
+        if !connected {
+            device.handshake_started = false;
+            device.initialised = false;
+        }
+    }
+
+    /// Whether the init handshake has been done since power-on
+    pub fn is_initialised(&self) -> bool {
+        self.device().initialised
+    }
+
+    /// Whether the controller is sending high-resolution reports
+    pub fn is_hires(&self) -> bool {
+        self.register(FORMAT_REGISTER) == FORMAT_HIRES
+    }
+
+    /// Current value of a register
+    pub fn register(&self, addr: u8) -> u8 {
+        self.device().registers[usize::from(addr)]
+    }
+
+    /// Number of reads made from register 0, including calibration reads
+    pub fn report_reads(&self) -> usize {
+        self.device().report_reads
+    }
+
+    fn transaction(
+        &mut self,
+        address: SevenBitAddress,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), ErrorKind> {
+        let mut device = self.device();
+        if u16::from(address) != EXT_I2C_ADDR || !device.connected {
+            return Err(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address));
+        }
+        for operation in operations {
+            match operation {
+                Operation::Write(bytes) => device.write(bytes),
+                Operation::Read(buf) => device.read(buf),
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ErrorType for FakeExtension {
+    type Error = ErrorKind;
+}
+
+impl embedded_hal::i2c::I2c for FakeExtension {
+    fn transaction(
+        &mut self,
+        address: SevenBitAddress,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), ErrorKind> {
+        FakeExtension::transaction(self, address, operations)
+    }
+}
+
+impl embedded_hal_async::i2c::I2c for FakeExtension {
+    async fn transaction(
+        &mut self,
+        address: SevenBitAddress,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), ErrorKind> {
+        FakeExtension::transaction(self, address, operations)
+    }
+}
@@ -0,0 +1,82 @@
+use embedded_hal_mock::eh1::{delay::NoopDelay, i2c};
+use wii_ext::blocking_impl::nunchuk::Nunchuk;
+use wii_ext::core::nunchuk::{
+    AccelCalibration, CalibrationData, NunchukReading, NunchukReadingCalibrated, ACCEL_ONE_G,
+    ACCEL_ZERO_G,
+};
+mod common;
+use common::{test_data, transactions};
+
+fn at_rest(accel_x: u16, accel_y: u16, accel_z: u16) -> NunchukReading {
+    NunchukReading::CENTERED
+        .with_accel_x(accel_x)
+        .with_accel_y(accel_y)
+        .with_accel_z(accel_z)
+}
+
+#[test]
+fn resting_report_reads_one_g_on_z() {
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_IDLE));
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_BTN_C));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut nunchuk = Nunchuk::new(i2c.clone(), NoopDelay::new()).unwrap();
+    // The idle report's accelerometer is at (501, 557, 683)
+    assert_eq!(
+        nunchuk.accel_calibration(),
+        AccelCalibration {
+            zero: [501, 557, 683 - ACCEL_ONE_G]
+        }
+    );
+
+    let input = nunchuk.read().unwrap();
+    assert_eq!(input.accel_offset, [0, 0, ACCEL_ONE_G as i16]);
+    // Raw values are still reported
+    assert_eq!(input.accel_z, 683);
+
+    // Another capture of the same nunchuk at rest, within 0.1g
+    let [x, y, z] = nunchuk.read().unwrap().accel_offset;
+    assert!(x.abs() <= 20 && y.abs() <= 20, "{x} {y}");
+    assert!(z.abs_diff(ACCEL_ONE_G as i16) <= 20, "{z}");
+    i2c.done();
+}
+
+#[test]
+fn different_nunchuks_agree_at_rest() {
+    for rest in [at_rest(512, 512, 712), at_rest(490, 530, 690)] {
+        let accel = AccelCalibration::from_resting(&rest);
+        let mut reading = NunchukReadingCalibrated::new(rest, &CalibrationData::default());
+        reading.apply_accel_calibration(&accel);
+        assert_eq!(reading.accel_offset, [0, 0, 200]);
+
+        // Tipped onto its side: gravity moves from Z to X
+        let tipped = at_rest(rest.accel_x + 200, rest.accel_y, rest.accel_z - 200);
+        let mut reading = NunchukReadingCalibrated::new(tipped, &CalibrationData::default());
+        reading.apply_accel_calibration(&accel);
+        assert_eq!(reading.accel_offset, [200, 0, 0]);
+    }
+}
+
+#[test]
+fn uncalibrated_accelerometer_uses_nominal_zero() {
+    assert_eq!(AccelCalibration::default().zero, [ACCEL_ZERO_G; 3]);
+    let reading =
+        NunchukReadingCalibrated::new(at_rest(500, 520, 712), &CalibrationData::default());
+    assert_eq!(reading.accel_offset, [-12, 8, 200]);
+}
+
+#[test]
+fn restored_accel_calibration_is_applied() {
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_IDLE));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut nunchuk = Nunchuk::new(i2c.clone(), NoopDelay::new()).unwrap();
+    // Stick calibration alone leaves the accelerometer calibration alone
+    nunchuk.set_calibration(CalibrationData::default());
+    nunchuk.set_accel_calibration(AccelCalibration::NOMINAL);
+    assert_eq!(
+        nunchuk.read().unwrap().accel_offset,
+        [501 - 512, 557 - 512, 683 - 512]
+    );
+    i2c.done();
+}
@@ -0,0 +1,95 @@
+use wii_ext::core::nunchuk::{
+    AccelCalibration, CalibrationData, NunchukReading, NunchukReadingCalibrated, ACCEL_ONE_G,
+    ACCEL_ZERO_G,
+};
+mod common;
+use common::test_data;
+
+fn accel(accel_x: u16, accel_y: u16, accel_z: u16) -> NunchukReading {
+    NunchukReading::CENTERED
+        .with_accel_x(accel_x)
+        .with_accel_y(accel_y)
+        .with_accel_z(accel_z)
+}
+
+#[test]
+fn zero_g_is_zero() {
+    let r = accel(ACCEL_ZERO_G, ACCEL_ZERO_G, ACCEL_ZERO_G);
+    assert_eq!(r.accel_magnitude_sq(), 0);
+    assert_eq!(r.accel_magnitude(), 0);
+}
+
+#[test]
+fn known_vectors() {
+    // Single axes, either direction
+    assert_eq!(accel(712, 512, 512).accel_magnitude(), 200);
+    assert_eq!(accel(512, 312, 512).accel_magnitude(), 200);
+    // 3-4-5 and 2-3-6-7 triangles
+    assert_eq!(accel(542, 552, 512).accel_magnitude(), 50);
+    assert_eq!(accel(532, 542, 572).accel_magnitude_sq(), 4900);
+    assert_eq!(accel(532, 542, 572).accel_magnitude(), 70);
+    // Rounded down
+    assert_eq!(accel(513, 513, 512).accel_magnitude(), 1);
+    assert_eq!(accel(612, 612, 612).accel_magnitude(), 173);
+}
+
+#[test]
+fn idle_nunchuk_measures_gravity_on_z() {
+    let r = NunchukReading::from_data(&test_data::NUNCHUCK_IDLE).unwrap();
+    // Lying flat, gravity is almost all on Z
+    let z = r.accel_z.abs_diff(ACCEL_ZERO_G);
+    let magnitude = r.accel_magnitude();
+    assert!(magnitude >= z);
+    assert!(magnitude - z < 25, "{magnitude} {z}");
+    assert!((150..=250).contains(&magnitude), "{magnitude}");
+}
+
+#[test]
+fn maximum_inputs_do_not_overflow() {
+    assert_eq!(accel(1023, 1023, 1023).accel_magnitude_sq(), 3 * 511 * 511);
+    assert_eq!(accel(0, 0, 0).accel_magnitude_sq(), 3 * 512 * 512);
+    assert_eq!(accel(0, 0, 0).accel_magnitude(), 886);
+    // Out of range values saturate
+    assert_eq!(accel(u16::MAX, u16::MAX, 0).accel_magnitude_sq(), u32::MAX);
+    assert_eq!(accel(u16::MAX, u16::MAX, 0).accel_magnitude(), 65535);
+}
+
+#[test]
+fn isqrt_is_exact_floor() {
+    for magnitude in 0..=1000u32 {
+        let r = accel(512 + magnitude as u16, 512, 512);
+        assert_eq!(u32::from(r.accel_magnitude()), magnitude);
+        // Just below the next square still rounds down
+        let r = accel(512 + magnitude as u16, 513, 512);
+        assert_eq!(u32::from(r.accel_magnitude()), magnitude.max(1));
+    }
+}
+
+#[test]
+fn calibrated_reading_matches_raw_with_nominal_zero() {
+    let raw = NunchukReading::from_data(&test_data::NUNCHUCK_IDLE).unwrap();
+    let calibrated = NunchukReadingCalibrated::new(raw, &Default::default());
+    assert_eq!(calibrated.accel_magnitude_sq(), raw.accel_magnitude_sq());
+    assert_eq!(calibrated.accel_magnitude(), raw.accel_magnitude());
+}
+
+#[test]
+fn calibrated_magnitude_uses_the_calibrated_zero() {
+    let raw = NunchukReading::from_data(&test_data::NUNCHUCK_IDLE).unwrap();
+    let accel = AccelCalibration::from_resting(&raw);
+    let calibrate = |r: NunchukReading| {
+        let mut calibrated = NunchukReadingCalibrated::new(r, &CalibrationData::default());
+        calibrated.apply_accel_calibration(&accel);
+        calibrated
+    };
+    let calibrated = calibrate(raw);
+    // Exactly 1g at rest, however far this nunchuk's zero is from nominal
+    assert_eq!(calibrated.accel_offset, [0, 0, ACCEL_ONE_G as i16]);
+    assert_eq!(calibrated.accel_magnitude(), ACCEL_ONE_G);
+
+    // A falling nunchuk reads its 0g point on every axis
+    let [x, y, z] = accel.zero;
+    let falling = raw.with_accel_x(x).with_accel_y(y).with_accel_z(z);
+    assert_eq!(calibrate(falling).accel_magnitude_sq(), 0);
+    assert_ne!(falling.accel_magnitude_sq(), 0);
+}
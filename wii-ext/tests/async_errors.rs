@@ -0,0 +1,72 @@
+use embassy_futures::block_on;
+use embedded_hal::i2c::{ErrorKind, NoAcknowledgeSource};
+use embedded_hal_mock::eh1::{
+    delay::NoopDelay,
+    i2c::{self, Transaction},
+};
+use wii_ext::async_impl::classic::Classic;
+use wii_ext::async_impl::nunchuk::Nunchuk;
+use wii_ext::core::EXT_I2C_ADDR;
+use wii_ext::Error;
+mod common;
+use common::{test_data, transactions};
+
+#[test]
+fn classic_read_keeps_bus_error() {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.extend(transactions::failed_read_with(
+        6,
+        ErrorKind::ArbitrationLoss,
+    ));
+    expectations.extend(transactions::failed_read_with(
+        6,
+        ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address),
+    ));
+    let mut i2c = i2c::Mock::new(&expectations);
+    block_on(async {
+        let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).await.unwrap();
+        assert!(matches!(
+            classic.read().await,
+            Err(Error::I2C(ErrorKind::ArbitrationLoss))
+        ));
+        assert!(matches!(
+            classic.read().await,
+            Err(Error::I2C(ErrorKind::NoAcknowledge(
+                NoAcknowledgeSource::Address
+            )))
+        ));
+    });
+    i2c.done();
+}
+
+#[test]
+fn nunchuk_read_keeps_bus_error() {
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    expectations.extend(transactions::failed_read_with(6, ErrorKind::Bus));
+    let mut i2c = i2c::Mock::new(&expectations);
+    block_on(async {
+        let mut nunchuk = Nunchuk::new(i2c.clone(), NoopDelay::new()).await.unwrap();
+        assert!(matches!(
+            nunchuk.read().await,
+            Err(Error::I2C(ErrorKind::Bus))
+        ));
+    });
+    i2c.done();
+}
+
+#[test]
+fn classic_register_write_keeps_bus_error() {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.extend(transactions::save_report_format(1));
+    expectations
+        .push(Transaction::write(EXT_I2C_ADDR as u8, vec![254, 3]).with_error(ErrorKind::Overrun));
+    let mut i2c = i2c::Mock::new(&expectations);
+    block_on(async {
+        let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).await.unwrap();
+        assert!(matches!(
+            classic.enable_hires().await,
+            Err(Error::I2C(ErrorKind::Overrun))
+        ));
+    });
+    i2c.done();
+}
@@ -0,0 +1,79 @@
+use embassy_futures::block_on;
+use embedded_hal::i2c::ErrorKind;
+use embedded_hal_mock::eh1::{
+    delay::NoopDelay,
+    i2c::{self, Transaction},
+};
+use wii_ext::async_impl::classic::Classic;
+use wii_ext::async_impl::nunchuk::Nunchuk;
+use wii_ext::core::EXT_I2C_ADDR;
+use wii_ext::Error;
+mod common;
+use common::{test_data, transactions};
+
+#[test]
+fn classic_new_initialises_and_calibrates() {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.extend(transactions::read(&test_data::CLASSIC_IDLE));
+    let mut i2c = i2c::Mock::new(&expectations);
+    block_on(async {
+        let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).await.unwrap();
+        let input = classic.read().await.unwrap();
+        assert_eq!(input.joystick_left_x, 0);
+        assert_eq!(input.joystick_left_y, 0);
+        assert_eq!(input.joystick_right_x, 0);
+        assert_eq!(input.joystick_right_y, 0);
+    });
+    i2c.done();
+}
+
+#[test]
+fn nunchuk_new_initialises_and_calibrates() {
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_JOY_U));
+    let mut i2c = i2c::Mock::new(&expectations);
+    block_on(async {
+        let mut nunchuk = Nunchuk::new(i2c.clone(), NoopDelay::new()).await.unwrap();
+        let input = nunchuk.read().await.unwrap();
+        assert!(input.joystick_y > 90);
+        assert!((-5..=5).contains(&input.joystick_x));
+    });
+    i2c.done();
+}
+
+#[test]
+fn nunchuk_new_fails_if_init_fails() {
+    let expectations =
+        [Transaction::write(EXT_I2C_ADDR as u8, vec![0]).with_error(ErrorKind::Other)];
+    let mut i2c = i2c::Mock::new(&expectations);
+    block_on(async {
+        let result = Nunchuk::new(i2c.clone(), NoopDelay::new()).await;
+        assert!(matches!(result, Err(Error::I2C(ErrorKind::Other))));
+    });
+    i2c.done();
+}
+
+#[test]
+fn classic_new_unchecked_skips_init() {
+    let mut i2c = i2c::Mock::new(&[]);
+    let _classic = Classic::new_unchecked(i2c.clone(), NoopDelay::new());
+    i2c.done();
+}
+
+#[test]
+fn classic_enable_hires_recalibrates() {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.extend(transactions::save_report_format(1));
+    expectations.push(Transaction::write(EXT_I2C_ADDR as u8, vec![254, 3]));
+    expectations.extend(transactions::read(&test_data::CLASSIC_HD_IDLE));
+    expectations.extend(transactions::read(&test_data::CLASSIC_HD_LJOY_U));
+    let mut i2c = i2c::Mock::new(&expectations);
+    block_on(async {
+        let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).await.unwrap();
+        classic.enable_hires().await.unwrap();
+        let input = classic.read().await.unwrap();
+        assert!(input.joystick_left_y > 90);
+        assert!((-5..=5).contains(&input.joystick_left_x));
+    });
+    i2c.done();
+}
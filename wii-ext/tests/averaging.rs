@@ -0,0 +1,90 @@
+use embassy_futures::block_on;
+use embedded_hal_mock::eh1::{
+    delay::NoopDelay,
+    i2c::{self, Transaction},
+};
+use wii_ext::blocking_impl::classic::ClassicBuilder;
+use wii_ext::blocking_impl::nunchuk::Nunchuk;
+use wii_ext::core::EXT_I2C_ADDR;
+mod common;
+use common::{test_data, transactions};
+
+/// Reads of `report` with `index` alternately nudged up and down by one
+fn jitter_reads(report: &[u8], index: usize, samples: usize) -> Vec<Transaction> {
+    let mut transactions = vec![];
+    for i in 0..samples {
+        let mut report = report.to_vec();
+        report[index] = if i % 2 == 0 {
+            report[index] + 1
+        } else {
+            report[index] - 1
+        };
+        transactions.extend(transactions::read(&report));
+    }
+    transactions
+}
+
+#[test]
+fn classic_hires_average_is_midpoint() {
+    let mut expectations = transactions::init_no_calibration();
+    expectations.extend(transactions::save_report_format(1));
+    expectations.push(Transaction::write(EXT_I2C_ADDR as u8, vec![254, 3]));
+    expectations.extend(transactions::read(&test_data::PRO_HD_IDLE));
+    // Right stick x
+    expectations.extend(jitter_reads(&test_data::PRO_HD_IDLE, 1, 4));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = ClassicBuilder::new()
+        .hires(true)
+        .build(i2c.clone(), NoopDelay::new())
+        .unwrap();
+    let input = classic.read_averaged(4).unwrap();
+    assert_eq!(input.joystick_right_x, 0);
+    assert_eq!(input.joystick_left_x, 0);
+    i2c.done();
+}
+
+#[test]
+fn nunchuk_average_is_midpoint_and_buttons_are_ored() {
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    expectations.extend(jitter_reads(&test_data::NUNCHUCK_IDLE, 0, 5));
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_BTN_Z));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut nunchuk = Nunchuk::new(i2c.clone(), NoopDelay::new()).unwrap();
+    let input = nunchuk.read_averaged(6).unwrap();
+    assert_eq!(input.joystick_x, 0);
+    assert_eq!(input.joystick_y, 0);
+    assert!(input.button_z);
+    assert!(!input.button_c);
+    i2c.done();
+}
+
+#[test]
+fn nunchuk_zero_samples_reads_once() {
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_JOY_U));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut nunchuk = Nunchuk::new(i2c.clone(), NoopDelay::new()).unwrap();
+    assert!(nunchuk.read_averaged(0).unwrap().joystick_y > 90);
+    i2c.done();
+}
+
+#[test]
+fn async_classic_average_is_midpoint() {
+    let mut expectations = transactions::init_no_calibration();
+    expectations.extend(transactions::save_report_format(1));
+    expectations.push(Transaction::write(EXT_I2C_ADDR as u8, vec![254, 3]));
+    expectations.extend(transactions::read(&test_data::PRO_HD_IDLE));
+    // Left stick y
+    expectations.extend(jitter_reads(&test_data::PRO_HD_IDLE, 2, 8));
+    let mut i2c = i2c::Mock::new(&expectations);
+    block_on(async {
+        let mut classic = wii_ext::async_impl::classic::ClassicBuilder::new()
+            .hires(true)
+            .build(i2c.clone(), NoopDelay::new())
+            .await
+            .unwrap();
+        let input = classic.read_averaged(8).await.unwrap();
+        assert_eq!(input.joystick_left_y, 0);
+    });
+    i2c.done();
+}
@@ -0,0 +1,141 @@
+use embedded_hal_mock::eh1::{delay::NoopDelay, i2c};
+use wii_ext::blocking_impl::classic::ClassicBuilder;
+use wii_ext::blocking_impl::nunchuk::Nunchuk;
+use wii_ext::core::events::Axis;
+use wii_ext::core::AxisCurve;
+mod common;
+use common::{test_data, transactions};
+
+/// Straight line from center to full deflection
+const LINEAR_TABLE: [u8; 17] = [
+    0, 8, 16, 24, 32, 40, 48, 56, 64, 71, 79, 87, 95, 103, 111, 119, 127,
+];
+
+#[test]
+fn expo_softens_midpoint() {
+    let expo = AxisCurve::Expo(50);
+    // Half deflection comes out at under a third
+    assert_eq!(expo.apply(64), 40);
+    assert_eq!(expo.apply(-64), -40);
+    assert!(expo.apply(10) < 10);
+    assert_eq!(expo.apply(0), 0);
+}
+
+#[test]
+fn curves_preserve_endpoints() {
+    let curves = [
+        AxisCurve::Expo(0),
+        AxisCurve::Expo(50),
+        AxisCurve::Expo(100),
+        AxisCurve::Expo(255),
+        AxisCurve::Table(LINEAR_TABLE),
+    ];
+    for curve in curves {
+        assert_eq!(curve.apply(127), 127, "{curve:?}");
+        assert_eq!(curve.apply(-127), -127, "{curve:?}");
+        assert_eq!(curve.apply(-128), -127, "{curve:?}");
+        assert_eq!(curve.apply(0), 0, "{curve:?}");
+    }
+}
+
+#[test]
+fn curves_are_symmetric() {
+    let curves = [
+        AxisCurve::Linear,
+        AxisCurve::Expo(30),
+        AxisCurve::Expo(100),
+        AxisCurve::Table([
+            0, 1, 2, 4, 6, 9, 12, 16, 20, 26, 33, 42, 52, 65, 82, 102, 127,
+        ]),
+    ];
+    for curve in curves {
+        for value in -127..=127i8 {
+            assert_eq!(
+                curve.apply(-value),
+                -curve.apply(value),
+                "{curve:?} {value}"
+            );
+        }
+    }
+}
+
+#[test]
+fn curves_are_monotonic() {
+    for curve in [AxisCurve::Expo(50), AxisCurve::Expo(100)] {
+        for value in 0..127i8 {
+            assert!(
+                curve.apply(value) <= curve.apply(value + 1),
+                "{curve:?} {value}"
+            );
+        }
+    }
+}
+
+#[test]
+fn table_interpolates_between_points() {
+    let table = AxisCurve::Table(LINEAR_TABLE);
+    for value in -127..=127i8 {
+        assert!((table.apply(value) - value).abs() <= 1, "{value}");
+    }
+    let mut points = [0; 17];
+    points[16] = 200;
+    // Outputs are limited to full deflection
+    assert_eq!(AxisCurve::Table(points).apply(127), 127);
+    assert_eq!(AxisCurve::Table(points).apply(-120), -23);
+}
+
+#[test]
+fn linear_leaves_values_unchanged() {
+    for value in i8::MIN..=i8::MAX {
+        assert_eq!(AxisCurve::Linear.apply(value), value);
+    }
+}
+
+#[test]
+fn classic_applies_curve_per_axis() {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.extend(transactions::read(&test_data::CLASSIC_LJOY_L));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut linear_expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    linear_expectations.extend(transactions::read(&test_data::CLASSIC_LJOY_L));
+    let mut linear_i2c = i2c::Mock::new(&linear_expectations);
+
+    let mut classic = ClassicBuilder::new()
+        .axis_curve(Axis::LeftX, AxisCurve::Expo(100))
+        // Triggers have no curve
+        .axis_curve(Axis::TriggerLeft, AxisCurve::Expo(100))
+        .build(i2c.clone(), NoopDelay::new())
+        .unwrap();
+    let mut linear = ClassicBuilder::new()
+        .build(linear_i2c.clone(), NoopDelay::new())
+        .unwrap();
+    let curved = classic.read().unwrap();
+    let expected = linear.read().unwrap();
+    assert_eq!(
+        curved.joystick_left_x,
+        AxisCurve::Expo(100).apply(expected.joystick_left_x)
+    );
+    assert_eq!(curved.joystick_left_y, expected.joystick_left_y);
+    assert_eq!(curved.trigger_left, expected.trigger_left);
+    i2c.done();
+    linear_i2c.done();
+}
+
+#[test]
+fn nunchuk_uses_left_stick_curves() {
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_JOY_L));
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_JOY_L));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut nunchuk = Nunchuk::new(i2c.clone(), NoopDelay::new()).unwrap();
+    let linear = nunchuk.read().unwrap();
+    nunchuk.set_axis_curve(Axis::LeftX, AxisCurve::Expo(50));
+    nunchuk.set_axis_curve(Axis::RightX, AxisCurve::Expo(100));
+    let curved = nunchuk.read().unwrap();
+    assert_eq!(
+        curved.joystick_x,
+        AxisCurve::Expo(50).apply(linear.joystick_x)
+    );
+    assert_eq!(curved.joystick_y, linear.joystick_y);
+    i2c.done();
+}
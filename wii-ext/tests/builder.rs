@@ -0,0 +1,106 @@
+use embedded_hal_mock::eh1::{
+    delay::NoopDelay,
+    i2c::{self, Transaction},
+};
+use wii_ext::blocking_impl::classic::ClassicBuilder;
+use wii_ext::blocking_impl::nunchuk::NunchukBuilder;
+use wii_ext::core::classic::CalibrationData as ClassicCalibration;
+use wii_ext::core::{ControllerType, EXT_I2C_ADDR};
+use wii_ext::Error;
+mod common;
+use common::{test_data, transactions};
+
+#[test]
+fn classic_builder_default_matches_new() {
+    let mut expectations = transactions::init(&test_data::PRO_IDLE);
+    expectations.extend(transactions::read(&test_data::PRO_BTN_B));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = ClassicBuilder::new()
+        .build(i2c.clone(), NoopDelay::new())
+        .unwrap();
+    let input = classic.read().unwrap();
+    assert!(input.button_b);
+    assert_eq!(input.joystick_left_x, 0);
+    i2c.done();
+}
+
+#[test]
+fn classic_builder_hires_verify_id() {
+    let mut expectations = transactions::init_no_calibration();
+    expectations.extend(transactions::read_id(&test_data::PRO_ID));
+    expectations.extend(transactions::save_report_format(1));
+    // Switch to HD mode before calibrating, so only one calibration read is needed
+    expectations.push(Transaction::write(EXT_I2C_ADDR as u8, vec![254, 3]));
+    expectations.extend(transactions::read(&test_data::PRO_HD_IDLE));
+    expectations.extend(transactions::read(&test_data::PRO_HD_LJOY_L));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = ClassicBuilder::new()
+        .hires(true)
+        .verify_id(true)
+        .build(i2c.clone(), NoopDelay::new())
+        .unwrap();
+    let input = classic.read().unwrap();
+    assert!(input.joystick_left_x < -90);
+    i2c.done();
+}
+
+#[test]
+fn classic_builder_verify_id_wrong_controller() {
+    let mut expectations = transactions::init_no_calibration();
+    expectations.extend(transactions::read_id(&test_data::NUNCHUCK_ID));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let result = ClassicBuilder::new()
+        .verify_id(true)
+        .build(i2c.clone(), NoopDelay::new());
+    assert!(matches!(
+        result,
+        Err(Error::UnexpectedController(Some(ControllerType::Nunchuk)))
+    ));
+    i2c.done();
+}
+
+#[test]
+fn classic_builder_skip_init_with_calibration() {
+    let idle = test_data::PRO_HD_IDLE;
+    let calibration = ClassicCalibration {
+        joystick_left_x: idle[0],
+        joystick_right_x: idle[1],
+        joystick_left_y: idle[2],
+        joystick_right_y: idle[3],
+        trigger_left: idle[4],
+        trigger_right: idle[5],
+    };
+    // No init, no calibration read, no report mode change
+    let expectations = transactions::read(&test_data::PRO_HD_RJOY_U);
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = ClassicBuilder::new()
+        .skip_init()
+        .hires(true)
+        .calibration(calibration)
+        .build(i2c.clone(), NoopDelay::new())
+        .unwrap();
+    assert_eq!(classic.calibration(), calibration);
+    let input = classic.read().unwrap();
+    assert!(input.joystick_right_y > 90);
+    i2c.done();
+}
+
+#[test]
+fn nunchuk_builder_deadzone() {
+    // Joystick slightly off-center from the calibration point
+    let nudged = [128, 127, 125, 139, 170, 95];
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    expectations.extend(transactions::read(&nudged));
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_JOY_R));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut nunchuk = NunchukBuilder::new()
+        .deadzone(5)
+        .build(i2c.clone(), NoopDelay::new())
+        .unwrap();
+    let input = nunchuk.read().unwrap();
+    assert_eq!(input.joystick_x, 0);
+    assert_eq!(input.joystick_y, 0);
+    let input = nunchuk.read().unwrap();
+    assert!(input.joystick_x > 90);
+    i2c.done();
+}
@@ -0,0 +1,60 @@
+use wii_ext::core::classic::{ClassicReading, ClassicReadingCalibrated};
+use wii_ext::core::events::{Button, Inputs};
+mod common;
+use common::test_data;
+
+const REPORTS: [([u8; 6], u16); 16] = [
+    (test_data::CLASSIC_IDLE, 0),
+    (test_data::CLASSIC_BTN_A, 0x0001),
+    (test_data::CLASSIC_BTN_B, 0x0002),
+    (test_data::CLASSIC_BTN_X, 0x0004),
+    (test_data::CLASSIC_BTN_Y, 0x0008),
+    (test_data::CLASSIC_BTN_L, 0x0010),
+    (test_data::CLASSIC_BTN_R, 0x0020),
+    (test_data::CLASSIC_BTN_ZL, 0x0040),
+    (test_data::CLASSIC_BTN_ZR, 0x0080),
+    (test_data::CLASSIC_BTN_MINUS, 0x0100),
+    (test_data::CLASSIC_BTN_PLUS, 0x0200),
+    (test_data::CLASSIC_BTN_HOME, 0x0400),
+    (test_data::CLASSIC_PAD_U, 0x0800),
+    (test_data::CLASSIC_PAD_D, 0x1000),
+    (test_data::CLASSIC_PAD_L, 0x2000),
+    (test_data::CLASSIC_PAD_R, 0x4000),
+];
+
+#[test]
+fn test_data_masks() {
+    for (report, mask) in REPORTS {
+        let reading = ClassicReading::from_data(&report).unwrap();
+        assert_eq!(reading.buttons_u16(), mask, "{report:?}");
+    }
+}
+
+#[test]
+fn bits_follow_button_discriminants() {
+    for &button in ClassicReadingCalibrated::BUTTONS {
+        let mut reading = ClassicReadingCalibrated::default();
+        reading.set_button(button, true);
+        assert_eq!(reading.buttons_u16(), 1 << button as u16, "{button:?}");
+    }
+    assert_eq!(Button::DpadRight as u16, 14);
+}
+
+#[test]
+fn apply_round_trips() {
+    for (report, mask) in REPORTS {
+        let reading = ClassicReading::from_data(&report).unwrap();
+        let mut applied = ClassicReading::from_data(&test_data::CLASSIC_IDLE).unwrap();
+        applied.apply_buttons_u16(mask);
+        assert_eq!(applied.buttons_u16(), mask);
+        // Only the buttons change, so the encoder can re-use the last axes
+        assert_eq!(applied.joystick_left_x, reading.joystick_left_x);
+        assert_eq!(applied.to_report()[4..], reading.to_report()[4..]);
+    }
+    let mut reading = ClassicReadingCalibrated::default();
+    reading.apply_buttons_u16(0x7FFF);
+    assert_eq!(reading.buttons_u16(), 0x7FFF);
+    // Bit 15 is reserved
+    reading.apply_buttons_u16(0x8000);
+    assert_eq!(reading, ClassicReadingCalibrated::default());
+}
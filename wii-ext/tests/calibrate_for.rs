@@ -0,0 +1,106 @@
+use embassy_futures::block_on;
+use embedded_hal_mock::eh1::{
+    delay::NoopDelay,
+    i2c::{self, Transaction},
+};
+use wii_ext::blocking_impl::classic::ClassicBuilder;
+use wii_ext::blocking_impl::nunchuk::Nunchuk;
+use wii_ext::classic_hd_report;
+use wii_ext::core::nunchuk::NunchukReading;
+use wii_ext::core::EXT_I2C_ADDR;
+mod common;
+use common::{test_data, transactions};
+
+/// Left stick X and right trigger of a controller being nudged while it calibrates
+const NOISY: [(u8, u8); 5] = [(128, 10), (130, 12), (127, 11), (160, 9), (129, 11)];
+
+fn hires_setup() -> Vec<Transaction> {
+    let mut expectations = transactions::init_no_calibration();
+    expectations.extend(transactions::save_report_format(1));
+    expectations.push(Transaction::write(EXT_I2C_ADDR as u8, vec![254, 3]));
+    expectations.extend(transactions::read(&test_data::PRO_HD_IDLE));
+    expectations
+}
+
+fn noisy_reports() -> Vec<Transaction> {
+    NOISY
+        .iter()
+        .flat_map(|&(x, trigger)| {
+            transactions::read(&classic_hd_report! { joystick_left_x: x, trigger_right: trigger })
+        })
+        .collect()
+}
+
+#[test]
+fn classic_calibrates_to_trimmed_mean() {
+    let mut expectations = hires_setup();
+    expectations.extend(noisy_reports());
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = ClassicBuilder::new()
+        .hires(true)
+        .build(i2c.clone(), NoopDelay::new())
+        .unwrap();
+    let spread = classic.calibrate_for(5, 5_000).unwrap();
+    // 127 and 160 are dropped: (128 + 130 + 129) / 3
+    assert_eq!(classic.calibration().joystick_left_x, 129);
+    // 9 and 12 are dropped: (10 + 11 + 11) / 3, rounded
+    assert_eq!(classic.calibration().trigger_right, 11);
+    assert_eq!(spread.joystick_left_x, 33);
+    assert_eq!(spread.trigger_right, 3);
+    assert_eq!(spread.joystick_left_y, 0);
+    i2c.done();
+}
+
+#[test]
+fn failed_read_keeps_calibration() {
+    let mut expectations = hires_setup();
+    expectations.extend(transactions::read(
+        &classic_hd_report! { joystick_left_x: 100 },
+    ));
+    expectations.extend(transactions::failed_read(8));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = ClassicBuilder::new()
+        .hires(true)
+        .build(i2c.clone(), NoopDelay::new())
+        .unwrap();
+    let before = classic.calibration();
+    assert!(classic.calibrate_for(3, 1_000).is_err());
+    assert_eq!(classic.calibration(), before);
+    i2c.done();
+}
+
+#[test]
+fn nunchuk_reports_accelerometer_spread() {
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    let idle = NunchukReading::from_data(&test_data::NUNCHUCK_IDLE).unwrap();
+    for (x, accel_z) in [(126, 700), (127, 720), (125, 705), (126, 710)] {
+        let report = idle.with_joystick_x(x).with_accel_z(accel_z).to_report();
+        expectations.extend(transactions::read(&report));
+    }
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut nunchuk = Nunchuk::new(i2c.clone(), NoopDelay::new()).unwrap();
+    let spread = nunchuk.calibrate_for(4, 5_000).unwrap();
+    // 125 and 127 are dropped
+    assert_eq!(nunchuk.calibration().joystick_x, 126);
+    assert_eq!(spread.joystick_x, 2);
+    assert_eq!(spread.accel_z, 20);
+    i2c.done();
+}
+
+#[test]
+fn async_classic_calibrates_to_trimmed_mean() {
+    let mut expectations = hires_setup();
+    expectations.extend(noisy_reports());
+    let mut i2c = i2c::Mock::new(&expectations);
+    block_on(async {
+        let mut classic = wii_ext::async_impl::classic::ClassicBuilder::new()
+            .hires(true)
+            .build(i2c.clone(), NoopDelay::new())
+            .await
+            .unwrap();
+        let spread = classic.calibrate_for(5, 5_000).await.unwrap();
+        assert_eq!(classic.calibration().joystick_left_x, 129);
+        assert_eq!(spread.joystick_left_x, 33);
+    });
+    i2c.done();
+}
@@ -0,0 +1,84 @@
+use embassy_futures::block_on;
+use embedded_hal_mock::eh1::{delay::NoopDelay, i2c};
+use wii_ext::blocking_impl::classic::Classic;
+use wii_ext::blocking_impl::nunchuk::Nunchuk;
+use wii_ext::core::classic::{CalibrationData, ClassicReading};
+use wii_ext::core::nunchuk::NunchukReading;
+mod common;
+use common::{test_data, transactions};
+
+/// The idle classic reading, with every analogue input offset by a different amount
+fn offset_classic_rest() -> ClassicReading {
+    let idle = ClassicReading::from_data(&test_data::CLASSIC_IDLE).unwrap();
+    idle.with_joystick_left_x(idle.joystick_left_x - 1)
+        .with_joystick_left_y(idle.joystick_left_y - 2)
+        .with_joystick_right_x(idle.joystick_right_x - 3)
+        .with_joystick_right_y(idle.joystick_right_y - 4)
+        .with_trigger_left(idle.trigger_left - 1)
+        .with_trigger_right(idle.trigger_right - 2)
+}
+
+#[test]
+fn classic_reads_subtract_injected_calibration() {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.extend(transactions::read(&test_data::CLASSIC_IDLE));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).unwrap();
+    let rest = offset_classic_rest();
+    classic.update_calibration_from(&rest);
+    assert_eq!(classic.calibration(), CalibrationData::from_resting(&rest));
+    let input = classic.read().unwrap();
+    assert_eq!(
+        (
+            input.joystick_left_x,
+            input.joystick_left_y,
+            input.joystick_right_x,
+            input.joystick_right_y,
+        ),
+        (1, 2, 3, 4)
+    );
+    assert_eq!((input.trigger_left, input.trigger_right), (1, 2));
+    i2c.done();
+}
+
+#[test]
+fn calibrating_from_a_reading_uses_no_bus() {
+    // Only the transactions made by new()
+    let expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).unwrap();
+    classic.update_calibration_from(&offset_classic_rest());
+    i2c.done();
+}
+
+#[test]
+fn nunchuk_reads_subtract_injected_calibration() {
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_IDLE));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut nunchuk = Nunchuk::new(i2c.clone(), NoopDelay::new()).unwrap();
+    let idle = NunchukReading::from_data(&test_data::NUNCHUCK_IDLE).unwrap();
+    let rest = idle
+        .with_joystick_x(idle.joystick_x + 5)
+        .with_joystick_y(idle.joystick_y - 6);
+    nunchuk.update_calibration_from(&rest);
+    let input = nunchuk.read().unwrap();
+    assert_eq!((input.joystick_x, input.joystick_y), (-5, 6));
+    i2c.done();
+}
+
+#[test]
+fn async_classic_reads_subtract_injected_calibration() {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.extend(transactions::read(&test_data::CLASSIC_IDLE));
+    let mut i2c = i2c::Mock::new(&expectations);
+    block_on(async {
+        let mut classic = wii_ext::async_impl::classic::Classic::new(i2c.clone(), NoopDelay::new())
+            .await
+            .unwrap();
+        classic.update_calibration_from(&offset_classic_rest());
+        let input = classic.read().await.unwrap();
+        assert_eq!((input.joystick_right_y, input.trigger_right), (4, 2));
+    });
+    i2c.done();
+}
@@ -0,0 +1,131 @@
+#![cfg(feature = "capture")]
+
+use embedded_hal_mock::eh1::{
+    delay::NoopDelay,
+    i2c::{self, Transaction},
+};
+use wii_ext::blocking_impl::classic::Classic;
+use wii_ext::capture::{capture, CaptureStep, Captures};
+use wii_ext::core::EXT_I2C_ADDR;
+mod common;
+use common::{test_data, transactions};
+
+const TEST_DATA: &str = include_str!("../src/test_data.rs");
+
+/// The first few standard captures of the Wii Classic controller
+fn classic_captures() -> Captures {
+    Captures {
+        id: test_data::CLASSIC_ID,
+        hires_default: Some(test_data::CLASSIC_HIRES_DEFAULT),
+        reports: vec![
+            ("IDLE", test_data::CLASSIC_IDLE),
+            ("BTN_B", test_data::CLASSIC_BTN_B),
+            ("BTN_A", test_data::CLASSIC_BTN_A),
+        ],
+        hd_reports: vec![],
+    }
+}
+
+#[test]
+fn formats_test_data_constants() {
+    let captures = Captures {
+        id: [1, 0, 164, 32, 1, 1],
+        hires_default: Some(1),
+        reports: vec![("IDLE", [160, 29, 15, 0, 255, 255])],
+        hd_reports: vec![("IDLE", [126, 130, 120, 124, 0, 0, 255, 255])],
+    };
+    assert_eq!(
+        captures.to_source("Some clone", "CLONE"),
+        "// Some clone
+pub const CLONE_ID: [u8; 6] = [1, 0, 164, 32, 1, 1];
+pub const CLONE_HIRES_DEFAULT: u8 = 1;
+pub const CLONE_IDLE: [u8; 6] = [160, 29, 15, 0, 255, 255];
+
+pub const CLONE_HD_IDLE: [u8; 8] = [126, 130, 120, 124, 0, 0, 255, 255];
+"
+    );
+}
+
+#[test]
+fn output_matches_existing_captures() {
+    let source = classic_captures().to_source("Wii Classic controller", "CLASSIC");
+    assert!(TEST_DATA.contains(&source), "{source}");
+}
+
+#[test]
+fn each_line_matches_existing_nunchuk_captures() {
+    let captures = Captures {
+        id: test_data::NUNCHUCK_ID,
+        hires_default: None,
+        reports: vec![
+            ("IDLE", test_data::NUNCHUCK_IDLE),
+            ("JOY_U", test_data::NUNCHUCK_JOY_U),
+            ("BTN_Z", test_data::NUNCHUCK_BTN_Z),
+        ],
+        hd_reports: vec![("IDLE", test_data::NUNCHUCK_HD_IDLE)],
+    };
+    let source = captures.to_source("Nunchuck", "NUNCHUCK");
+    for line in source.lines().filter(|line| !line.is_empty()) {
+        assert!(TEST_DATA.lines().any(|l| l == line), "{line}");
+    }
+}
+
+#[test]
+fn session_reads_each_step() {
+    let steps = [
+        CaptureStep::new("IDLE", "Leave the controller at rest"),
+        CaptureStep::new("BTN_X", "Hold X"),
+    ];
+    let mut expectations = transactions::init_no_calibration();
+    expectations.extend(transactions::read_id(&test_data::CLASSIC_ID));
+    expectations.push(Transaction::write(EXT_I2C_ADDR as u8, vec![0]));
+    expectations.extend(transactions::read(&test_data::CLASSIC_IDLE));
+    expectations.extend(transactions::read(&test_data::CLASSIC_BTN_X));
+    expectations.extend([
+        // Save the report format, then switch to hi-res
+        Transaction::write(EXT_I2C_ADDR as u8, vec![0xfe]),
+        Transaction::read(EXT_I2C_ADDR as u8, vec![test_data::CLASSIC_HIRES_DEFAULT]),
+        Transaction::write(EXT_I2C_ADDR as u8, vec![0]),
+        Transaction::write(EXT_I2C_ADDR as u8, vec![0xfe, 0x03]),
+        Transaction::write(EXT_I2C_ADDR as u8, vec![0]),
+    ]);
+    expectations.extend(transactions::read(&test_data::CLASSIC_HD_IDLE));
+    expectations.extend(transactions::read(&test_data::CLASSIC_HD_BTN_X));
+    expectations.extend([
+        // Restore the report format
+        Transaction::write(EXT_I2C_ADDR as u8, vec![0xfe, 0x01]),
+        Transaction::write(EXT_I2C_ADDR as u8, vec![0]),
+    ]);
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = Classic::new_uninit(i2c.clone(), NoopDelay::new())
+        .init_no_calibration()
+        .unwrap();
+    let mut prompts = Vec::new();
+    let captures = capture(&mut classic, &steps, true, |step, hires| {
+        prompts.push((step.name, hires))
+    })
+    .unwrap();
+    assert_eq!(
+        prompts,
+        [
+            ("IDLE", false),
+            ("BTN_X", false),
+            ("IDLE", true),
+            ("BTN_X", true)
+        ]
+    );
+    assert_eq!(captures.id, test_data::CLASSIC_ID);
+    assert_eq!(captures.hires_default, Some(1));
+    assert_eq!(
+        captures.reports,
+        [
+            ("IDLE", test_data::CLASSIC_IDLE),
+            ("BTN_X", test_data::CLASSIC_BTN_X)
+        ]
+    );
+    assert_eq!(
+        captures.hd_reports[1],
+        ("BTN_X", test_data::CLASSIC_HD_BTN_X)
+    );
+    i2c.done();
+}
@@ -0,0 +1,134 @@
+// The blocking classic tests, run against the async driver
+use embassy_futures::block_on;
+use embedded_hal_mock::eh1::{
+    delay::NoopDelay,
+    i2c::{self, Transaction},
+};
+use wii_ext::async_impl::classic::Classic;
+use wii_ext::core::classic::ClassicReadingCalibrated;
+use wii_ext::core::{ControllerType, EXT_I2C_ADDR};
+mod common;
+use common::{test_data, transactions};
+
+/// There's a certain amount of slop around the center position.
+/// Allow up to this range without it being an error
+const ZERO_SLOP: i8 = 8;
+/// The max value at full deflection is ~100, but allow a bit less than that
+const AXIS_MAX: i8 = 90;
+
+/// Create a controller calibrated on `calibration`, then take a single reading of `report`
+fn read_once(calibration: &[u8], report: &[u8]) -> ClassicReadingCalibrated {
+    let mut expectations = transactions::init(calibration);
+    expectations.extend(transactions::read(report));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let input = block_on(async {
+        let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).await.unwrap();
+        classic.read().await.unwrap()
+    });
+    i2c.done();
+    input
+}
+
+#[test]
+fn classic_idle() {
+    let input = read_once(&test_data::CLASSIC_IDLE, &test_data::CLASSIC_IDLE);
+    assert_eq!(input, ClassicReadingCalibrated::default());
+}
+
+/// Whether a reading has a particular button pressed
+type Pressed = fn(&ClassicReadingCalibrated) -> bool;
+
+#[test]
+fn classic_buttons() {
+    let cases: [(&[u8], Pressed); 15] = [
+        (&test_data::CLASSIC_BTN_A, |r| r.button_a),
+        (&test_data::CLASSIC_BTN_B, |r| r.button_b),
+        (&test_data::CLASSIC_BTN_X, |r| r.button_x),
+        (&test_data::CLASSIC_BTN_Y, |r| r.button_y),
+        (&test_data::CLASSIC_BTN_L, |r| r.button_trigger_l),
+        (&test_data::CLASSIC_BTN_R, |r| r.button_trigger_r),
+        (&test_data::CLASSIC_BTN_ZL, |r| r.button_zl),
+        (&test_data::CLASSIC_BTN_ZR, |r| r.button_zr),
+        (&test_data::CLASSIC_PAD_U, |r| r.dpad_up),
+        (&test_data::CLASSIC_PAD_D, |r| r.dpad_down),
+        (&test_data::CLASSIC_PAD_L, |r| r.dpad_left),
+        (&test_data::CLASSIC_PAD_R, |r| r.dpad_right),
+        (&test_data::CLASSIC_BTN_MINUS, |r| r.button_minus),
+        (&test_data::CLASSIC_BTN_PLUS, |r| r.button_plus),
+        (&test_data::CLASSIC_BTN_HOME, |r| r.button_home),
+    ];
+    for (report, pressed) in cases {
+        let input = read_once(&test_data::CLASSIC_IDLE, report);
+        assert!(pressed(&input), "{report:?}");
+    }
+}
+
+#[test]
+fn classic_calibrated_joysticks() {
+    let input = read_once(&test_data::CLASSIC_IDLE, &test_data::CLASSIC_LJOY_L);
+    assert!(input.joystick_left_x < -AXIS_MAX);
+    assert!((-ZERO_SLOP..=ZERO_SLOP).contains(&input.joystick_left_y));
+    let input = read_once(&test_data::CLASSIC_IDLE, &test_data::CLASSIC_RJOY_U);
+    assert!(input.joystick_right_y > AXIS_MAX);
+    assert!((-ZERO_SLOP..=ZERO_SLOP).contains(&input.joystick_right_x));
+}
+
+#[test]
+fn classic_update_calibration() {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    // Recalibrate while the stick is held left, so that becomes center
+    expectations.extend(transactions::read(&test_data::CLASSIC_LJOY_L));
+    expectations.extend(transactions::read(&test_data::CLASSIC_LJOY_L));
+    let mut i2c = i2c::Mock::new(&expectations);
+    block_on(async {
+        let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).await.unwrap();
+        classic.update_calibration().await.unwrap();
+        let input = classic.read().await.unwrap();
+        assert_eq!(input.joystick_left_x, 0);
+    });
+    i2c.done();
+}
+
+#[test]
+fn classic_enable_hires() {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.extend(transactions::save_report_format(1));
+    expectations.push(Transaction::write(EXT_I2C_ADDR as u8, vec![254, 3]));
+    expectations.extend(transactions::read(&test_data::CLASSIC_HD_IDLE));
+    expectations.extend(transactions::read(&test_data::CLASSIC_HD_BTN_X));
+    let mut i2c = i2c::Mock::new(&expectations);
+    block_on(async {
+        let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).await.unwrap();
+        classic.enable_hires().await.unwrap();
+        let input = classic.read().await.unwrap();
+        assert!(input.button_x);
+        assert!((-ZERO_SLOP..=ZERO_SLOP).contains(&input.joystick_left_x));
+    });
+    i2c.done();
+}
+
+#[test]
+fn classic_identify_controller() {
+    let mut expectations = transactions::init(&test_data::PRO_IDLE);
+    expectations.extend(transactions::read_id(&test_data::PRO_ID));
+    let mut i2c = i2c::Mock::new(&expectations);
+    block_on(async {
+        let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).await.unwrap();
+        let controller = classic.identify_controller().await.unwrap();
+        assert_eq!(controller, Some(ControllerType::ClassicPro));
+    });
+    i2c.done();
+}
+
+#[test]
+fn classic_id_is_read_from_0xfa() {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.extend(transactions::read_id(&test_data::CLASSIC_ID));
+    let mut i2c = i2c::Mock::new(&expectations);
+    block_on(async {
+        let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).await.unwrap();
+        let controller = classic.identify_controller().await.unwrap();
+        assert_eq!(controller, Some(ControllerType::Classic));
+    });
+    i2c.done();
+}
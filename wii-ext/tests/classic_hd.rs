@@ -1,7 +1,9 @@
+use embassy_futures::block_on;
 use embedded_hal_mock::eh1::delay::NoopDelay;
 use embedded_hal_mock::eh1::i2c::{self, Transaction};
 use paste::paste;
 use wii_ext::blocking_impl::classic::Classic;
+use wii_ext::core::classic::ClassicReadingCalibrated;
 use wii_ext::core::EXT_I2C_ADDR;
 mod common;
 use common::test_data;
@@ -14,6 +16,50 @@ const TRIGGER_SLOP: i8 = 10;
 /// The max value at full deflection is ~100, but allow a bit less than that
 const AXIS_MAX: i8 = 90;
 
+/// Transactions for init, switching to hi-res mode, then reading `sample`
+fn hd_expectations(hd_idle: &[u8], sample: &[u8]) -> Vec<Transaction> {
+    vec![
+        // Reset controller
+        Transaction::write(EXT_I2C_ADDR as u8, vec![0]),
+        // Init
+        Transaction::write(EXT_I2C_ADDR as u8, vec![240, 85]),
+        Transaction::write(EXT_I2C_ADDR as u8, vec![251, 0]),
+        // Calibration read (discarded - use any data)
+        Transaction::write(EXT_I2C_ADDR as u8, vec![0]),
+        Transaction::read(EXT_I2C_ADDR as u8, test_data::CLASSIC_IDLE.to_vec()),
+        // Save the power-on report format
+        Transaction::write(EXT_I2C_ADDR as u8, vec![254]),
+        Transaction::read(EXT_I2C_ADDR as u8, vec![1]),
+        Transaction::write(EXT_I2C_ADDR as u8, vec![0]),
+        // Switch to HD mode
+        Transaction::write(EXT_I2C_ADDR as u8, vec![254, 3]),
+        // HD-Mode Calibration read, 8 bytes
+        Transaction::write(EXT_I2C_ADDR as u8, vec![0]),
+        Transaction::read(EXT_I2C_ADDR as u8, hd_idle.to_vec()),
+        // Input read, 8 bytes
+        Transaction::write(EXT_I2C_ADDR as u8, vec![0]),
+        Transaction::read(EXT_I2C_ADDR as u8, sample.to_vec()),
+    ]
+}
+
+/// Check each axis of `input` against its acceptable (low, high) range
+fn assert_ranges(input: &ClassicReadingCalibrated, ranges: [(i8, i8); 6]) {
+    let axes = [
+        ("left_x", input.joystick_left_x),
+        ("left_y", input.joystick_left_y),
+        ("right_x", input.joystick_right_x),
+        ("right_y", input.joystick_right_y),
+        ("trigger_left", input.trigger_left),
+        ("trigger_right", input.trigger_right),
+    ];
+    for ((name, value), (low, high)) in axes.into_iter().zip(ranges) {
+        assert!(
+            (low..=high).contains(&value),
+            "{name} = {value}, expected between {low} and {high}"
+        );
+    }
+}
+
 macro_rules! assert_joystick_hd {
     ( $x:ident, $y:ident,
           $lxl:expr, $lxh:expr,
@@ -25,78 +71,36 @@ macro_rules! assert_joystick_hd {
         ) => {
         paste! {
             #[test]
-             fn [<test_calibrated_hd_ $y:lower>]()  {
-                let expectations = vec![
-                    // Reset controller
-                    Transaction::write(EXT_I2C_ADDR as u8, vec![0]),
-                    // Init
-                    Transaction::write(EXT_I2C_ADDR as u8, vec![240, 85]),
-                    Transaction::write(EXT_I2C_ADDR as u8, vec![251, 0]),
-
-                    // Calibration read (discarded - use any data)
-                    Transaction::write(EXT_I2C_ADDR as u8, vec![0]),
-                    Transaction::read(EXT_I2C_ADDR as u8, test_data::CLASSIC_IDLE.to_vec()),
-
-                    // Switch to HD mode
-                    Transaction::write(EXT_I2C_ADDR as u8, vec![254, 3]),
-
-                    // HD-Mode Calibration read
-                    Transaction::write(EXT_I2C_ADDR as u8, vec![0]),
-                    Transaction::read(EXT_I2C_ADDR as u8, test_data::$x.to_vec()),
-                    // Input read
-                    Transaction::write(EXT_I2C_ADDR as u8, vec![0]),
-                    Transaction::read(EXT_I2C_ADDR as u8, test_data::$y.to_vec()),
-                ];
+            fn [<test_calibrated_hd_ $y:lower>]() {
+                let expectations = hd_expectations(&test_data::$x, &test_data::$y);
                 let mut i2c = i2c::Mock::new(&expectations);
                 let delay = NoopDelay::new();
                 let mut classic = Classic::new(i2c.clone(), delay).unwrap();
                 classic.enable_hires().unwrap();
                 let input = classic.read().unwrap();
-
-                assert!(
-                    ($lxl..=$lxh).contains(&input.joystick_left_x),
-                    "left_x = {}, expected between {} and {}",
-                    input.joystick_left_x,
-                    $lxl,
-                    $lxh
-                );
-                assert!(
-                    ($lyl..=$lyh).contains(&input.joystick_left_y),
-                    "left_y = {}, expected between {} and {}",
-                    input.joystick_left_y,
-                    $lyl,
-                    $lyh
-                );
-                assert!(
-                    ($rxl..=$rxh).contains(&input.joystick_right_x),
-                    "right_x = {}, expected between {} and {}",
-                    input.joystick_right_x,
-                    $rxl,
-                    $rxh
-                );
-                assert!(
-                    ($ryl..=$ryh).contains(&input.joystick_right_y),
-                    "right_y = {}, expected between {} and {}",
-                    input.joystick_right_y,
-                    $ryl,
-                    $ryh
-                );
-                assert!(
-                    ($ltl..=$lth).contains(&input.trigger_left),
-                    "trigger_left = {}, expected between {} and {}",
-                    input.trigger_left,
-                    $ltl,
-                    $lth
-                );
-                assert!(
-                    ($rtl..=$rth).contains(&input.trigger_right),
-                    "trigger_right = {}, expected between {} and {}",
-                    input.trigger_right,
-                    $rtl,
-                    $rth
+                assert_ranges(
+                    &input,
+                    [($lxl, $lxh), ($lyl, $lyh), ($rxl, $rxh), ($ryl, $ryh), ($ltl, $lth), ($rtl, $rth)],
                 );
                 i2c.done();
             }
+
+            #[test]
+            fn [<test_calibrated_hd_async_ $y:lower>]() {
+                use wii_ext::async_impl::classic::Classic;
+                let expectations = hd_expectations(&test_data::$x, &test_data::$y);
+                let mut i2c = i2c::Mock::new(&expectations);
+                block_on(async {
+                    let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).await.unwrap();
+                    classic.enable_hires().await.unwrap();
+                    let input = classic.read().await.unwrap();
+                    assert_ranges(
+                        &input,
+                        [($lxl, $lxh), ($lyl, $lyh), ($rxl, $rxh), ($ryl, $ryh), ($ltl, $lth), ($rtl, $rth)],
+                    );
+                });
+                i2c.done();
+            }
         }
     };
 }
@@ -0,0 +1,129 @@
+use embassy_futures::block_on;
+use embedded_hal_mock::eh1::{
+    delay::NoopDelay,
+    i2c::{self, Transaction},
+};
+use wii_ext::blocking_impl::classic::{Classic, ClassicBuilder};
+use wii_ext::core::classic::{HiRes, Standard};
+use wii_ext::core::{Ready, EXT_I2C_ADDR};
+use wii_ext::Error;
+mod common;
+use common::{test_data, transactions};
+
+fn enable_hires(calibration: &[u8]) -> Vec<Transaction> {
+    let mut transactions = transactions::save_report_format(1);
+    transactions.push(Transaction::write(EXT_I2C_ADDR as u8, vec![254, 3]));
+    transactions.extend(transactions::read(calibration));
+    transactions
+}
+
+#[test]
+fn standard_driver_reads_standard_reports() {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.extend(transactions::read(&test_data::CLASSIC_LJOY_L));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let classic = Classic::new(i2c.clone(), NoopDelay::new()).unwrap();
+    let mut classic: Classic<_, _, Ready, Standard> = classic.into_standard().ok().unwrap();
+    let input = classic.read().unwrap();
+    assert!(input.joystick_left_x < -50);
+    i2c.done();
+}
+
+#[test]
+fn enable_hires_consumes_standard_driver() {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.extend(enable_hires(&test_data::CLASSIC_HD_IDLE));
+    expectations.extend(transactions::read(&test_data::CLASSIC_HD_LJOY_U));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let classic = Classic::new(i2c.clone(), NoopDelay::new()).unwrap();
+    let classic = classic.into_standard().ok().unwrap();
+    let mut classic: Classic<_, _, Ready, HiRes> = classic.enable_hires().unwrap();
+    let input = classic.read().unwrap();
+    assert!(input.joystick_left_y > 90);
+    assert!((-5..=5).contains(&input.joystick_left_x));
+    i2c.done();
+}
+
+#[test]
+fn hires_driver_rejects_format_register_writes() {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.extend(enable_hires(&test_data::CLASSIC_HD_IDLE));
+    expectations.extend(transactions::read(&test_data::CLASSIC_HD_BTN_X));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let classic = Classic::new(i2c.clone(), NoopDelay::new()).unwrap();
+    let mut classic = classic
+        .into_standard()
+        .ok()
+        .unwrap()
+        .enable_hires()
+        .unwrap();
+    // Nothing is written, so the controller keeps sending 8 byte reports
+    assert!(matches!(
+        classic.write_register(0xFE, 0x01),
+        Err(Error::InvalidInputData)
+    ));
+    assert!(classic.read().unwrap().button_x);
+    i2c.done();
+}
+
+#[test]
+fn standard_driver_rejects_format_register_writes() {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    // Other registers are written as usual
+    expectations.push(Transaction::write(EXT_I2C_ADDR as u8, vec![0xF0, 0x55]));
+    expectations.push(Transaction::write(EXT_I2C_ADDR as u8, vec![0]));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let classic = Classic::new(i2c.clone(), NoopDelay::new()).unwrap();
+    let mut classic = classic.into_standard().ok().unwrap();
+    assert!(matches!(
+        classic.write_register(0xFE, 0x03),
+        Err(Error::InvalidInputData)
+    ));
+    classic.write_register(0xF0, 0x55).unwrap();
+    i2c.done();
+}
+
+#[test]
+fn dyn_driver_only_converts_to_its_own_mode() {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.extend(enable_hires(&test_data::CLASSIC_HD_IDLE));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).unwrap();
+    classic.enable_hires().unwrap();
+    let classic = classic.into_standard().err().unwrap();
+    let classic = classic.into_hires().ok().unwrap();
+    let classic = classic.into_dyn();
+    assert!(classic.into_standard().is_err());
+    i2c.done();
+}
+
+#[test]
+fn builder_hires_converts_to_hires_driver() {
+    let mut expectations = transactions::init_no_calibration();
+    expectations.extend(enable_hires(&test_data::CLASSIC_HD_IDLE));
+    expectations.extend(transactions::read(&test_data::CLASSIC_HD_RJOY_D));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let classic = ClassicBuilder::new()
+        .hires(true)
+        .build(i2c.clone(), NoopDelay::new())
+        .unwrap();
+    let mut classic = classic.into_hires().ok().unwrap();
+    assert!(classic.read().unwrap().joystick_right_y < -90);
+    i2c.done();
+}
+
+#[test]
+fn async_enable_hires_consumes_standard_driver() {
+    use wii_ext::async_impl::classic::Classic;
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.extend(enable_hires(&test_data::CLASSIC_HD_IDLE));
+    expectations.extend(transactions::read(&test_data::CLASSIC_HD_LJOY_R));
+    let mut i2c = i2c::Mock::new(&expectations);
+    block_on(async {
+        let classic = Classic::new(i2c.clone(), NoopDelay::new()).await.unwrap();
+        let classic = classic.into_standard().ok().unwrap();
+        let mut classic = classic.enable_hires().await.unwrap();
+        assert!(classic.read().await.unwrap().joystick_left_x > 90);
+    });
+    i2c.done();
+}
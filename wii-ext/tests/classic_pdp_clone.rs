@@ -13,12 +13,6 @@ use common::test_data::*;
 const ZERO_SLOP: i8 = 5;
 /// Triggers are sloppier, or I accidentally pressed them during testing
 const TRIGGER_SLOP: i8 = 25;
-/// The max value at full deflection is ~100, but allow a bit less than that
-const AXIS_MAX: i8 = 90;
-
-/// The max value for the right stick is greatly reduced?
-/// Need to retest in hi-resolution
-const R_AXIS_MAX: i8 = 45;
 
 fn assert_digital_eq(first: ClassicReading, other: ClassicReading) {
     assert_eq!(first.button_a, other.button_a);
@@ -103,10 +97,7 @@ macro_rules! assert_button_fn {
                 let delay = NoopDelay::new();
                 let mut classic = Classic::new(i2c.clone(), delay).unwrap();
                 let input = classic.read_uncalibrated().unwrap();
-                assert_digital_eq(input, ClassicReading {
-                    $x: true,
-                    ..Default::default()
-                });
+                assert_digital_eq(input, ClassicReading::default().[<with_ $x>](true));
                 i2c.done();
             }
         }
@@ -178,11 +169,7 @@ fn classic_calibrated_joy_left() {
     let mut classic = Classic::new(i2c.clone(), delay).unwrap();
     let input = classic.read().unwrap();
 
-    assert!(
-        (i8::MIN..-AXIS_MAX).contains(&input.joystick_left_x),
-        "left_x = {}",
-        input.joystick_left_x
-    );
+    assert_eq!(input.joystick_left_x, -128);
     assert!(
         (-ZERO_SLOP..ZERO_SLOP).contains(&input.joystick_left_y),
         "left_y = {}",
@@ -294,7 +281,7 @@ macro_rules! assert_joysticks {
 #[rustfmt::skip]
 assert_joysticks!(
     PDP_LINK_IDLE, PDP_LINK_LJOY_L, // Set idle and test sample
-    i8::MIN, -AXIS_MAX, // acceptable range for left x axis
+    -128, -128, // acceptable range for left x axis
     -ZERO_SLOP, ZERO_SLOP, // acceptable range for left y axis
     -ZERO_SLOP, ZERO_SLOP, // acceptable range for right x axis
     -ZERO_SLOP, ZERO_SLOP, // acceptable range for right y axis
@@ -306,7 +293,7 @@ assert_joysticks!(
 #[rustfmt::skip]
 assert_joysticks!(
     PDP_LINK_IDLE, PDP_LINK_LJOY_R, // Set idle and test sample
-    AXIS_MAX, i8::MAX, // acceptable range for left x axis
+    119, 119, // acceptable range for left x axis
     -ZERO_SLOP, ZERO_SLOP, // acceptable range for left y axis
     -ZERO_SLOP, ZERO_SLOP, // acceptable range for right x axis
     -ZERO_SLOP, ZERO_SLOP, // acceptable range for right y axis
@@ -319,7 +306,7 @@ assert_joysticks!(
 assert_joysticks!(
     PDP_LINK_IDLE, PDP_LINK_LJOY_D, // Set idle and test sample
     -ZERO_SLOP, ZERO_SLOP, // acceptable range for left x axis
-    i8::MIN, -AXIS_MAX, // acceptable range for left y axis
+    -116, -116, // acceptable range for left y axis
     -ZERO_SLOP, ZERO_SLOP, // acceptable range for right x axis
     -ZERO_SLOP, ZERO_SLOP, // acceptable range for right y axis
     -TRIGGER_SLOP, TRIGGER_SLOP, // acceptable range for left trigger
@@ -331,7 +318,7 @@ assert_joysticks!(
 assert_joysticks!(
     PDP_LINK_IDLE, PDP_LINK_LJOY_U, // Set idle and test sample
     -ZERO_SLOP, ZERO_SLOP, // acceptable range for left x axis
-    AXIS_MAX, i8::MAX, // acceptable range for left y axis
+    127, 127, // acceptable range for left y axis
     -ZERO_SLOP, ZERO_SLOP, // acceptable range for right x axis
     -ZERO_SLOP, ZERO_SLOP, // acceptable range for right y axis
     -TRIGGER_SLOP, TRIGGER_SLOP, // acceptable range for left trigger
@@ -344,7 +331,7 @@ assert_joysticks!(
     PDP_LINK_IDLE, PDP_LINK_RJOY_L, // Set idle and test sample
     -ZERO_SLOP, ZERO_SLOP, // acceptable range for left x axis
     -ZERO_SLOP, ZERO_SLOP, // acceptable range for left y axis
-    i8::MIN, -AXIS_MAX, // acceptable range for right x axis
+    -120, -120, // acceptable range for right x axis
     -ZERO_SLOP, ZERO_SLOP, // acceptable range for right y axis
     -TRIGGER_SLOP, TRIGGER_SLOP, // acceptable range for left trigger
     -TRIGGER_SLOP, TRIGGER_SLOP // // acceptable range for right trigger
@@ -356,7 +343,7 @@ assert_joysticks!(
     PDP_LINK_IDLE, PDP_LINK_RJOY_R, // Set idle and test sample
     -ZERO_SLOP, ZERO_SLOP, // acceptable range for left x axis
     -ZERO_SLOP, ZERO_SLOP, // acceptable range for left y axis
-    AXIS_MAX, i8::MAX, // acceptable range for right x axis
+    127, 127, // acceptable range for right x axis
     -ZERO_SLOP, ZERO_SLOP, // acceptable range for right y axis
     -TRIGGER_SLOP, TRIGGER_SLOP, // acceptable range for left trigger
     -TRIGGER_SLOP, TRIGGER_SLOP // // acceptable range for right trigger
@@ -369,7 +356,7 @@ assert_joysticks!(
     -ZERO_SLOP, ZERO_SLOP, // acceptable range for left x axis
     -ZERO_SLOP, ZERO_SLOP, // acceptable range for left y axis
     -ZERO_SLOP, ZERO_SLOP, // acceptable range for right x axis
-    i8::MIN, -R_AXIS_MAX, // acceptable range for right y axis
+    -120, -120, // acceptable range for right y axis
     -TRIGGER_SLOP, TRIGGER_SLOP, // acceptable range for left trigger
     -TRIGGER_SLOP, TRIGGER_SLOP // // acceptable range for right trigger
 );
@@ -381,7 +368,7 @@ assert_joysticks!(
     -ZERO_SLOP, ZERO_SLOP, // acceptable range for left x axis
     -ZERO_SLOP, ZERO_SLOP, // acceptable range for left y axis
     -ZERO_SLOP, ZERO_SLOP, // acceptable range for right x axis
-    R_AXIS_MAX, i8::MAX, // acceptable range for right y axis
+    127, 127, // acceptable range for right y axis
     -TRIGGER_SLOP, TRIGGER_SLOP, // acceptable range for left trigger
     -TRIGGER_SLOP, TRIGGER_SLOP // // acceptable range for right trigger
 );
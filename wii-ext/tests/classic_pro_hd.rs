@@ -37,6 +37,11 @@ macro_rules! assert_joystick_hd {
                     Transaction::write(EXT_I2C_ADDR as u8, vec![0]),
                     Transaction::read(EXT_I2C_ADDR as u8, test_data::PRO_IDLE.to_vec()),
 
+                    // Save the power-on report format
+                    Transaction::write(EXT_I2C_ADDR as u8, vec![254]),
+                    Transaction::read(EXT_I2C_ADDR as u8, vec![1]),
+                    Transaction::write(EXT_I2C_ADDR as u8, vec![0]),
+
                     // Switch to HD mode
                     Transaction::write(EXT_I2C_ADDR as u8, vec![254, 3]),
 
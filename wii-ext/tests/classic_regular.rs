@@ -101,10 +101,7 @@ macro_rules! assert_button_fn {
                 let delay = NoopDelay::new();
                 let mut classic = Classic::new(i2c.clone(), delay).unwrap();
                 let input = classic.read_uncalibrated().unwrap();
-                assert_digital_eq(input, ClassicReading {
-                    $x: true,
-                    ..Default::default()
-                });
+                assert_digital_eq(input, ClassicReading::default().[<with_ $x>](true));
                 i2c.done();
             }
         }
@@ -179,11 +176,7 @@ fn classic_calibrated_joy_left() {
     let mut classic = Classic::new(i2c.clone(), delay).unwrap();
     let input = classic.read().unwrap();
 
-    assert!(
-        (i8::MIN..=-AXIS_MAX).contains(&input.joystick_left_x),
-        "left_x = {}",
-        input.joystick_left_x
-    );
+    assert_eq!(input.joystick_left_x, -100);
     assert!(
         (-ZERO_SLOP..=ZERO_SLOP).contains(&input.joystick_left_y),
         "left_y = {}",
@@ -295,7 +288,7 @@ macro_rules! assert_joysticks {
 #[rustfmt::skip]
 assert_joysticks!(
     CLASSIC_IDLE, CLASSIC_LJOY_L, // Set idle and test sample
-    i8::MIN, -AXIS_MAX, // acceptable range for left x axis
+    -100, -100, // acceptable range for left x axis
     -ZERO_SLOP, ZERO_SLOP, // acceptable range for left y axis
     -ZERO_SLOP, ZERO_SLOP, // acceptable range for right x axis
     -ZERO_SLOP, ZERO_SLOP, // acceptable range for right y axis
@@ -307,7 +300,7 @@ assert_joysticks!(
 #[rustfmt::skip]
 assert_joysticks!(
     CLASSIC_IDLE, CLASSIC_LJOY_R, // Set idle and test sample
-    AXIS_MAX, i8::MAX, // acceptable range for left x axis
+    98, 98, // acceptable range for left x axis
     -ZERO_SLOP, ZERO_SLOP, // acceptable range for left y axis
     -ZERO_SLOP, ZERO_SLOP, // acceptable range for right x axis
     -ZERO_SLOP, ZERO_SLOP, // acceptable range for right y axis
@@ -320,7 +313,7 @@ assert_joysticks!(
 assert_joysticks!(
     CLASSIC_IDLE, CLASSIC_LJOY_D, // Set idle and test sample
     -ZERO_SLOP, ZERO_SLOP, // acceptable range for left x axis
-    i8::MIN, -AXIS_MAX, // acceptable range for left y axis
+    -96, -96, // acceptable range for left y axis
     -ZERO_SLOP, ZERO_SLOP, // acceptable range for right x axis
     -ZERO_SLOP, ZERO_SLOP, // acceptable range for right y axis
     -TRIGGER_SLOP, TRIGGER_SLOP, // acceptable range for left trigger
@@ -332,7 +325,7 @@ assert_joysticks!(
 assert_joysticks!(
     CLASSIC_IDLE, CLASSIC_LJOY_U, // Set idle and test sample
     -ZERO_SLOP, ZERO_SLOP, // acceptable range for left x axis
-    AXIS_MAX, i8::MAX, // acceptable range for left y axis
+    111, 111, // acceptable range for left y axis
     -ZERO_SLOP, ZERO_SLOP, // acceptable range for right x axis
     -ZERO_SLOP, ZERO_SLOP, // acceptable range for right y axis
     -TRIGGER_SLOP, TRIGGER_SLOP, // acceptable range for left trigger
@@ -345,7 +338,7 @@ assert_joysticks!(
     CLASSIC_IDLE, CLASSIC_RJOY_L, // Set idle and test sample
     -ZERO_SLOP, ZERO_SLOP, // acceptable range for left x axis
     -ZERO_SLOP, ZERO_SLOP, // acceptable range for left y axis
-    i8::MIN, -AXIS_MAX, // acceptable range for right x axis
+    -96, -96, // acceptable range for right x axis
     -ZERO_SLOP, ZERO_SLOP, // acceptable range for right y axis
     -TRIGGER_SLOP, TRIGGER_SLOP, // acceptable range for left trigger
     -TRIGGER_SLOP, TRIGGER_SLOP // // acceptable range for right trigger
@@ -357,7 +350,7 @@ assert_joysticks!(
     CLASSIC_IDLE, CLASSIC_RJOY_R, // Set idle and test sample
     -ZERO_SLOP, ZERO_SLOP, // acceptable range for left x axis
     -ZERO_SLOP, ZERO_SLOP, // acceptable range for left y axis
-    AXIS_MAX, i8::MAX, // acceptable range for right x axis
+    110, 110, // acceptable range for right x axis
     -ZERO_SLOP, ZERO_SLOP, // acceptable range for right y axis
     -TRIGGER_SLOP, TRIGGER_SLOP, // acceptable range for left trigger
     -TRIGGER_SLOP, TRIGGER_SLOP // // acceptable range for right trigger
@@ -370,7 +363,7 @@ assert_joysticks!(
     -ZERO_SLOP, ZERO_SLOP, // acceptable range for left x axis
     -ZERO_SLOP, ZERO_SLOP, // acceptable range for left y axis
     -ZERO_SLOP, ZERO_SLOP, // acceptable range for right x axis
-    i8::MIN, -AXIS_MAX, // acceptable range for right y axis
+    -112, -112, // acceptable range for right y axis
     -TRIGGER_SLOP, TRIGGER_SLOP, // acceptable range for left trigger
     -TRIGGER_SLOP, TRIGGER_SLOP // // acceptable range for right trigger
 );
@@ -382,7 +375,7 @@ assert_joysticks!(
     -ZERO_SLOP, ZERO_SLOP, // acceptable range for left x axis
     -ZERO_SLOP, ZERO_SLOP, // acceptable range for left y axis
     -ZERO_SLOP, ZERO_SLOP, // acceptable range for right x axis
-    AXIS_MAX, i8::MAX, // acceptable range for right y axis
+    102, 102, // acceptable range for right y axis
     -TRIGGER_SLOP, TRIGGER_SLOP, // acceptable range for left trigger
     -TRIGGER_SLOP, TRIGGER_SLOP // // acceptable range for right trigger
 );
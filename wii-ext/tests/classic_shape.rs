@@ -0,0 +1,68 @@
+use wii_ext::core::classic::ClassicReadingCalibrated;
+use wii_ext::core::events::{Button, Inputs};
+use wii_ext::core::nunchuk::{ClassicShape, NunchukReadingCalibrated};
+
+fn pressed(reading: &ClassicReadingCalibrated) -> Vec<Button> {
+    ClassicReadingCalibrated::BUTTONS
+        .iter()
+        .copied()
+        .filter(|&button| reading[button])
+        .collect()
+}
+
+#[test]
+fn centered_stick_passes_through() {
+    let nunchuk = NunchukReadingCalibrated::default()
+        .with_accel_x(600)
+        .with_accel_z(712);
+    assert_eq!(
+        nunchuk.to_classic_shape(),
+        ClassicReadingCalibrated::default()
+    );
+}
+
+#[test]
+fn stick_is_the_left_stick() {
+    let nunchuk = NunchukReadingCalibrated::default()
+        .with_joystick_x(i8::MIN)
+        .with_joystick_y(90);
+    let classic = nunchuk.to_classic_shape();
+    assert_eq!(classic.left_stick(), nunchuk.stick());
+    assert_eq!(classic.right_stick(), Default::default());
+    assert_eq!((classic.trigger_left, classic.trigger_right), (0, 0));
+}
+
+#[test]
+fn default_button_mapping() {
+    let mut nunchuk = NunchukReadingCalibrated::default().with_button_c(true);
+    assert_eq!(pressed(&nunchuk.to_classic_shape()), [Button::A]);
+    nunchuk.button_z = true;
+    assert_eq!(
+        pressed(&nunchuk.to_classic_shape()),
+        [Button::A, Button::ZL]
+    );
+    nunchuk.button_c = false;
+    assert_eq!(pressed(&nunchuk.to_classic_shape()), [Button::ZL]);
+}
+
+#[test]
+fn custom_button_mapping() {
+    let shape = ClassicShape::new(Button::B, Button::R);
+    let nunchuk = NunchukReadingCalibrated::default()
+        .with_button_c(true)
+        .with_button_z(true);
+    assert_eq!(pressed(&shape.convert(&nunchuk)), [Button::B, Button::R]);
+
+    // Both on one button: pressed while either is
+    let shape = ClassicShape::new(Button::A, Button::A);
+    for (c, z) in [(true, false), (false, true), (true, true)] {
+        let nunchuk = NunchukReadingCalibrated::default()
+            .with_button_c(c)
+            .with_button_z(z);
+        assert_eq!(pressed(&shape.convert(&nunchuk)), [Button::A]);
+    }
+
+    // Nunchuk-only buttons are dropped
+    let shape = ClassicShape::new(Button::C, Button::Z);
+    assert_eq!(pressed(&shape.convert(&nunchuk)), []);
+}
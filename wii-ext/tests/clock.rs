@@ -0,0 +1,71 @@
+use embassy_futures::block_on;
+use embedded_hal_mock::eh1::{delay::NoopDelay, i2c};
+use wii_ext::blocking_impl::classic::Classic;
+use wii_ext::blocking_impl::nunchuk::Nunchuk;
+use wii_ext::core::clock::Clock;
+mod common;
+use common::{test_data, transactions};
+
+/// A clock that moves on 1000us each time it is read
+#[derive(Default)]
+struct FakeClock(u64);
+
+impl Clock for FakeClock {
+    fn now_micros(&mut self) -> u64 {
+        self.0 += 1000;
+        self.0
+    }
+}
+
+#[test]
+fn readings_carry_their_timestamps() {
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_BTN_C));
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_BTN_Z));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut nunchuk = Nunchuk::new(i2c.clone(), NoopDelay::new()).unwrap();
+    let mut clock = FakeClock::default();
+    let first = nunchuk.read_timestamped(&mut clock).unwrap();
+    let second = nunchuk.read_timestamped(&mut clock).unwrap();
+    assert_eq!(first.micros, 1000);
+    assert!(first.reading.button_c);
+    assert_eq!(second.micros, 2000);
+    assert!(second.reading.button_z);
+    i2c.done();
+}
+
+#[test]
+fn clock_is_not_read_again_by_plain_reads() {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.extend(transactions::read(&test_data::CLASSIC_BTN_A));
+    expectations.extend(transactions::read(&test_data::CLASSIC_BTN_B));
+    expectations.extend(transactions::read(&test_data::CLASSIC_BTN_X));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).unwrap();
+    let mut clock = FakeClock::default();
+    let first = classic.read_timestamped(&mut clock).unwrap();
+    assert!(classic.read().unwrap().button_b);
+    let second = classic.read_timestamped(&mut clock).unwrap();
+    assert!(first.reading.button_a && second.reading.button_x);
+    assert!(second.micros > first.micros);
+    assert_eq!(clock.0, 2000);
+    i2c.done();
+}
+
+#[test]
+fn async_readings_carry_their_timestamps() {
+    use wii_ext::async_impl::classic::Classic;
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.extend(transactions::read(&test_data::CLASSIC_BTN_Y));
+    expectations.extend(transactions::read(&test_data::CLASSIC_BTN_A));
+    let mut i2c = i2c::Mock::new(&expectations);
+    block_on(async {
+        let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).await.unwrap();
+        let mut clock = FakeClock(5000);
+        let first = classic.read_timestamped(&mut clock).await.unwrap();
+        let second = classic.read_timestamped(&mut clock).await.unwrap();
+        assert_eq!((first.micros, second.micros), (6000, 7000));
+        assert!(first.reading.button_y && second.reading.button_a);
+    });
+    i2c.done();
+}
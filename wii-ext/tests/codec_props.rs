@@ -0,0 +1,151 @@
+use embedded_hal_mock::eh1::{delay::NoopDelay, i2c};
+use proptest::prelude::*;
+use wii_ext::blocking_impl::classic::Classic;
+use wii_ext::core::classic::ClassicReading;
+use wii_ext::core::nunchuk::NunchukReading;
+mod common;
+use common::{test_data, transactions};
+
+/// Largest decode error for an axis sent with 6 bits of precision
+const ERROR_6BIT: u8 = 3;
+/// Largest decode error for an axis sent with 5 bits of precision
+const ERROR_5BIT: u8 = 5;
+
+prop_compose! {
+    fn classic_reading()(
+        axes in any::<[u8; 6]>(),
+        buttons in any::<[bool; 15]>(),
+    ) -> ClassicReading {
+        ClassicReading::default()
+.with_joystick_left_x(axes[0])
+.with_joystick_left_y(axes[1])
+.with_joystick_right_x(axes[2])
+.with_joystick_right_y(axes[3])
+.with_trigger_left(axes[4])
+.with_trigger_right(axes[5])
+.with_dpad_up(buttons[0])
+.with_dpad_down(buttons[1])
+.with_dpad_left(buttons[2])
+.with_dpad_right(buttons[3])
+.with_button_b(buttons[4])
+.with_button_a(buttons[5])
+.with_button_x(buttons[6])
+.with_button_y(buttons[7])
+.with_button_trigger_l(buttons[8])
+.with_button_trigger_r(buttons[9])
+.with_button_zl(buttons[10])
+.with_button_zr(buttons[11])
+.with_button_minus(buttons[12])
+.with_button_plus(buttons[13])
+.with_button_home(buttons[14])
+    }
+}
+
+prop_compose! {
+    fn nunchuk_reading()(
+        joystick in any::<[u8; 2]>(),
+        accel in prop::array::uniform3(0u16..1024),
+        buttons in any::<[bool; 2]>(),
+    ) -> NunchukReading {
+        NunchukReading::default()
+.with_joystick_x(joystick[0])
+.with_joystick_y(joystick[1])
+.with_accel_x(accel[0])
+.with_accel_y(accel[1])
+.with_accel_z(accel[2])
+.with_button_c(buttons[0])
+.with_button_z(buttons[1])
+    }
+}
+
+fn assert_same_buttons(a: &ClassicReading, b: &ClassicReading) {
+    let buttons = |r: &ClassicReading| {
+        [
+            r.dpad_up,
+            r.dpad_down,
+            r.dpad_left,
+            r.dpad_right,
+            r.button_b,
+            r.button_a,
+            r.button_x,
+            r.button_y,
+            r.button_trigger_l,
+            r.button_trigger_r,
+            r.button_zl,
+            r.button_zr,
+            r.button_minus,
+            r.button_plus,
+            r.button_home,
+        ]
+    };
+    assert_eq!(buttons(a), buttons(b));
+}
+
+proptest! {
+    #[test]
+    fn classic_standard_round_trip(reading in classic_reading()) {
+        let report = reading.to_report();
+        let decoded = ClassicReading::from_data(&report).unwrap();
+        assert_same_buttons(&decoded, &reading);
+        let axes = [
+            (decoded.joystick_left_x, reading.joystick_left_x, ERROR_6BIT),
+            (decoded.joystick_left_y, reading.joystick_left_y, ERROR_6BIT),
+            (decoded.joystick_right_x, reading.joystick_right_x, ERROR_5BIT),
+            (decoded.joystick_right_y, reading.joystick_right_y, ERROR_5BIT),
+            (decoded.trigger_left, reading.trigger_left, ERROR_5BIT),
+            (decoded.trigger_right, reading.trigger_right, ERROR_5BIT),
+        ];
+        for (decoded, original, error) in axes {
+            prop_assert!(decoded.abs_diff(original) <= error, "{decoded} vs {original}");
+        }
+        // Values already at the report's precision survive exactly
+        prop_assert_eq!(decoded.to_report(), report);
+    }
+
+    #[test]
+    fn classic_hd_round_trip(reading in classic_reading()) {
+        let decoded = ClassicReading::from_data(&reading.to_hd_report()).unwrap();
+        prop_assert_eq!(decoded, reading);
+    }
+
+    #[test]
+    fn classic_alt_round_trip(reading in classic_reading()) {
+        let decoded = ClassicReading::from_data(&reading.to_alt_report()).unwrap();
+        prop_assert_eq!(decoded, reading);
+    }
+
+    #[test]
+    fn nunchuk_round_trip(reading in nunchuk_reading()) {
+        let decoded = NunchukReading::from_data(&reading.to_report()).unwrap();
+        prop_assert_eq!(decoded, reading);
+    }
+
+    #[test]
+    fn classic_from_data_accepts_only_report_sizes(data in prop::collection::vec(any::<u8>(), 0..16)) {
+        let decoded = ClassicReading::from_data(&data);
+        prop_assert_eq!(decoded.is_some(), matches!(data.len(), 6 | 8 | 9));
+    }
+
+    #[test]
+    fn nunchuk_from_data_never_panics(data in prop::collection::vec(any::<u8>(), 0..16)) {
+        let decoded = NunchukReading::from_data(&data);
+        prop_assert_eq!(decoded.is_some(), data.len() >= 6);
+    }
+
+    /// With the identity check on, the driver rejects reports with the constant bit clear
+    #[test]
+    fn checked_classic_reads_require_constant_bits(report in any::<[u8; 6]>()) {
+        let sane = report[4] & 1 != 0;
+        let mut expectations = transactions::init(&test_data::PRO_IDLE);
+        expectations.extend(transactions::read_id(&test_data::PRO_ID));
+        expectations.extend(transactions::read(&report));
+        if !sane {
+            expectations.extend(transactions::read_id(&test_data::PRO_ID));
+        }
+        let mut i2c = i2c::Mock::new(&expectations);
+        let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).unwrap();
+        classic.enable_identity_check(0).unwrap();
+        prop_assert_eq!(classic.read().is_ok(), sane);
+        i2c.done();
+    }
+}
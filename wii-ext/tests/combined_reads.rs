@@ -0,0 +1,110 @@
+use embassy_futures::block_on;
+use embedded_hal_mock::eh1::{
+    delay::NoopDelay,
+    i2c::{self, Transaction},
+};
+use wii_ext::blocking_impl::classic::ClassicBuilder;
+use wii_ext::blocking_impl::nunchuk::NunchukBuilder;
+use wii_ext::core::classic::ModeChangeOptions;
+use wii_ext::core::EXT_I2C_ADDR;
+mod common;
+use common::{test_data, transactions};
+
+/// A report read as a single seek + read transaction
+fn combined_read(report: &[u8]) -> Vec<Transaction> {
+    vec![Transaction::write_read(
+        EXT_I2C_ADDR as u8,
+        vec![0],
+        report.to_vec(),
+    )]
+}
+
+#[test]
+fn nunchuk_combined_reads() {
+    let mut expectations = transactions::init_no_calibration();
+    expectations.extend(combined_read(&test_data::NUNCHUCK_IDLE));
+    expectations.extend(combined_read(&test_data::NUNCHUCK_JOY_D));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut nunchuk = NunchukBuilder::new()
+        .combined_reads(true)
+        .build(i2c.clone(), NoopDelay::new())
+        .unwrap();
+    let input = nunchuk.read().unwrap();
+    assert!(input.joystick_y < -90);
+    i2c.done();
+}
+
+#[test]
+fn classic_hires_combined_reads() {
+    let mut expectations = transactions::init_no_calibration();
+    expectations.extend(transactions::save_report_format(1));
+    expectations.push(Transaction::write(EXT_I2C_ADDR as u8, vec![254, 3]));
+    expectations.extend(combined_read(&test_data::PRO_HD_IDLE));
+    // The split API sends the combined transaction when the read is finished
+    expectations.extend(combined_read(&test_data::PRO_HD_LJOY_U));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = ClassicBuilder::new()
+        .hires(true)
+        .combined_reads(true)
+        .build(i2c.clone(), NoopDelay::new())
+        .unwrap();
+    classic.begin_read().unwrap();
+    let input = classic.finish_read().unwrap();
+    assert!(input.joystick_left_y > 90);
+    i2c.done();
+}
+
+#[test]
+fn register_write_keeps_the_deferred_seek() {
+    let mut expectations = transactions::init_no_calibration();
+    expectations.extend(transactions::save_report_format(1));
+    expectations.push(Transaction::write(EXT_I2C_ADDR as u8, vec![254, 3]));
+    expectations.extend(combined_read(&test_data::PRO_HD_IDLE));
+    // The write moves the cursor, so the read still seeks back to 0
+    expectations.push(Transaction::write(EXT_I2C_ADDR as u8, vec![254, 3]));
+    expectations.extend(combined_read(&test_data::PRO_HD_LJOY_U));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = ClassicBuilder::new()
+        .hires(true)
+        .combined_reads(true)
+        .build(i2c.clone(), NoopDelay::new())
+        .unwrap();
+    classic.begin_read().unwrap();
+    let options = ModeChangeOptions::DEFAULT
+        .with_force(true)
+        .with_recalibrate(false);
+    classic.enable_hires_with(options).unwrap();
+    let input = classic.finish_read().unwrap();
+    assert!(input.joystick_left_y > 90);
+    i2c.done();
+}
+
+#[test]
+fn classic_without_combined_reads_uses_two_calls() {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.extend(transactions::read(&test_data::CLASSIC_BTN_A));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = ClassicBuilder::new()
+        .combined_reads(false)
+        .build(i2c.clone(), NoopDelay::new())
+        .unwrap();
+    assert!(classic.read().unwrap().button_a);
+    i2c.done();
+}
+
+#[test]
+fn async_nunchuk_combined_reads() {
+    let mut expectations = transactions::init_no_calibration();
+    expectations.extend(combined_read(&test_data::NUNCHUCK_IDLE));
+    expectations.extend(combined_read(&test_data::NUNCHUCK_BTN_C));
+    let mut i2c = i2c::Mock::new(&expectations);
+    block_on(async {
+        let mut nunchuk = wii_ext::async_impl::nunchuk::NunchukBuilder::new()
+            .combined_reads(true)
+            .build(i2c.clone(), NoopDelay::new())
+            .await
+            .unwrap();
+        assert!(nunchuk.read().await.unwrap().button_c);
+    });
+    i2c.done();
+}
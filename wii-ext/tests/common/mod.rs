@@ -1 +1,5 @@
+// The captured reports are shipped with the library for downstream tests
+#[allow(dead_code)]
+#[path = "../../src/test_data.rs"]
 pub mod test_data;
+pub mod transactions;
@@ -0,0 +1,81 @@
+#![allow(dead_code)]
+
+use embedded_hal::i2c::ErrorKind;
+use embedded_hal_mock::eh1::i2c::Transaction;
+use wii_ext::core::EXT_I2C_ADDR;
+
+// Helpers for building the i2c transactions the drivers are expected to perform
+
+/// Transactions performed by new(): init and a calibration read
+pub fn init(calibration: &[u8]) -> Vec<Transaction> {
+    let mut transactions = init_no_calibration();
+    // Calibration read
+    transactions.extend(read(calibration));
+    transactions
+}
+
+/// Transactions performed by the init handshake alone
+pub fn init_no_calibration() -> Vec<Transaction> {
+    vec![
+        // Reset controller
+        Transaction::write(EXT_I2C_ADDR as u8, vec![0]),
+        // Init
+        Transaction::write(EXT_I2C_ADDR as u8, vec![240, 85]),
+        Transaction::write(EXT_I2C_ADDR as u8, vec![251, 0]),
+    ]
+}
+
+/// Transactions performed by the init handshake with `verify_writes()` on
+pub fn init_verified() -> Vec<Transaction> {
+    let mut transactions = vec![Transaction::write(EXT_I2C_ADDR as u8, vec![0])];
+    transactions.extend(verified_write(0xF0, 0x55, 0x55));
+    transactions.extend(verified_write(0xFB, 0x00, 0x00));
+    transactions
+}
+
+/// Transactions performed by writing `value` to `addr` and reading back `read_back`
+pub fn verified_write(addr: u8, value: u8, read_back: u8) -> Vec<Transaction> {
+    vec![
+        Transaction::write(EXT_I2C_ADDR as u8, vec![addr, value]),
+        Transaction::write(EXT_I2C_ADDR as u8, vec![addr]),
+        Transaction::read(EXT_I2C_ADDR as u8, vec![read_back, 0, 0, 0, 0, 0]),
+    ]
+}
+
+/// Transactions performed when reading the controller ID
+pub fn read_id(id: &[u8]) -> Vec<Transaction> {
+    vec![
+        Transaction::write(EXT_I2C_ADDR as u8, vec![0xfa]),
+        Transaction::read(EXT_I2C_ADDR as u8, id.to_vec()),
+    ]
+}
+
+/// Transactions performed by a single report read
+pub fn read(report: &[u8]) -> Vec<Transaction> {
+    vec![
+        Transaction::write(EXT_I2C_ADDR as u8, vec![0]),
+        Transaction::read(EXT_I2C_ADDR as u8, report.to_vec()),
+    ]
+}
+
+/// A report read that fails on the bus after seeking
+pub fn failed_read(len: usize) -> Vec<Transaction> {
+    failed_read_with(len, ErrorKind::Other)
+}
+
+/// A report read that fails on the bus with `error` after seeking
+pub fn failed_read_with(len: usize, error: ErrorKind) -> Vec<Transaction> {
+    vec![
+        Transaction::write(EXT_I2C_ADDR as u8, vec![0]),
+        Transaction::read(EXT_I2C_ADDR as u8, vec![0; len]).with_error(error),
+    ]
+}
+
+/// Transactions performed when saving the power-on report format before first changing it
+pub fn save_report_format(value: u8) -> Vec<Transaction> {
+    vec![
+        Transaction::write(EXT_I2C_ADDR as u8, vec![254]),
+        Transaction::read(EXT_I2C_ADDR as u8, vec![value]),
+        Transaction::write(EXT_I2C_ADDR as u8, vec![0]),
+    ]
+}
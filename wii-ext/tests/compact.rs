@@ -0,0 +1,149 @@
+mod common;
+
+use common::test_data;
+use wii_ext::core::classic::{self, ClassicReading, ClassicReadingCalibrated};
+use wii_ext::core::events::{Button, Inputs};
+use wii_ext::core::nunchuk::{self, NunchukReading, NunchukReadingCalibrated};
+use wii_ext::core::wire::{CompactClassic, CompactNunchuk};
+
+const CLASSIC_REPORTS: [[u8; 6]; 24] = [
+    test_data::CLASSIC_IDLE,
+    test_data::CLASSIC_BTN_B,
+    test_data::CLASSIC_BTN_A,
+    test_data::CLASSIC_BTN_X,
+    test_data::CLASSIC_BTN_Y,
+    test_data::CLASSIC_BTN_L,
+    test_data::CLASSIC_BTN_R,
+    test_data::CLASSIC_BTN_ZL,
+    test_data::CLASSIC_BTN_ZR,
+    test_data::CLASSIC_PAD_U,
+    test_data::CLASSIC_PAD_D,
+    test_data::CLASSIC_PAD_L,
+    test_data::CLASSIC_PAD_R,
+    test_data::CLASSIC_BTN_MINUS,
+    test_data::CLASSIC_BTN_PLUS,
+    test_data::CLASSIC_BTN_HOME,
+    test_data::CLASSIC_LJOY_U,
+    test_data::CLASSIC_LJOY_D,
+    test_data::CLASSIC_LJOY_L,
+    test_data::CLASSIC_RJOY_R,
+    test_data::CLASSIC_LTRIG,
+    test_data::CLASSIC_RTRIG,
+    test_data::CLASSIC_LTRIG_W_BUTTON,
+    test_data::CLASSIC_RJOY_DL,
+];
+
+const NUNCHUK_REPORTS: [[u8; 6]; 7] = [
+    test_data::NUNCHUCK_IDLE,
+    test_data::NUNCHUCK_JOY_U,
+    test_data::NUNCHUCK_JOY_D,
+    test_data::NUNCHUCK_JOY_L,
+    test_data::NUNCHUCK_JOY_R,
+    test_data::NUNCHUCK_BTN_C,
+    test_data::NUNCHUCK_BTN_Z,
+];
+
+fn classic_readings() -> impl Iterator<Item = ClassicReadingCalibrated> {
+    let idle = ClassicReading::from_data(&test_data::CLASSIC_IDLE).unwrap();
+    let calibration = classic::CalibrationData {
+        joystick_left_x: idle.joystick_left_x,
+        joystick_left_y: idle.joystick_left_y,
+        joystick_right_x: idle.joystick_right_x,
+        joystick_right_y: idle.joystick_right_y,
+        trigger_left: idle.trigger_left,
+        trigger_right: idle.trigger_right,
+    };
+    CLASSIC_REPORTS.iter().map(move |report| {
+        ClassicReadingCalibrated::new(ClassicReading::from_data(report).unwrap(), &calibration)
+    })
+}
+
+fn nunchuk_readings() -> impl Iterator<Item = NunchukReadingCalibrated> {
+    let idle = NunchukReading::from_data(&test_data::NUNCHUCK_IDLE).unwrap();
+    let calibration = nunchuk::CalibrationData {
+        joystick_x: idle.joystick_x,
+        joystick_y: idle.joystick_y,
+    };
+    NUNCHUK_REPORTS.iter().map(move |report| {
+        NunchukReadingCalibrated::new(NunchukReading::from_data(report).unwrap(), &calibration)
+    })
+}
+
+#[test]
+fn sizes() {
+    assert_eq!(CompactClassic::SIZE, 8);
+    assert_eq!(CompactNunchuk::SIZE, 8);
+    assert_eq!(
+        core::mem::size_of::<[CompactClassic; 4]>(),
+        4 * CompactClassic::SIZE
+    );
+}
+
+#[test]
+fn classic_test_vectors_round_trip() {
+    for reading in classic_readings() {
+        let compact = reading.to_compact();
+        assert_eq!(ClassicReadingCalibrated::from_compact(&compact), reading);
+    }
+}
+
+#[test]
+fn nunchuk_test_vectors_round_trip() {
+    for reading in nunchuk_readings() {
+        let compact = reading.to_compact();
+        assert_eq!(NunchukReadingCalibrated::from_compact(&compact), reading);
+    }
+}
+
+#[test]
+fn bit_order_matches_wire_format() {
+    for reading in classic_readings() {
+        let wire = reading.to_wire();
+        let compact = reading.to_compact();
+        assert_eq!(compact.buttons.to_le_bytes(), [wire[1], wire[2]]);
+        assert_eq!(compact.axes.map(|axis| axis as u8), wire[3..]);
+        for button in ClassicReadingCalibrated::BUTTONS {
+            assert_eq!(
+                compact.buttons & 1 << *button as u16 != 0,
+                reading.button(*button)
+            );
+        }
+    }
+    for reading in nunchuk_readings() {
+        let wire = reading.to_wire();
+        let compact = reading.to_compact();
+        assert_eq!(compact.buttons, wire[1]);
+        assert_eq!(compact.accel.to_le_bytes(), wire[4..]);
+    }
+}
+
+#[test]
+fn classic_layout() {
+    let reading = ClassicReadingCalibrated::default()
+        .with_joystick_left_x(-1)
+        .with_trigger_right(30)
+        .with_button_a(true)
+        .with_dpad_right(true);
+    assert_eq!(
+        reading.to_compact(),
+        CompactClassic {
+            buttons: 1 << Button::A as u16 | 1 << Button::DpadRight as u16,
+            axes: [-1, 0, 0, 0, 0, 30],
+        }
+    );
+}
+
+#[test]
+fn nunchuk_accel_is_clamped_to_10_bits() {
+    let reading = NunchukReadingCalibrated::default()
+        .with_accel_x(0xFFFF)
+        .with_accel_z(2)
+        .with_button_z(true);
+    let compact = reading.to_compact();
+    assert_eq!(compact.buttons, 0b10);
+    assert_eq!(compact.accel, 0x3FF | 2 << 20);
+    assert_eq!(
+        NunchukReadingCalibrated::from_compact(&compact).accel_x,
+        0x3FF
+    );
+}
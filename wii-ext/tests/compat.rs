@@ -0,0 +1,73 @@
+// Only uses the old module paths, which are deprecated
+#![allow(deprecated)]
+
+use embassy_futures::block_on;
+use embedded_hal::i2c::ErrorKind;
+use embedded_hal_mock::eh1::{delay::NoopDelay, i2c};
+use wii_ext::classic::ClassicReadingCalibrated;
+use wii_ext::classic_async;
+use wii_ext::classic_sync::{Classic, ClassicError};
+use wii_ext::nunchuk::{Nunchuk, NunchukError, NunchukReadingCalibrated};
+mod common;
+use common::{test_data, transactions};
+
+fn read_classic(i2c: i2c::Mock) -> Result<ClassicReadingCalibrated, ClassicError<ErrorKind>> {
+    let mut delay = NoopDelay::new();
+    let mut classic = Classic::new(i2c, &mut delay)?;
+    Ok(classic.read()?)
+}
+
+fn read_nunchuk(i2c: i2c::Mock) -> Result<NunchukReadingCalibrated, NunchukError<ErrorKind>> {
+    let mut delay = NoopDelay::new();
+    let mut nunchuk = Nunchuk::new(i2c, &mut delay)?;
+    Ok(nunchuk.read()?)
+}
+
+#[test]
+fn classic_sync_path() {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.extend(transactions::read(&test_data::CLASSIC_BTN_A));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let input = read_classic(i2c.clone()).unwrap();
+    assert!(input.button_a);
+    i2c.done();
+}
+
+#[test]
+fn nunchuk_path() {
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_BTN_C));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let input = read_nunchuk(i2c.clone()).unwrap();
+    assert!(input.button_c);
+    i2c.done();
+}
+
+#[test]
+fn classic_async_new_does_not_init() {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.extend(transactions::read(&test_data::CLASSIC_BTN_B));
+    let mut i2c = i2c::Mock::new(&expectations);
+    // Nothing is sent until init()
+    let mut classic = classic_async::Classic::new(i2c.clone(), NoopDelay::new());
+    block_on(async {
+        classic.init().await.unwrap();
+        let input = classic.read().await.unwrap();
+        assert!(input.button_b);
+    });
+    let _classic: wii_ext::async_impl::classic::Classic<_, _> = classic.into_inner();
+    i2c.done();
+}
+
+#[test]
+fn legacy_errors_keep_bus_error() {
+    let expectations = [transactions::init_no_calibration()[0]
+        .clone()
+        .with_error(ErrorKind::Other)];
+    let mut i2c = i2c::Mock::new(&expectations);
+    assert!(matches!(
+        read_classic(i2c.clone()),
+        Err(ClassicError::Error(ErrorKind::Other))
+    ));
+    i2c.done();
+}
@@ -0,0 +1,96 @@
+#![cfg(feature = "eh0")]
+use embedded_hal_mock::eh0::{
+    delay::NoopDelay,
+    i2c::{Mock, Transaction},
+    MockError,
+};
+use std::io::ErrorKind;
+use wii_ext::blocking_impl::classic::{Classic, ClassicBuilder};
+use wii_ext::blocking_impl::nunchuk::Nunchuk;
+use wii_ext::core::EXT_I2C_ADDR;
+use wii_ext::eh0::{Eh0Delay, Eh0Error, Eh0I2c};
+use wii_ext::Error;
+mod common;
+use common::test_data;
+
+// The embedded-hal 0.2 mock has its own transaction type, so these mirror common::transactions
+
+fn init(calibration: &[u8]) -> Vec<Transaction> {
+    let mut transactions = init_no_calibration();
+    transactions.extend(read(calibration));
+    transactions
+}
+
+fn init_no_calibration() -> Vec<Transaction> {
+    vec![
+        Transaction::write(EXT_I2C_ADDR as u8, vec![0]),
+        Transaction::write(EXT_I2C_ADDR as u8, vec![240, 85]),
+        Transaction::write(EXT_I2C_ADDR as u8, vec![251, 0]),
+    ]
+}
+
+fn combined_read(report: &[u8]) -> Transaction {
+    Transaction::write_read(EXT_I2C_ADDR as u8, vec![0], report.to_vec())
+}
+
+fn read(report: &[u8]) -> Vec<Transaction> {
+    vec![
+        Transaction::write(EXT_I2C_ADDR as u8, vec![0]),
+        Transaction::read(EXT_I2C_ADDR as u8, report.to_vec()),
+    ]
+}
+
+#[test]
+fn classic_from_eh0_bus() {
+    let mut expectations = init(&test_data::CLASSIC_IDLE);
+    expectations.extend(read(&test_data::CLASSIC_BTN_B));
+    let i2c = Mock::new(&expectations);
+    let mut classic = Classic::new(Eh0I2c::new(i2c), Eh0Delay::new(NoopDelay::new())).unwrap();
+    let input = classic.read().unwrap();
+    assert!(input.button_b);
+    assert!(!input.button_a);
+    let (i2c, _delay) = classic.destroy();
+    i2c.into_inner().done();
+}
+
+#[test]
+fn nunchuk_from_eh0_bus() {
+    let mut expectations = init(&test_data::NUNCHUCK_IDLE);
+    expectations.extend(read(&test_data::NUNCHUCK_JOY_R));
+    let i2c = Mock::new(&expectations);
+    let mut nunchuk = Nunchuk::new(Eh0I2c::new(i2c), Eh0Delay::new(NoopDelay::new())).unwrap();
+    let input = nunchuk.read().unwrap();
+    assert!(input.joystick_x > 90);
+    let (i2c, _delay) = nunchuk.destroy();
+    i2c.into_inner().done();
+}
+
+#[test]
+fn combined_reads_use_write_read() {
+    let mut expectations = init_no_calibration();
+    expectations.push(combined_read(&test_data::CLASSIC_IDLE));
+    expectations.push(combined_read(&test_data::CLASSIC_BTN_X));
+    let i2c = Mock::new(&expectations);
+    let mut classic = ClassicBuilder::new()
+        .combined_reads(true)
+        .build(Eh0I2c::new(i2c), Eh0Delay::new(NoopDelay::new()))
+        .unwrap();
+    assert!(classic.read().unwrap().button_x);
+    let (i2c, _delay) = classic.destroy();
+    i2c.into_inner().done();
+}
+
+#[test]
+fn eh0_bus_errors_are_kept() {
+    let expectations =
+        [Transaction::write(EXT_I2C_ADDR as u8, vec![0])
+            .with_error(MockError::Io(ErrorKind::Other))];
+    let i2c = Mock::new(&expectations);
+    let mut check = i2c.clone();
+    let result = Nunchuk::new(Eh0I2c::new(i2c), Eh0Delay::new(NoopDelay::new()));
+    assert!(matches!(
+        result,
+        Err(Error::I2C(Eh0Error(MockError::Io(ErrorKind::Other))))
+    ));
+    check.done();
+}
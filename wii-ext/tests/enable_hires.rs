@@ -0,0 +1,90 @@
+use embassy_futures::block_on;
+use embedded_hal_mock::eh1::{
+    delay::NoopDelay,
+    i2c::{self, Transaction},
+};
+use wii_ext::blocking_impl::classic::Classic;
+use wii_ext::core::classic::{CalibrationData, ClassicReading, ModeChangeOptions};
+use wii_ext::core::EXT_I2C_ADDR;
+mod common;
+use common::{test_data, transactions};
+
+fn hires_write() -> Transaction {
+    Transaction::write(EXT_I2C_ADDR as u8, vec![254, 3])
+}
+
+#[test]
+fn second_enable_hires_does_nothing() {
+    let mut expectations = transactions::init(&test_data::PRO_IDLE);
+    expectations.extend(transactions::save_report_format(1));
+    expectations.push(hires_write());
+    expectations.extend(transactions::read(&test_data::PRO_HD_IDLE));
+    // No 0xFE write or calibration read for the second call
+    expectations.extend(transactions::read(&test_data::PRO_HD_LJOY_U));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).unwrap();
+    classic.enable_hires().unwrap();
+    classic.enable_hires().unwrap();
+    assert!(classic.read().unwrap().joystick_left_y > 90);
+    i2c.done();
+}
+
+#[test]
+fn forced_enable_hires_writes_again() {
+    let mut expectations = transactions::init(&test_data::PRO_IDLE);
+    expectations.extend(transactions::save_report_format(1));
+    expectations.push(hires_write());
+    expectations.extend(transactions::read(&test_data::PRO_HD_IDLE));
+    expectations.push(hires_write());
+    expectations.extend(transactions::read(&test_data::PRO_HD_IDLE));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).unwrap();
+    classic.enable_hires().unwrap();
+    classic
+        .enable_hires_with(ModeChangeOptions::DEFAULT.with_force(true))
+        .unwrap();
+    i2c.done();
+}
+
+#[test]
+fn enable_hires_without_recalibration() {
+    let mut expectations = transactions::init(&test_data::PRO_IDLE);
+    expectations.extend(transactions::save_report_format(1));
+    // No calibration read after the mode change
+    expectations.push(hires_write());
+    expectations.extend(transactions::read(&test_data::PRO_HD_LJOY_U));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).unwrap();
+    classic
+        .enable_hires_with(ModeChangeOptions::DEFAULT.with_recalibrate(false))
+        .unwrap();
+    // A calibration saved from an earlier session
+    let rest = ClassicReading::from_data(&test_data::PRO_HD_IDLE).unwrap();
+    classic.set_calibration(CalibrationData {
+        joystick_left_x: rest.joystick_left_x,
+        joystick_left_y: rest.joystick_left_y,
+        joystick_right_x: rest.joystick_right_x,
+        joystick_right_y: rest.joystick_right_y,
+        trigger_left: rest.trigger_left,
+        trigger_right: rest.trigger_right,
+    });
+    assert!(classic.read().unwrap().joystick_left_y > 90);
+    i2c.done();
+}
+
+#[test]
+fn async_second_enable_hires_does_nothing() {
+    let mut expectations = transactions::init(&test_data::PRO_IDLE);
+    expectations.extend(transactions::save_report_format(1));
+    expectations.push(hires_write());
+    expectations.extend(transactions::read(&test_data::PRO_HD_IDLE));
+    let mut i2c = i2c::Mock::new(&expectations);
+    block_on(async {
+        let mut classic = wii_ext::async_impl::classic::Classic::new(i2c.clone(), NoopDelay::new())
+            .await
+            .unwrap();
+        classic.enable_hires().await.unwrap();
+        classic.enable_hires().await.unwrap();
+    });
+    i2c.done();
+}
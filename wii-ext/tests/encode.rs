@@ -0,0 +1,51 @@
+use paste::paste;
+use wii_ext::core::classic::ClassicReading;
+use wii_ext::core::nunchuk::NunchukReading;
+mod common;
+use common::test_data;
+
+macro_rules! assert_round_trip {
+    ($($name:ident),* $(,)?) => {
+        $(paste! {
+            #[test]
+            fn [<$name:lower _round_trips>]() {
+                let report = test_data::$name;
+                let reading = ClassicReading::from_data(&report).unwrap();
+                let encoded = if report.len() == 6 {
+                    reading.to_report().to_vec()
+                } else {
+                    reading.to_hd_report().to_vec()
+                };
+                assert_eq!(encoded, report);
+            }
+        })*
+    };
+}
+
+assert_round_trip!(
+    CLASSIC_IDLE,
+    CLASSIC_BTN_A,
+    CLASSIC_BTN_ZR,
+    CLASSIC_PAD_L,
+    CLASSIC_BTN_HOME,
+    CLASSIC_LJOY_UR,
+    CLASSIC_RJOY_DL,
+    CLASSIC_LTRIG_W_BUTTON,
+    CLASSIC_RTRIG,
+    CLASSIC_HD_IDLE,
+    CLASSIC_HD_LJOY_U,
+    CLASSIC_HD_RJOY_D,
+);
+
+#[test]
+fn nunchuk_round_trips() {
+    for report in [
+        test_data::NUNCHUCK_IDLE,
+        test_data::NUNCHUCK_JOY_U,
+        test_data::NUNCHUCK_BTN_C,
+        test_data::NUNCHUCK_BTN_Z,
+    ] {
+        let reading = NunchukReading::from_data(&report).unwrap();
+        assert_eq!(reading.to_report(), report);
+    }
+}
@@ -0,0 +1,193 @@
+use embassy_futures::block_on;
+use embedded_hal::i2c::ErrorKind;
+use embedded_hal_mock::eh1::{
+    delay::NoopDelay,
+    i2c::{self, Transaction},
+};
+use wii_ext::core::EXT_I2C_ADDR;
+use wii_ext::Error;
+mod common;
+use common::{test_data, transactions};
+
+/// Both drivers return the same error type, so one function can use `?` on either
+fn read_both(i2c: i2c::Mock) -> Result<(bool, bool), Error<ErrorKind>> {
+    let mut classic = wii_ext::blocking_impl::classic::Classic::new(i2c.clone(), NoopDelay::new())?;
+    let a = classic.read()?.button_a;
+    let (i2c, _) = classic.destroy();
+    let mut nunchuk = wii_ext::blocking_impl::nunchuk::Nunchuk::new(i2c, NoopDelay::new())?;
+    let c = nunchuk.read()?.button_c;
+    Ok((a, c))
+}
+
+async fn read_both_async(i2c: i2c::Mock) -> Result<(bool, bool), Error<ErrorKind>> {
+    let mut classic =
+        wii_ext::async_impl::classic::Classic::new(i2c.clone(), NoopDelay::new()).await?;
+    let a = classic.read().await?.button_a;
+    let (i2c, _) = classic.destroy();
+    let mut nunchuk = wii_ext::async_impl::nunchuk::Nunchuk::new(i2c, NoopDelay::new()).await?;
+    let c = nunchuk.read().await?.button_c;
+    Ok((a, c))
+}
+
+fn expectations() -> Vec<Transaction> {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.extend(transactions::read(&test_data::CLASSIC_BTN_A));
+    expectations.extend(transactions::init(&test_data::NUNCHUCK_IDLE));
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_BTN_C));
+    expectations
+}
+
+#[test]
+fn blocking_drivers_share_error_type() {
+    let mut i2c = i2c::Mock::new(&expectations());
+    assert!(matches!(read_both(i2c.clone()), Ok((true, true))));
+    i2c.done();
+}
+
+#[test]
+fn async_drivers_share_error_type() {
+    let mut i2c = i2c::Mock::new(&expectations());
+    assert!(matches!(
+        block_on(read_both_async(i2c.clone())),
+        Ok((true, true))
+    ));
+    i2c.done();
+}
+
+#[test]
+fn async_errors_carry_bus_error() {
+    let expectations =
+        [Transaction::write(EXT_I2C_ADDR as u8, vec![0]).with_error(ErrorKind::Other)];
+    let mut i2c = i2c::Mock::new(&expectations);
+    let result = block_on(read_both_async(i2c.clone()));
+    assert!(matches!(result, Err(Error::I2C(ErrorKind::Other))));
+    i2c.done();
+}
+
+#[test]
+#[allow(deprecated)]
+fn errors_convert_to_legacy_async_error() {
+    use wii_ext::async_impl::interface::AsyncImplError;
+    let legacy: AsyncImplError = Error::I2C(ErrorKind::Other).into();
+    assert!(matches!(legacy, AsyncImplError::I2C));
+    let legacy: AsyncImplError = Error::<ErrorKind>::Timeout.into();
+    assert!(matches!(legacy, AsyncImplError::Timeout));
+}
+
+/// A bus error that implements `core::error::Error`, unlike the mock's `ErrorKind`
+#[derive(Debug)]
+struct BusError(ErrorKind);
+
+impl core::fmt::Display for BusError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "bus error: {}", self.0)
+    }
+}
+
+impl core::error::Error for BusError {}
+
+impl embedded_hal::i2c::Error for BusError {
+    fn kind(&self) -> ErrorKind {
+        self.0
+    }
+}
+
+/// Wraps the mock so that its errors are `BusError`s
+struct WrappedMock(i2c::Mock);
+
+impl embedded_hal::i2c::ErrorType for WrappedMock {
+    type Error = BusError;
+}
+
+impl embedded_hal::i2c::I2c for WrappedMock {
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [embedded_hal::i2c::Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        self.0.transaction(address, operations).map_err(BusError)
+    }
+
+    fn write(&mut self, address: u8, write: &[u8]) -> Result<(), Self::Error> {
+        self.0.write(address, write).map_err(BusError)
+    }
+
+    fn read(&mut self, address: u8, read: &mut [u8]) -> Result<(), Self::Error> {
+        self.0.read(address, read).map_err(BusError)
+    }
+}
+
+#[test]
+fn error_source_is_bus_error() {
+    let expectations =
+        [Transaction::write(EXT_I2C_ADDR as u8, vec![0]).with_error(ErrorKind::Other)];
+    let mut i2c = i2c::Mock::new(&expectations);
+    let result =
+        wii_ext::blocking_impl::nunchuk::Nunchuk::new(WrappedMock(i2c.clone()), NoopDelay::new());
+    let Err(error) = result else {
+        panic!("init should fail");
+    };
+    let error: &dyn core::error::Error = &error;
+    assert_eq!(error.to_string(), "i2c bus error");
+    let source = error.source().expect("bus error should be the source");
+    assert_eq!(source.to_string(), BusError(ErrorKind::Other).to_string());
+    assert!(source.source().is_none());
+    i2c.done();
+}
+
+#[test]
+fn errors_without_a_bus_error_have_no_source() {
+    let error = Error::<BusError>::InvalidInputData;
+    assert!(core::error::Error::source(&error).is_none());
+}
+
+#[test]
+fn error_variants_format() {
+    use wii_ext::core::ControllerType;
+    let messages = [
+        Error::I2C(ErrorKind::Other).to_string(),
+        Error::<ErrorKind>::InvalidInputData.to_string(),
+        Error::<ErrorKind>::ReadNotStarted.to_string(),
+        Error::<ErrorKind>::Timeout.to_string(),
+        Error::<ErrorKind>::Stuck.to_string(),
+        Error::<ErrorKind>::UnexpectedController(Some(ControllerType::Nunchuk)).to_string(),
+        Error::<ErrorKind>::ControllerChanged {
+            previous: Some(ControllerType::Classic),
+            current: None,
+        }
+        .to_string(),
+        Error::RetriesExhausted(ErrorKind::Other).to_string(),
+        Error::<ErrorKind>::InconsistentData.to_string(),
+    ];
+    assert_eq!(messages[0], "i2c bus error");
+    assert_eq!(messages[7], "i2c bus error, retries exhausted");
+    assert_eq!(messages[5], "unexpected controller type: Some(Nunchuk)");
+    assert_eq!(messages[6], "controller changed from Some(Classic) to None");
+    for (i, message) in messages.iter().enumerate() {
+        assert!(!message.is_empty());
+        assert!(!messages[..i].contains(message), "{message} repeated");
+    }
+}
+
+#[test]
+#[allow(deprecated)]
+fn legacy_errors_format() {
+    use wii_ext::async_impl::interface::AsyncImplError;
+    use wii_ext::blocking_impl::classic::ClassicError;
+    use wii_ext::blocking_impl::nunchuk::NunchukError;
+    assert_eq!(AsyncImplError::I2C.to_string(), "i2c bus error");
+    assert_eq!(
+        AsyncImplError::Timeout.to_string(),
+        Error::<()>::Timeout.to_string()
+    );
+    assert_eq!(
+        ClassicError::<ErrorKind>::ParseError.to_string(),
+        "could not parse report"
+    );
+
+    let error = NunchukError::Error(BusError(ErrorKind::Other));
+    assert_eq!(error.to_string(), "i2c bus error");
+    assert!(core::error::Error::source(&error).is_some());
+    let error = ClassicError::Error(BusError(ErrorKind::Other));
+    assert!(core::error::Error::source(&error).is_some());
+}
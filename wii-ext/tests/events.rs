@@ -0,0 +1,220 @@
+use wii_ext::core::classic::ClassicReadingCalibrated;
+use wii_ext::core::events::{
+    Axis, Button, Event, EventQueue, EventTracker, InputEvent, InputEvents, Inputs, OverflowPolicy,
+};
+use wii_ext::core::nunchuk::NunchukReadingCalibrated;
+
+fn pressed(button: Button) -> InputEvent {
+    InputEvent::ButtonPressed(button)
+}
+
+fn released(button: Button) -> InputEvent {
+    InputEvent::ButtonReleased(button)
+}
+
+/// A reading with A, B, X and Y held
+fn face_buttons() -> ClassicReadingCalibrated {
+    ClassicReadingCalibrated::default()
+        .with_button_a(true)
+        .with_button_b(true)
+        .with_button_x(true)
+        .with_button_y(true)
+}
+
+#[test]
+fn button_edges_are_queued_in_order() {
+    let mut queue: EventQueue<8> = EventQueue::new(OverflowPolicy::DropNewest, 0);
+    let mut reading = ClassicReadingCalibrated::default();
+    queue.update(&reading);
+    assert!(queue.is_empty());
+
+    reading.button_a = true;
+    reading.dpad_left = true;
+    queue.update(&reading);
+    // Holding buttons doesn't repeat events
+    queue.update(&reading);
+    reading.button_a = false;
+    queue.update(&reading);
+    assert_eq!(
+        queue.drain().collect::<Vec<_>>(),
+        [
+            pressed(Button::A),
+            pressed(Button::DpadLeft),
+            released(Button::A)
+        ]
+    );
+    assert!(queue.is_empty());
+}
+
+#[test]
+fn axis_events_respect_threshold() {
+    let mut queue: EventQueue<8> = EventQueue::new(OverflowPolicy::DropNewest, 4);
+    let mut reading = ClassicReadingCalibrated::default();
+    // Jitter within the threshold is ignored
+    for x in [3, -4, 4] {
+        reading.joystick_left_x = x;
+        queue.update(&reading);
+    }
+    assert!(queue.is_empty());
+    reading.joystick_left_x = 5;
+    queue.update(&reading);
+    // Movement is measured from the last reported position, not the last reading
+    reading.joystick_left_x = 8;
+    queue.update(&reading);
+    reading.joystick_left_x = 10;
+    queue.update(&reading);
+    reading.joystick_left_x = i8::MIN;
+    queue.update(&reading);
+    let moved = |value| InputEvent::AxisMoved {
+        axis: Axis::LeftX,
+        value,
+    };
+    assert_eq!(
+        queue.drain().collect::<Vec<_>>(),
+        [moved(5), moved(10), moved(i8::MIN)]
+    );
+}
+
+#[test]
+fn drop_newest_keeps_first_events() {
+    let mut queue: EventQueue<2> = EventQueue::new(OverflowPolicy::DropNewest, 0);
+    queue.update(&face_buttons());
+    assert_eq!(queue.len(), 2);
+    assert_eq!(queue.overflow_count(), 2);
+    assert_eq!(
+        queue.drain().collect::<Vec<_>>(),
+        [pressed(Button::A), pressed(Button::B)]
+    );
+    // The state is still tracked, even though the events were dropped
+    queue.update(&ClassicReadingCalibrated::default());
+    assert_eq!(queue.pop(), Some(released(Button::A)));
+}
+
+#[test]
+fn drop_oldest_keeps_last_events() {
+    let mut queue: EventQueue<2> = EventQueue::new(OverflowPolicy::DropOldest, 0);
+    queue.update(&face_buttons());
+    assert_eq!(queue.overflow_count(), 2);
+    assert_eq!(
+        queue.drain().collect::<Vec<_>>(),
+        [pressed(Button::X), pressed(Button::Y)]
+    );
+    queue.clear_overflow_count();
+    assert_eq!(queue.overflow_count(), 0);
+}
+
+#[test]
+fn nunchuk_events() {
+    let mut queue: EventQueue<4> = EventQueue::new(OverflowPolicy::DropOldest, 10);
+    let reading = NunchukReadingCalibrated::default()
+        .with_joystick_y(-100)
+        .with_accel_x(900)
+        .with_button_z(true);
+    queue.update(&reading);
+    assert_eq!(
+        queue.drain().collect::<Vec<_>>(),
+        [
+            pressed(Button::Z),
+            InputEvent::AxisMoved {
+                axis: Axis::LeftY,
+                value: -100
+            }
+        ]
+    );
+}
+
+#[test]
+fn inputs_match_fields() {
+    let reading = face_buttons();
+    for button in Button::ALL {
+        let expected = matches!(button, Button::A | Button::B | Button::X | Button::Y);
+        assert_eq!(reading.button(button), expected, "{button:?}");
+    }
+    assert!(!NunchukReadingCalibrated::default().button(Button::A));
+    assert_eq!(
+        NunchukReadingCalibrated::default()
+            .with_joystick_x(7)
+            .axis(Axis::LeftX),
+        7
+    );
+}
+
+#[test]
+fn tracker_produces_scripted_event_sequence() {
+    let mut tracker = EventTracker::default();
+    let idle = ClassicReadingCalibrated::default();
+    let holding_a = idle.with_button_a(true);
+    let holding_a_stick_left = holding_a.with_joystick_left_x(-60);
+    let script = [
+        Some(idle),
+        Some(holding_a),
+        Some(holding_a),
+        Some(holding_a_stick_left),
+        None,
+        None,
+        Some(holding_a),
+        Some(idle),
+    ];
+    let events: Vec<Event> = script
+        .into_iter()
+        .flat_map(|reading| tracker.update(reading))
+        .collect();
+    assert_eq!(
+        events,
+        [
+            Event::Connected,
+            Event::ButtonPressed(Button::A),
+            Event::AxisChanged(Axis::LeftX, -60),
+            Event::Disconnected,
+            // Reconnecting reports what is held, compared with a default reading
+            Event::Connected,
+            Event::ButtonPressed(Button::A),
+            Event::ButtonReleased(Button::A),
+        ]
+    );
+    assert_eq!(tracker.last(), Some(&idle));
+}
+
+#[test]
+fn input_events_between_two_readings() {
+    let previous = ClassicReadingCalibrated::default()
+        .with_button_zl(true)
+        .with_trigger_left(20);
+    let current = ClassicReadingCalibrated::default()
+        .with_button_home(true)
+        .with_joystick_right_y(3);
+    let events: Vec<Event> = InputEvents::new(Some(previous), Some(current)).collect();
+    assert_eq!(
+        events,
+        [
+            Event::ButtonReleased(Button::ZL),
+            Event::ButtonPressed(Button::Home),
+            Event::AxisChanged(Axis::RightY, 3),
+            Event::AxisChanged(Axis::TriggerLeft, 0),
+        ]
+    );
+    assert_eq!(
+        InputEvents::<ClassicReadingCalibrated>::new(None, None).count(),
+        0
+    );
+}
+
+#[test]
+fn nunchuk_tracker_uses_nunchuk_inputs() {
+    let mut tracker = EventTracker::default();
+    let reading = NunchukReadingCalibrated::default()
+        .with_joystick_x(90)
+        .with_accel_z(700)
+        .with_button_c(true);
+    let events: Vec<Event> = tracker.update(Some(reading)).collect();
+    assert_eq!(
+        events,
+        [
+            Event::Connected,
+            Event::ButtonPressed(Button::C),
+            Event::AxisChanged(Axis::LeftX, 90),
+        ]
+    );
+    let events: Vec<Event> = tracker.update(None).collect();
+    assert_eq!(events, [Event::Disconnected]);
+}
@@ -0,0 +1,115 @@
+#![cfg(feature = "test-support")]
+use embassy_futures::block_on;
+use embedded_hal_mock::eh1::delay::NoopDelay;
+use wii_ext::blocking_impl::classic::{Classic, ClassicBuilder};
+use wii_ext::blocking_impl::nunchuk::Nunchuk;
+use wii_ext::core::classic::{ClassicReading, ReportMode};
+use wii_ext::core::events::Button;
+use wii_ext::testing::FakeExtension;
+
+#[test]
+fn classic_driver_reads_fake_buttons() {
+    let fake = FakeExtension::classic();
+    let mut classic = Classic::new(fake.clone(), NoopDelay::new()).unwrap();
+    assert!(fake.is_initialised());
+    assert_eq!(classic.read().unwrap(), Default::default());
+    fake.press(Button::A);
+    fake.press(Button::DpadUp);
+    let input = classic.read().unwrap();
+    assert!(input.button_a && input.dpad_up);
+    assert!(!input.button_b);
+    fake.release(Button::A);
+    assert!(!classic.read().unwrap().button_a);
+}
+
+#[test]
+fn classic_driver_reads_fake_sticks() {
+    let fake = FakeExtension::classic_pro();
+    let mut classic = Classic::new(fake.clone(), NoopDelay::new()).unwrap();
+    fake.set_classic(fake.classic_reading().unwrap().with_joystick_left_x(0x10));
+    let input = classic.read().unwrap();
+    assert!(input.joystick_left_x < -100);
+    assert_eq!(input.joystick_right_x, 0);
+}
+
+#[test]
+fn hires_mode_switches_fake_report_format() {
+    let fake = FakeExtension::classic();
+    let mut classic = ClassicBuilder::new()
+        .hires(true)
+        .build(fake.clone(), NoopDelay::new())
+        .unwrap();
+    assert!(fake.is_hires());
+    fake.set_classic(fake.classic_reading().unwrap().with_joystick_right_y(0xFF));
+    // Full 8 bit precision
+    assert_eq!(classic.read().unwrap().joystick_right_y, 0x7F);
+    classic.shutdown().unwrap();
+    assert!(!fake.is_hires());
+}
+
+#[test]
+fn nunchuk_driver_reads_queued_readings() {
+    let fake = FakeExtension::nunchuk();
+    let mut nunchuk = Nunchuk::new(fake.clone(), NoopDelay::new()).unwrap();
+    let idle = fake.nunchuk_reading().unwrap();
+    fake.queue_nunchuk([idle.with_button_c(true), idle.with_joystick_y(0xF0)]);
+    assert!(nunchuk.read().unwrap().button_c);
+    let input = nunchuk.read().unwrap();
+    assert!(!input.button_c);
+    assert!(input.joystick_y > 100);
+    // The last queued reading stays in place
+    assert!(nunchuk.read().unwrap().joystick_y > 100);
+}
+
+#[test]
+fn replugged_fake_needs_init() {
+    let fake = FakeExtension::nunchuk();
+    let mut nunchuk = Nunchuk::new(fake.clone(), NoopDelay::new()).unwrap();
+    fake.set_connected(false);
+    assert!(nunchuk.read().is_err());
+    fake.set_connected(true);
+    assert!(!fake.is_initialised());
+    nunchuk.init().unwrap();
+    assert!(fake.is_initialised());
+    fake.press(Button::Z);
+    assert!(nunchuk.read().unwrap().button_z);
+}
+
+#[test]
+fn verify_id_checks_fake_type() {
+    let fake = FakeExtension::classic_pro();
+    let classic = ClassicBuilder::new()
+        .verify_id(true)
+        .build(fake.clone(), NoopDelay::new());
+    assert!(classic.is_ok());
+    let fake = FakeExtension::nunchuk();
+    let classic = ClassicBuilder::new()
+        .verify_id(true)
+        .build(fake, NoopDelay::new());
+    assert!(classic.is_err());
+}
+
+#[test]
+fn async_driver_reads_fake() {
+    use wii_ext::async_impl::classic::Classic;
+    let fake = FakeExtension::classic();
+    block_on(async {
+        let mut classic = Classic::new(fake.clone(), NoopDelay::new()).await.unwrap();
+        fake.press(Button::ZR);
+        assert!(classic.read().await.unwrap().button_zr);
+    });
+    assert!(fake.report_reads() >= 2);
+}
+
+#[test]
+fn alt_mode_with_fake_extension() {
+    let fake = FakeExtension::classic();
+    let mut classic = Classic::new(fake.clone(), NoopDelay::new()).unwrap();
+    classic.set_report_mode(ReportMode::Alt).unwrap();
+    assert_eq!(classic.report_mode().unwrap(), ReportMode::Alt);
+    fake.set_classic(ClassicReading::CENTERED.with_joystick_left_x(0xFF));
+    // Full 8 bit precision, as in hi-resolution mode
+    assert_eq!(classic.read().unwrap().joystick_left_x, 0x7F);
+    // The controller ID follows the report format
+    assert!(classic.identify_controller().unwrap().is_some());
+}
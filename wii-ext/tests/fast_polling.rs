@@ -0,0 +1,81 @@
+use embassy_futures::block_on;
+use embedded_hal_mock::eh1::{
+    delay::NoopDelay,
+    i2c::{self, Transaction},
+};
+use wii_ext::blocking_impl::classic::ClassicBuilder;
+use wii_ext::blocking_impl::nunchuk::NunchukBuilder;
+use wii_ext::core::EXT_I2C_ADDR;
+mod common;
+use common::{test_data, transactions};
+
+/// A report read without the seek write
+fn fast_read(report: &[u8]) -> Vec<Transaction> {
+    vec![Transaction::read(EXT_I2C_ADDR as u8, report.to_vec())]
+}
+
+#[test]
+fn nunchuk_fast_polling_skips_seek() {
+    // The calibration read seeks to 0, after that the cursor is known
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    expectations.extend(fast_read(&test_data::NUNCHUCK_BTN_C));
+    expectations.extend(fast_read(&test_data::NUNCHUCK_BTN_Z));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut nunchuk = NunchukBuilder::new()
+        .fast_polling(true)
+        .build(i2c.clone(), NoopDelay::new())
+        .unwrap();
+    assert!(nunchuk.read().unwrap().button_c);
+    assert!(nunchuk.read().unwrap().button_z);
+    i2c.done();
+}
+
+#[test]
+fn nunchuk_conservative_mode_always_seeks() {
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_BTN_C));
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_BTN_Z));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut nunchuk = NunchukBuilder::new()
+        .build(i2c.clone(), NoopDelay::new())
+        .unwrap();
+    assert!(nunchuk.read().unwrap().button_c);
+    assert!(nunchuk.read().unwrap().button_z);
+    i2c.done();
+}
+
+#[test]
+fn classic_fast_polling_seeks_after_id_read() {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.extend(fast_read(&test_data::CLASSIC_BTN_A));
+    // Reading the ID moves the cursor away from 0
+    expectations.extend(transactions::read_id(&test_data::CLASSIC_ID));
+    expectations.extend(transactions::read(&test_data::CLASSIC_BTN_B));
+    expectations.extend(fast_read(&test_data::CLASSIC_BTN_X));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = ClassicBuilder::new()
+        .fast_polling(true)
+        .build(i2c.clone(), NoopDelay::new())
+        .unwrap();
+    assert!(classic.read().unwrap().button_a);
+    classic.identify_controller().unwrap();
+    assert!(classic.read().unwrap().button_b);
+    assert!(classic.read().unwrap().button_x);
+    i2c.done();
+}
+
+#[test]
+fn async_classic_fast_polling_skips_seek() {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.extend(fast_read(&test_data::CLASSIC_BTN_A));
+    let mut i2c = i2c::Mock::new(&expectations);
+    block_on(async {
+        let mut classic = wii_ext::async_impl::classic::ClassicBuilder::new()
+            .fast_polling(true)
+            .build(i2c.clone(), NoopDelay::new())
+            .await
+            .unwrap();
+        assert!(classic.read().await.unwrap().button_a);
+    });
+    i2c.done();
+}
@@ -0,0 +1,72 @@
+use wii_ext::core::nunchuk::{
+    CalibrationData, FreeFallDetector, FreeFallEvent, NunchukReading, NunchukReadingCalibrated,
+};
+
+/// At rest, with gravity on Z
+const REST: (u16, u16, u16) = (512, 512, 712);
+/// Falling, with a little noise
+const FALLING: (u16, u16, u16) = (520, 505, 530);
+
+fn frame((accel_x, accel_y, accel_z): (u16, u16, u16)) -> NunchukReadingCalibrated {
+    let raw = NunchukReading::CENTERED
+        .with_accel_x(accel_x)
+        .with_accel_y(accel_y)
+        .with_accel_z(accel_z);
+    NunchukReadingCalibrated::new(raw, &CalibrationData::default())
+}
+
+/// Feed frames to a detector, collecting (frame index, event) pairs
+fn run(detector: &mut FreeFallDetector, frames: &[(u16, u16, u16)]) -> Vec<(usize, FreeFallEvent)> {
+    frames
+        .iter()
+        .enumerate()
+        .filter_map(|(i, f)| detector.update(&frame(*f)).map(|e| (i, e)))
+        .collect()
+}
+
+#[test]
+fn fall_starts_and_ends_once() {
+    let mut detector = FreeFallDetector::new(60, 3);
+    let mut frames = vec![REST; 3];
+    frames.extend([FALLING; 6]);
+    frames.extend([REST; 5]);
+    let events = run(&mut detector, &frames);
+    assert_eq!(
+        events,
+        [(5, FreeFallEvent::Started), (11, FreeFallEvent::Ended)]
+    );
+    assert!(!detector.falling());
+}
+
+#[test]
+fn brief_dip_does_not_trigger() {
+    let mut detector = FreeFallDetector::new(60, 3);
+    let frames = [REST, FALLING, FALLING, REST, FALLING, REST, REST];
+    assert!(run(&mut detector, &frames).is_empty());
+    assert!(!detector.falling());
+}
+
+#[test]
+fn glitch_during_fall_does_not_end_it() {
+    let mut detector = FreeFallDetector::default();
+    let frames = [
+        FALLING, FALLING, FALLING, REST, FALLING, FALLING, REST, REST,
+    ];
+    assert_eq!(run(&mut detector, &frames), [(2, FreeFallEvent::Started)]);
+    assert!(detector.falling());
+}
+
+#[test]
+fn threshold_is_a_magnitude() {
+    let mut detector = FreeFallDetector::new(60, 1);
+    // 59 counts spread over all three axes is still under the threshold
+    assert_eq!(
+        detector.update(&frame((546, 478, 546))),
+        Some(FreeFallEvent::Started)
+    );
+    // 60 counts on one axis is not
+    assert_eq!(
+        detector.update(&frame((512, 512, 572))),
+        Some(FreeFallEvent::Ended)
+    );
+}
@@ -0,0 +1,115 @@
+use wii_ext::core::classic::ClassicReadingCalibrated;
+use wii_ext::core::nunchuk::NunchukReadingCalibrated;
+use wii_ext::core::scale::{
+    axis_to_i16, trigger_to_u16, AxisExtents, ClassicAxesI16, ClassicExtents, NunchukExtents,
+};
+
+/// A stick that reaches further left than right, as measured on a real controller
+const LOPSIDED: AxisExtents = AxisExtents {
+    negative: 90,
+    positive: 100,
+};
+
+#[test]
+fn full_extents_map_i8_range_to_i16_range() {
+    assert_eq!(axis_to_i16(i8::MIN, AxisExtents::FULL), i16::MIN);
+    assert_eq!(axis_to_i16(i8::MAX, AxisExtents::FULL), i16::MAX);
+    assert_eq!(axis_to_i16(0, AxisExtents::FULL), 0);
+    assert_eq!(axis_to_i16(-64, AxisExtents::FULL), -16384);
+}
+
+#[test]
+fn asymmetric_extents_map_each_end_exactly() {
+    assert_eq!(axis_to_i16(-90, LOPSIDED), i16::MIN);
+    assert_eq!(axis_to_i16(100, LOPSIDED), i16::MAX);
+    assert_eq!(axis_to_i16(0, LOPSIDED), 0);
+    // Halfway each way is half of each half of the range
+    assert_eq!(axis_to_i16(-45, LOPSIDED), -16384);
+    assert_eq!(axis_to_i16(50, LOPSIDED), 16383);
+}
+
+#[test]
+fn values_past_extents_saturate() {
+    assert_eq!(axis_to_i16(i8::MIN, LOPSIDED), i16::MIN);
+    assert_eq!(axis_to_i16(i8::MAX, LOPSIDED), i16::MAX);
+    assert_eq!(trigger_to_u16(i8::MAX, 31), u16::MAX);
+}
+
+#[test]
+fn axis_scaling_is_monotonic_for_any_extents() {
+    for negative in [0, 1, 45, 128, 255] {
+        for positive in [0, 1, 45, 127, 255] {
+            let extents = AxisExtents { negative, positive };
+            for value in i8::MIN..i8::MAX {
+                assert!(axis_to_i16(value, extents) <= axis_to_i16(value + 1, extents));
+            }
+        }
+    }
+}
+
+#[test]
+fn zero_extent_scales_to_zero() {
+    let one_way = AxisExtents {
+        negative: 0,
+        positive: 127,
+    };
+    assert_eq!(axis_to_i16(-100, one_way), 0);
+    assert_eq!(trigger_to_u16(100, 0), 0);
+}
+
+#[test]
+fn triggers_map_to_u16_range() {
+    assert_eq!(trigger_to_u16(0, 127), 0);
+    assert_eq!(trigger_to_u16(-5, 127), 0);
+    assert_eq!(trigger_to_u16(127, 127), u16::MAX);
+    assert_eq!(trigger_to_u16(31, 31), u16::MAX);
+    assert_eq!(trigger_to_u16(i8::MIN, 127), 0);
+}
+
+#[test]
+fn classic_reading_full_scale() {
+    let reading = ClassicReadingCalibrated::default()
+        .with_joystick_left_x(-90)
+        .with_joystick_left_y(100)
+        .with_joystick_right_x(i8::MIN)
+        .with_joystick_right_y(0)
+        .with_trigger_left(31)
+        .with_trigger_right(0);
+    let extents = ClassicExtents {
+        joystick_left_x: LOPSIDED,
+        joystick_left_y: LOPSIDED,
+        trigger_left: 31,
+        ..Default::default()
+    };
+    assert_eq!(
+        reading.to_i16_full_scale(&extents),
+        ClassicAxesI16 {
+            joystick_left_x: i16::MIN,
+            joystick_left_y: i16::MAX,
+            joystick_right_x: i16::MIN,
+            joystick_right_y: 0,
+            trigger_left: u16::MAX,
+            trigger_right: 0,
+        }
+    );
+    assert_eq!(
+        ClassicReadingCalibrated::default().to_i16_full_scale(&ClassicExtents::default()),
+        ClassicAxesI16::default()
+    );
+}
+
+#[test]
+fn nunchuk_reading_full_scale() {
+    let reading = NunchukReadingCalibrated::default()
+        .with_joystick_x(100)
+        .with_joystick_y(-90);
+    let extents = NunchukExtents {
+        joystick_x: LOPSIDED,
+        joystick_y: LOPSIDED,
+    };
+    let axes = reading.to_i16_full_scale(&extents);
+    assert_eq!((axes.joystick_x, axes.joystick_y), (i16::MAX, i16::MIN));
+    let axes = reading.to_i16_full_scale(&NunchukExtents::default());
+    assert_eq!(axes.joystick_x, 25800);
+    assert_eq!(axes.joystick_y, -23040);
+}
@@ -0,0 +1,235 @@
+#![cfg(feature = "hid")]
+
+use wii_ext::core::classic::ClassicReadingCalibrated;
+use wii_ext::core::nunchuk::NunchukReadingCalibrated;
+use wii_ext::hid::{
+    pack, pack_nunchuk, ClassicHidReport, JoystickReport, KeyboardReport, NunchukHidReport,
+    UnsignedJoystickReport, CLASSIC_DESCRIPTOR, HAT_CENTERED, JOYSTICK_DESCRIPTOR,
+    KEYBOARD_DESCRIPTOR, NUNCHUK_DESCRIPTOR,
+};
+
+#[test]
+fn idle_classic_report() {
+    let report = JoystickReport::from(&ClassicReadingCalibrated::default());
+    assert_eq!(report.to_bytes(), [0, 0, 0, 0, 0, 0, HAT_CENTERED, 0, 0]);
+}
+
+#[test]
+fn classic_axes_follow_hid_conventions() {
+    let reading = ClassicReadingCalibrated::default()
+        .with_joystick_left_x(-128)
+        .with_joystick_left_y(100)
+        .with_joystick_right_x(90)
+        .with_joystick_right_y(-128)
+        .with_trigger_left(30)
+        .with_trigger_right(127);
+    let report = JoystickReport::from(&reading);
+    // Up on the stick is negative Y, and -128 is clamped to the logical minimum
+    assert_eq!(
+        report.to_bytes(),
+        [0x81, 0x9C, 0x5A, 0x7F, 0x1E, 0x7F, HAT_CENTERED, 0, 0]
+    );
+}
+
+#[test]
+fn classic_buttons_bit_order() {
+    let reading = ClassicReadingCalibrated::default()
+        .with_button_a(true)
+        .with_button_y(true)
+        .with_button_zr(true)
+        .with_button_home(true);
+    let report = JoystickReport::from(&reading);
+    assert_eq!(report.buttons, 0b100_1000_1001);
+    assert_eq!(report.to_bytes()[7..], [0b1000_1001, 0b100]);
+}
+
+#[test]
+fn classic_dpad_hat() {
+    let hat = |up, down, left, right| {
+        let reading = ClassicReadingCalibrated::default()
+            .with_dpad_up(up)
+            .with_dpad_down(down)
+            .with_dpad_left(left)
+            .with_dpad_right(right);
+        JoystickReport::from(&reading).hat
+    };
+    assert_eq!(hat(true, false, false, false), 0);
+    assert_eq!(hat(true, false, false, true), 1);
+    assert_eq!(hat(false, false, false, true), 2);
+    assert_eq!(hat(false, true, false, true), 3);
+    assert_eq!(hat(false, true, false, false), 4);
+    assert_eq!(hat(false, true, true, false), 5);
+    assert_eq!(hat(false, false, true, false), 6);
+    assert_eq!(hat(true, false, true, false), 7);
+    // Opposite directions cancel out
+    assert_eq!(hat(true, true, false, false), HAT_CENTERED);
+    assert_eq!(hat(true, true, false, true), 2);
+}
+
+#[test]
+fn nunchuk_report() {
+    let reading = NunchukReadingCalibrated::default()
+        .with_joystick_x(50)
+        .with_joystick_y(-50)
+        .with_accel_x(512)
+        .with_accel_y(1023)
+        .with_accel_z(0)
+        .with_button_c(false)
+        .with_button_z(true);
+    let report = JoystickReport::from(&reading);
+    assert_eq!(
+        report.to_bytes(),
+        [0x32, 0x32, 0x00, 0x7F, 0x00, 0x81, HAT_CENTERED, 0b10, 0]
+    );
+}
+
+/// Total size in bits of the Input items declared by a report descriptor
+fn input_report_bits(descriptor: &[u8]) -> usize {
+    let (mut size, mut count, mut bits) = (0, 0, 0);
+    let mut items = descriptor;
+    while let Some((&prefix, rest)) = items.split_first() {
+        let len = match prefix & 0x03 {
+            3 => 4,
+            n => n as usize,
+        };
+        let value = rest[..len]
+            .iter()
+            .rev()
+            .fold(0, |v, &b| (v << 8) | b as usize);
+        match prefix & 0xFC {
+            // Report Size
+            0x74 => size = value,
+            // Report Count
+            0x94 => count = value,
+            // Input
+            0x80 => bits += size * count,
+            _ => {}
+        }
+        items = &rest[len..];
+    }
+    bits
+}
+
+#[test]
+fn descriptor_declares_report_size() {
+    assert_eq!(
+        input_report_bits(JOYSTICK_DESCRIPTOR),
+        JoystickReport::SIZE * 8
+    );
+    assert_eq!(
+        input_report_bits(KEYBOARD_DESCRIPTOR),
+        KeyboardReport::SIZE * 8
+    );
+}
+
+#[test]
+fn unsigned_axes_center_on_0x80() {
+    let report = UnsignedJoystickReport::from(&ClassicReadingCalibrated::default());
+    assert_eq!(
+        [report.x, report.y, report.rx, report.ry, report.z, report.rz],
+        [0x80; 6]
+    );
+    assert_eq!(report.hat, HAT_CENTERED);
+    let report = UnsignedJoystickReport::from(
+        &NunchukReadingCalibrated::default()
+            .with_accel_x(512)
+            .with_accel_y(512)
+            .with_accel_z(512),
+    );
+    assert_eq!(
+        [report.x, report.y, report.rx, report.ry, report.z, report.rz],
+        [0x80; 6]
+    );
+}
+
+#[test]
+fn unsigned_axes_full_deflection() {
+    let reading = ClassicReadingCalibrated::default()
+        .with_joystick_left_x(-128)
+        .with_joystick_left_y(-128)
+        .with_joystick_right_x(127)
+        .with_joystick_right_y(127)
+        .with_trigger_left(127)
+        .with_trigger_right(-128);
+    let report = UnsignedJoystickReport::from(&reading);
+    assert_eq!(report.x, 0x00);
+    // Down is positive
+    assert_eq!(report.y, 0xFF);
+    assert_eq!(report.rx, 0xFF);
+    assert_eq!(report.ry, 0x01);
+    assert_eq!(report.z, 0xFF);
+    assert_eq!(report.rz, 0x00);
+}
+
+#[test]
+fn unsigned_report_button_bits() {
+    let classic_button = |set: fn(&mut ClassicReadingCalibrated)| {
+        let mut reading = ClassicReadingCalibrated::default();
+        set(&mut reading);
+        UnsignedJoystickReport::from(&reading).buttons
+    };
+    assert_eq!(classic_button(|r| r.button_a = true), 1 << 0);
+    assert_eq!(classic_button(|r| r.button_b = true), 1 << 1);
+    assert_eq!(classic_button(|r| r.button_x = true), 1 << 2);
+    assert_eq!(classic_button(|r| r.button_y = true), 1 << 3);
+    assert_eq!(classic_button(|r| r.button_trigger_l = true), 1 << 4);
+    assert_eq!(classic_button(|r| r.button_trigger_r = true), 1 << 5);
+    assert_eq!(classic_button(|r| r.button_zl = true), 1 << 6);
+    assert_eq!(classic_button(|r| r.button_zr = true), 1 << 7);
+    assert_eq!(classic_button(|r| r.button_minus = true), 1 << 8);
+    assert_eq!(classic_button(|r| r.button_plus = true), 1 << 9);
+    assert_eq!(classic_button(|r| r.button_home = true), 1 << 10);
+
+    let nunchuk_button = |c, z| {
+        let reading = NunchukReadingCalibrated::default()
+            .with_button_c(c)
+            .with_button_z(z);
+        UnsignedJoystickReport::from(&reading).buttons
+    };
+    assert_eq!(nunchuk_button(true, false), 1 << 0);
+    assert_eq!(nunchuk_button(false, true), 1 << 1);
+}
+
+#[test]
+fn hid_report_structs_match_descriptors() {
+    assert_eq!(
+        input_report_bits(CLASSIC_DESCRIPTOR),
+        core::mem::size_of::<ClassicHidReport>() * 8
+    );
+    assert_eq!(
+        input_report_bits(NUNCHUK_DESCRIPTOR),
+        core::mem::size_of::<NunchukHidReport>() * 8
+    );
+}
+
+#[test]
+fn pack_classic() {
+    let reading = ClassicReadingCalibrated::default()
+        .with_joystick_left_x(10)
+        .with_joystick_left_y(20)
+        .with_joystick_right_x(-30)
+        .with_joystick_right_y(-40)
+        .with_trigger_left(100)
+        .with_trigger_right(-3)
+        .with_dpad_down(true)
+        .with_dpad_left(true)
+        .with_button_b(true)
+        .with_button_home(true);
+    assert_eq!(
+        pack(&reading).to_bytes(),
+        [0x0A, 0xEC, 0xE2, 0x28, 0x64, 0x00, 5, 0b10, 0b100]
+    );
+}
+
+#[test]
+fn pack_nunchuk_report() {
+    let reading = NunchukReadingCalibrated::default()
+        .with_accel_x(512)
+        .with_accel_y(0x1FF)
+        .with_accel_z(0x3FF)
+        .with_button_c(true);
+    assert_eq!(
+        pack_nunchuk(&reading).to_bytes(),
+        [0, 0, 0x00, 0x02, 0xFF, 0x01, 0xFF, 0x03, 0b01]
+    );
+}
@@ -0,0 +1,95 @@
+use embassy_futures::block_on;
+use embedded_hal::i2c::{ErrorKind, NoAcknowledgeSource};
+use embedded_hal_mock::eh1::{
+    delay::NoopDelay,
+    i2c::{self, Transaction},
+};
+use wii_ext::blocking_impl::classic::Classic;
+use wii_ext::blocking_impl::nunchuk::NunchukBuilder;
+use wii_ext::core::{ControllerType, EXT_I2C_ADDR};
+mod common;
+use common::{test_data, transactions};
+
+/// An ID read that is NACKed
+fn nacked_id() -> Vec<Transaction> {
+    vec![
+        Transaction::write(EXT_I2C_ADDR as u8, vec![0xfa]),
+        Transaction::read(EXT_I2C_ADDR as u8, vec![0; 6])
+            .with_error(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address)),
+    ]
+}
+
+#[test]
+fn blank_id_is_read_again() {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.extend(transactions::read_id(&[0xFF; 6]));
+    expectations.extend(transactions::read_id(&test_data::CLASSIC_ID));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).unwrap();
+    assert_eq!(
+        classic.identify_controller().unwrap(),
+        Some(ControllerType::Classic)
+    );
+    i2c.done();
+}
+
+#[test]
+fn nacked_id_is_read_again() {
+    let mut expectations = transactions::init_no_calibration();
+    expectations.extend(nacked_id());
+    expectations.extend(transactions::read_id(&[0, 0, 0xA4, 0x00, 0xFF, 0x00]));
+    expectations.extend(transactions::read_id(&test_data::NUNCHUCK_ID));
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_IDLE));
+    let mut i2c = i2c::Mock::new(&expectations);
+    // verify_id identifies the controller during build()
+    NunchukBuilder::new()
+        .verify_id(true)
+        .build(i2c.clone(), NoopDelay::new())
+        .unwrap();
+    i2c.done();
+}
+
+#[test]
+fn garbage_returned_after_last_attempt() {
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    expectations.extend(transactions::read_id(&[0xFF; 6]));
+    expectations.extend(transactions::read_id(&[0xFF; 6]));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut nunchuk = NunchukBuilder::new()
+        .id_read_attempts(2)
+        .build(i2c.clone(), NoopDelay::new())
+        .unwrap();
+    assert_eq!(nunchuk.identify_controller().unwrap(), None);
+    i2c.done();
+}
+
+#[test]
+fn single_attempt_does_not_retry() {
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    expectations.extend(nacked_id());
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut nunchuk = NunchukBuilder::new()
+        .id_read_attempts(1)
+        .build(i2c.clone(), NoopDelay::new())
+        .unwrap();
+    assert!(nunchuk.identify_controller().is_err());
+    i2c.done();
+}
+
+#[test]
+fn async_blank_id_is_read_again() {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.extend(transactions::read_id(&[0xFF; 6]));
+    expectations.extend(transactions::read_id(&test_data::CLASSIC_ID));
+    let mut i2c = i2c::Mock::new(&expectations);
+    block_on(async {
+        let mut classic = wii_ext::async_impl::classic::Classic::new(i2c.clone(), NoopDelay::new())
+            .await
+            .unwrap();
+        assert_eq!(
+            classic.identify_controller().await.unwrap(),
+            Some(ControllerType::Classic)
+        );
+    });
+    i2c.done();
+}
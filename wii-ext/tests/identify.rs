@@ -0,0 +1,48 @@
+use wii_ext::core::{identify_controller, ControllerType};
+mod common;
+use common::test_data;
+
+#[test]
+fn captured_ids() {
+    let ids = [
+        (test_data::NUNCHUCK_ID, ControllerType::Nunchuk),
+        (test_data::CLASSIC_ID, ControllerType::Classic),
+        (test_data::PRO_ID, ControllerType::ClassicPro),
+        (test_data::NES_ID, ControllerType::ClassicPro),
+        (test_data::SNES_ID, ControllerType::ClassicPro),
+        (test_data::PDP_LINK_ID, ControllerType::ClassicPro),
+    ];
+    for (id, expected) in ids {
+        assert_eq!(identify_controller(id), Some(expected), "{id:02x?}");
+    }
+}
+
+#[test]
+fn high_resolution_mode_ids() {
+    assert_eq!(
+        identify_controller([0, 0, 0xA4, 0x20, 3, 1]),
+        Some(ControllerType::Classic)
+    );
+    assert_eq!(
+        identify_controller([1, 0, 0xA4, 0x20, 3, 1]),
+        Some(ControllerType::ClassicPro)
+    );
+}
+
+#[test]
+fn unknown_ids() {
+    for id in [
+        [0xFF; 6],
+        [0; 6],
+        // A Motion Plus
+        [0, 0, 0xA4, 0x20, 0x04, 0x05],
+        // Guitar
+        [0, 0, 0xA4, 0x20, 0x01, 0x03],
+        // Unknown report format
+        [0, 0, 0xA4, 0x20, 0x07, 0x01],
+        // Right device type, wrong signature
+        [0, 0, 0xA5, 0x20, 0x01, 0x01],
+    ] {
+        assert_eq!(identify_controller(id), None, "{id:02x?}");
+    }
+}
@@ -0,0 +1,118 @@
+use embedded_hal_mock::eh1::{delay::NoopDelay, i2c};
+use wii_ext::blocking_impl::classic::Classic;
+use wii_ext::blocking_impl::nunchuk::Nunchuk;
+use wii_ext::core::ControllerType;
+use wii_ext::Error;
+mod common;
+use common::{test_data, transactions};
+
+#[test]
+fn classic_swapped_for_nunchuk() {
+    let mut expectations = transactions::init(&test_data::PRO_IDLE);
+    expectations.extend(transactions::read_id(&test_data::PRO_ID));
+    expectations.extend(transactions::read(&test_data::PRO_BTN_A));
+    // Controller is swapped: nunchuk data fails the classic constant bit check
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_IDLE));
+    expectations.extend(transactions::read_id(&test_data::NUNCHUCK_ID));
+
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).unwrap();
+    classic.enable_identity_check(0).unwrap();
+
+    let input = classic.read().unwrap();
+    assert!(input.button_a);
+
+    match classic.read() {
+        Err(Error::ControllerChanged { previous, current }) => {
+            assert_eq!(previous, Some(ControllerType::ClassicPro));
+            assert_eq!(current, Some(ControllerType::Nunchuk));
+        }
+        other => panic!("expected ControllerChanged, got {:?}", other),
+    }
+    i2c.done();
+}
+
+#[test]
+fn classic_same_controller_reports_invalid_data() {
+    let mut expectations = transactions::init(&test_data::PRO_IDLE);
+    expectations.extend(transactions::read_id(&test_data::PRO_ID));
+    // Corrupt frame, but the controller is still the same one
+    expectations.extend(transactions::read(&[160, 31, 17, 0, 254, 255]));
+    expectations.extend(transactions::read_id(&test_data::PRO_ID));
+    expectations.extend(transactions::read(&test_data::PRO_IDLE));
+
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).unwrap();
+    classic.enable_identity_check(0).unwrap();
+
+    assert!(matches!(classic.read(), Err(Error::InvalidInputData)));
+    assert!(classic.read().is_ok());
+    i2c.done();
+}
+
+#[test]
+fn classic_identity_check_is_rate_limited() {
+    let mut expectations = transactions::init(&test_data::PRO_IDLE);
+    expectations.extend(transactions::read_id(&test_data::PRO_ID));
+    // Three anomalous reads are allowed before the ID is read again
+    for _ in 0..3 {
+        expectations.extend(transactions::read(&test_data::NUNCHUCK_IDLE));
+    }
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_IDLE));
+    expectations.extend(transactions::read_id(&test_data::NUNCHUCK_ID));
+
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).unwrap();
+    classic.enable_identity_check(3).unwrap();
+
+    for _ in 0..3 {
+        assert!(matches!(classic.read(), Err(Error::InvalidInputData)));
+    }
+    assert!(matches!(
+        classic.read(),
+        Err(Error::ControllerChanged { .. })
+    ));
+    i2c.done();
+}
+
+#[test]
+fn classic_identity_check_disabled() {
+    let mut expectations = transactions::init(&test_data::PRO_IDLE);
+    expectations.extend(transactions::read_id(&test_data::PRO_ID));
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_IDLE));
+
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).unwrap();
+    classic.enable_identity_check(0).unwrap();
+    classic.disable_identity_check();
+
+    // Without the check, the nunchuk data is decoded as if it were a classic controller
+    assert!(classic.read().is_ok());
+    i2c.done();
+}
+
+#[test]
+fn nunchuk_swapped_for_classic() {
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    expectations.extend(transactions::read_id(&test_data::NUNCHUCK_ID));
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_BTN_C));
+    // Controller unplugged, a new one has not been initialised yet
+    expectations.extend(transactions::read(&[255, 255, 255, 255, 255, 255]));
+    expectations.extend(transactions::read_id(&test_data::PRO_ID));
+
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut nunchuk = Nunchuk::new(i2c.clone(), NoopDelay::new()).unwrap();
+    nunchuk.enable_identity_check(0).unwrap();
+
+    let input = nunchuk.read().unwrap();
+    assert!(input.button_c);
+
+    match nunchuk.read() {
+        Err(Error::ControllerChanged { previous, current }) => {
+            assert_eq!(previous, Some(ControllerType::Nunchuk));
+            assert_eq!(current, Some(ControllerType::ClassicPro));
+        }
+        other => panic!("expected ControllerChanged, got {:?}", other),
+    }
+    i2c.done();
+}
@@ -0,0 +1,132 @@
+use wii_ext::core::classic::{CalibrationData, ClassicReading, ClassicReadingCalibrated};
+use wii_ext::core::events::Inputs;
+use wii_ext::core::nunchuk::{self, NunchukReading, NunchukReadingCalibrated};
+mod common;
+use common::test_data;
+
+/// Stick deflections smaller than this are noise
+const THRESHOLD: u8 = 10;
+
+const CLASSIC_BUTTONS: [[u8; 6]; 15] = [
+    test_data::CLASSIC_BTN_B,
+    test_data::CLASSIC_BTN_A,
+    test_data::CLASSIC_BTN_X,
+    test_data::CLASSIC_BTN_Y,
+    test_data::CLASSIC_BTN_L,
+    test_data::CLASSIC_BTN_R,
+    test_data::CLASSIC_BTN_ZL,
+    test_data::CLASSIC_BTN_ZR,
+    test_data::CLASSIC_PAD_U,
+    test_data::CLASSIC_PAD_D,
+    test_data::CLASSIC_PAD_L,
+    test_data::CLASSIC_PAD_R,
+    test_data::CLASSIC_BTN_MINUS,
+    test_data::CLASSIC_BTN_PLUS,
+    test_data::CLASSIC_BTN_HOME,
+];
+
+const CLASSIC_AXES: [[u8; 6]; 18] = [
+    test_data::CLASSIC_LJOY_U,
+    test_data::CLASSIC_LJOY_D,
+    test_data::CLASSIC_LJOY_L,
+    test_data::CLASSIC_LJOY_R,
+    test_data::CLASSIC_RJOY_U,
+    test_data::CLASSIC_RJOY_D,
+    test_data::CLASSIC_RJOY_L,
+    test_data::CLASSIC_RJOY_R,
+    test_data::CLASSIC_LJOY_UR,
+    test_data::CLASSIC_LJOY_DR,
+    test_data::CLASSIC_LJOY_DL,
+    test_data::CLASSIC_LJOY_UL,
+    test_data::CLASSIC_RJOY_UR,
+    test_data::CLASSIC_RJOY_DR,
+    test_data::CLASSIC_RJOY_DL,
+    test_data::CLASSIC_RJOY_UL,
+    test_data::CLASSIC_LTRIG,
+    test_data::CLASSIC_RTRIG,
+];
+
+fn raw(report: &[u8; 6]) -> ClassicReading {
+    ClassicReading::from_data(report).unwrap()
+}
+
+fn calibrated(report: &[u8; 6]) -> ClassicReadingCalibrated {
+    let idle = raw(&test_data::CLASSIC_IDLE);
+    let calibration = CalibrationData {
+        joystick_left_x: idle.joystick_left_x,
+        joystick_left_y: idle.joystick_left_y,
+        joystick_right_x: idle.joystick_right_x,
+        joystick_right_y: idle.joystick_right_y,
+        trigger_left: idle.trigger_left,
+        trigger_right: idle.trigger_right,
+    };
+    ClassicReadingCalibrated::new(raw(report), &calibration)
+}
+
+#[test]
+fn classic_idle() {
+    let reading = calibrated(&test_data::CLASSIC_IDLE);
+    assert!(reading.is_idle(0));
+    assert!(!reading.any_button_pressed());
+    // The raw idle report is close enough to nominal center for a loose threshold
+    assert!(raw(&test_data::CLASSIC_IDLE).is_idle(32));
+    assert!(!raw(&test_data::CLASSIC_IDLE).any_button_pressed());
+}
+
+#[test]
+fn classic_buttons() {
+    for report in CLASSIC_BUTTONS {
+        let reading = calibrated(&report);
+        assert!(reading.any_button_pressed(), "{report:?}");
+        assert!(!reading.is_idle(THRESHOLD));
+        assert!(raw(&report).any_button_pressed());
+        assert!(!raw(&report).is_idle(u8::MAX));
+    }
+}
+
+#[test]
+fn classic_axes() {
+    for report in CLASSIC_AXES {
+        let reading = calibrated(&report);
+        assert!(!reading.any_button_pressed(), "{report:?}");
+        assert!(reading.any_axis_active(THRESHOLD), "{report:?}");
+        assert!(!reading.is_idle(THRESHOLD));
+        assert!(!raw(&report).any_button_pressed());
+    }
+}
+
+#[test]
+fn nunchuk() {
+    let idle = NunchukReading::from_data(&test_data::NUNCHUCK_IDLE).unwrap();
+    let calibration = nunchuk::CalibrationData {
+        joystick_x: idle.joystick_x,
+        joystick_y: idle.joystick_y,
+    };
+    let calibrated = |report: &[u8; 6]| {
+        NunchukReadingCalibrated::new(NunchukReading::from_data(report).unwrap(), &calibration)
+    };
+    assert!(calibrated(&test_data::NUNCHUCK_IDLE).is_idle(0));
+    assert!(idle.is_idle(THRESHOLD));
+    for report in [test_data::NUNCHUCK_BTN_C, test_data::NUNCHUCK_BTN_Z] {
+        assert!(calibrated(&report).any_button_pressed());
+        assert!(NunchukReading::from_data(&report)
+            .unwrap()
+            .any_button_pressed());
+    }
+    for report in [
+        test_data::NUNCHUCK_JOY_U,
+        test_data::NUNCHUCK_JOY_D,
+        test_data::NUNCHUCK_JOY_L,
+        test_data::NUNCHUCK_JOY_R,
+    ] {
+        let reading = calibrated(&report);
+        assert!(!reading.any_button_pressed());
+        assert!(reading.any_axis_active(THRESHOLD));
+        assert!(NunchukReading::from_data(&report)
+            .unwrap()
+            .any_axis_active(THRESHOLD));
+    }
+    // Moving the nunchuk isn't input
+    let tilted = NunchukReadingCalibrated::default().with_accel_x(900);
+    assert!(tilted.is_idle(0));
+}
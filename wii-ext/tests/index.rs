@@ -0,0 +1,116 @@
+use wii_ext::core::classic::{ClassicReading, ClassicReadingCalibrated};
+use wii_ext::core::events::{Axis, Button};
+use wii_ext::core::nunchuk::{NunchukReading, NunchukReadingCalibrated};
+
+/// Every button on the raw and calibrated types is backed by the named field
+macro_rules! check_buttons {
+    ($reading:ident { $($button:ident => $field:ident),* $(,)? }) => {
+        for button in Button::ALL {
+            let mut reading = $reading::default();
+            reading.set_button(button, true);
+            let mut expected = $reading::default();
+            $(expected.$field = button == Button::$button;)*
+            assert_eq!(reading, expected, "{:?}", button);
+            for other in Button::ALL {
+                assert_eq!(reading[other], other == button && expected != $reading::default());
+            }
+        }
+    };
+}
+
+/// Every axis on the raw and calibrated types is backed by the named field
+macro_rules! check_axes {
+    ($reading:ident { $($axis:ident => $field:ident),* $(,)? }) => {
+        for axis in Axis::ALL {
+            let mut reading = $reading::default();
+            reading.set_axis(axis, 42);
+            let mut expected = $reading::default();
+            $(expected.$field = if axis == Axis::$axis { 42 } else { 0 };)*
+            assert_eq!(reading, expected, "{:?}", axis);
+            for other in Axis::ALL {
+                let value = if other == axis && expected != $reading::default() { 42 } else { 0 };
+                assert_eq!(reading[other], value);
+            }
+        }
+    };
+}
+
+#[test]
+fn classic_buttons() {
+    check_buttons!(ClassicReading {
+        A => button_a,
+        B => button_b,
+        X => button_x,
+        Y => button_y,
+        L => button_trigger_l,
+        R => button_trigger_r,
+        ZL => button_zl,
+        ZR => button_zr,
+        Minus => button_minus,
+        Plus => button_plus,
+        Home => button_home,
+        DpadUp => dpad_up,
+        DpadDown => dpad_down,
+        DpadLeft => dpad_left,
+        DpadRight => dpad_right,
+    });
+    check_buttons!(ClassicReadingCalibrated {
+        A => button_a,
+        B => button_b,
+        X => button_x,
+        Y => button_y,
+        L => button_trigger_l,
+        R => button_trigger_r,
+        ZL => button_zl,
+        ZR => button_zr,
+        Minus => button_minus,
+        Plus => button_plus,
+        Home => button_home,
+        DpadUp => dpad_up,
+        DpadDown => dpad_down,
+        DpadLeft => dpad_left,
+        DpadRight => dpad_right,
+    });
+}
+
+#[test]
+fn classic_axes() {
+    check_axes!(ClassicReading {
+        LeftX => joystick_left_x,
+        LeftY => joystick_left_y,
+        RightX => joystick_right_x,
+        RightY => joystick_right_y,
+        TriggerLeft => trigger_left,
+        TriggerRight => trigger_right,
+    });
+    check_axes!(ClassicReadingCalibrated {
+        LeftX => joystick_left_x,
+        LeftY => joystick_left_y,
+        RightX => joystick_right_x,
+        RightY => joystick_right_y,
+        TriggerLeft => trigger_left,
+        TriggerRight => trigger_right,
+    });
+}
+
+#[test]
+fn nunchuk_buttons_and_axes() {
+    check_buttons!(NunchukReading { C => button_c, Z => button_z });
+    check_buttons!(NunchukReadingCalibrated { C => button_c, Z => button_z });
+    check_axes!(NunchukReading { LeftX => joystick_x, LeftY => joystick_y });
+    check_axes!(NunchukReadingCalibrated { LeftX => joystick_x, LeftY => joystick_y });
+}
+
+#[test]
+fn missing_inputs_are_ignored() {
+    let mut nunchuk = NunchukReadingCalibrated::default();
+    nunchuk.set_button(Button::A, true);
+    nunchuk.set_axis(Axis::RightX, 10);
+    assert_eq!(nunchuk, NunchukReadingCalibrated::default());
+    assert!(!nunchuk[Button::A]);
+    assert_eq!(nunchuk[Axis::TriggerLeft], 0);
+
+    let mut classic = ClassicReadingCalibrated::default();
+    classic.set_button(Button::C, true);
+    assert_eq!(classic, ClassicReadingCalibrated::default());
+}
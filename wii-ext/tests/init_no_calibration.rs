@@ -0,0 +1,56 @@
+use embedded_hal_mock::eh1::{delay::NoopDelay, i2c};
+use wii_ext::blocking_impl::classic::Classic;
+use wii_ext::blocking_impl::nunchuk::Nunchuk;
+use wii_ext::core::classic::CalibrationData as ClassicCalibration;
+use wii_ext::core::nunchuk::CalibrationData as NunchukCalibration;
+mod common;
+use common::{test_data, transactions};
+
+#[test]
+fn classic_init_no_calibration() {
+    let expectations = transactions::init_no_calibration();
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = Classic::new_unchecked(i2c.clone(), NoopDelay::new());
+    classic.init_no_calibration().unwrap();
+    assert_eq!(classic.calibration(), ClassicCalibration::default());
+    i2c.done();
+}
+
+#[test]
+fn classic_init_no_calibration_keeps_injected_calibration() {
+    let calibration = ClassicCalibration {
+        joystick_left_x: 1,
+        joystick_left_y: 2,
+        joystick_right_x: 3,
+        joystick_right_y: 4,
+        trigger_left: 5,
+        trigger_right: 6,
+    };
+    let expectations = transactions::init_no_calibration();
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = Classic::new_unchecked(i2c.clone(), NoopDelay::new());
+    classic.set_calibration(calibration);
+    classic.init_no_calibration().unwrap();
+    assert_eq!(classic.calibration(), calibration);
+    i2c.done();
+}
+
+#[test]
+fn nunchuk_calibrate_after_init_no_calibration() {
+    let mut expectations = transactions::init_no_calibration();
+    // Calibration read, some time after init
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_IDLE));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut nunchuk = Nunchuk::new_unchecked(i2c.clone(), NoopDelay::new());
+    nunchuk.init_no_calibration().unwrap();
+    assert_eq!(nunchuk.calibration(), NunchukCalibration::default());
+    nunchuk.update_calibration().unwrap();
+    assert_eq!(
+        nunchuk.calibration(),
+        NunchukCalibration {
+            joystick_x: test_data::NUNCHUCK_IDLE[0],
+            joystick_y: test_data::NUNCHUCK_IDLE[1],
+        }
+    );
+    i2c.done();
+}
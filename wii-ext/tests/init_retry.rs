@@ -0,0 +1,88 @@
+use embassy_futures::block_on;
+use embedded_hal::i2c::{ErrorKind, NoAcknowledgeSource};
+use embedded_hal_mock::eh1::{
+    delay::NoopDelay,
+    i2c::{self, Transaction},
+};
+use wii_ext::blocking_impl::classic::Classic;
+use wii_ext::blocking_impl::nunchuk::NunchukBuilder;
+use wii_ext::core::{RetryPolicy, EXT_I2C_ADDR};
+use wii_ext::Error;
+mod common;
+use common::{test_data, transactions};
+
+/// An init handshake where the controller NACKs the first register write
+fn failed_handshake() -> Vec<Transaction> {
+    vec![
+        Transaction::write(EXT_I2C_ADDR as u8, vec![0]),
+        Transaction::write(EXT_I2C_ADDR as u8, vec![240, 85])
+            .with_error(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address)),
+    ]
+}
+
+#[test]
+fn classic_new_with_retries_succeeds_on_second_attempt() {
+    let mut expectations = failed_handshake();
+    expectations.extend(transactions::init(&test_data::CLASSIC_IDLE));
+    expectations.extend(transactions::read(&test_data::CLASSIC_BTN_A));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = Classic::new_with_retries(i2c.clone(), NoopDelay::new(), 3).unwrap();
+    assert!(classic.read().unwrap().button_a);
+    i2c.done();
+}
+
+#[test]
+fn returns_last_error_when_attempts_run_out() {
+    let mut expectations = failed_handshake();
+    expectations.extend(failed_handshake());
+    let mut i2c = i2c::Mock::new(&expectations);
+    let result = NunchukBuilder::new()
+        .init_retry_policy(RetryPolicy::new(2, 1_000))
+        .build(i2c.clone(), NoopDelay::new());
+    assert!(matches!(
+        result,
+        Err(Error::I2C(ErrorKind::NoAcknowledge(_)))
+    ));
+    i2c.done();
+}
+
+#[test]
+fn wrong_controller_is_not_retried() {
+    let mut expectations = transactions::init_no_calibration();
+    expectations.extend(transactions::read_id(&test_data::CLASSIC_ID));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let result = NunchukBuilder::new()
+        .verify_id(true)
+        .init_retry_policy(RetryPolicy::new(3, 1_000))
+        .build(i2c.clone(), NoopDelay::new());
+    assert!(matches!(result, Err(Error::UnexpectedController(_))));
+    i2c.done();
+}
+
+#[test]
+fn build_does_not_retry_by_default() {
+    let expectations = failed_handshake();
+    let mut i2c = i2c::Mock::new(&expectations);
+    let result = NunchukBuilder::new().build(i2c.clone(), NoopDelay::new());
+    assert!(matches!(result, Err(Error::I2C(_))));
+    i2c.done();
+}
+
+#[test]
+fn async_nunchuk_new_with_retries() {
+    let mut expectations = failed_handshake();
+    expectations.extend(transactions::init(&test_data::NUNCHUCK_IDLE));
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_BTN_C));
+    let mut i2c = i2c::Mock::new(&expectations);
+    block_on(async {
+        let mut nunchuk = wii_ext::async_impl::nunchuk::Nunchuk::new_with_retries(
+            i2c.clone(),
+            NoopDelay::new(),
+            2,
+        )
+        .await
+        .unwrap();
+        assert!(nunchuk.read().await.unwrap().button_c);
+    });
+    i2c.done();
+}
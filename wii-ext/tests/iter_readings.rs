@@ -0,0 +1,64 @@
+use embedded_hal::i2c::ErrorKind;
+use embedded_hal_mock::eh1::i2c::{self, Transaction};
+use std::cell::Cell;
+use std::rc::Rc;
+use wii_ext::blocking_impl::classic::Classic;
+use wii_ext::blocking_impl::nunchuk::Nunchuk;
+use wii_ext::core::EXT_I2C_ADDR;
+use wii_ext::Error;
+mod common;
+use common::{test_data, transactions};
+
+const INTERVAL_US: u32 = 10_000;
+
+/// A delay that counts how many times it waited for the polling interval
+#[derive(Clone, Default)]
+struct IntervalCounter(Rc<Cell<u32>>);
+
+impl embedded_hal::delay::DelayNs for IntervalCounter {
+    fn delay_ns(&mut self, ns: u32) {
+        if ns == INTERVAL_US * 1000 {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+}
+
+#[test]
+fn classic_iter_yields_readings_and_errors() {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.extend(transactions::read(&test_data::CLASSIC_LJOY_L));
+    expectations.push(Transaction::write(EXT_I2C_ADDR as u8, vec![0]));
+    expectations
+        .push(Transaction::read(EXT_I2C_ADDR as u8, vec![0; 6]).with_error(ErrorKind::Other));
+    expectations.extend(transactions::read(&test_data::CLASSIC_BTN_A));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let delay = IntervalCounter::default();
+    let mut classic = Classic::new(i2c.clone(), delay.clone()).unwrap();
+    let items: Vec<_> = classic.iter_readings(INTERVAL_US).take(3).collect();
+    assert!(items[0].as_ref().unwrap().joystick_left_x < -50);
+    assert!(matches!(items[1], Err(Error::I2C(ErrorKind::Other))));
+    assert!(items[2].as_ref().unwrap().button_a);
+    // No wait before the first read
+    assert_eq!(delay.0.get(), 2);
+    i2c.done();
+}
+
+#[test]
+fn driver_is_usable_after_leaving_the_loop() {
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_JOY_L));
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_JOY_R));
+    // Recalibration read
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_IDLE));
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_JOY_R));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut nunchuk = Nunchuk::new(i2c.clone(), IntervalCounter::default()).unwrap();
+    for input in nunchuk.iter_readings(INTERVAL_US) {
+        if input.unwrap().joystick_x > 90 {
+            break;
+        }
+    }
+    nunchuk.update_calibration().unwrap();
+    assert!(nunchuk.read().unwrap().joystick_x > 90);
+    i2c.done();
+}
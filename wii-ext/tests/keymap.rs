@@ -0,0 +1,120 @@
+use wii_ext::core::classic::ClassicReadingCalibrated;
+use wii_ext::core::events::Button;
+use wii_ext::core::keymap::{KeyEvent, KeyMap};
+use wii_ext::core::nunchuk::NunchukReadingCalibrated;
+
+const KEY_Z: u8 = 0x1D;
+const KEY_X: u8 = 0x1B;
+const KEY_UP: u8 = 0x52;
+const KEY_LEFT_SHIFT: u8 = 0xE1;
+
+const MAP: KeyMap<4> = KeyMap::new([
+    (Button::A, KEY_Z),
+    (Button::B, KEY_X),
+    (Button::DpadUp, KEY_UP),
+    (Button::Minus, KEY_LEFT_SHIFT),
+]);
+
+fn events(map: &mut KeyMap<4>, reading: &ClassicReadingCalibrated) -> Vec<KeyEvent> {
+    map.update(reading).collect()
+}
+
+#[test]
+fn scripted_button_sequence() {
+    let mut map = MAP;
+    let mut reading = ClassicReadingCalibrated::default();
+    assert_eq!(events(&mut map, &reading), []);
+
+    reading.button_a = true;
+    reading.dpad_up = true;
+    assert_eq!(
+        events(&mut map, &reading),
+        [KeyEvent::Pressed(KEY_Z), KeyEvent::Pressed(KEY_UP)]
+    );
+    // Holding doesn't repeat
+    assert_eq!(events(&mut map, &reading), []);
+    assert_eq!(map.held_keys().collect::<Vec<_>>(), [KEY_Z, KEY_UP]);
+
+    reading.button_a = false;
+    reading.button_b = true;
+    assert_eq!(
+        events(&mut map, &reading),
+        [KeyEvent::Released(KEY_Z), KeyEvent::Pressed(KEY_X)]
+    );
+
+    // Unmapped buttons are ignored
+    reading.button_home = true;
+    assert_eq!(events(&mut map, &reading), []);
+
+    assert_eq!(
+        map.release_all().collect::<Vec<_>>(),
+        [KeyEvent::Released(KEY_X), KeyEvent::Released(KEY_UP)]
+    );
+    assert_eq!(map.held_keys().count(), 0);
+}
+
+#[test]
+fn state_updates_without_consuming_events() {
+    let mut map = MAP;
+    let reading = ClassicReadingCalibrated::default().with_button_b(true);
+    drop(map.update(&reading));
+    assert_eq!(map.held_keys().collect::<Vec<_>>(), [KEY_X]);
+    assert_eq!(events(&mut map, &reading), []);
+}
+
+#[test]
+fn nunchuk_buttons_map_too() {
+    let mut map = KeyMap::new([(Button::C, KEY_Z), (Button::Z, KEY_X), (Button::A, KEY_UP)]);
+    let reading = NunchukReadingCalibrated::default().with_button_c(true);
+    assert_eq!(
+        map.update(&reading).collect::<Vec<_>>(),
+        [KeyEvent::Pressed(KEY_Z)]
+    );
+}
+
+#[cfg(feature = "hid")]
+mod report {
+    use super::*;
+    use wii_ext::hid::{KeyboardReport, KEY_ERROR_ROLL_OVER};
+
+    #[test]
+    fn report_follows_held_keys() {
+        let mut map = MAP;
+        let mut reading = ClassicReadingCalibrated::default();
+        map.update(&reading).for_each(drop);
+        assert_eq!(KeyboardReport::from(&map).to_bytes(), [0; 8]);
+
+        reading.button_a = true;
+        reading.dpad_up = true;
+        reading.button_minus = true;
+        map.update(&reading).for_each(drop);
+        assert_eq!(
+            KeyboardReport::from(&map).to_bytes(),
+            [0b0000_0010, 0, KEY_Z, KEY_UP, 0, 0, 0, 0]
+        );
+
+        reading.button_a = false;
+        map.update(&reading).for_each(drop);
+        assert_eq!(
+            KeyboardReport::from(&map).to_bytes(),
+            [0b0000_0010, 0, KEY_UP, 0, 0, 0, 0, 0]
+        );
+    }
+
+    #[test]
+    fn repeated_keys_take_one_slot() {
+        let report = KeyboardReport::from_keys([KEY_Z, KEY_Z, KEY_X]);
+        assert_eq!(report.keys, [KEY_Z, KEY_X, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn too_many_keys_roll_over() {
+        let report = KeyboardReport::from_keys([4, 5, 6, 7, 8, 9, KEY_LEFT_SHIFT]);
+        assert_eq!(report.keys, [4, 5, 6, 7, 8, 9]);
+        assert_eq!(report.modifiers, 0b10);
+
+        let report = KeyboardReport::from_keys([4, 5, 6, 7, KEY_LEFT_SHIFT, 8, 9, 10]);
+        assert_eq!(report.modifiers, 0b10);
+        assert_eq!(report.keys, [KEY_ERROR_ROLL_OVER; 6]);
+    }
+}
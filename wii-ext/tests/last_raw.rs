@@ -0,0 +1,90 @@
+use embassy_futures::block_on;
+use embedded_hal_mock::eh1::{
+    delay::NoopDelay,
+    i2c::{self, Transaction},
+};
+use wii_ext::blocking_impl::classic::{Classic, ClassicBuilder};
+use wii_ext::blocking_impl::nunchuk::Nunchuk;
+use wii_ext::core::raw::{RawFrame, RawReport};
+use wii_ext::core::EXT_I2C_ADDR;
+mod common;
+use common::{test_data, transactions};
+
+#[test]
+fn classic_keeps_last_report() {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.extend(transactions::read(&test_data::CLASSIC_LJOY_UR));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).unwrap();
+    // The calibration read
+    assert_eq!(
+        classic.last_raw(),
+        Some(RawFrame::Standard(RawReport(test_data::CLASSIC_IDLE)))
+    );
+    classic.read().unwrap();
+    assert_eq!(
+        classic.last_raw(),
+        Some(RawFrame::Standard(RawReport(test_data::CLASSIC_LJOY_UR)))
+    );
+    i2c.done();
+}
+
+#[test]
+fn hires_report_is_8_bytes() {
+    let mut expectations = transactions::init_no_calibration();
+    expectations.extend(transactions::save_report_format(1));
+    expectations.push(Transaction::write(EXT_I2C_ADDR as u8, vec![254, 3]));
+    expectations.extend(transactions::read(&test_data::CLASSIC_HD_IDLE));
+    expectations.extend(transactions::read(&test_data::CLASSIC_HD_BTN_X));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = ClassicBuilder::new()
+        .hires(true)
+        .build(i2c.clone(), NoopDelay::new())
+        .unwrap();
+    classic.read().unwrap();
+    assert_eq!(
+        classic.last_raw(),
+        Some(RawFrame::HiRes(RawReport(test_data::CLASSIC_HD_BTN_X)))
+    );
+    i2c.done();
+}
+
+#[test]
+fn failed_decode_keeps_offending_report() {
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    expectations.extend(transactions::read(&[0; 6]));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut nunchuk = Nunchuk::new(i2c.clone(), NoopDelay::new()).unwrap();
+    assert!(nunchuk.read().is_err());
+    assert_eq!(
+        nunchuk.last_raw(),
+        Some(RawFrame::Standard(RawReport([0; 6])))
+    );
+    i2c.done();
+}
+
+#[test]
+fn nothing_kept_before_first_read() {
+    let mut i2c = i2c::Mock::new(&[]);
+    let classic = Classic::new_unchecked(i2c.clone(), NoopDelay::new());
+    assert_eq!(classic.last_raw(), None);
+    i2c.done();
+}
+
+#[test]
+fn async_nunchuk_keeps_last_report() {
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_JOY_R));
+    let mut i2c = i2c::Mock::new(&expectations);
+    block_on(async {
+        let mut nunchuk = wii_ext::async_impl::nunchuk::Nunchuk::new(i2c.clone(), NoopDelay::new())
+            .await
+            .unwrap();
+        nunchuk.read().await.unwrap();
+        assert_eq!(
+            nunchuk.last_raw(),
+            Some(RawFrame::Standard(RawReport(test_data::NUNCHUCK_JOY_R)))
+        );
+    });
+    i2c.done();
+}
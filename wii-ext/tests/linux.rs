@@ -0,0 +1,41 @@
+#![cfg(feature = "linux")]
+
+use embedded_hal::delay::DelayNs;
+use std::time::{Duration, Instant};
+use wii_ext::linux::{classic, nunchuk, open, LinuxError, StdDelay};
+use wii_ext::Error;
+
+#[test]
+fn std_delay_sleeps() {
+    let start = Instant::now();
+    StdDelay.delay_us(2_000);
+    assert!(start.elapsed() >= Duration::from_millis(2));
+}
+
+#[test]
+fn open_failure_is_a_bus_error() {
+    let result = open("/dev/i2c-does-not-exist");
+    assert!(matches!(result, Err(Error::I2C(_))));
+    assert!(matches!(
+        classic("/dev/i2c-does-not-exist"),
+        Err(Error::I2C(_))
+    ));
+    assert!(matches!(
+        nunchuk("/dev/i2c-does-not-exist"),
+        Err(Error::I2C(_))
+    ));
+}
+
+#[test]
+fn errors_work_with_question_mark() {
+    fn run() -> Result<(), Box<dyn std::error::Error>> {
+        classic("/dev/i2c-does-not-exist")?;
+        Ok(())
+    }
+    let error = run().unwrap_err();
+    assert_eq!(error.to_string(), "i2c bus error");
+    // The underlying error is kept as the source
+    let source = error.source().unwrap();
+    assert!(source.to_string().starts_with("embedded-hal 0.2 bus error"));
+    assert!(error.downcast_ref::<LinuxError>().is_some());
+}
@@ -0,0 +1,78 @@
+#![cfg(feature = "log")]
+use embedded_hal::i2c::ErrorKind;
+use embedded_hal_mock::eh1::{
+    delay::NoopDelay,
+    i2c::{self, Transaction},
+};
+use log::{Level, Log, Metadata, Record};
+use std::sync::{Mutex, MutexGuard};
+use wii_ext::blocking_impl::classic::Classic;
+use wii_ext::core::EXT_I2C_ADDR;
+mod common;
+use common::{test_data, transactions};
+
+/// Records every log message, for the tests to inspect
+struct Capture(Mutex<Vec<(Level, String)>>);
+
+impl Log for Capture {
+    fn enabled(&self, _: &Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record) {
+        let message = record.args().to_string();
+        self.0.lock().unwrap().push((record.level(), message));
+    }
+
+    fn flush(&self) {}
+}
+
+static CAPTURE: Capture = Capture(Mutex::new(Vec::new()));
+/// Tests share the global logger, so they take turns
+static SERIAL: Mutex<()> = Mutex::new(());
+
+/// Install the logger if needed, and clear the records of any earlier test
+fn capture() -> MutexGuard<'static, ()> {
+    let guard = SERIAL.lock().unwrap_or_else(|e| e.into_inner());
+    if log::set_logger(&CAPTURE).is_ok() {
+        log::set_max_level(log::LevelFilter::Trace);
+    }
+    CAPTURE.0.lock().unwrap().clear();
+    guard
+}
+
+fn records() -> Vec<(Level, String)> {
+    CAPTURE.0.lock().unwrap().clone()
+}
+
+#[test]
+fn init_sequence_is_logged() {
+    let _guard = capture();
+    let mut i2c = i2c::Mock::new(&transactions::init(&test_data::CLASSIC_IDLE));
+    Classic::new(i2c.clone(), NoopDelay::new()).unwrap();
+    let records = records();
+    // Three writes for the init sequence, then a seek and a read for calibration
+    assert_eq!(records.len(), 5);
+    assert!(records.iter().all(|(level, _)| *level == Level::Trace));
+    assert_eq!(records[0].1, "seek to 0x00");
+    assert_eq!(records[1].1, "write [F0 55]");
+    assert!(records[4].1.starts_with("read ["));
+    i2c.done();
+}
+
+#[test]
+fn failures_are_logged() {
+    let _guard = capture();
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.push(Transaction::write(EXT_I2C_ADDR as u8, vec![0]).with_error(ErrorKind::Other));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).unwrap();
+    assert!(classic.read().is_err());
+    let records = records();
+    assert_eq!(records.len(), 6);
+    assert_eq!(
+        records[5],
+        (Level::Debug, "write failed: Other".to_string())
+    );
+    i2c.done();
+}
@@ -0,0 +1,109 @@
+use embedded_hal::i2c::ErrorKind;
+use embedded_hal_mock::eh1::{
+    delay::NoopDelay,
+    i2c::{self, Transaction},
+};
+use wii_ext::blocking_impl::classic::Classic;
+use wii_ext::blocking_impl::manager::{Manager, Reading, SlotState};
+use wii_ext::blocking_impl::nunchuk::Nunchuk;
+use wii_ext::core::EXT_I2C_ADDR;
+mod common;
+use common::{test_data, transactions};
+
+/// A re-init where the controller doesn't acknowledge the reset
+fn failed_reinit() -> Vec<Transaction> {
+    vec![Transaction::write(EXT_I2C_ADDR as u8, vec![0]).with_error(ErrorKind::Other)]
+}
+
+#[test]
+fn failing_slot_does_not_stall_healthy_slot() {
+    let mut classic_expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    classic_expectations.extend(transactions::read(&test_data::CLASSIC_BTN_A));
+    classic_expectations.extend(transactions::read(&test_data::CLASSIC_BTN_B));
+    classic_expectations.extend(transactions::read(&test_data::CLASSIC_BTN_X));
+    let mut nunchuk_expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    // Unplugged: the read fails, then so does each attempt to re-init
+    nunchuk_expectations.extend(transactions::failed_read(6));
+    nunchuk_expectations.extend(failed_reinit());
+    nunchuk_expectations.extend(failed_reinit());
+    let mut classic_i2c = i2c::Mock::new(&classic_expectations);
+    let mut nunchuk_i2c = i2c::Mock::new(&nunchuk_expectations);
+    let mut classic = Classic::new(classic_i2c.clone(), NoopDelay::new()).unwrap();
+    let mut nunchuk = Nunchuk::new(nunchuk_i2c.clone(), NoopDelay::new()).unwrap();
+
+    let mut manager = Manager::new([Some(&mut classic as _), Some(&mut nunchuk as _)]);
+    assert_eq!(manager.state(1), SlotState::Connected);
+    manager.poll_all();
+    assert_eq!(manager.state(0), SlotState::Connected);
+    assert_eq!(manager.state(1), SlotState::Disconnected);
+    assert_eq!(manager.latest(1), None);
+    assert_eq!(manager.disconnects(1), 1);
+    let Some(Reading::Classic(input)) = manager.latest(0) else {
+        panic!("expected a classic reading");
+    };
+    assert!(input.button_a);
+
+    manager.poll_all();
+    assert!(matches!(manager.latest(0), Some(Reading::Classic(r)) if r.button_b));
+    manager.poll_all();
+    assert!(matches!(manager.latest(0), Some(Reading::Classic(r)) if r.button_x));
+    assert_eq!(manager.state(1), SlotState::Disconnected);
+    // Still one disconnect, the failed re-inits don't count again
+    assert_eq!(manager.disconnects(1), 1);
+    assert_eq!(manager.disconnects(0), 0);
+    assert_eq!(manager.stats(0).unwrap().i2c_errors, 0);
+    assert_eq!(manager.stats(1).unwrap().i2c_errors, 1);
+    classic_i2c.done();
+    nunchuk_i2c.done();
+}
+
+#[test]
+fn disconnected_slot_reconnects() {
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    expectations.extend(transactions::failed_read(6));
+    expectations.extend(failed_reinit());
+    // Plugged back in
+    expectations.extend(transactions::init_no_calibration());
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_BTN_Z));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut nunchuk = Nunchuk::new(i2c.clone(), NoopDelay::new()).unwrap();
+
+    let mut manager = Manager::new([Some(&mut nunchuk as _)]);
+    manager.poll_all();
+    manager.poll_all();
+    assert_eq!(manager.state(0), SlotState::Disconnected);
+    manager.poll_all();
+    assert_eq!(manager.state(0), SlotState::Connected);
+    assert!(matches!(manager.latest(0), Some(Reading::Nunchuk(r)) if r.button_z));
+    // Both attempts count, including the one that failed
+    assert_eq!(manager.stats(0).unwrap().reinits, 2);
+    i2c.done();
+}
+
+#[test]
+fn poll_takes_occupied_slots_in_turn() {
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_BTN_C));
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_BTN_Z));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut nunchuk = Nunchuk::new(i2c.clone(), NoopDelay::new()).unwrap();
+
+    let mut manager = Manager::<_, 3>::new([None, Some(&mut nunchuk as _), None]);
+    assert_eq!(manager.state(0), SlotState::Empty);
+    assert_eq!(manager.stats(0), None);
+    assert_eq!(manager.poll(), Some(1));
+    assert_eq!(manager.poll(), Some(1));
+    assert!(matches!(manager.latest(1), Some(Reading::Nunchuk(r)) if r.button_z));
+    // Out of range slots are empty
+    assert_eq!(manager.state(7), SlotState::Empty);
+    assert_eq!(manager.latest(7), None);
+    i2c.done();
+}
+
+#[test]
+fn poll_with_no_controllers_does_nothing() {
+    let mut manager = Manager::<ErrorKind, 2>::new([None, None]);
+    assert_eq!(manager.poll(), None);
+    manager.poll_all();
+    assert!(manager.into_inner().iter().all(Option::is_none));
+}
@@ -0,0 +1,56 @@
+use wii_ext::core::motion_plus::{MotionPlusReading, GYRO_ZERO};
+
+#[test]
+fn centered_report() {
+    let report = MotionPlusReading::CENTERED.to_report();
+    assert_eq!(report, [0x00, 0x00, 0x00, 0x83, 0x82, 0x82]);
+    let reading = MotionPlusReading::from_report(&report).unwrap();
+    assert_eq!(reading, MotionPlusReading::CENTERED);
+    assert_eq!(
+        (reading.yaw_dps(), reading.roll_dps(), reading.pitch_dps()),
+        (0, 0, 0)
+    );
+}
+
+#[test]
+fn mixed_range_flags() {
+    // Yaw fast, roll slow, pitch fast, with an extension plugged in
+    let report = [0x00, 0x00, 0x00, 0b1010_0000, 0b1010_0011, 0b0110_0010];
+    let reading = MotionPlusReading::from_report(&report).unwrap();
+    assert_eq!(reading.yaw, 0x2800);
+    assert_eq!(reading.roll, 0x2800);
+    assert_eq!(reading.pitch, 0x1800);
+    assert!(!reading.yaw_slow);
+    assert!(reading.roll_slow);
+    assert!(!reading.pitch_slow);
+    assert!(reading.extension_connected);
+    // The same deflection is about 4.5 times faster in the fast range
+    assert_eq!(reading.roll_dps(), 14_875);
+    assert_eq!(reading.yaw_dps(), 67_613);
+    assert_eq!(reading.pitch_dps(), -67_613);
+    assert_eq!(
+        MotionPlusReading::from_report(&reading.to_report()),
+        Some(reading)
+    );
+}
+
+#[test]
+fn full_scale() {
+    let slow = MotionPlusReading::CENTERED
+        .with_yaw(0)
+        .with_roll(GYRO_ZERO * 2 - 1)
+        .with_pitch(GYRO_ZERO * 2 - 1)
+        .with_pitch_slow(false);
+    assert_eq!(slow.yaw_dps(), -59_500);
+    assert_eq!(slow.roll_dps(), 59_492);
+    assert_eq!(slow.pitch_dps(), 270_421);
+}
+
+#[test]
+fn other_reports_are_rejected() {
+    // An extension report passed through the Motion Plus has bit 1 of byte 5 clear
+    assert_eq!(
+        MotionPlusReading::from_report(&[0x80, 0x80, 0x80, 0x80, 0x80, 0xFD]),
+        None
+    );
+}
@@ -0,0 +1,101 @@
+#![cfg(feature = "mux")]
+
+use core::cell::RefCell;
+use embassy_futures::block_on;
+use embedded_hal::i2c::ErrorKind;
+use embedded_hal_bus::i2c::RefCellDevice;
+use embedded_hal_mock::eh1::{
+    delay::NoopDelay,
+    i2c::{self, Transaction},
+};
+use wii_ext::blocking_impl::classic::Classic;
+use wii_ext::blocking_impl::nunchuk::Nunchuk;
+use wii_ext::mux::{MuxError, MuxedBus, TCA9548A_ADDR};
+use wii_ext::Error;
+mod common;
+use common::{test_data, transactions};
+
+fn select(channel: u8) -> Transaction {
+    Transaction::write(TCA9548A_ADDR, vec![1 << channel])
+}
+
+/// Put a channel select before each transaction
+fn on_channel(channel: u8, transactions: Vec<Transaction>) -> Vec<Transaction> {
+    transactions
+        .into_iter()
+        .flat_map(|t| [select(channel), t])
+        .collect()
+}
+
+#[test]
+fn channels_are_selected_before_each_transaction() {
+    let mut expectations = on_channel(0, transactions::init(&test_data::CLASSIC_IDLE));
+    expectations.extend(on_channel(1, transactions::init(&test_data::NUNCHUCK_IDLE)));
+    expectations.extend(on_channel(0, transactions::read(&test_data::CLASSIC_BTN_A)));
+    expectations.extend(on_channel(
+        1,
+        transactions::read(&test_data::NUNCHUCK_BTN_C),
+    ));
+    expectations.extend(on_channel(0, transactions::read(&test_data::CLASSIC_BTN_B)));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let bus = RefCell::new(i2c.clone());
+    let classic = MuxedBus::new(RefCellDevice::new(&bus), TCA9548A_ADDR, 0).unwrap();
+    let nunchuk = MuxedBus::new(RefCellDevice::new(&bus), TCA9548A_ADDR, 1).unwrap();
+    let mut classic = Classic::new(classic, NoopDelay::new()).unwrap();
+    let mut nunchuk = Nunchuk::new(nunchuk, NoopDelay::new()).unwrap();
+    assert!(classic.read().unwrap().button_a);
+    assert!(nunchuk.read().unwrap().button_c);
+    assert!(classic.read().unwrap().button_b);
+    i2c.done();
+}
+
+#[test]
+fn select_failure_is_reported_separately() {
+    let mut expectations = on_channel(7, transactions::init(&test_data::CLASSIC_IDLE));
+    // The mux doesn't answer
+    expectations.push(select(7).with_error(ErrorKind::Other));
+    // The mux answers, but the controller doesn't
+    expectations.push(select(7));
+    expectations.push(
+        Transaction::write(0x52, vec![0]).with_error(ErrorKind::NoAcknowledge(
+            embedded_hal::i2c::NoAcknowledgeSource::Address,
+        )),
+    );
+    let mut i2c = i2c::Mock::new(&expectations);
+    let bus = MuxedBus::new(i2c.clone(), TCA9548A_ADDR, 7).unwrap();
+    let mut classic = Classic::new(bus, NoopDelay::new()).unwrap();
+    assert!(matches!(
+        classic.read(),
+        Err(Error::I2C(MuxError::Select(ErrorKind::Other)))
+    ));
+    assert!(matches!(
+        classic.read(),
+        Err(Error::I2C(MuxError::Device(ErrorKind::NoAcknowledge(_))))
+    ));
+    i2c.done();
+}
+
+#[test]
+fn channel_must_exist() {
+    let mut i2c = i2c::Mock::new(&[]);
+    assert!(MuxedBus::new(i2c.clone(), TCA9548A_ADDR, 8).is_none());
+    assert_eq!(MuxedBus::new(i2c.clone(), 0x77, 3).unwrap().channel(), 3);
+    i2c.done();
+}
+
+#[test]
+fn async_drivers_select_channels() {
+    use wii_ext::async_impl::nunchuk::Nunchuk;
+    let mut expectations = on_channel(2, transactions::init(&test_data::NUNCHUCK_IDLE));
+    expectations.extend(on_channel(
+        2,
+        transactions::read(&test_data::NUNCHUCK_BTN_Z),
+    ));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let bus = MuxedBus::new(i2c.clone(), TCA9548A_ADDR, 2).unwrap();
+    block_on(async {
+        let mut nunchuk = Nunchuk::new(bus, NoopDelay::new()).await.unwrap();
+        assert!(nunchuk.read().await.unwrap().button_z);
+    });
+    i2c.done();
+}
@@ -0,0 +1,66 @@
+use embedded_hal_mock::eh1::{delay::NoopDelay, i2c};
+use wii_ext::blocking_impl::classic::Classic;
+use wii_ext::blocking_impl::nunchuk::Nunchuk;
+use wii_ext::core::classic::CalibrationData as ClassicCalibration;
+use wii_ext::core::nunchuk::CalibrationData as NunchukCalibration;
+mod common;
+use common::{test_data, transactions};
+
+#[test]
+fn classic_new_unchecked_no_transactions() {
+    let mut i2c = i2c::Mock::new(&[]);
+    let classic = Classic::new_unchecked(i2c.clone(), NoopDelay::new());
+    assert_eq!(classic.calibration(), ClassicCalibration::default());
+    i2c.done();
+}
+
+#[test]
+fn nunchuk_new_unchecked_no_transactions() {
+    let mut i2c = i2c::Mock::new(&[]);
+    let nunchuk = Nunchuk::new_unchecked(i2c.clone(), NoopDelay::new());
+    assert_eq!(nunchuk.calibration(), NunchukCalibration::default());
+    i2c.done();
+}
+
+#[test]
+fn classic_new_unchecked_hires_read() {
+    let idle = test_data::CLASSIC_HD_IDLE;
+    let calibration = ClassicCalibration {
+        joystick_left_x: idle[0],
+        joystick_right_x: idle[1],
+        joystick_left_y: idle[2],
+        joystick_right_y: idle[3],
+        trigger_left: idle[4],
+        trigger_right: idle[5],
+    };
+    let expectations = transactions::read(&test_data::CLASSIC_HD_LJOY_R);
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = Classic::new_unchecked(i2c.clone(), NoopDelay::new());
+    classic.set_calibration(calibration);
+    classic.assume_hires(true);
+    let input = classic.read().unwrap();
+    assert_eq!(
+        input.joystick_left_x,
+        (test_data::CLASSIC_HD_LJOY_R[0] - idle[0]) as i8
+    );
+    assert!((-5..=5).contains(&input.joystick_left_y));
+    i2c.done();
+}
+
+#[test]
+fn nunchuk_new_unchecked_read() {
+    let calibration = NunchukCalibration {
+        joystick_x: test_data::NUNCHUCK_IDLE[0],
+        joystick_y: test_data::NUNCHUCK_IDLE[1],
+    };
+    let expectations = transactions::read(&test_data::NUNCHUCK_IDLE);
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut nunchuk = Nunchuk::new_unchecked(i2c.clone(), NoopDelay::new());
+    nunchuk.set_calibration(calibration);
+    let input = nunchuk.read().unwrap();
+    assert_eq!(input.joystick_x, 0);
+    assert_eq!(input.joystick_y, 0);
+    assert!(!input.button_c);
+    assert!(!input.button_z);
+    i2c.done();
+}
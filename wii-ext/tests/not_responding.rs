@@ -0,0 +1,89 @@
+use embassy_futures::block_on;
+use embedded_hal_mock::eh1::{
+    delay::NoopDelay,
+    i2c::{self, Transaction},
+};
+use wii_ext::blocking_impl::classic::{Classic, ClassicBuilder};
+use wii_ext::blocking_impl::nunchuk::Nunchuk;
+use wii_ext::core::EXT_I2C_ADDR;
+use wii_ext::Error;
+mod common;
+use common::{test_data, transactions};
+
+#[test]
+fn classic_zero_report_is_not_responding() {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.extend(transactions::read(&[0; 6]));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).unwrap();
+    assert!(matches!(classic.read(), Err(Error::NotResponding)));
+    assert!(classic.check_failures().not_responding);
+    assert_eq!(classic.stats().invalid_data_errors, 1);
+    i2c.done();
+}
+
+#[test]
+fn classic_hires_zero_report_is_not_responding() {
+    let mut expectations = transactions::init_no_calibration();
+    expectations.extend(transactions::save_report_format(1));
+    expectations.push(Transaction::write(EXT_I2C_ADDR as u8, vec![254, 3]));
+    expectations.extend(transactions::read(&test_data::PRO_HD_IDLE));
+    expectations.extend(transactions::read(&[0; 8]));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = ClassicBuilder::new()
+        .hires(true)
+        .build(i2c.clone(), NoopDelay::new())
+        .unwrap();
+    assert!(matches!(classic.read(), Err(Error::NotResponding)));
+    i2c.done();
+}
+
+#[test]
+fn nunchuk_zero_report_is_not_responding() {
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    expectations.extend(transactions::read(&[0; 6]));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut nunchuk = Nunchuk::new(i2c.clone(), NoopDelay::new()).unwrap();
+    assert!(matches!(nunchuk.read(), Err(Error::NotResponding)));
+    i2c.done();
+}
+
+#[test]
+fn valid_reports_are_responding() {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.extend(transactions::read(&test_data::CLASSIC_BTN_A));
+    expectations.extend(transactions::read(&test_data::CLASSIC_LJOY_L));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).unwrap();
+    assert!(classic.read().unwrap().button_a);
+    assert!(classic.read().unwrap().joystick_left_x < -90);
+    assert!(!classic.check_failures().not_responding);
+    i2c.done();
+}
+
+#[test]
+fn recovery_reinitialises_after_zero_report() {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.extend(transactions::read(&[0; 6]));
+    expectations.extend(transactions::init_no_calibration());
+    expectations.extend(transactions::read(&test_data::CLASSIC_BTN_A));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).unwrap();
+    assert!(classic.read_with_recovery(2).unwrap().button_a);
+    assert_eq!(classic.stats().reinits, 1);
+    i2c.done();
+}
+
+#[test]
+fn async_zero_report_is_not_responding() {
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    expectations.extend(transactions::read(&[0; 6]));
+    let mut i2c = i2c::Mock::new(&expectations);
+    block_on(async {
+        let mut nunchuk = wii_ext::async_impl::nunchuk::Nunchuk::new(i2c.clone(), NoopDelay::new())
+            .await
+            .unwrap();
+        assert!(matches!(nunchuk.read().await, Err(Error::NotResponding)));
+    });
+    i2c.done();
+}
@@ -0,0 +1,87 @@
+// The blocking nunchuk tests, run against the async driver
+use embassy_futures::block_on;
+use embedded_hal_mock::eh1::{delay::NoopDelay, i2c};
+use wii_ext::async_impl::nunchuk::Nunchuk;
+use wii_ext::core::nunchuk::NunchukReadingCalibrated;
+use wii_ext::core::ControllerType;
+mod common;
+use common::{test_data, transactions};
+
+/// There's a certain amount of slop around the center position.
+/// Allow up to this range without it being an error
+const ZERO_SLOP: i8 = 5;
+/// The max value at full deflection is ~100, but allow a bit less than that
+const AXIS_MAX: i8 = 90;
+
+/// Create a nunchuk calibrated on `calibration`, then take a single reading of `report`
+fn read_once(calibration: &[u8], report: &[u8]) -> NunchukReadingCalibrated {
+    let mut expectations = transactions::init(calibration);
+    expectations.extend(transactions::read(report));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let input = block_on(async {
+        let mut nunchuk = Nunchuk::new(i2c.clone(), NoopDelay::new()).await.unwrap();
+        nunchuk.read().await.unwrap()
+    });
+    i2c.done();
+    input
+}
+
+#[test]
+fn nunchuck_idle() {
+    let input = read_once(&test_data::NUNCHUCK_IDLE, &test_data::NUNCHUCK_IDLE);
+    assert!(!input.button_c);
+    assert!(!input.button_z);
+    assert_eq!(input.joystick_x, 0);
+    assert_eq!(input.joystick_y, 0);
+}
+
+#[test]
+fn nunchuck_calibrated_joystick() {
+    let input = read_once(&test_data::NUNCHUCK_IDLE, &test_data::NUNCHUCK_JOY_L);
+    assert!(input.joystick_x < -AXIS_MAX);
+    assert!((-ZERO_SLOP..=ZERO_SLOP).contains(&input.joystick_y));
+    let input = read_once(&test_data::NUNCHUCK_IDLE, &test_data::NUNCHUCK_JOY_R);
+    assert!(input.joystick_x > AXIS_MAX);
+    let input = read_once(&test_data::NUNCHUCK_IDLE, &test_data::NUNCHUCK_JOY_U);
+    assert!(input.joystick_y > AXIS_MAX);
+    let input = read_once(&test_data::NUNCHUCK_IDLE, &test_data::NUNCHUCK_JOY_D);
+    assert!(input.joystick_y < -AXIS_MAX);
+}
+
+#[test]
+fn nunchuck_buttons() {
+    let input = read_once(&test_data::NUNCHUCK_IDLE, &test_data::NUNCHUCK_BTN_C);
+    assert!(input.button_c);
+    assert!(!input.button_z);
+    let input = read_once(&test_data::NUNCHUCK_IDLE, &test_data::NUNCHUCK_BTN_Z);
+    assert!(!input.button_c);
+    assert!(input.button_z);
+}
+
+#[test]
+fn nunchuck_update_calibration() {
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_JOY_U));
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_JOY_U));
+    let mut i2c = i2c::Mock::new(&expectations);
+    block_on(async {
+        let mut nunchuk = Nunchuk::new(i2c.clone(), NoopDelay::new()).await.unwrap();
+        nunchuk.update_calibration().await.unwrap();
+        let input = nunchuk.read().await.unwrap();
+        assert_eq!(input.joystick_y, 0);
+    });
+    i2c.done();
+}
+
+#[test]
+fn nunchuck_identify_controller() {
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    expectations.extend(transactions::read_id(&test_data::NUNCHUCK_ID));
+    let mut i2c = i2c::Mock::new(&expectations);
+    block_on(async {
+        let mut nunchuk = Nunchuk::new(i2c.clone(), NoopDelay::new()).await.unwrap();
+        let controller = nunchuk.identify_controller().await.unwrap();
+        assert_eq!(controller, Some(ControllerType::Nunchuk));
+    });
+    i2c.done();
+}
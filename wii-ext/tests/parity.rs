@@ -0,0 +1,119 @@
+// The blocking and async drivers share their decoding and calibration logic,
+// so they must produce identical readings from identical bus traffic
+use embassy_futures::block_on;
+use embedded_hal_mock::eh1::{
+    delay::NoopDelay,
+    i2c::{self, Transaction},
+};
+use wii_ext::core::EXT_I2C_ADDR;
+mod common;
+use common::{test_data, transactions};
+
+fn classic_readings(hires: bool, reports: &[&[u8]]) -> (Vec<String>, Vec<String>) {
+    let calibration: &[u8] = if hires {
+        &test_data::CLASSIC_HD_IDLE
+    } else {
+        &test_data::CLASSIC_IDLE
+    };
+    let mut expectations = transactions::init_no_calibration();
+    if hires {
+        expectations.extend(transactions::save_report_format(1));
+        expectations.push(Transaction::write(EXT_I2C_ADDR as u8, vec![254, 3]));
+    }
+    expectations.extend(transactions::read(calibration));
+    for report in reports {
+        expectations.extend(transactions::read(report));
+    }
+
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = wii_ext::blocking_impl::classic::ClassicBuilder::new()
+        .hires(hires)
+        .deadzone(2)
+        .build(i2c.clone(), NoopDelay::new())
+        .unwrap();
+    let blocking = reports
+        .iter()
+        .map(|_| format!("{:?}", classic.read().unwrap()))
+        .collect();
+    i2c.done();
+
+    let mut i2c = i2c::Mock::new(&expectations);
+    let asynchronous = block_on(async {
+        let mut classic = wii_ext::async_impl::classic::ClassicBuilder::new()
+            .hires(hires)
+            .deadzone(2)
+            .build(i2c.clone(), NoopDelay::new())
+            .await
+            .unwrap();
+        let mut readings = vec![];
+        for _ in reports {
+            readings.push(format!("{:?}", classic.read().await.unwrap()));
+        }
+        readings
+    });
+    i2c.done();
+    (blocking, asynchronous)
+}
+
+#[test]
+fn classic_blocking_and_async_match() {
+    let (blocking, asynchronous) = classic_readings(
+        false,
+        &[
+            &test_data::CLASSIC_LJOY_L,
+            &test_data::CLASSIC_RJOY_U,
+            &test_data::CLASSIC_BTN_HOME,
+            &test_data::CLASSIC_LTRIG,
+        ],
+    );
+    assert_eq!(blocking, asynchronous);
+}
+
+#[test]
+fn classic_hires_blocking_and_async_match() {
+    let (blocking, asynchronous) = classic_readings(
+        true,
+        &[
+            &test_data::CLASSIC_HD_LJOY_R,
+            &test_data::CLASSIC_HD_RJOY_D,
+            &test_data::CLASSIC_HD_BTN_X,
+        ],
+    );
+    assert_eq!(blocking, asynchronous);
+}
+
+#[test]
+fn nunchuk_blocking_and_async_match() {
+    let reports = [
+        test_data::NUNCHUCK_JOY_U,
+        test_data::NUNCHUCK_JOY_L,
+        test_data::NUNCHUCK_BTN_C,
+    ];
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    for report in &reports {
+        expectations.extend(transactions::read(report));
+    }
+
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut nunchuk =
+        wii_ext::blocking_impl::nunchuk::Nunchuk::new(i2c.clone(), NoopDelay::new()).unwrap();
+    let blocking: Vec<String> = reports
+        .iter()
+        .map(|_| format!("{:?}", nunchuk.read().unwrap()))
+        .collect();
+    i2c.done();
+
+    let mut i2c = i2c::Mock::new(&expectations);
+    let asynchronous: Vec<String> = block_on(async {
+        let mut nunchuk = wii_ext::async_impl::nunchuk::Nunchuk::new(i2c.clone(), NoopDelay::new())
+            .await
+            .unwrap();
+        let mut readings = vec![];
+        for _ in &reports {
+            readings.push(format!("{:?}", nunchuk.read().await.unwrap()));
+        }
+        readings
+    });
+    i2c.done();
+    assert_eq!(blocking, asynchronous);
+}
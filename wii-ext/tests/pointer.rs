@@ -0,0 +1,100 @@
+use wii_ext::core::nunchuk::{
+    NunchukReadingCalibrated, PointerEmulator, PointerReport, PointerSource,
+};
+use wii_ext::core::tilt::TiltStick;
+use wii_ext::core::AxisCurve;
+
+/// A level nunchuk with the stick at `(x, y)`
+fn stick(joystick_x: i8, joystick_y: i8) -> NunchukReadingCalibrated {
+    NunchukReadingCalibrated::default()
+        .with_joystick_x(joystick_x)
+        .with_joystick_y(joystick_y)
+        .with_accel_x(512)
+        .with_accel_y(512)
+        .with_accel_z(712)
+}
+
+#[test]
+fn no_movement_at_rest() {
+    let mut pointer = PointerEmulator::default();
+    for _ in 0..100 {
+        assert_eq!(pointer.update(&stick(0, 0)), PointerReport::default());
+        // Noise inside the deadzone doesn't build up
+        assert_eq!(pointer.update(&stick(3, -2)), PointerReport::default());
+    }
+}
+
+#[test]
+fn movement_is_proportional_to_deflection() {
+    let mut pointer = PointerEmulator::default();
+    let full = pointer.update(&stick(127, 0));
+    assert_eq!((full.dx, full.dy), (10, 0));
+    let mut pointer = PointerEmulator::default();
+    let half: i32 = (0..10)
+        .map(|_| i32::from(pointer.update(&stick(-64, 0)).dx))
+        .sum();
+    // Fractions carry over, so ten readings at half deflection move about half as far
+    assert!((-51..=-49).contains(&half), "{half}");
+    // Stick up is pointer up the screen
+    assert_eq!(pointer.update(&stick(0, 127)).dy, -10);
+}
+
+#[test]
+fn curve_softens_small_deflections() {
+    let mut linear = PointerEmulator::default();
+    let mut expo = PointerEmulator::default();
+    expo.curve = AxisCurve::Expo(100);
+    let linear: i32 = (0..20)
+        .map(|_| i32::from(linear.update(&stick(40, 0)).dx))
+        .sum();
+    let expo: i32 = (0..20)
+        .map(|_| i32::from(expo.update(&stick(40, 0)).dx))
+        .sum();
+    assert!(expo < linear, "{expo} {linear}");
+    // Full deflection is unchanged
+    let mut expo = PointerEmulator::default();
+    expo.curve = AxisCurve::Expo(100);
+    assert_eq!(expo.update(&stick(127, 0)).dx, 10);
+}
+
+#[test]
+fn holding_z_scrolls() {
+    let mut pointer = PointerEmulator::default();
+    let mut input = stick(127, 127);
+    input.button_z = true;
+    let report = pointer.update(&input);
+    assert_eq!(
+        report,
+        PointerReport {
+            wheel: 2,
+            ..PointerReport::default()
+        }
+    );
+    input.button_z = false;
+    let report = pointer.update(&input);
+    assert_eq!((report.dx, report.dy, report.wheel), (10, -10, 0));
+    assert!(!report.right);
+}
+
+#[test]
+fn z_is_a_button_without_scrolling() {
+    let mut pointer = PointerEmulator::default();
+    pointer.scroll_with_z = false;
+    let mut input = stick(0, 127);
+    input.button_z = true;
+    input.button_c = true;
+    let report = pointer.update(&input);
+    assert!(report.left && report.right);
+    assert_eq!((report.dy, report.wheel), (-10, 0));
+}
+
+#[test]
+fn tilt_moves_the_pointer() {
+    let mut pointer = PointerEmulator::new(PointerSource::Tilt(TiltStick::default()));
+    // Rolled right by half a g, stick untouched
+    let mut input = stick(0, 0);
+    input.accel_x = 612;
+    assert_eq!(pointer.update(&input).dx, 10);
+    // The stick is ignored
+    assert_eq!(pointer.update(&stick(127, 0)), PointerReport::default());
+}
@@ -0,0 +1,141 @@
+use embassy_futures::block_on;
+use embedded_hal_mock::eh1::{delay::NoopDelay, i2c};
+use std::cell::Cell;
+use std::rc::Rc;
+use wii_ext::blocking_impl::classic::ClassicBuilder;
+use wii_ext::blocking_impl::nunchuk::Nunchuk;
+use wii_ext::core::clock::Clock;
+use wii_ext::core::{EarlyRead, PollInterval, INTERMESSAGE_DELAY_MICROSEC_U32};
+mod common;
+use common::{test_data, transactions};
+
+/// A clock that only moves when the test says so
+#[derive(Default)]
+struct TestClock(u64);
+
+impl TestClock {
+    fn advance(&mut self, micros: u64) {
+        self.0 += micros;
+    }
+}
+
+impl Clock for TestClock {
+    fn now_micros(&mut self) -> u64 {
+        self.0
+    }
+}
+
+/// A delay that records each wait it is asked for
+#[derive(Clone, Default)]
+struct RecordingDelay(Rc<Cell<Vec<u32>>>);
+
+impl RecordingDelay {
+    fn take(&self) -> Vec<u32> {
+        self.0.take()
+    }
+}
+
+impl embedded_hal::delay::DelayNs for RecordingDelay {
+    fn delay_ns(&mut self, ns: u32) {
+        let mut waits = self.0.take();
+        waits.push(ns / 1000);
+        self.0.set(waits);
+    }
+}
+
+impl embedded_hal_async::delay::DelayNs for RecordingDelay {
+    async fn delay_ns(&mut self, ns: u32) {
+        embedded_hal::delay::DelayNs::delay_ns(self, ns)
+    }
+}
+
+const fn interval(early_read: EarlyRead) -> PollInterval {
+    PollInterval {
+        min_us: 10_000,
+        early_read,
+    }
+}
+
+#[test]
+fn early_read_waits_out_the_interval() {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.extend(transactions::read(&test_data::CLASSIC_BTN_A));
+    expectations.extend(transactions::read(&test_data::CLASSIC_BTN_B));
+    expectations.extend(transactions::read(&test_data::CLASSIC_BTN_X));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let delay = RecordingDelay::default();
+    let mut classic = ClassicBuilder::new()
+        .min_poll_interval(interval(EarlyRead::Wait))
+        .build(i2c.clone(), delay.clone())
+        .unwrap();
+    let mut clock = TestClock::default();
+    let read_delay = INTERMESSAGE_DELAY_MICROSEC_U32;
+    delay.take();
+    assert!(classic.read_throttled(&mut clock).unwrap().button_a);
+    assert_eq!(delay.take(), [read_delay]);
+    clock.advance(4000);
+    assert!(classic.read_throttled(&mut clock).unwrap().button_b);
+    assert_eq!(delay.take(), [6000, read_delay]);
+    // Late reads don't wait
+    clock.advance(20_000);
+    assert!(classic.read_throttled(&mut clock).unwrap().button_x);
+    assert_eq!(delay.take(), [read_delay]);
+    i2c.done();
+}
+
+#[test]
+fn early_read_returns_cached_reading() {
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_BTN_C));
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_BTN_Z));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut nunchuk = Nunchuk::new(i2c.clone(), NoopDelay::new()).unwrap();
+    nunchuk.set_min_poll_interval(Some(interval(EarlyRead::Cached)));
+    let mut clock = TestClock::default();
+    let first = nunchuk.read_throttled(&mut clock).unwrap();
+    assert!(first.button_c);
+    clock.advance(9999);
+    // No bus transactions for this one
+    assert_eq!(nunchuk.read_throttled(&mut clock).unwrap(), first);
+    clock.advance(1);
+    assert!(nunchuk.read_throttled(&mut clock).unwrap().button_z);
+    i2c.done();
+}
+
+#[test]
+fn async_early_read_waits_out_the_interval() {
+    use wii_ext::async_impl::nunchuk::NunchukBuilder;
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_BTN_C));
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_BTN_Z));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let delay = RecordingDelay::default();
+    let mut clock = TestClock::default();
+    block_on(async {
+        let mut nunchuk = NunchukBuilder::new()
+            .min_poll_interval(interval(EarlyRead::Wait))
+            .build(i2c.clone(), delay.clone())
+            .await
+            .unwrap();
+        assert!(nunchuk.read_throttled(&mut clock).await.unwrap().button_c);
+        delay.take();
+        clock.advance(2500);
+        assert!(nunchuk.read_throttled(&mut clock).await.unwrap().button_z);
+        assert_eq!(delay.take(), [7500, INTERMESSAGE_DELAY_MICROSEC_U32]);
+    });
+    i2c.done();
+}
+
+#[test]
+fn plain_read_ignores_the_interval() {
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_BTN_C));
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_BTN_Z));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut nunchuk = Nunchuk::new(i2c.clone(), NoopDelay::new()).unwrap();
+    nunchuk.set_min_poll_interval(Some(interval(EarlyRead::Cached)));
+    let mut clock = TestClock::default();
+    assert!(nunchuk.read_throttled(&mut clock).unwrap().button_c);
+    assert!(nunchuk.read().unwrap().button_z);
+    i2c.done();
+}
@@ -0,0 +1,85 @@
+#![cfg(feature = "embassy")]
+use embassy_futures::select::{select, Either};
+use embassy_futures::{block_on, yield_now};
+use embassy_sync::blocking_mutex::raw::NoopRawMutex;
+use embassy_sync::watch::Watch;
+use embedded_hal::i2c::ErrorKind;
+use embedded_hal_mock::eh1::{
+    delay::NoopDelay,
+    i2c::{self, Transaction},
+};
+use wii_ext::async_impl::classic::Classic;
+use wii_ext::async_impl::nunchuk::Nunchuk;
+use wii_ext::async_impl::poller::{run, ReadingReceiver, Readings};
+use wii_ext::core::EXT_I2C_ADDR;
+mod common;
+use common::{test_data, transactions};
+
+/// A delay that only yields, so the consumer gets to run between polls
+struct YieldDelay;
+
+impl embedded_hal_async::delay::DelayNs for YieldDelay {
+    async fn delay_ns(&mut self, _ns: u32) {
+        yield_now().await;
+    }
+}
+
+#[test]
+fn poller_publishes_readings() {
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_JOY_L));
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_JOY_R));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let readings: Readings<NoopRawMutex, _, 1> = Watch::new();
+    block_on(async {
+        let nunchuk = Nunchuk::new(i2c.clone(), NoopDelay::new()).await.unwrap();
+        let mut receiver = ReadingReceiver::new(&readings).unwrap();
+        assert!(receiver.latest().is_none());
+        let consumer = async {
+            let left = receiver.next_reading().await;
+            let right = receiver.next_reading().await;
+            (left, right)
+        };
+        let Either::Second((left, right)) =
+            select(run(nunchuk, YieldDelay, 10_000, &readings), consumer).await;
+        assert!(left.joystick_x < -90);
+        assert!(right.joystick_x > 90);
+    });
+    i2c.done();
+}
+
+#[test]
+fn poller_recovers_and_reports_failures() {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    // Fails once, then recovers by re-initialising
+    expectations.extend(transactions::failed_read(6));
+    expectations.extend(transactions::init_no_calibration());
+    expectations.extend(transactions::read(&test_data::CLASSIC_LJOY_L));
+    // Fails on every attempt
+    for _ in 0..2 {
+        expectations.extend(transactions::failed_read(6));
+        expectations.extend(transactions::init_no_calibration());
+    }
+    expectations.extend(transactions::failed_read(6));
+    // Polling carries on after the failure
+    expectations.extend(transactions::read(&test_data::CLASSIC_BTN_A));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let readings: Readings<NoopRawMutex, _, 1> = Watch::new();
+    block_on(async {
+        let classic = Classic::new(i2c.clone(), NoopDelay::new()).await.unwrap();
+        let mut receiver = ReadingReceiver::new(&readings).unwrap();
+        let consumer = async {
+            let recovered = receiver.changed().await;
+            let failed = receiver.changed().await;
+            assert!(receiver.latest().is_none());
+            let next = receiver.changed().await;
+            (recovered, failed, next)
+        };
+        let Either::Second((recovered, failed, next)) =
+            select(run(classic, YieldDelay, 10_000, &readings), consumer).await;
+        assert!(recovered.unwrap().joystick_left_x < -50);
+        assert!(failed.is_none());
+        assert!(next.unwrap().button_a);
+    });
+    i2c.done();
+}
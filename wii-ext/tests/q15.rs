@@ -0,0 +1,81 @@
+#![cfg(feature = "fixed")]
+
+use wii_ext::core::classic::ClassicReadingCalibrated;
+use wii_ext::core::nunchuk::NunchukReadingCalibrated;
+use wii_ext::core::q15::{axis_to_q15, trigger_to_u0f16, I1F15, U0F16};
+use wii_ext::core::scale::{axis_to_i16, AxisExtents, ClassicExtents, NunchukExtents};
+
+const HUNDRED: AxisExtents = AxisExtents {
+    negative: 100,
+    positive: 100,
+};
+
+#[test]
+fn center_half_and_full_deflection() {
+    assert_eq!(axis_to_q15(0, HUNDRED), I1F15::ZERO);
+    // Q15 = axis_to_i16() / 32768
+    assert_eq!(axis_to_q15(-50, HUNDRED), I1F15::from_num(-0.5));
+    // 50 * 32767 / 100, rounded down
+    assert_eq!(axis_to_q15(50, HUNDRED), I1F15::from_bits(16383));
+    assert_eq!(axis_to_q15(-100, HUNDRED), I1F15::from_num(-1));
+    assert_eq!(axis_to_q15(100, HUNDRED), I1F15::MAX);
+}
+
+#[test]
+fn triggers_are_fractions() {
+    assert_eq!(trigger_to_u0f16(0, 100), U0F16::ZERO);
+    assert_eq!(trigger_to_u0f16(50, 100), U0F16::from_bits(65535 / 2));
+    assert_eq!(trigger_to_u0f16(100, 100), U0F16::MAX);
+}
+
+#[test]
+fn q15_agrees_with_i16_scaling() {
+    for extents in [AxisExtents::FULL, HUNDRED] {
+        for value in i8::MIN..=i8::MAX {
+            let q15 = axis_to_q15(value, extents);
+            assert_eq!(q15.to_bits(), axis_to_i16(value, extents));
+            let reading = ClassicReadingCalibrated::default().with_joystick_right_y(value);
+            let extents = ClassicExtents {
+                joystick_right_y: extents,
+                ..Default::default()
+            };
+            assert_eq!(
+                reading.to_q15(&extents).joystick_right_y.to_bits(),
+                reading.to_i16_full_scale(&extents).joystick_right_y
+            );
+        }
+    }
+}
+
+#[test]
+fn accessors_use_full_i8_range() {
+    let reading = ClassicReadingCalibrated::default()
+        .with_joystick_left_x(-64)
+        .with_joystick_left_y(i8::MIN)
+        .with_joystick_right_x(i8::MAX);
+    assert_eq!(reading.joystick_left_x_q15(), I1F15::from_num(-0.5));
+    assert_eq!(reading.joystick_left_y_q15(), I1F15::MIN);
+    assert_eq!(reading.joystick_right_x_q15(), I1F15::MAX);
+    assert_eq!(reading.joystick_right_y_q15(), I1F15::ZERO);
+}
+
+#[test]
+fn nunchuk_q15() {
+    let reading = NunchukReadingCalibrated::default()
+        .with_joystick_x(-50)
+        .with_joystick_y(100);
+    let axes = reading.to_q15(&NunchukExtents {
+        joystick_x: HUNDRED,
+        joystick_y: HUNDRED,
+    });
+    assert_eq!(axes.joystick_x, I1F15::from_num(-0.5));
+    assert_eq!(axes.joystick_y, I1F15::MAX);
+    assert_eq!(
+        reading.joystick_x_q15(),
+        axis_to_q15(-50, AxisExtents::FULL)
+    );
+    assert_eq!(
+        reading.joystick_y_q15(),
+        axis_to_q15(100, AxisExtents::FULL)
+    );
+}
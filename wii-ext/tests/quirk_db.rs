@@ -0,0 +1,145 @@
+use embedded_hal_mock::eh1::{
+    delay::NoopDelay,
+    i2c::{self, Transaction},
+};
+use wii_ext::blocking_impl::classic::ClassicBuilder;
+use wii_ext::core::classic::Quirks;
+use wii_ext::core::events::Button;
+use wii_ext::core::quirk_db::{find_quirks, Capabilities, QuirkEntry, KNOWN_QUIRKS};
+use wii_ext::core::EXT_I2C_ADDR;
+mod common;
+use common::{test_data, transactions};
+
+/// Transactions performed by the quirk lookup: the ID, then a report
+fn lookup(id: &[u8], report: &[u8]) -> Vec<Transaction> {
+    let mut transactions = transactions::read_id(id);
+    transactions.extend(transactions::read(report));
+    transactions.push(Transaction::write(EXT_I2C_ADDR as u8, vec![0]));
+    transactions
+}
+
+fn known(id: &[u8; 6], report: &[u8; 6]) -> Option<&'static str> {
+    find_quirks(KNOWN_QUIRKS, id, report).map(|entry| entry.name)
+}
+
+#[test]
+fn known_controllers_are_found() {
+    assert_eq!(
+        known(&test_data::NES_ID, &test_data::NES_IDLE),
+        Some("NES Classic Mini controller")
+    );
+    assert_eq!(
+        known(&test_data::SNES_ID, &test_data::SNES_IDLE),
+        Some("SNES Classic Mini controller")
+    );
+    let pro_style = Some("Classic Pro style controller (eg: PDP Link)");
+    assert_eq!(
+        known(&test_data::PDP_LINK_ID, &test_data::PDP_LINK_IDLE),
+        pro_style
+    );
+    assert_eq!(known(&test_data::PRO_ID, &test_data::PRO_IDLE), pro_style);
+    // Genuine classic controllers have analogue triggers, and need no quirks
+    assert_eq!(
+        known(&test_data::CLASSIC_ID, &test_data::CLASSIC_IDLE),
+        None
+    );
+    assert_eq!(
+        known(&test_data::NUNCHUCK_ID, &test_data::NUNCHUCK_IDLE),
+        None
+    );
+}
+
+#[test]
+fn pdp_link_gets_quirks_without_user_code() {
+    let mut expectations = transactions::init_no_calibration();
+    expectations.extend(lookup(&test_data::PDP_LINK_ID, &test_data::PDP_LINK_IDLE));
+    expectations.extend(transactions::read(&test_data::PDP_LINK_IDLE));
+    expectations.extend(transactions::read(&test_data::PDP_LINK_BTN_L));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = ClassicBuilder::new()
+        .known_quirks(true)
+        .build(i2c.clone(), NoopDelay::new())
+        .unwrap();
+    assert!(classic.quirks().ignore_analog_triggers);
+    assert!(!classic.capabilities().analog_triggers);
+    // L moves the analogue trigger to full on this pad, which is now ignored
+    let input = classic.read().unwrap();
+    assert!(input.button_trigger_l);
+    assert_eq!(input.trigger_left, 0);
+    i2c.done();
+}
+
+#[test]
+fn nes_mini_has_no_sticks() {
+    let mut expectations = transactions::init_no_calibration();
+    expectations.extend(lookup(&test_data::NES_ID, &test_data::NES_IDLE));
+    expectations.extend(transactions::read(&test_data::NES_IDLE));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let classic = ClassicBuilder::new()
+        .known_quirks(true)
+        .build(i2c.clone(), NoopDelay::new())
+        .unwrap();
+    assert!(!classic.capabilities().sticks);
+    i2c.done();
+}
+
+#[test]
+fn unknown_controller_keeps_defaults() {
+    let mut expectations = transactions::init_no_calibration();
+    expectations.extend(lookup(&test_data::CLASSIC_ID, &test_data::CLASSIC_IDLE));
+    expectations.extend(transactions::read(&test_data::CLASSIC_IDLE));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let classic = ClassicBuilder::new()
+        .known_quirks(true)
+        .build(i2c.clone(), NoopDelay::new())
+        .unwrap();
+    assert_eq!(classic.quirks(), Quirks::NONE);
+    assert_eq!(classic.capabilities(), Capabilities::FULL);
+    i2c.done();
+}
+
+const USER_QUIRKS: &[QuirkEntry] = &[QuirkEntry {
+    name: "Knock-off pad",
+    id: test_data::CLASSIC_ID,
+    fingerprint: None,
+    quirks: Quirks::NONE.invert_button(Button::ZL),
+    capabilities: Capabilities {
+        hires: false,
+        ..Capabilities::FULL
+    },
+}];
+
+#[test]
+fn user_entries_are_checked_first() {
+    let mut expectations = transactions::init_no_calibration();
+    expectations.extend(lookup(&test_data::CLASSIC_ID, &test_data::CLASSIC_IDLE));
+    // No hi-res mode switch, as the entry says the pad can't do it
+    expectations.extend(transactions::read(&test_data::CLASSIC_IDLE));
+    expectations.extend(transactions::read(&test_data::CLASSIC_IDLE));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = ClassicBuilder::new()
+        .hires(true)
+        .quirk_table(USER_QUIRKS)
+        .build(i2c.clone(), NoopDelay::new())
+        .unwrap();
+    assert!(classic.read().unwrap().button_zl);
+    assert!(classic.into_standard().is_ok());
+    i2c.done();
+}
+
+#[test]
+fn explicit_quirks_win() {
+    let mut expectations = transactions::init_no_calibration();
+    expectations.extend(lookup(&test_data::PDP_LINK_ID, &test_data::PDP_LINK_IDLE));
+    expectations.extend(transactions::read(&test_data::PDP_LINK_IDLE));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let classic = ClassicBuilder::new()
+        .quirks(Quirks::NONE)
+        .known_quirks(true)
+        .build(i2c.clone(), NoopDelay::new())
+        .unwrap();
+    assert_eq!(classic.quirks(), Quirks::NONE);
+    // The capabilities are still recorded
+    assert!(!classic.capabilities().analog_triggers);
+    i2c.done();
+}
@@ -0,0 +1,103 @@
+use embedded_hal_mock::eh1::{delay::NoopDelay, i2c};
+use wii_ext::blocking_impl::classic::{Classic, ClassicBuilder};
+use wii_ext::core::classic::{ClassicReading, Quirks};
+use wii_ext::core::events::{Axis, Button};
+mod common;
+use common::{test_data, transactions};
+
+#[test]
+fn no_quirks_change_nothing() {
+    let decoded = ClassicReading::from_data(&test_data::CLASSIC_BTN_ZL).unwrap();
+    let mut reading = decoded;
+    Quirks::default().apply(&mut reading);
+    assert_eq!(reading, decoded);
+    assert_eq!(Quirks::default(), Quirks::NONE);
+}
+
+#[test]
+fn nunchuk_buttons_are_ignored() {
+    assert_eq!(Quirks::NONE.invert_button(Button::C), Quirks::NONE);
+    assert_eq!(Quirks::NONE.invert_button(Button::Z), Quirks::NONE);
+    let quirks = Quirks::NONE
+        .invert_button(Button::Z)
+        .invert_button(Button::B);
+    assert_eq!(quirks, Quirks::NONE.invert_button(Button::B));
+    // A in particular is left alone
+    let decoded = ClassicReading::from_data(&test_data::CLASSIC_BTN_A).unwrap();
+    let mut reading = decoded;
+    quirks.apply(&mut reading);
+    assert!(reading.button_a);
+}
+
+#[test]
+fn inverted_button_is_flipped() {
+    let quirks = Quirks::NONE.invert_button(Button::ZL);
+    assert_eq!(quirks.inverted_buttons, 1 << 6);
+    // This pad sets the ZL bit when released, so the genuine "pressed" report means released
+    let mut reading = ClassicReading::from_data(&test_data::CLASSIC_BTN_ZL).unwrap();
+    assert!(reading.button_zl);
+    quirks.apply(&mut reading);
+    assert!(!reading.button_zl);
+    // And the genuine idle report means pressed
+    let mut reading = ClassicReading::from_data(&test_data::CLASSIC_IDLE).unwrap();
+    quirks.apply(&mut reading);
+    assert!(reading.button_zl);
+    assert!(!reading.button_zr);
+}
+
+#[test]
+fn inverted_axis_is_mirrored() {
+    let quirks = Quirks::NONE.invert_axis(Axis::RightY);
+    let decoded = ClassicReading::from_data(&test_data::CLASSIC_RJOY_U).unwrap();
+    let mut reading = decoded;
+    quirks.apply(&mut reading);
+    assert_eq!(reading.joystick_right_y, 255 - decoded.joystick_right_y);
+    assert_eq!(reading.joystick_right_x, decoded.joystick_right_x);
+    assert_eq!(reading.joystick_left_y, decoded.joystick_left_y);
+}
+
+#[test]
+fn analog_triggers_can_be_ignored() {
+    let quirks = Quirks {
+        ignore_analog_triggers: true,
+        ..Quirks::NONE
+    };
+    let mut reading = ClassicReading::from_data(&test_data::CLASSIC_LTRIG_W_BUTTON).unwrap();
+    quirks.apply(&mut reading);
+    assert_eq!((reading.trigger_left, reading.trigger_right), (0, 0));
+    assert!(reading.button_trigger_l);
+}
+
+#[test]
+fn driver_applies_quirks_before_calibration() {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.extend(transactions::read(&test_data::CLASSIC_BTN_ZL));
+    expectations.extend(transactions::read(&test_data::CLASSIC_RJOY_U));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = ClassicBuilder::new()
+        .quirks(
+            Quirks::NONE
+                .invert_button(Button::ZL)
+                .invert_axis(Axis::RightY),
+        )
+        .build(i2c.clone(), NoopDelay::new())
+        .unwrap();
+    assert!(!classic.read().unwrap().button_zl);
+    // Calibrated against the inverted idle position, so up reads as down
+    let input = classic.read().unwrap();
+    assert!(input.joystick_right_y < -50, "{}", input.joystick_right_y);
+    assert!(input.button_zl);
+    i2c.done();
+}
+
+#[test]
+fn quirks_can_be_set_after_creation() {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.extend(transactions::read(&test_data::CLASSIC_BTN_ZR));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).unwrap();
+    classic.set_quirks(Quirks::NONE.invert_button(Button::ZR));
+    assert_eq!(classic.quirks().inverted_buttons, 1 << Button::ZR as u16);
+    assert!(!classic.read().unwrap().button_zr);
+    i2c.done();
+}
@@ -0,0 +1,196 @@
+use embassy_futures::block_on;
+use embedded_hal_mock::eh1::{delay::NoopDelay, i2c};
+use wii_ext::blocking_impl::classic::ClassicBuilder;
+use wii_ext::blocking_impl::nunchuk::Nunchuk;
+use wii_ext::core::classic::{CalibrationData, ClassicReading, ClassicReadingCalibrated};
+use wii_ext::core::events::Axis;
+use wii_ext::core::nunchuk::{self, NunchukReading, NunchukReadingCalibrated};
+use wii_ext::core::RangePolicy;
+use wii_ext::Error;
+mod common;
+use common::{test_data, transactions};
+
+/// Calibration taken with the left stick held fully left and down
+const BOGUS: CalibrationData = CalibrationData {
+    joystick_left_x: 0,
+    joystick_left_y: 0,
+    joystick_right_x: 128,
+    joystick_right_y: 128,
+    trigger_left: 0,
+    trigger_right: 0,
+};
+
+#[test]
+fn saturate_clamps_far_readings() {
+    let reading = ClassicReading::CENTERED.with_joystick_left_x(255);
+    let calibrated =
+        ClassicReadingCalibrated::with_policy(reading, &BOGUS, RangePolicy::Saturate).unwrap();
+    assert_eq!(calibrated.joystick_left_x, 127);
+    assert_eq!(calibrated, ClassicReadingCalibrated::new(reading, &BOGUS));
+}
+
+#[test]
+fn strict_rejects_far_readings() {
+    let reading = ClassicReading::CENTERED.with_joystick_left_x(255);
+    let result =
+        ClassicReadingCalibrated::with_policy(reading, &BOGUS, RangePolicy::Strict { margin: 16 });
+    assert_eq!(result, Err(Axis::LeftX));
+}
+
+#[test]
+fn strict_rejects_far_negative_readings() {
+    // Calibrated with the right trigger fully pressed
+    let calibration = CalibrationData {
+        trigger_right: 255,
+        ..BOGUS
+    };
+    let result = ClassicReadingCalibrated::with_policy(
+        ClassicReading::CENTERED,
+        &calibration,
+        RangePolicy::Strict { margin: 16 },
+    );
+    assert_eq!(result, Err(Axis::TriggerRight));
+}
+
+#[test]
+fn strict_clamps_within_margin() {
+    // 145 is past i8::MAX, but within the margin
+    let reading = ClassicReading::CENTERED
+        .with_joystick_left_x(145)
+        .with_joystick_left_y(128);
+    let calibrated =
+        ClassicReadingCalibrated::with_policy(reading, &BOGUS, RangePolicy::Strict { margin: 18 })
+            .unwrap();
+    assert_eq!(calibrated.joystick_left_x, 127);
+    let result =
+        ClassicReadingCalibrated::with_policy(reading, &BOGUS, RangePolicy::Strict { margin: 17 });
+    assert_eq!(result, Err(Axis::LeftX));
+}
+
+#[test]
+fn nunchuk_policies() {
+    let calibration = nunchuk::CalibrationData {
+        joystick_x: 128,
+        joystick_y: 255,
+    };
+    let reading = NunchukReading::CENTERED.with_joystick_y(0);
+    let calibrated =
+        NunchukReadingCalibrated::with_policy(reading, &calibration, RangePolicy::Saturate)
+            .unwrap();
+    assert_eq!(calibrated.joystick_y, -128);
+    let result = NunchukReadingCalibrated::with_policy(
+        reading,
+        &calibration,
+        RangePolicy::Strict { margin: 0 },
+    );
+    assert_eq!(result, Err(Axis::LeftY));
+}
+
+fn hd_classic_calibration(right_y: u8) -> CalibrationData {
+    let idle = test_data::PRO_HD_IDLE;
+    CalibrationData {
+        joystick_left_x: idle[0],
+        joystick_right_x: idle[1],
+        joystick_left_y: idle[2],
+        joystick_right_y: right_y,
+        trigger_left: idle[4],
+        trigger_right: idle[5],
+    }
+}
+
+#[test]
+fn classic_driver_defaults_to_saturate() {
+    let expectations = transactions::read(&test_data::PRO_HD_RJOY_U);
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = ClassicBuilder::new()
+        .skip_init()
+        .hires(true)
+        .calibration(hd_classic_calibration(0))
+        .build(i2c.clone(), NoopDelay::new())
+        .unwrap();
+    assert_eq!(classic.read().unwrap().joystick_right_y, 127);
+    i2c.done();
+}
+
+#[test]
+fn classic_driver_strict_returns_out_of_range() {
+    let mut expectations = transactions::read(&test_data::PRO_HD_RJOY_U);
+    expectations.extend(transactions::read(&test_data::PRO_HD_RJOY_U));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = ClassicBuilder::new()
+        .skip_init()
+        .hires(true)
+        .calibration(hd_classic_calibration(0))
+        .range_policy(RangePolicy::Strict { margin: 32 })
+        .build(i2c.clone(), NoopDelay::new())
+        .unwrap();
+    assert!(matches!(
+        classic.read(),
+        Err(Error::OutOfRange { axis: Axis::RightY })
+    ));
+    // 245 - 100 is within the margin
+    classic.set_calibration(hd_classic_calibration(100));
+    assert_eq!(classic.read().unwrap().joystick_right_y, 127);
+    i2c.done();
+}
+
+#[test]
+fn nunchuk_driver_switches_policy() {
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_JOY_R));
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_JOY_R));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut nunchuk = Nunchuk::new(i2c.clone(), NoopDelay::new()).unwrap();
+    nunchuk.set_calibration(nunchuk::CalibrationData {
+        joystick_x: 0,
+        ..nunchuk.calibration()
+    });
+    assert_eq!(nunchuk.read().unwrap().joystick_x, 127);
+    nunchuk.set_range_policy(RangePolicy::Strict { margin: 0 });
+    let err = nunchuk.read().unwrap_err();
+    assert!(matches!(err, Error::OutOfRange { axis: Axis::LeftX }));
+    assert!(!err.is_transient());
+    i2c.done();
+}
+
+#[test]
+fn recovery_returns_out_of_range_without_retrying() {
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    // A single read, with no re-init after it
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_JOY_R));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut nunchuk = Nunchuk::new(i2c.clone(), NoopDelay::new()).unwrap();
+    nunchuk.set_calibration(nunchuk::CalibrationData {
+        joystick_x: 0,
+        ..nunchuk.calibration()
+    });
+    nunchuk.set_range_policy(RangePolicy::Strict { margin: 0 });
+    assert!(matches!(
+        nunchuk.read_with_recovery(5),
+        Err(Error::OutOfRange { axis: Axis::LeftX })
+    ));
+    i2c.done();
+}
+
+#[test]
+fn async_nunchuk_strict_returns_out_of_range() {
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_JOY_R));
+    let mut i2c = i2c::Mock::new(&expectations);
+    block_on(async {
+        let mut nunchuk = wii_ext::async_impl::nunchuk::NunchukBuilder::new()
+            .range_policy(RangePolicy::Strict { margin: 0 })
+            .build(i2c.clone(), NoopDelay::new())
+            .await
+            .unwrap();
+        nunchuk.set_calibration(nunchuk::CalibrationData {
+            joystick_x: 0,
+            ..nunchuk.calibration()
+        });
+        assert!(matches!(
+            nunchuk.read().await,
+            Err(Error::OutOfRange { axis: Axis::LeftX })
+        ));
+    });
+    i2c.done();
+}
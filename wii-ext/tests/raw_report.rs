@@ -0,0 +1,64 @@
+use wii_ext::core::raw::{HexBytes, RawExtReport, RawFrame, RawReport};
+mod common;
+use common::test_data;
+
+#[test]
+fn formats_as_fixed_width_hex() {
+    let report = RawReport(test_data::CLASSIC_IDLE);
+    assert_eq!(format!("{report}"), "[61 E0 91 63 FF FF]");
+    assert_eq!(format!("{report:?}"), "[61 E0 91 63 FF FF]");
+}
+
+#[test]
+fn formats_hires_report() {
+    let report = RawReport(test_data::CLASSIC_HD_IDLE);
+    assert_eq!(format!("{report}"), "[84 7F 82 88 1F 1A FF FF]");
+}
+
+#[test]
+fn empty_bytes_format_as_brackets() {
+    assert_eq!(format!("{}", HexBytes(&[])), "[]");
+    assert_eq!(format!("{:?}", HexBytes(&[0x0A])), "[0A]");
+}
+
+#[test]
+fn bit_reads_active_low_buttons() {
+    let idle = RawReport(test_data::CLASSIC_IDLE);
+    let btn_a = RawReport(test_data::CLASSIC_BTN_A);
+    assert!(idle.bit(5, 4));
+    assert!(!btn_a.bit(5, 4));
+    assert!(!idle.is_all(0xFF));
+    assert!(RawReport([0xFF; 6]).is_all(0xFF));
+    assert!(RawExtReport::from([0; 6]).is_all(0));
+}
+
+#[test]
+fn frame_from_slice_picks_format_by_length() {
+    assert!(matches!(
+        RawFrame::from_slice(&[0; 6]),
+        Some(RawFrame::Standard(_))
+    ));
+    assert!(matches!(
+        RawFrame::from_slice(&[0; 8]),
+        Some(RawFrame::HiRes(_))
+    ));
+    assert!(matches!(
+        RawFrame::from_slice(&[0; 9]),
+        Some(RawFrame::Alt(_))
+    ));
+    assert_eq!(RawFrame::from_slice(&[0; 7]), None);
+    assert_eq!(RawFrame::from_slice(&[]), None);
+}
+
+#[test]
+fn frame_formats_its_bytes() {
+    let frame = RawFrame::from_slice(&test_data::NUNCHUCK_IDLE).unwrap();
+    assert_eq!(frame.bytes(), &test_data::NUNCHUCK_IDLE[..]);
+    assert_eq!(format!("{frame}"), format!("{}", HexBytes(frame.bytes())));
+}
+
+#[test]
+fn wrapper_is_the_size_of_the_array() {
+    assert_eq!(core::mem::size_of::<RawReport<6>>(), 6);
+    assert_eq!(core::mem::size_of::<RawReport<8>>(), 8);
+}
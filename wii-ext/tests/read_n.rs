@@ -0,0 +1,106 @@
+use embassy_futures::block_on;
+use embedded_hal_mock::eh1::{delay::NoopDelay, i2c};
+use wii_ext::blocking_impl::classic::Classic;
+use wii_ext::blocking_impl::nunchuk::Nunchuk;
+use wii_ext::core::classic::ClassicReadingCalibrated;
+use wii_ext::core::nunchuk::NunchukReadingCalibrated;
+use wii_ext::Error;
+mod common;
+use common::{test_data, transactions};
+
+/// A quick flick of the left stick, with A pressed at the end
+const FLICK: [[u8; 6]; 4] = [
+    test_data::CLASSIC_IDLE,
+    test_data::CLASSIC_LJOY_L,
+    test_data::CLASSIC_LJOY_R,
+    test_data::CLASSIC_BTN_A,
+];
+
+#[test]
+fn classic_fills_buffer_in_order() {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    for report in &FLICK {
+        expectations.extend(transactions::read(report));
+    }
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).unwrap();
+    let mut out = [ClassicReadingCalibrated::default(); 4];
+    let before = classic.stats().successful_reads;
+    classic.read_n(&mut out).unwrap();
+    assert_eq!(classic.stats().successful_reads - before, 4);
+    assert_eq!(out[0], ClassicReadingCalibrated::default());
+    assert!(out[1].joystick_left_x < -90);
+    assert!(out[2].joystick_left_x > 90);
+    assert!(out[3].button_a);
+    i2c.done();
+}
+
+#[test]
+fn classic_reports_failed_index() {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.extend(transactions::read(&test_data::CLASSIC_LJOY_L));
+    expectations.extend(transactions::read(&test_data::CLASSIC_LJOY_R));
+    expectations.extend(transactions::failed_read(6));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).unwrap();
+    let mut out = [ClassicReadingCalibrated::default(); 4];
+    let e = classic.read_n(&mut out).unwrap_err();
+    assert_eq!(e.index, 2);
+    assert!(matches!(e.error, Error::I2C(_)));
+    // Elements before the failure are kept, the rest are untouched
+    assert!(out[0].joystick_left_x < -90);
+    assert!(out[1].joystick_left_x > 90);
+    assert_eq!(out[2], ClassicReadingCalibrated::default());
+    assert_eq!(out[3], ClassicReadingCalibrated::default());
+    i2c.done();
+}
+
+#[test]
+fn empty_buffer_reads_nothing() {
+    let expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).unwrap();
+    classic.read_n(&mut []).unwrap();
+    i2c.done();
+}
+
+#[test]
+fn nunchuk_fills_buffer_in_order() {
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    for report in [
+        test_data::NUNCHUCK_JOY_U,
+        test_data::NUNCHUCK_JOY_D,
+        test_data::NUNCHUCK_BTN_C,
+        test_data::NUNCHUCK_BTN_Z,
+    ] {
+        expectations.extend(transactions::read(&report));
+    }
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut nunchuk = Nunchuk::new(i2c.clone(), NoopDelay::new()).unwrap();
+    let mut out = [NunchukReadingCalibrated::default(); 4];
+    nunchuk.read_n(&mut out).unwrap();
+    assert!(out[0].joystick_y > 90);
+    assert!(out[1].joystick_y < -90);
+    assert!(out[2].button_c);
+    assert!(out[3].button_z);
+    i2c.done();
+}
+
+#[test]
+fn async_classic_reports_failed_index() {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.extend(transactions::read(&test_data::CLASSIC_BTN_A));
+    expectations.extend(transactions::failed_read(6));
+    let mut i2c = i2c::Mock::new(&expectations);
+    block_on(async {
+        let mut classic = wii_ext::async_impl::classic::Classic::new(i2c.clone(), NoopDelay::new())
+            .await
+            .unwrap();
+        let mut out = [ClassicReadingCalibrated::default(); 4];
+        let e = classic.read_n(&mut out).await.unwrap_err();
+        assert_eq!(e.index, 1);
+        assert!(out[0].button_a);
+        assert!(!out[1].button_a);
+    });
+    i2c.done();
+}
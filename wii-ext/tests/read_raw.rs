@@ -0,0 +1,133 @@
+use embassy_futures::block_on;
+use embedded_hal_mock::eh1::{
+    delay::NoopDelay,
+    i2c::{self, Transaction},
+};
+use wii_ext::blocking_impl::classic::Classic;
+use wii_ext::blocking_impl::nunchuk::Nunchuk;
+use wii_ext::core::classic::ClassicReading;
+use wii_ext::core::nunchuk::NunchukReading;
+use wii_ext::core::EXT_I2C_ADDR;
+mod common;
+use common::{test_data, transactions};
+
+fn enable_hires(calibration: &[u8]) -> Vec<Transaction> {
+    let mut transactions = transactions::save_report_format(1);
+    transactions.push(Transaction::write(EXT_I2C_ADDR as u8, vec![254, 3]));
+    transactions.extend(transactions::read(calibration));
+    transactions
+}
+
+#[test]
+fn classic_raw_read_uses_the_same_transactions_as_read() {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.extend(transactions::read(&test_data::CLASSIC_BTN_A));
+    expectations.extend(transactions::read(&test_data::CLASSIC_BTN_B));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).unwrap();
+    assert!(classic.read().unwrap().button_a);
+    // Standard reports leave the end of the buffer alone
+    let mut buf = [0xAA; 8];
+    assert_eq!(classic.read_raw_into(&mut buf).unwrap(), 6);
+    assert_eq!(buf[..6], test_data::CLASSIC_BTN_B);
+    assert_eq!(buf[6..], [0xAA, 0xAA]);
+    i2c.done();
+}
+
+#[test]
+fn classic_hires_raw_read_fills_the_buffer() {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.extend(enable_hires(&test_data::CLASSIC_HD_IDLE));
+    expectations.extend(transactions::read(&test_data::CLASSIC_HD_BTN_X));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).unwrap();
+    classic.enable_hires().unwrap();
+    let mut buf = [0; 8];
+    assert_eq!(classic.read_raw_into(&mut buf).unwrap(), 8);
+    assert_eq!(buf, test_data::CLASSIC_HD_BTN_X);
+    assert!(ClassicReading::from_hd_report(&buf).button_x);
+    i2c.done();
+}
+
+#[test]
+fn nunchuk_raw_read_uses_the_same_transactions_as_read() {
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_BTN_C));
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_BTN_Z));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut nunchuk = Nunchuk::new(i2c.clone(), NoopDelay::new()).unwrap();
+    assert!(nunchuk.read().unwrap().button_c);
+    let mut buf = [0; 6];
+    nunchuk.read_raw_into(&mut buf).unwrap();
+    assert_eq!(buf, test_data::NUNCHUCK_BTN_Z);
+    assert!(NunchukReading::from_report(&buf).button_z);
+    i2c.done();
+}
+
+#[test]
+fn raw_read_ends_a_started_read() {
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    expectations.push(Transaction::write(EXT_I2C_ADDR as u8, vec![0]));
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_BTN_C));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut nunchuk = Nunchuk::new(i2c.clone(), NoopDelay::new()).unwrap();
+    nunchuk.begin_read().unwrap();
+    let mut buf = [0; 6];
+    nunchuk.read_raw_into(&mut buf).unwrap();
+    assert_eq!(buf, test_data::NUNCHUCK_BTN_C);
+    assert!(matches!(
+        nunchuk.finish_read(),
+        Err(wii_ext::Error::ReadNotStarted)
+    ));
+    i2c.done();
+}
+
+#[test]
+fn async_raw_reads() {
+    use wii_ext::async_impl::classic::Classic;
+    use wii_ext::async_impl::nunchuk::Nunchuk;
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.extend(transactions::read(&test_data::CLASSIC_PAD_U));
+    expectations.extend(enable_hires(&test_data::CLASSIC_HD_IDLE));
+    expectations.extend(transactions::read(&test_data::CLASSIC_HD_LTRIG));
+    let mut i2c = i2c::Mock::new(&expectations);
+    block_on(async {
+        let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).await.unwrap();
+        let mut buf = [0xAA; 8];
+        assert_eq!(classic.read_raw_into(&mut buf).await.unwrap(), 6);
+        assert_eq!(buf[..6], test_data::CLASSIC_PAD_U);
+        classic.enable_hires().await.unwrap();
+        assert_eq!(classic.read_raw_into(&mut buf).await.unwrap(), 8);
+        assert_eq!(buf, test_data::CLASSIC_HD_LTRIG);
+    });
+    i2c.done();
+
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_JOY_L));
+    let mut i2c = i2c::Mock::new(&expectations);
+    block_on(async {
+        let mut nunchuk = Nunchuk::new(i2c.clone(), NoopDelay::new()).await.unwrap();
+        let mut buf = [0; 6];
+        nunchuk.read_raw_into(&mut buf).await.unwrap();
+        assert_eq!(buf, test_data::NUNCHUCK_JOY_L);
+    });
+    i2c.done();
+}
+
+#[test]
+fn typed_decoders_match_from_data() {
+    for report in [test_data::CLASSIC_IDLE, test_data::PRO_BTN_HOME] {
+        assert_eq!(
+            ClassicReading::from_data(&report),
+            Some(ClassicReading::from_report(&report))
+        );
+    }
+    assert_eq!(
+        ClassicReading::from_data(&test_data::PRO_HD_LJOY_UR),
+        Some(ClassicReading::from_hd_report(&test_data::PRO_HD_LJOY_UR))
+    );
+    assert_eq!(
+        NunchukReading::from_data(&test_data::NUNCHUCK_JOY_D),
+        Some(NunchukReading::from_report(&test_data::NUNCHUCK_JOY_D))
+    );
+}
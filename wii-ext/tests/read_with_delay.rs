@@ -0,0 +1,103 @@
+use embassy_futures::block_on;
+use embedded_hal_mock::eh1::i2c;
+use std::cell::Cell;
+use std::rc::Rc;
+use wii_ext::core::INTERMESSAGE_DELAY_MICROSEC_U32;
+mod common;
+use common::{test_data, transactions};
+
+/// A delay that adds up how long it was asked to wait
+#[derive(Clone, Default)]
+struct TotalDelay(Rc<Cell<u64>>);
+
+impl TotalDelay {
+    /// Total delay since the last call, in microseconds
+    fn take_us(&self) -> u64 {
+        self.0.take() / 1000
+    }
+}
+
+impl embedded_hal::delay::DelayNs for TotalDelay {
+    fn delay_ns(&mut self, ns: u32) {
+        self.0.set(self.0.get() + u64::from(ns));
+    }
+}
+
+impl embedded_hal_async::delay::DelayNs for TotalDelay {
+    async fn delay_ns(&mut self, ns: u32) {
+        self.0.set(self.0.get() + u64::from(ns));
+    }
+}
+
+#[test]
+fn classic_read_with_delay() {
+    use wii_ext::blocking_impl::classic::Classic;
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.extend(transactions::read(&test_data::CLASSIC_BTN_B));
+    expectations.extend(transactions::read(&test_data::CLASSIC_BTN_A));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let delay = TotalDelay::default();
+    let mut classic = Classic::new(i2c.clone(), delay.clone()).unwrap();
+    delay.take_us();
+    assert!(classic.read_with_delay(5000).unwrap().button_b);
+    assert_eq!(delay.take_us(), 5000);
+    // The override only applies to one read
+    assert!(classic.read().unwrap().button_a);
+    assert_eq!(delay.take_us(), u64::from(INTERMESSAGE_DELAY_MICROSEC_U32));
+    i2c.done();
+}
+
+#[test]
+fn nunchuk_read_with_delay() {
+    use wii_ext::blocking_impl::nunchuk::Nunchuk;
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_BTN_C));
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_BTN_Z));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let delay = TotalDelay::default();
+    let mut nunchuk = Nunchuk::new(i2c.clone(), delay.clone()).unwrap();
+    delay.take_us();
+    assert!(nunchuk.read_with_delay(5000).unwrap().button_c);
+    assert_eq!(delay.take_us(), 5000);
+    assert!(nunchuk.read().unwrap().button_z);
+    assert_eq!(delay.take_us(), u64::from(INTERMESSAGE_DELAY_MICROSEC_U32));
+    i2c.done();
+}
+
+#[test]
+fn async_classic_read_with_delay() {
+    use wii_ext::async_impl::classic::Classic;
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.extend(transactions::read(&test_data::CLASSIC_BTN_B));
+    expectations.extend(transactions::read(&test_data::CLASSIC_BTN_A));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let delay = TotalDelay::default();
+    block_on(async {
+        let mut classic = Classic::new(i2c.clone(), delay.clone()).await.unwrap();
+        delay.take_us();
+        assert!(classic.read_with_delay(5000).await.unwrap().button_b);
+        assert_eq!(delay.take_us(), 5000);
+        assert!(classic.read().await.unwrap().button_a);
+        assert_eq!(delay.take_us(), u64::from(INTERMESSAGE_DELAY_MICROSEC_U32));
+    });
+    i2c.done();
+}
+
+#[test]
+fn async_nunchuk_read_with_delay() {
+    use wii_ext::async_impl::nunchuk::Nunchuk;
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_BTN_C));
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_BTN_Z));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let delay = TotalDelay::default();
+    block_on(async {
+        let mut nunchuk = Nunchuk::new(i2c.clone(), delay.clone()).await.unwrap();
+        delay.take_us();
+        assert!(nunchuk.read_with_delay(5000).await.unwrap().button_c);
+        assert_eq!(delay.take_us(), 5000);
+        assert!(nunchuk.read().await.unwrap().button_z);
+        assert_eq!(delay.take_us(), u64::from(INTERMESSAGE_DELAY_MICROSEC_U32));
+    });
+    i2c.done();
+}
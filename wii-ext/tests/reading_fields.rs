@@ -0,0 +1,39 @@
+use wii_ext::core::classic::{ClassicReading, ClassicReadingCalibrated};
+use wii_ext::core::nunchuk::{NunchukReading, NunchukReadingCalibrated};
+
+#[test]
+fn literals_and_builders_agree() {
+    let literal = ClassicReading {
+        joystick_left_x: 0xFF,
+        button_a: true,
+        ..ClassicReading::CENTERED
+    };
+    let built = ClassicReading::CENTERED
+        .with_joystick_left_x(0xFF)
+        .with_button_a(true);
+    assert_eq!(literal, built);
+
+    let literal = NunchukReading {
+        joystick_y: 0,
+        button_z: true,
+        ..NunchukReading::CENTERED
+    };
+    let built = NunchukReading::CENTERED
+        .with_joystick_y(0)
+        .with_button_z(true);
+    assert_eq!(literal, built);
+}
+
+#[test]
+fn accessors_read_fields() {
+    let reading = ClassicReadingCalibrated::default()
+        .with_trigger_left(12)
+        .with_dpad_up(true);
+    assert_eq!(reading.trigger_left(), reading.trigger_left);
+    assert!(reading.dpad_up());
+    assert!(!reading.button_b());
+
+    let reading = NunchukReadingCalibrated::default().with_accel_z(700);
+    assert_eq!(reading.accel_z(), 700);
+    assert_eq!(reading.accel_offset(), [0; 3]);
+}
@@ -0,0 +1,189 @@
+use embedded_hal::i2c::ErrorKind;
+use embedded_hal_mock::eh1::{
+    delay::NoopDelay,
+    i2c::{self, Transaction},
+};
+use wii_ext::blocking_impl::classic::{Classic, ClassicBuilder};
+use wii_ext::blocking_impl::nunchuk::{Nunchuk, NunchukBuilder};
+use wii_ext::core::{RetryPolicy, EXT_I2C_ADDR};
+use wii_ext::Error;
+mod common;
+use common::{test_data, transactions};
+
+#[test]
+fn nunchuk_recovers_after_failed_read() {
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    expectations.extend(transactions::failed_read(6));
+    // Re-init, without recalibrating
+    expectations.extend(transactions::init_no_calibration());
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_JOY_L));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut nunchuk = Nunchuk::new(i2c.clone(), NoopDelay::new()).unwrap();
+    let input = nunchuk.read_with_recovery(3).unwrap();
+    // Calibration from before the failure is still used
+    assert!(input.joystick_x < -90);
+    i2c.done();
+}
+
+#[test]
+fn classic_recovery_restores_hires() {
+    let mut expectations = transactions::init_no_calibration();
+    expectations.extend(transactions::save_report_format(1));
+    expectations.push(Transaction::write(EXT_I2C_ADDR as u8, vec![254, 3]));
+    expectations.extend(transactions::read(&test_data::PRO_HD_IDLE));
+    expectations.extend(transactions::failed_read(8));
+    // Re-init, then switch back to HD mode
+    expectations.extend(transactions::init_no_calibration());
+    expectations.push(Transaction::write(EXT_I2C_ADDR as u8, vec![254, 3]));
+    expectations.extend(transactions::read(&test_data::PRO_HD_RJOY_R));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = ClassicBuilder::new()
+        .hires(true)
+        .build(i2c.clone(), NoopDelay::new())
+        .unwrap();
+    let input = classic.read_with_recovery(2).unwrap();
+    assert!(input.joystick_right_x > 90);
+    i2c.done();
+}
+
+#[test]
+fn classic_recovery_gives_up_after_max_attempts() {
+    let mut expectations = transactions::init(&test_data::PRO_IDLE);
+    expectations.extend(transactions::failed_read(6));
+    expectations.extend(transactions::init_no_calibration());
+    expectations.extend(transactions::failed_read(6));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).unwrap();
+    let result = classic.read_with_recovery(2);
+    assert!(matches!(result, Err(Error::I2C(ErrorKind::Other))));
+    i2c.done();
+}
+
+#[test]
+fn classic_recovery_does_not_retry_wrong_controller() {
+    let mut expectations = transactions::init(&test_data::PRO_IDLE);
+    expectations.extend(transactions::read_id(&test_data::PRO_ID));
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_IDLE));
+    expectations.extend(transactions::read_id(&test_data::NUNCHUCK_ID));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).unwrap();
+    classic.enable_identity_check(0).unwrap();
+    let result = classic.read_with_recovery(5);
+    assert!(matches!(result, Err(Error::ControllerChanged { .. })));
+    i2c.done();
+}
+
+#[test]
+fn recovery_hook_runs_once_after_repeated_failures() {
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    for _ in 0..3 {
+        expectations.extend(transactions::failed_read(6));
+        expectations.extend(transactions::init_no_calibration());
+    }
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_JOY_R));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut nunchuk = Nunchuk::new(i2c.clone(), NoopDelay::new()).unwrap();
+    let mut calls = vec![];
+    let input = nunchuk
+        .read_with_recovery_hook(5, 2, |errors| calls.push(errors))
+        .unwrap();
+    assert!(input.joystick_x > 90);
+    assert_eq!(calls, [2]);
+    assert_eq!(nunchuk.stats().reinits, 3);
+    i2c.done();
+}
+
+#[test]
+fn recovery_hook_runs_once_with_interleaved_errors() {
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    for _ in 0..2 {
+        expectations.extend(transactions::failed_read(6));
+        expectations.extend(transactions::init_no_calibration());
+    }
+    // A blank report fails without a bus error, resetting the count
+    expectations.extend(transactions::read(&[0; 6]));
+    expectations.extend(transactions::init_no_calibration());
+    for _ in 0..2 {
+        expectations.extend(transactions::failed_read(6));
+        expectations.extend(transactions::init_no_calibration());
+    }
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_JOY_R));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut nunchuk = Nunchuk::new(i2c.clone(), NoopDelay::new()).unwrap();
+    let mut calls = vec![];
+    let input = nunchuk
+        .read_with_recovery_hook(6, 2, |errors| calls.push(errors))
+        .unwrap();
+    assert!(input.joystick_x > 90);
+    assert_eq!(calls, [2]);
+    i2c.done();
+}
+
+#[test]
+fn recovery_hook_counts_exhausted_retries_as_bus_errors() {
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    for attempt in 0..3 {
+        if attempt > 0 {
+            expectations.extend(transactions::init_no_calibration());
+        }
+        // Both tries of each read fail
+        expectations.extend(transactions::failed_read(6));
+        expectations
+            .push(Transaction::read(EXT_I2C_ADDR as u8, vec![0; 6]).with_error(ErrorKind::Other));
+    }
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut nunchuk = NunchukBuilder::new()
+        .retry_policy(RetryPolicy::new(2, 0))
+        .build(i2c.clone(), NoopDelay::new())
+        .unwrap();
+    let mut calls = vec![];
+    let result = nunchuk.read_with_recovery_hook(3, 1, |errors| calls.push(errors));
+    assert!(matches!(result, Err(Error::RetriesExhausted(_))));
+    assert_eq!(calls, [1]);
+    i2c.done();
+}
+
+#[test]
+fn recovery_hook_not_called_below_threshold() {
+    let mut expectations = transactions::init(&test_data::PRO_IDLE);
+    expectations.extend(transactions::failed_read(6));
+    expectations.extend(transactions::init_no_calibration());
+    expectations.extend(transactions::read(&test_data::PRO_LJOY_L));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).unwrap();
+    let mut called = false;
+    let input = classic
+        .read_with_recovery_hook(3, 2, |_| called = true)
+        .unwrap();
+    assert!(input.joystick_left_x < -50);
+    assert!(!called);
+    i2c.done();
+}
+
+#[test]
+fn async_recovery_hook_is_awaited() {
+    use std::cell::Cell;
+    use wii_ext::async_impl::classic::Classic;
+    let mut expectations = transactions::init(&test_data::PRO_IDLE);
+    expectations.extend(transactions::failed_read(6));
+    expectations.extend(transactions::init_no_calibration());
+    expectations.extend(transactions::read(&test_data::PRO_LJOY_L));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let calls = Cell::new(0);
+    embassy_futures::block_on(async {
+        let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).await.unwrap();
+        let input = classic
+            .read_with_recovery_hook(3, 1, |errors| {
+                let calls = &calls;
+                async move {
+                    embassy_futures::yield_now().await;
+                    calls.set(calls.get() + errors);
+                }
+            })
+            .await
+            .unwrap();
+        assert!(input.joystick_left_x < -50);
+    });
+    assert_eq!(calls.get(), 1);
+    i2c.done();
+}
@@ -0,0 +1,111 @@
+use embedded_hal_mock::eh1::{
+    delay::NoopDelay,
+    i2c::{self, Transaction},
+};
+use wii_ext::blocking_impl::classic::Classic;
+use wii_ext::blocking_impl::nunchuk::Nunchuk;
+use wii_ext::core::EXT_I2C_ADDR;
+mod common;
+use common::{test_data, transactions};
+
+#[test]
+fn classic_read_register() {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.extend([
+        // Seek to the report format register
+        Transaction::write(EXT_I2C_ADDR as u8, vec![0xfe]),
+        Transaction::read(EXT_I2C_ADDR as u8, vec![test_data::CLASSIC_HIRES_DEFAULT]),
+        // Restore the cursor
+        Transaction::write(EXT_I2C_ADDR as u8, vec![0]),
+    ]);
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).unwrap();
+    let value = classic.read_register(0xfe).unwrap();
+    assert_eq!(value, test_data::CLASSIC_HIRES_DEFAULT);
+    i2c.done();
+}
+
+#[test]
+fn nunchuk_read_registers() {
+    let calibration = [0x80, 0x80, 0x80, 0x00, 0x80, 0x80, 0x80, 0x00];
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    expectations.extend([
+        // Seek to the factory calibration registers
+        Transaction::write(EXT_I2C_ADDR as u8, vec![0x20]),
+        Transaction::read(EXT_I2C_ADDR as u8, calibration.to_vec()),
+        // Restore the cursor
+        Transaction::write(EXT_I2C_ADDR as u8, vec![0]),
+        // Normal polling is unaffected
+        Transaction::write(EXT_I2C_ADDR as u8, vec![0]),
+        Transaction::read(EXT_I2C_ADDR as u8, test_data::NUNCHUCK_BTN_Z.to_vec()),
+    ]);
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut nunchuk = Nunchuk::new(i2c.clone(), NoopDelay::new()).unwrap();
+    let mut buf = [0u8; 8];
+    nunchuk.read_registers(0x20, &mut buf).unwrap();
+    assert_eq!(buf, calibration);
+    assert!(nunchuk.read().unwrap().button_z);
+    i2c.done();
+}
+
+#[test]
+fn classic_write_report_format_register() {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.extend(transactions::save_report_format(1));
+    expectations.extend([
+        // Switch to hi-res mode
+        Transaction::write(EXT_I2C_ADDR as u8, vec![0xfe, 0x03]),
+        // Restore the cursor
+        Transaction::write(EXT_I2C_ADDR as u8, vec![0]),
+        // The driver now reads hi-res reports
+        Transaction::write(EXT_I2C_ADDR as u8, vec![0]),
+        Transaction::read(EXT_I2C_ADDR as u8, test_data::CLASSIC_HD_BTN_X.to_vec()),
+    ]);
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).unwrap();
+    classic.write_register(0xfe, 0x03).unwrap();
+    let input = classic.read_uncalibrated().unwrap();
+    assert!(input.button_x);
+    assert_eq!(input.joystick_left_x, test_data::CLASSIC_HD_BTN_X[0]);
+    i2c.done();
+}
+
+#[test]
+fn classic_dump_registers() {
+    let dump: Vec<u8> = (0..32).collect();
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    // Seek to the start address
+    expectations.push(Transaction::write(EXT_I2C_ADDR as u8, vec![0x00]));
+    // Read in report-sized chunks
+    for chunk in dump.chunks(6) {
+        expectations.push(Transaction::read(EXT_I2C_ADDR as u8, chunk.to_vec()));
+    }
+    // Restore the cursor
+    expectations.push(Transaction::write(EXT_I2C_ADDR as u8, vec![0]));
+
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).unwrap();
+    let mut buf = [0u8; 32];
+    let len = classic.dump_registers(0x00, &mut buf).unwrap();
+    assert_eq!(len, 32);
+    assert_eq!(buf.to_vec(), dump);
+    i2c.done();
+}
+
+#[test]
+fn nunchuk_dump_registers_stops_at_end_of_register_space() {
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    expectations.extend([
+        Transaction::write(EXT_I2C_ADDR as u8, vec![0xfa]),
+        Transaction::read(EXT_I2C_ADDR as u8, test_data::NUNCHUCK_ID.to_vec()),
+        Transaction::write(EXT_I2C_ADDR as u8, vec![0]),
+    ]);
+
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut nunchuk = Nunchuk::new(i2c.clone(), NoopDelay::new()).unwrap();
+    let mut buf = [0u8; 32];
+    let len = nunchuk.dump_registers(0xfa, &mut buf).unwrap();
+    assert_eq!(len, 6);
+    assert_eq!(buf[..len], test_data::NUNCHUCK_ID);
+    i2c.done();
+}
@@ -0,0 +1,73 @@
+use wii_ext::core::classic::ClassicReading;
+use wii_ext::core::nunchuk::NunchukReading;
+use wii_ext::{classic_hd_report, classic_report, nunchuk_report};
+mod common;
+use common::test_data;
+
+#[test]
+fn converted_vectors_match_captures() {
+    // The captures the macro-built constants replaced
+    let captures = [
+        (test_data::PRO_BTN_B, [160, 31, 17, 0, 255, 191]),
+        (test_data::PRO_BTN_A, [160, 31, 17, 0, 255, 239]),
+        (test_data::PRO_BTN_X, [160, 31, 17, 0, 255, 247]),
+        (test_data::PRO_BTN_Y, [160, 31, 17, 0, 255, 223]),
+        (test_data::PRO_BTN_ZL, [160, 31, 17, 0, 255, 127]),
+        (test_data::PRO_BTN_ZR, [160, 31, 17, 0, 255, 251]),
+        (test_data::PRO_PAD_U, [160, 31, 17, 0, 255, 254]),
+        (test_data::PRO_PAD_D, [160, 31, 17, 0, 191, 255]),
+        (test_data::PRO_PAD_L, [160, 31, 17, 0, 255, 253]),
+        (test_data::PRO_PAD_R, [160, 31, 17, 0, 127, 255]),
+        (test_data::PRO_BTN_MINUS, [160, 31, 17, 0, 239, 255]),
+        (test_data::PRO_BTN_PLUS, [160, 31, 17, 0, 251, 255]),
+        (test_data::PRO_BTN_HOME, [160, 31, 17, 0, 247, 255]),
+    ];
+    for (built, captured) in captures {
+        assert_eq!(built, captured);
+    }
+}
+
+#[test]
+fn macro_matches_other_captures() {
+    assert_eq!(
+        classic_report! { ..test_data::CLASSIC_IDLE, button_a },
+        test_data::CLASSIC_BTN_A
+    );
+    assert_eq!(
+        classic_report! { ..test_data::NES_IDLE, button_minus },
+        test_data::NES_BTN_SELECT
+    );
+    let report = classic_report! { ..test_data::CLASSIC_IDLE, dpad_down, dpad_right };
+    let reading = ClassicReading::from_data(&report).unwrap();
+    assert_eq!(
+        reading,
+        ClassicReading::from_data(&test_data::CLASSIC_IDLE)
+            .unwrap()
+            .with_dpad_down(true)
+            .with_dpad_right(true)
+    );
+}
+
+#[test]
+fn fields_are_set() {
+    let report = classic_report! { joystick_right_y: 0, trigger_left: 0xFF, button_home };
+    let reading = ClassicReading::from_data(&report).unwrap();
+    // Standard reports only keep the top bits of each axis
+    assert_eq!(reading.joystick_right_y, 0);
+    assert_eq!(reading.trigger_left, 0xFF);
+    assert!(reading.button_home);
+    assert!(!reading.button_a);
+    let report = classic_hd_report! { joystick_left_x: 0x12, button_zr, button_a: false };
+    assert_eq!(report, [0x12, 0x80, 0x80, 0x80, 0, 0, 0xFF, 0xFB]);
+    let report = nunchuk_report! { ..test_data::NUNCHUCK_IDLE, button_z };
+    assert!(NunchukReading::from_data(&report).unwrap().button_z);
+    assert_eq!(nunchuk_report! {}, NunchukReading::CENTERED.to_report());
+}
+
+// The macros can build constants
+const CENTERED_HD: [u8; 8] = classic_hd_report! {};
+
+#[test]
+fn macros_work_in_constants() {
+    assert_eq!(CENTERED_HD, ClassicReading::CENTERED.to_hd_report());
+}
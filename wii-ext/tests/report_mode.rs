@@ -0,0 +1,168 @@
+use embassy_futures::block_on;
+use embedded_hal_mock::eh1::{
+    delay::NoopDelay,
+    i2c::{self, Transaction},
+};
+use wii_ext::blocking_impl::classic::Classic;
+use wii_ext::core::classic::{ClassicReading, ModeChangeOptions, ReportMode};
+use wii_ext::core::EXT_I2C_ADDR;
+use wii_ext::{classic_hd_report, Error};
+mod common;
+use common::{test_data, transactions};
+
+/// Transactions performed by switching to `value` and reading 0xFE back as `read_back`
+fn set_mode(value: u8, read_back: u8) -> Vec<Transaction> {
+    vec![
+        Transaction::write(EXT_I2C_ADDR as u8, vec![0xFE, value]),
+        // Verify
+        Transaction::write(EXT_I2C_ADDR as u8, vec![0xFE]),
+        Transaction::read(EXT_I2C_ADDR as u8, vec![read_back]),
+        Transaction::write(EXT_I2C_ADDR as u8, vec![0]),
+    ]
+}
+
+#[test]
+fn register_values() {
+    for (mode, value, len) in [
+        (ReportMode::Standard, 0x01, Some(6)),
+        (ReportMode::Alt, 0x02, Some(9)),
+        (ReportMode::HiRes, 0x03, Some(8)),
+        (ReportMode::Unknown(0x05), 0x05, None),
+    ] {
+        assert_eq!(ReportMode::from_register(value), mode);
+        assert_eq!(mode.register_value(), value);
+        assert_eq!(mode.report_len(), len);
+    }
+}
+
+#[test]
+fn alt_report_decodes_like_hires() {
+    let hd = classic_hd_report! { joystick_left_x: 0x12, joystick_right_y: 0xF0, trigger_left: 0x40, button_zl, dpad_up };
+    let reading = ClassicReading::from_hd_report(&hd);
+    let alt = reading.to_alt_report();
+    assert_eq!(ClassicReading::from_data(&alt), Some(reading));
+    // The low stick bits in byte 4 don't change the 8 bit axes
+    let mut low_bits = alt;
+    low_bits[4] = 0xFF;
+    assert_eq!(ClassicReading::from_alt_report(&low_bits), reading);
+}
+
+#[test]
+fn set_each_mode() {
+    for (mode, report) in [
+        (ReportMode::Standard, test_data::CLASSIC_IDLE.to_vec()),
+        (
+            ReportMode::Alt,
+            ClassicReading::from_data(&test_data::CLASSIC_HD_IDLE)
+                .unwrap()
+                .to_alt_report()
+                .to_vec(),
+        ),
+        (ReportMode::HiRes, test_data::CLASSIC_HD_IDLE.to_vec()),
+    ] {
+        let value = mode.register_value();
+        let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+        expectations.extend(transactions::save_report_format(1));
+        expectations.extend(set_mode(value, value));
+        // Calibration, then a normal read, both in the new format
+        expectations.extend(transactions::read(&report));
+        expectations.extend(transactions::read(&report));
+        let mut i2c = i2c::Mock::new(&expectations);
+        let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).unwrap();
+        classic.set_report_mode(mode).unwrap();
+        let reading = classic.read().unwrap();
+        assert!(reading.left_stick().is_centered(0), "{mode:?}");
+        assert!(reading.right_stick().is_centered(0), "{mode:?}");
+        i2c.done();
+    }
+}
+
+#[test]
+fn set_mode_with_options() {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.extend(transactions::save_report_format(1));
+    // Already standard, so only the hi-resolution switch is written, without recalibrating
+    expectations.extend(set_mode(0x03, 0x03));
+    expectations.extend(transactions::read(&test_data::CLASSIC_HD_IDLE));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).unwrap();
+    classic
+        .set_report_mode_with(ReportMode::Standard, ModeChangeOptions::DEFAULT)
+        .unwrap();
+    classic
+        .set_report_mode_with(
+            ReportMode::HiRes,
+            ModeChangeOptions::DEFAULT.with_recalibrate(false),
+        )
+        .unwrap();
+    assert_eq!(
+        classic.read_uncalibrated().unwrap(),
+        ClassicReading::from_hd_report(&test_data::CLASSIC_HD_IDLE)
+    );
+    i2c.done();
+}
+
+#[test]
+fn failed_verify() {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.extend(transactions::save_report_format(1));
+    // A clone that ignores the write
+    expectations.extend(set_mode(0x03, 0x01));
+    expectations.extend(transactions::read(&test_data::CLASSIC_BTN_A));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).unwrap();
+    match classic.set_report_mode(ReportMode::HiRes) {
+        Err(Error::WriteVerifyFailed {
+            addr: 0xFE,
+            expected: 0x03,
+            got: 0x01,
+        }) => {}
+        other => panic!("expected WriteVerifyFailed, got {:?}", other),
+    }
+    // Still decoding standard reports
+    assert!(classic.read().unwrap().button_a);
+    i2c.done();
+}
+
+#[test]
+fn unknown_mode_is_rejected() {
+    let mut i2c = i2c::Mock::new(&[]);
+    let mut classic = Classic::new_unchecked(i2c.clone(), NoopDelay::new());
+    assert!(matches!(
+        classic.set_report_mode(ReportMode::Unknown(0x04)),
+        Err(Error::InvalidInputData)
+    ));
+    i2c.done();
+}
+
+#[test]
+fn query_report_mode() {
+    let expectations = [
+        Transaction::write(EXT_I2C_ADDR as u8, vec![0xFE]),
+        Transaction::read(EXT_I2C_ADDR as u8, vec![0x02]),
+        Transaction::write(EXT_I2C_ADDR as u8, vec![0]),
+    ];
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = Classic::new_unchecked(i2c.clone(), NoopDelay::new());
+    assert_eq!(classic.report_mode().unwrap(), ReportMode::Alt);
+    i2c.done();
+}
+
+#[test]
+fn async_set_mode() {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.extend(transactions::save_report_format(1));
+    expectations.extend(set_mode(0x02, 0x02));
+    let alt = ClassicReading::from_data(&test_data::CLASSIC_HD_IDLE)
+        .unwrap()
+        .to_alt_report();
+    expectations.extend(transactions::read(&alt));
+    let mut i2c = i2c::Mock::new(&expectations);
+    block_on(async {
+        let mut classic = wii_ext::async_impl::classic::Classic::new(i2c.clone(), NoopDelay::new())
+            .await
+            .unwrap();
+        classic.set_report_mode(ReportMode::Alt).await.unwrap();
+    });
+    i2c.done();
+}
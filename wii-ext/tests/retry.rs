@@ -0,0 +1,111 @@
+use embassy_futures::block_on;
+use embedded_hal::i2c::ErrorKind;
+use embedded_hal_mock::eh1::{
+    delay::NoopDelay,
+    i2c::{self, Transaction},
+};
+use wii_ext::blocking_impl::nunchuk::NunchukBuilder;
+use wii_ext::core::{RetryPolicy, EXT_I2C_ADDR};
+use wii_ext::Error;
+mod common;
+use common::{test_data, transactions};
+
+/// A report read that is NACKed
+fn nack(len: usize) -> Transaction {
+    Transaction::read(EXT_I2C_ADDR as u8, vec![0; len]).with_error(ErrorKind::NoAcknowledge(
+        embedded_hal::i2c::NoAcknowledgeSource::Address,
+    ))
+}
+
+/// A report read where the read is NACKed `nacks` times before returning `report`
+fn flaky_read(nacks: usize, report: &[u8]) -> Vec<Transaction> {
+    let mut expectations = vec![Transaction::write(EXT_I2C_ADDR as u8, vec![0])];
+    expectations.extend((0..nacks).map(|_| nack(report.len())));
+    expectations.push(Transaction::read(EXT_I2C_ADDR as u8, report.to_vec()));
+    expectations
+}
+
+#[test]
+fn retries_nacks_until_success() {
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    expectations.extend(flaky_read(2, &test_data::NUNCHUCK_BTN_C));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut nunchuk = NunchukBuilder::new()
+        .retry_policy(RetryPolicy::new(3, 100))
+        .build(i2c.clone(), NoopDelay::new())
+        .unwrap();
+    assert!(nunchuk.read().unwrap().button_c);
+    i2c.done();
+}
+
+#[test]
+fn gives_up_after_max_attempts() {
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    expectations.push(Transaction::write(EXT_I2C_ADDR as u8, vec![0]));
+    expectations.extend((0..3).map(|_| nack(6)));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut nunchuk = NunchukBuilder::new()
+        .retry_policy(RetryPolicy::new(3, 100))
+        .build(i2c.clone(), NoopDelay::new())
+        .unwrap();
+    let result = nunchuk.read();
+    assert!(matches!(
+        result,
+        Err(Error::RetriesExhausted(ErrorKind::NoAcknowledge(_)))
+    ));
+    // The whole NACK storm counts as one failed read
+    assert_eq!(nunchuk.stats().i2c_errors, 1);
+    i2c.done();
+}
+
+#[test]
+fn default_policy_does_not_retry() {
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    expectations.push(Transaction::write(EXT_I2C_ADDR as u8, vec![0]));
+    expectations.push(nack(6));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut nunchuk = NunchukBuilder::new()
+        .build(i2c.clone(), NoopDelay::new())
+        .unwrap();
+    assert!(matches!(nunchuk.read(), Err(Error::I2C(_))));
+    i2c.done();
+}
+
+#[test]
+fn async_retries_nacks_until_success() {
+    use wii_ext::async_impl::classic::ClassicBuilder;
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.extend(flaky_read(2, &test_data::CLASSIC_BTN_B));
+    let mut i2c = i2c::Mock::new(&expectations);
+    block_on(async {
+        let mut classic = ClassicBuilder::new()
+            .retry_policy(RetryPolicy::new(3, 100))
+            .build(i2c.clone(), NoopDelay::new())
+            .await
+            .unwrap();
+        assert!(classic.read().await.unwrap().button_b);
+    });
+    i2c.done();
+}
+
+#[test]
+fn async_gives_up_after_max_attempts() {
+    use wii_ext::async_impl::classic::ClassicBuilder;
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.push(Transaction::write(EXT_I2C_ADDR as u8, vec![0]));
+    expectations.extend((0..2).map(|_| nack(6)));
+    let mut i2c = i2c::Mock::new(&expectations);
+    block_on(async {
+        let mut classic = ClassicBuilder::new()
+            .retry_policy(RetryPolicy::new(2, 100))
+            .build(i2c.clone(), NoopDelay::new())
+            .await
+            .unwrap();
+        assert!(matches!(
+            classic.read().await,
+            Err(Error::RetriesExhausted(_))
+        ));
+        assert_eq!(classic.stats().i2c_errors, 1);
+    });
+    i2c.done();
+}
@@ -0,0 +1,52 @@
+use core::cell::RefCell;
+use embedded_hal::i2c::I2c;
+use embedded_hal_bus::i2c::RefCellDevice;
+use embedded_hal_mock::eh1::{
+    delay::NoopDelay,
+    i2c::{self, Transaction},
+};
+use wii_ext::blocking_impl::classic::Classic;
+use wii_ext::blocking_impl::nunchuk::Nunchuk;
+mod common;
+use common::{test_data, transactions};
+
+/// Address of some other device on the bus, eg: an OLED display
+const OTHER_ADDR: u8 = 0x3c;
+
+#[test]
+fn nunchuk_shares_refcell_bus() {
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    expectations.push(Transaction::write(OTHER_ADDR, vec![0xae]));
+    // Traffic to other addresses between the seek and the read doesn't move our cursor
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_BTN_C)[..1].to_vec());
+    expectations.push(Transaction::write(OTHER_ADDR, vec![0xaf]));
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_BTN_C)[1..].to_vec());
+    let mut mock = i2c::Mock::new(&expectations);
+    let bus = RefCell::new(mock.clone());
+    let mut delay = NoopDelay::new();
+
+    let mut nunchuk = Nunchuk::new(RefCellDevice::new(&bus), &mut delay).unwrap();
+    let mut display = RefCellDevice::new(&bus);
+    display.write(OTHER_ADDR, &[0xae]).unwrap();
+    nunchuk.begin_read().unwrap();
+    display.write(OTHER_ADDR, &[0xaf]).unwrap();
+    assert!(nunchuk.finish_read().unwrap().button_c);
+    mock.done();
+}
+
+#[test]
+fn classic_borrows_bus() {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.extend(transactions::read(&test_data::CLASSIC_BTN_A));
+    expectations.push(Transaction::write(OTHER_ADDR, vec![0x00]));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut bus = i2c.clone();
+    let mut delay = NoopDelay::new();
+    {
+        let mut classic = Classic::new(&mut bus, &mut delay).unwrap();
+        assert!(classic.read().unwrap().button_a);
+    }
+    // The bus is usable again once the driver is gone
+    bus.write(OTHER_ADDR, &[0x00]).unwrap();
+    i2c.done();
+}
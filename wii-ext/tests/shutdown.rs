@@ -0,0 +1,113 @@
+use embassy_futures::block_on;
+use embedded_hal::i2c::ErrorKind;
+use embedded_hal_mock::eh1::{
+    delay::NoopDelay,
+    i2c::{self, Transaction},
+};
+use wii_ext::blocking_impl::classic::{Classic, ClassicBuilder};
+use wii_ext::core::EXT_I2C_ADDR;
+mod common;
+use common::{test_data, transactions};
+
+/// Transactions performed when restoring the power-on report format
+fn restore_report_format(value: u8) -> Vec<Transaction> {
+    vec![
+        Transaction::write(EXT_I2C_ADDR as u8, vec![254, value]),
+        Transaction::write(EXT_I2C_ADDR as u8, vec![0]),
+    ]
+}
+
+#[test]
+fn classic_shutdown_without_mode_change_is_destroy() {
+    let expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    let mut i2c = i2c::Mock::new(&expectations);
+    let classic = Classic::new(i2c.clone(), NoopDelay::new()).unwrap();
+    classic.shutdown().unwrap();
+    i2c.done();
+}
+
+#[test]
+fn classic_shutdown_restores_standard_mode() {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.extend(transactions::save_report_format(1));
+    expectations.push(Transaction::write(EXT_I2C_ADDR as u8, vec![254, 3]));
+    expectations.extend(transactions::read(&test_data::CLASSIC_HD_IDLE));
+    expectations.extend(restore_report_format(1));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).unwrap();
+    classic.enable_hires().unwrap();
+    classic.shutdown().unwrap();
+    i2c.done();
+}
+
+#[test]
+fn classic_shutdown_restores_the_power_on_format() {
+    // An adapter that powers on sending 0x02 format reports
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.extend(transactions::save_report_format(2));
+    expectations.push(Transaction::write(EXT_I2C_ADDR as u8, vec![254, 3]));
+    expectations.extend(transactions::read(&test_data::CLASSIC_HD_IDLE));
+    // Only the first change saves the format
+    expectations.push(Transaction::write(EXT_I2C_ADDR as u8, vec![254, 1]));
+    expectations.push(Transaction::write(EXT_I2C_ADDR as u8, vec![0]));
+    expectations.extend(restore_report_format(2));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).unwrap();
+    classic.enable_hires().unwrap();
+    classic.write_register(0xFE, 0x01).unwrap();
+    classic.shutdown().unwrap();
+    i2c.done();
+}
+
+#[test]
+fn unreadable_power_on_format_does_not_stop_enable_hires() {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    // A controller that doesn't answer the 1 byte read of 0xFE
+    expectations.push(Transaction::write(EXT_I2C_ADDR as u8, vec![254]));
+    expectations.push(Transaction::read(EXT_I2C_ADDR as u8, vec![0]).with_error(
+        ErrorKind::NoAcknowledge(embedded_hal::i2c::NoAcknowledgeSource::Data),
+    ));
+    expectations.push(Transaction::write(EXT_I2C_ADDR as u8, vec![254, 3]));
+    expectations.extend(transactions::read(&test_data::CLASSIC_HD_IDLE));
+    expectations.extend(transactions::read(&test_data::CLASSIC_HD_BTN_X));
+    // Standard reports are assumed
+    expectations.extend(restore_report_format(1));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).unwrap();
+    classic.enable_hires().unwrap();
+    assert!(classic.read().unwrap().button_x);
+    classic.shutdown().unwrap();
+    i2c.done();
+}
+
+#[test]
+fn classic_shutdown_after_assumed_hires_is_destroy() {
+    // The controller was already in hi-res mode before this driver took over
+    let mut i2c = i2c::Mock::new(&[]);
+    let classic = ClassicBuilder::new()
+        .skip_init()
+        .hires(true)
+        .build(i2c.clone(), NoopDelay::new())
+        .unwrap();
+    classic.shutdown().unwrap();
+    i2c.done();
+}
+
+#[test]
+fn async_classic_shutdown_restores_standard_mode() {
+    let mut expectations = transactions::init_no_calibration();
+    expectations.extend(transactions::save_report_format(1));
+    expectations.push(Transaction::write(EXT_I2C_ADDR as u8, vec![254, 3]));
+    expectations.extend(transactions::read(&test_data::CLASSIC_HD_IDLE));
+    expectations.extend(restore_report_format(1));
+    let mut i2c = i2c::Mock::new(&expectations);
+    block_on(async {
+        let classic = wii_ext::async_impl::classic::ClassicBuilder::new()
+            .hires(true)
+            .build(i2c.clone(), NoopDelay::new())
+            .await
+            .unwrap();
+        classic.shutdown().await.unwrap();
+    });
+    i2c.done();
+}
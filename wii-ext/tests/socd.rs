@@ -0,0 +1,126 @@
+use embedded_hal_mock::eh1::{delay::NoopDelay, i2c};
+use wii_ext::blocking_impl::classic::ClassicBuilder;
+use wii_ext::classic_report;
+use wii_ext::core::classic::{ClassicReadingCalibrated, SocdCleaner, SocdPolicy};
+mod common;
+use common::{test_data, transactions};
+
+/// D-pad directions held: up, down, left, right
+type Dpad = [bool; 4];
+
+const NONE: Dpad = [false; 4];
+const UP: Dpad = [true, false, false, false];
+const DOWN: Dpad = [false, true, false, false];
+const LEFT: Dpad = [false, false, true, false];
+const RIGHT: Dpad = [false, false, false, true];
+const UP_DOWN: Dpad = [true, true, false, false];
+const LEFT_RIGHT: Dpad = [false, false, true, true];
+
+/// Feed d-pad states through a cleaner, returning what it reports for each
+fn clean(policy: SocdPolicy, sequence: &[Dpad]) -> Vec<Dpad> {
+    let mut cleaner = SocdCleaner::new(policy);
+    sequence
+        .iter()
+        .map(|&[dpad_up, dpad_down, dpad_left, dpad_right]| {
+            let mut r = ClassicReadingCalibrated::default()
+                .with_dpad_up(dpad_up)
+                .with_dpad_down(dpad_down)
+                .with_dpad_left(dpad_left)
+                .with_dpad_right(dpad_right);
+            cleaner.apply(&mut r);
+            [r.dpad_up, r.dpad_down, r.dpad_left, r.dpad_right]
+        })
+        .collect()
+}
+
+#[test]
+fn pass_through_is_the_default() {
+    assert_eq!(SocdCleaner::default().policy, SocdPolicy::PassThrough);
+    let sequence = [UP_DOWN, LEFT_RIGHT, [true; 4]];
+    assert_eq!(clean(SocdPolicy::PassThrough, &sequence), sequence);
+}
+
+#[test]
+fn neutral() {
+    assert_eq!(
+        clean(
+            SocdPolicy::Neutral,
+            &[UP_DOWN, LEFT_RIGHT, [true; 4], UP, RIGHT]
+        ),
+        [NONE, NONE, NONE, UP, RIGHT]
+    );
+}
+
+#[test]
+fn up_priority() {
+    assert_eq!(
+        clean(
+            SocdPolicy::UpPriority,
+            &[UP_DOWN, LEFT_RIGHT, [true; 4], DOWN, LEFT]
+        ),
+        [UP, NONE, UP, DOWN, LEFT]
+    );
+}
+
+#[test]
+fn last_input_wins() {
+    for (first, second) in [(UP, DOWN), (DOWN, UP), (LEFT, RIGHT), (RIGHT, LEFT)] {
+        let both = [0, 1, 2, 3].map(|i| first[i] || second[i]);
+        assert_eq!(
+            clean(
+                SocdPolicy::LastInput,
+                &[first, both, both, second, both, first]
+            ),
+            // Holding both keeps the newer press, releasing it goes back to the other
+            [first, second, second, second, first, first]
+        );
+    }
+}
+
+#[test]
+fn last_input_with_both_pressed_at_once_is_neutral() {
+    assert_eq!(
+        clean(SocdPolicy::LastInput, &[UP_DOWN, UP_DOWN, UP]),
+        [NONE, NONE, UP]
+    );
+    assert_eq!(
+        clean(SocdPolicy::LastInput, &[NONE, LEFT_RIGHT, LEFT_RIGHT]),
+        [NONE, NONE, NONE]
+    );
+}
+
+#[test]
+fn pairs_are_independent() {
+    assert_eq!(
+        clean(
+            SocdPolicy::LastInput,
+            &[UP, UP_DOWN, [true, true, true, false], [true; 4]]
+        ),
+        [
+            UP,
+            DOWN,
+            [false, true, true, false],
+            [false, true, false, true]
+        ]
+    );
+}
+
+#[test]
+fn driver_cleans_calibrated_reads() {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.extend(transactions::read(
+        &classic_report! { ..test_data::CLASSIC_IDLE, dpad_left },
+    ));
+    expectations.extend(transactions::read(
+        &classic_report! { ..test_data::CLASSIC_IDLE, dpad_left, dpad_right },
+    ));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = ClassicBuilder::new()
+        .socd_policy(SocdPolicy::LastInput)
+        .build(i2c.clone(), NoopDelay::new())
+        .unwrap();
+    assert!(classic.read().unwrap().dpad_left);
+    let input = classic.read().unwrap();
+    assert!(!input.dpad_left && input.dpad_right);
+    i2c.done();
+}
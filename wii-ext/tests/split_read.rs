@@ -0,0 +1,51 @@
+use embedded_hal_mock::eh1::{delay::NoopDelay, i2c};
+use wii_ext::blocking_impl::classic::Classic;
+use wii_ext::blocking_impl::nunchuk::Nunchuk;
+use wii_ext::Error;
+mod common;
+use common::{test_data, transactions};
+
+#[test]
+fn classic_split_read_matches_read() {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    // The split read performs the same transactions as read()
+    expectations.extend(transactions::read(&test_data::CLASSIC_BTN_A));
+    expectations.extend(transactions::read(&test_data::CLASSIC_BTN_A));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).unwrap();
+
+    let blocking = classic.read().unwrap();
+    classic.begin_read().unwrap();
+    // Application does other work here instead of waiting
+    let split = classic.finish_read().unwrap();
+
+    assert!(split.button_a);
+    assert_eq!(split.button_a, blocking.button_a);
+    assert_eq!(split.joystick_left_x, blocking.joystick_left_x);
+    assert_eq!(split.joystick_right_y, blocking.joystick_right_y);
+    i2c.done();
+}
+
+#[test]
+fn classic_finish_without_begin() {
+    let expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).unwrap();
+    assert!(matches!(classic.finish_read(), Err(Error::ReadNotStarted)));
+    i2c.done();
+}
+
+#[test]
+fn nunchuk_split_read_only_finishes_once() {
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_BTN_Z));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut nunchuk = Nunchuk::new(i2c.clone(), NoopDelay::new()).unwrap();
+
+    nunchuk.begin_read().unwrap();
+    let input = nunchuk.finish_read_uncalibrated().unwrap();
+    assert!(input.button_z);
+    // The pending read has been consumed
+    assert!(matches!(nunchuk.finish_read(), Err(Error::ReadNotStarted)));
+    i2c.done();
+}
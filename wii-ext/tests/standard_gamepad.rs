@@ -0,0 +1,78 @@
+use wii_ext::core::classic::ClassicReadingCalibrated;
+use wii_ext::core::nunchuk::NunchukReadingCalibrated;
+use wii_ext::core::standard_gamepad::*;
+
+/// Index of the only pressed button, for a classic reading with one button set
+fn pressed_index(set: fn(&mut ClassicReadingCalibrated), face_buttons: FaceButtons) -> usize {
+    let mut reading = ClassicReadingCalibrated::default();
+    set(&mut reading);
+    let pad = StandardGamepad::from_classic(&reading, face_buttons);
+    let pressed: Vec<usize> = (0..BUTTON_COUNT).filter(|&i| pad.pressed(i)).collect();
+    assert_eq!(pressed.len(), 1, "{pressed:?}");
+    pressed[0]
+}
+
+#[test]
+fn classic_buttons_by_position() {
+    let index = |set| pressed_index(set, FaceButtons::Position);
+    assert_eq!(index(|r| r.button_b = true), 0);
+    assert_eq!(index(|r| r.button_a = true), 1);
+    assert_eq!(index(|r| r.button_y = true), 2);
+    assert_eq!(index(|r| r.button_x = true), 3);
+    assert_eq!(index(|r| r.button_trigger_l = true), 4);
+    assert_eq!(index(|r| r.button_trigger_r = true), 5);
+    assert_eq!(index(|r| r.button_zl = true), 6);
+    assert_eq!(index(|r| r.button_zr = true), 7);
+    assert_eq!(index(|r| r.button_minus = true), 8);
+    assert_eq!(index(|r| r.button_plus = true), 9);
+    assert_eq!(index(|r| r.dpad_up = true), 12);
+    assert_eq!(index(|r| r.dpad_down = true), 13);
+    assert_eq!(index(|r| r.dpad_left = true), 14);
+    assert_eq!(index(|r| r.dpad_right = true), 15);
+    assert_eq!(index(|r| r.button_home = true), 16);
+}
+
+#[test]
+fn classic_face_buttons_by_label() {
+    let index = |set| pressed_index(set, FaceButtons::Label);
+    assert_eq!(index(|r| r.button_a = true), 0);
+    assert_eq!(index(|r| r.button_b = true), 1);
+    assert_eq!(index(|r| r.button_x = true), 2);
+    assert_eq!(index(|r| r.button_y = true), 3);
+    // Everything else is the same as the position mapping
+    assert_eq!(index(|r| r.button_zr = true), 7);
+    assert_eq!(index(|r| r.button_home = true), 16);
+}
+
+#[test]
+fn default_mapping_is_by_position() {
+    let reading = ClassicReadingCalibrated::default().with_button_b(true);
+    assert!(StandardGamepad::from(&reading).pressed(SOUTH));
+}
+
+#[test]
+fn classic_axes() {
+    let reading = ClassicReadingCalibrated::default()
+        .with_joystick_left_x(10)
+        .with_joystick_left_y(20)
+        .with_joystick_right_x(-30)
+        .with_joystick_right_y(-128)
+        .with_trigger_left(100);
+    let pad = StandardGamepad::from(&reading);
+    // Up is negative in the standard layout
+    assert_eq!(pad.axes, [10, -20, -30, 127]);
+    assert!(!pad.pressed(BUTTON_COUNT));
+}
+
+#[test]
+fn nunchuk_is_left_half_of_a_gamepad() {
+    let reading = NunchukReadingCalibrated::default()
+        .with_joystick_x(-50)
+        .with_joystick_y(60)
+        .with_button_c(true)
+        .with_button_z(true);
+    let pad = StandardGamepad::from(&reading);
+    assert_eq!(pad.axes, [-50, -60, 0, 0]);
+    let pressed: Vec<usize> = (0..BUTTON_COUNT).filter(|&i| pad.pressed(i)).collect();
+    assert_eq!(pressed, [LEFT_BUMPER, LEFT_TRIGGER]);
+}
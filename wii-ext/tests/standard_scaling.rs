@@ -0,0 +1,71 @@
+use wii_ext::core::classic::ClassicReading;
+
+/// A standard report with the sticks at these raw 6 bit (left) and 5 bit (right) values
+fn sticks(lx: u8, ly: u8, rx: u8, ry: u8) -> [u8; 6] {
+    [
+        (rx & 0b1_1000) << 3 | lx,
+        (rx & 0b0_0110) << 5 | ly,
+        (rx & 0b0_0001) << 7 | ry,
+        0,
+        0xFF,
+        0xFF,
+    ]
+}
+
+fn decode_sticks(report: [u8; 6]) -> [u8; 4] {
+    let r = ClassicReading::from_report(&report);
+    [
+        r.joystick_left_x,
+        r.joystick_left_y,
+        r.joystick_right_x,
+        r.joystick_right_y,
+    ]
+}
+
+#[test]
+fn stick_minimum_scales_to_0() {
+    assert_eq!(decode_sticks(sticks(0, 0, 0, 0)), [0; 4]);
+}
+
+#[test]
+fn stick_center_scales_to_128() {
+    assert_eq!(decode_sticks(sticks(32, 32, 16, 16)), [128; 4]);
+}
+
+#[test]
+fn stick_maximum_scales_to_255() {
+    assert_eq!(decode_sticks(sticks(63, 63, 31, 31)), [255; 4]);
+}
+
+#[test]
+fn centered_sticks_decode_as_centered_reading() {
+    let r = ClassicReading::from_report(&sticks(32, 32, 16, 16));
+    assert_eq!(r, ClassicReading::CENTERED);
+}
+
+#[test]
+fn stick_scaling_is_symmetric_about_center() {
+    // One step either side of center moves the same distance
+    assert_eq!(decode_sticks(sticks(31, 33, 15, 17)), [124, 132, 120, 136]);
+}
+
+#[test]
+fn triggers_scale_linearly() {
+    for (raw, scaled) in [(0, 0), (16, 131), (31, 255)] {
+        let report = [
+            0,
+            0,
+            (raw & 0b1_1000) << 2,
+            (raw & 0b0_0111) << 5 | raw,
+            0xFF,
+            0xFF,
+        ];
+        let r = ClassicReading::from_report(&report);
+        assert_eq!((r.trigger_left, r.trigger_right), (scaled, scaled));
+    }
+}
+
+#[test]
+fn centered_reading_encodes_to_nominal_center() {
+    assert_eq!(ClassicReading::CENTERED.to_report(), sticks(32, 32, 16, 16));
+}
@@ -0,0 +1,93 @@
+use wii_ext::core::nunchuk::{NunchukReadingCalibrated, StationaryDetector, StationaryEvent};
+
+fn frame((accel_x, accel_y, accel_z): (u16, u16, u16)) -> NunchukReadingCalibrated {
+    NunchukReadingCalibrated::default()
+        .with_accel_x(accel_x)
+        .with_accel_y(accel_y)
+        .with_accel_z(accel_z)
+}
+
+/// Feed frames to a detector, collecting (frame index, event) pairs
+fn run<const N: usize>(
+    detector: &mut StationaryDetector<N>,
+    frames: &[(u16, u16, u16)],
+) -> Vec<(usize, StationaryEvent)> {
+    frames
+        .iter()
+        .enumerate()
+        .filter_map(|(i, f)| detector.update(&frame(*f)).map(|e| (i, e)))
+        .collect()
+}
+
+/// At rest with gravity on Z, and ±1 count of noise on every axis
+fn still(count: usize) -> Vec<(u16, u16, u16)> {
+    let noise = [0, 1, -1, 1, 0, -1, -1, 1];
+    (0..count)
+        .map(|i| {
+            let n = |offset: usize| noise[(i + offset) % noise.len()];
+            (
+                512u16.saturating_add_signed(n(0)),
+                512u16.saturating_add_signed(n(3)),
+                712u16.saturating_add_signed(n(5)),
+            )
+        })
+        .collect()
+}
+
+/// Slowly tilting back and forth, about 1 count per sample on X
+fn wave(count: usize) -> Vec<(u16, u16, u16)> {
+    (0..count)
+        .map(|i| {
+            let phase = (i % 80) as u16;
+            let x = if phase < 40 { phase } else { 80 - phase };
+            (492 + x, 512, 712)
+        })
+        .collect()
+}
+
+#[test]
+fn noisy_still_sequence_is_stationary() {
+    let mut detector: StationaryDetector<16> = StationaryDetector::default();
+    let events = run(&mut detector, &still(100));
+    // Stationary as soon as the window is full, and stays that way
+    assert_eq!(events, [(15, StationaryEvent::Settled)]);
+    assert!(detector.is_stationary());
+    assert_eq!(detector.spread(), [2, 2, 2]);
+}
+
+#[test]
+fn slow_wave_is_not_stationary() {
+    let mut detector: StationaryDetector<16> = StationaryDetector::default();
+    assert_eq!(run(&mut detector, &wave(200)), []);
+    assert!(!detector.is_stationary());
+}
+
+#[test]
+fn moving_after_settling() {
+    let mut detector: StationaryDetector<8> = StationaryDetector::new(4);
+    let mut frames = still(20);
+    frames.extend(wave(40).iter().skip(10));
+    frames.extend(still(20));
+    assert_eq!(
+        run(&mut detector, &frames),
+        [
+            (7, StationaryEvent::Settled),
+            (20, StationaryEvent::Moved),
+            (57, StationaryEvent::Settled),
+        ]
+    );
+}
+
+#[test]
+fn reset_waits_for_a_full_window() {
+    let mut detector: StationaryDetector<4> = StationaryDetector::default();
+    run(&mut detector, &still(4));
+    assert!(detector.is_stationary());
+    detector.reset();
+    assert!(!detector.is_stationary());
+    assert_eq!(run(&mut detector, &still(3)), []);
+    assert_eq!(
+        detector.update(&frame((512, 512, 712))),
+        Some(StationaryEvent::Settled)
+    );
+}
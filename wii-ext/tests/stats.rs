@@ -0,0 +1,74 @@
+use embassy_futures::block_on;
+use embedded_hal::i2c::ErrorKind;
+use embedded_hal_mock::eh1::{
+    delay::NoopDelay,
+    i2c::{self, Transaction},
+};
+use wii_ext::blocking_impl::classic::Classic;
+use wii_ext::blocking_impl::nunchuk::Nunchuk;
+use wii_ext::core::{Stats, EXT_I2C_ADDR};
+mod common;
+use common::{test_data, transactions};
+
+#[test]
+fn nunchuk_counts_reads_errors_and_reinits() {
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_BTN_C));
+    expectations.extend(transactions::failed_read(6));
+    expectations.extend(transactions::failed_read(6));
+    expectations.extend(transactions::init_no_calibration());
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_BTN_Z));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut nunchuk = Nunchuk::new(i2c.clone(), NoopDelay::new()).unwrap();
+    nunchuk.read().unwrap();
+    assert!(nunchuk.read().is_err());
+    nunchuk.read_with_recovery(2).unwrap();
+    assert_eq!(
+        nunchuk.stats(),
+        Stats {
+            i2c_errors: 2,
+            invalid_data_errors: 0,
+            reinits: 1,
+            // Including the calibration read
+            successful_reads: 3,
+            resyncs: 0,
+        }
+    );
+    i2c.done();
+}
+
+#[test]
+fn classic_counts_failed_seek_and_resets() {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.push(Transaction::write(EXT_I2C_ADDR as u8, vec![0]).with_error(ErrorKind::Other));
+    expectations.extend(transactions::read(&test_data::CLASSIC_BTN_A));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).unwrap();
+    assert!(classic.read().is_err());
+    assert_eq!(classic.stats().i2c_errors, 1);
+    classic.reset_stats();
+    assert_eq!(classic.stats(), Stats::default());
+    classic.read().unwrap();
+    assert_eq!(classic.stats().successful_reads, 1);
+    i2c.done();
+}
+
+#[test]
+fn async_classic_counts_errors() {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.extend(transactions::failed_read(6));
+    expectations.extend(transactions::read(&test_data::CLASSIC_BTN_B));
+    let mut i2c = i2c::Mock::new(&expectations);
+    block_on(async {
+        let mut classic = wii_ext::async_impl::classic::Classic::new(i2c.clone(), NoopDelay::new())
+            .await
+            .unwrap();
+        assert!(classic.read().await.is_err());
+        classic.read().await.unwrap();
+        let stats = classic.stats();
+        assert_eq!(stats.i2c_errors, 1);
+        assert_eq!(stats.successful_reads, 2);
+        assert_eq!(stats.reinits, 0);
+    });
+    i2c.done();
+}
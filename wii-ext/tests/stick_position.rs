@@ -0,0 +1,90 @@
+use wii_ext::core::classic::ClassicReadingCalibrated;
+use wii_ext::core::nunchuk::NunchukReadingCalibrated;
+use wii_ext::core::StickPosition;
+
+#[test]
+fn accessors_follow_flat_fields() {
+    let reading = ClassicReadingCalibrated::default()
+        .with_joystick_left_x(-5)
+        .with_joystick_left_y(6)
+        .with_joystick_right_x(i8::MIN)
+        .with_joystick_right_y(i8::MAX);
+    assert_eq!(reading.left_stick(), StickPosition::new(-5, 6));
+    assert_eq!(reading.right_stick(), StickPosition::new(i8::MIN, i8::MAX));
+    let reading = NunchukReadingCalibrated::default()
+        .with_joystick_x(3)
+        .with_joystick_y(-4);
+    assert_eq!(reading.stick(), StickPosition::new(3, -4));
+    assert_eq!(<(i8, i8)>::from(reading.stick()), (3, -4));
+}
+
+#[test]
+fn magnitude_at_extremes() {
+    assert_eq!(StickPosition::CENTER.magnitude_sq(), 0);
+    assert_eq!(StickPosition::new(3, -4).magnitude(), 5);
+    let corner = StickPosition::new(i8::MIN, i8::MIN);
+    assert_eq!(corner.magnitude_sq(), 32768);
+    assert_eq!(corner.magnitude(), 181);
+    assert_eq!(StickPosition::new(i8::MIN, 0).magnitude(), 128);
+}
+
+#[test]
+fn centered_within_slop() {
+    assert!(StickPosition::CENTER.is_centered(0));
+    assert!(StickPosition::new(-3, 3).is_centered(3));
+    assert!(!StickPosition::new(-4, 0).is_centered(3));
+    assert!(!StickPosition::new(i8::MIN, 0).is_centered(127));
+    assert!(StickPosition::new(i8::MIN, i8::MAX).is_centered(128));
+}
+
+#[test]
+fn clamping_to_a_circle() {
+    // Inside the circle, unchanged
+    assert_eq!(
+        StickPosition::new(3, -4).clamped_to(5),
+        StickPosition::new(3, -4)
+    );
+    assert_eq!(
+        StickPosition::new(30, -40).clamped_to(5),
+        StickPosition::new(3, -4)
+    );
+    assert_eq!(
+        StickPosition::new(7, 7).clamped_to(0),
+        StickPosition::CENTER
+    );
+    for position in [
+        StickPosition::new(i8::MIN, i8::MIN),
+        StickPosition::new(i8::MIN, i8::MAX),
+        StickPosition::new(i8::MAX, i8::MIN),
+        StickPosition::new(i8::MIN, 0),
+        StickPosition::new(100, -100),
+    ] {
+        let clamped = position.clamped_to(127);
+        assert!(clamped.magnitude_sq() <= 127 * 127, "{clamped:?}");
+    }
+    assert_eq!(
+        StickPosition::new(i8::MIN, i8::MIN).clamped_to(127),
+        StickPosition::new(-89, -89)
+    );
+    assert_eq!(
+        StickPosition::new(i8::MIN, 0).clamped_to(127),
+        StickPosition::new(-127, 0)
+    );
+}
+
+#[test]
+fn negation_saturates() {
+    assert_eq!(
+        -StickPosition::new(i8::MIN, i8::MAX),
+        StickPosition::new(i8::MAX, -i8::MAX)
+    );
+    assert_eq!(-StickPosition::new(5, -6), StickPosition::new(-5, 6));
+}
+
+#[test]
+fn map_applies_to_both_axes() {
+    let halved = StickPosition::new(i8::MIN, 101).map(|v| v / 2);
+    assert_eq!(halved, StickPosition::new(-64, 50));
+    let inverted = StickPosition::new(i8::MIN, 0).map(i8::saturating_neg);
+    assert_eq!(inverted, StickPosition::new(i8::MAX, 0));
+}
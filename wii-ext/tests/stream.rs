@@ -0,0 +1,68 @@
+#![cfg(feature = "futures")]
+use core::future::poll_fn;
+use core::pin::pin;
+use embassy_futures::block_on;
+use embedded_hal::i2c::ErrorKind;
+use embedded_hal_mock::eh1::{
+    delay::NoopDelay,
+    i2c::{self, Transaction},
+};
+use futures_core::Stream;
+use wii_ext::async_impl::classic::Classic;
+use wii_ext::async_impl::nunchuk::Nunchuk;
+use wii_ext::core::EXT_I2C_ADDR;
+use wii_ext::Error;
+mod common;
+use common::{test_data, transactions};
+
+/// Take the first `n` items from a stream
+async fn take<S: Stream>(stream: S, n: usize) -> Vec<S::Item> {
+    let mut stream = pin!(stream);
+    let mut items = Vec::new();
+    while items.len() < n {
+        match poll_fn(|cx| stream.as_mut().poll_next(cx)).await {
+            Some(item) => items.push(item),
+            None => break,
+        }
+    }
+    items
+}
+
+#[test]
+fn classic_stream_yields_readings_and_errors() {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.extend(transactions::read(&test_data::CLASSIC_LJOY_L));
+    expectations.push(Transaction::write(EXT_I2C_ADDR as u8, vec![0]));
+    expectations
+        .push(Transaction::read(EXT_I2C_ADDR as u8, vec![0; 6]).with_error(ErrorKind::Other));
+    expectations.extend(transactions::read(&test_data::CLASSIC_BTN_A));
+    let mut i2c = i2c::Mock::new(&expectations);
+    block_on(async {
+        let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).await.unwrap();
+        let items = take(classic.readings_stream(10_000), 3).await;
+        assert!(items[0].as_ref().unwrap().joystick_left_x < -50);
+        assert!(matches!(items[1], Err(Error::I2C(ErrorKind::Other))));
+        assert!(items[2].as_ref().unwrap().button_a);
+    });
+    i2c.done();
+}
+
+#[test]
+fn dropping_stream_leaves_driver_usable() {
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_JOY_L));
+    // Recalibration read
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_IDLE));
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_JOY_R));
+    let mut i2c = i2c::Mock::new(&expectations);
+    block_on(async {
+        let mut nunchuk = Nunchuk::new(i2c.clone(), NoopDelay::new()).await.unwrap();
+        let items = take(nunchuk.readings_stream(10_000), 1).await;
+        assert!(items[0].as_ref().unwrap().joystick_x < -90);
+        // The stream only borrowed the driver
+        nunchuk.update_calibration().await.unwrap();
+        let items = take(nunchuk.readings_stream(10_000), 1).await;
+        assert!(items[0].as_ref().unwrap().joystick_x > 90);
+    });
+    i2c.done();
+}
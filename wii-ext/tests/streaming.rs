@@ -0,0 +1,109 @@
+use embassy_futures::block_on;
+use embedded_hal::i2c::ErrorKind;
+use embedded_hal_mock::eh1::{
+    delay::NoopDelay,
+    i2c::{self, Transaction},
+};
+use wii_ext::blocking_impl::classic::{Classic, ClassicBuilder};
+use wii_ext::blocking_impl::nunchuk::Nunchuk;
+use wii_ext::core::EXT_I2C_ADDR;
+use wii_ext::Error;
+mod common;
+use common::{test_data, transactions};
+
+/// A report read without a seek first
+fn streamed(report: &[u8]) -> Transaction {
+    Transaction::read(EXT_I2C_ADDR as u8, report.to_vec())
+}
+
+#[test]
+fn streamed_reads_skip_the_seek() {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.push(streamed(&test_data::CLASSIC_BTN_A));
+    expectations.push(streamed(&test_data::CLASSIC_BTN_B));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).unwrap();
+    assert!(classic.read_streamed().unwrap().button_a);
+    assert!(classic.read_streamed().unwrap().button_b);
+    assert_eq!(classic.stats().resyncs, 0);
+    i2c.done();
+}
+
+#[test]
+fn first_streamed_read_seeks_if_the_cursor_is_unknown() {
+    let mut expectations = transactions::read(&test_data::CLASSIC_BTN_X);
+    expectations.push(streamed(&test_data::CLASSIC_BTN_Y));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = ClassicBuilder::new()
+        .skip_init()
+        .build(i2c.clone(), NoopDelay::new())
+        .unwrap();
+    assert!(classic.read_streamed().unwrap().button_x);
+    assert!(classic.read_streamed().unwrap().button_y);
+    i2c.done();
+}
+
+#[test]
+fn invalid_frame_resyncs() {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    // The constant bit is clear, so this didn't come from the start of a report
+    expectations.push(streamed(&[0; 6]));
+    expectations.extend(transactions::read(&test_data::CLASSIC_PAD_U));
+    expectations.push(streamed(&test_data::CLASSIC_PAD_D));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).unwrap();
+    assert!(classic.read_streamed().unwrap().dpad_up);
+    assert!(classic.read_streamed().unwrap().dpad_down);
+    assert_eq!(classic.stats().resyncs, 1);
+    i2c.done();
+}
+
+#[test]
+fn bus_error_is_returned_and_next_read_seeks() {
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    expectations.push(streamed(&test_data::NUNCHUCK_IDLE).with_error(ErrorKind::Other));
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_BTN_C));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut nunchuk = Nunchuk::new(i2c.clone(), NoopDelay::new()).unwrap();
+    assert!(matches!(
+        nunchuk.read_streamed(),
+        Err(Error::I2C(ErrorKind::Other))
+    ));
+    assert!(nunchuk.read_streamed().unwrap().button_c);
+    assert_eq!(nunchuk.stats().i2c_errors, 1);
+    assert_eq!(nunchuk.stats().resyncs, 0);
+    i2c.done();
+}
+
+#[test]
+fn nunchuk_resyncs_on_a_blank_frame() {
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    expectations.push(streamed(&[0xFF; 6]));
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_BTN_Z));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut nunchuk = Nunchuk::new(i2c.clone(), NoopDelay::new()).unwrap();
+    assert!(nunchuk.read_streamed().unwrap().button_z);
+    assert_eq!(nunchuk.stats().resyncs, 1);
+    i2c.done();
+}
+
+#[test]
+fn async_hires_streamed_reads() {
+    use wii_ext::async_impl::classic::Classic;
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.extend(transactions::save_report_format(1));
+    expectations.push(Transaction::write(EXT_I2C_ADDR as u8, vec![254, 3]));
+    expectations.extend(transactions::read(&test_data::CLASSIC_HD_IDLE));
+    expectations.push(streamed(&test_data::CLASSIC_HD_BTN_X));
+    expectations.push(streamed(&[0; 8]));
+    expectations.extend(transactions::read(&test_data::CLASSIC_HD_LJOY_U));
+    let mut i2c = i2c::Mock::new(&expectations);
+    block_on(async {
+        let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).await.unwrap();
+        classic.enable_hires().await.unwrap();
+        assert!(classic.read_streamed().await.unwrap().button_x);
+        assert!(classic.read_streamed().await.unwrap().joystick_left_y > 90);
+        assert_eq!(classic.stats().resyncs, 1);
+    });
+    i2c.done();
+}
@@ -0,0 +1,214 @@
+use embassy_futures::block_on;
+use embedded_hal_mock::eh1::{delay::NoopDelay, i2c};
+use wii_ext::blocking_impl::classic::{Classic, ClassicBuilder};
+use wii_ext::blocking_impl::nunchuk::{Nunchuk, NunchukBuilder};
+use wii_ext::core::classic::ReportMode;
+use wii_ext::core::{CheckFailures, ReportCheck};
+use wii_ext::Error;
+mod common;
+use common::{test_data, transactions};
+
+/// Transactions performed by building a strict nunchuk driver, calibrating on NUNCHUCK_IDLE
+fn strict_nunchuk_setup() -> Vec<i2c::Transaction> {
+    let mut transactions = transactions::init_verified();
+    transactions.extend(transactions::read_id(&test_data::NUNCHUCK_ID));
+    // Calibration reads are verified too
+    transactions.extend(transactions::read(&test_data::NUNCHUCK_IDLE));
+    transactions.extend(transactions::read(&test_data::NUNCHUCK_IDLE));
+    transactions
+}
+
+fn strict_nunchuk(i2c: &i2c::Mock) -> Nunchuk<i2c::Mock, NoopDelay> {
+    NunchukBuilder::new()
+        .strict(true)
+        .build(i2c.clone(), NoopDelay::new())
+        .unwrap()
+}
+
+/// Transactions performed by building a strict classic driver, calibrating on CLASSIC_IDLE
+fn strict_classic_setup() -> Vec<i2c::Transaction> {
+    let mut transactions = transactions::init_verified();
+    transactions.extend(transactions::read_id(&test_data::CLASSIC_ID));
+    transactions.extend(transactions::read(&test_data::CLASSIC_IDLE));
+    transactions.extend(transactions::read(&test_data::CLASSIC_IDLE));
+    transactions
+}
+
+fn strict_classic(i2c: &i2c::Mock) -> Classic<i2c::Mock, NoopDelay> {
+    ClassicBuilder::new()
+        .strict(true)
+        .build(i2c.clone(), NoopDelay::new())
+        .unwrap()
+}
+
+#[test]
+fn strict_reads_pass_every_check() {
+    let mut expectations = strict_nunchuk_setup();
+    // verify_reads: each sample is read twice
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_BTN_C));
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_BTN_C));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut nunchuk = strict_nunchuk(&i2c);
+    assert!(nunchuk.read().unwrap().button_c);
+    assert_eq!(nunchuk.check_failures(), CheckFailures::default());
+    assert!(!nunchuk.check_failures().any());
+    i2c.done();
+}
+
+#[test]
+fn strict_checks_controller_id() {
+    let mut expectations = transactions::init_verified();
+    expectations.extend(transactions::read_id(&test_data::CLASSIC_ID));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let result = NunchukBuilder::new()
+        .strict(true)
+        .build(i2c.clone(), NoopDelay::new());
+    assert!(matches!(result, Err(Error::UnexpectedController(_))));
+    i2c.done();
+}
+
+#[test]
+fn strict_rejects_blank_report() {
+    let mut expectations = strict_nunchuk_setup();
+    expectations.extend(transactions::read(&[0xFF; 6]));
+    // The identity check reads the ID for the anomalous report
+    expectations.extend(transactions::read_id(&test_data::NUNCHUCK_ID));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut nunchuk = strict_nunchuk(&i2c);
+    assert!(matches!(
+        nunchuk.read(),
+        Err(Error::RejectedReport(ReportCheck::Blank))
+    ));
+    let failures = nunchuk.check_failures();
+    assert!(failures.blank_report);
+    assert!(!failures.constant_bits);
+    assert_eq!(nunchuk.stats().invalid_data_errors, 1);
+    i2c.done();
+}
+
+#[test]
+fn blank_report_decoded_without_strict() {
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    expectations.extend(transactions::read(&[0xFF; 6]));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut nunchuk = NunchukBuilder::new()
+        .build(i2c.clone(), NoopDelay::new())
+        .unwrap();
+    assert!(nunchuk.read().is_ok());
+    assert!(!nunchuk.check_failures().any());
+    i2c.done();
+}
+
+#[test]
+fn strict_rejects_wrong_constant_bits() {
+    let mut report = test_data::CLASSIC_IDLE;
+    report[4] &= !1;
+    let mut expectations = strict_classic_setup();
+    expectations.extend(transactions::read(&report));
+    expectations.extend(transactions::read_id(&test_data::CLASSIC_ID));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = strict_classic(&i2c);
+    assert!(matches!(
+        classic.read(),
+        Err(Error::RejectedReport(ReportCheck::ConstantBits))
+    ));
+    assert!(classic.check_failures().constant_bits);
+    i2c.done();
+}
+
+#[test]
+fn strict_rejects_hires_wraparound() {
+    let mut report = test_data::PRO_HD_IDLE;
+    report[6] = report[0];
+    report[7] = report[1];
+    let mut expectations = transactions::init_verified();
+    expectations.extend(transactions::read_id(&test_data::CLASSIC_ID));
+    expectations.extend(transactions::save_report_format(1));
+    expectations.extend(transactions::verified_write(0xFE, 0x03, 0x03));
+    expectations.extend(transactions::read(&test_data::PRO_HD_IDLE));
+    expectations.extend(transactions::read(&test_data::PRO_HD_IDLE));
+    expectations.extend(transactions::read(&report));
+    // The wrapped button byte fails the constant bit check too, so the ID is read
+    expectations.extend(transactions::read_id(&test_data::CLASSIC_ID));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = ClassicBuilder::new()
+        .hires(true)
+        .strict(true)
+        .build(i2c.clone(), NoopDelay::new())
+        .unwrap();
+    assert!(matches!(
+        classic.read(),
+        Err(Error::RejectedReport(ReportCheck::Wraparound))
+    ));
+    let failures = classic.check_failures();
+    assert!(failures.hires_wraparound);
+    assert!(!failures.constant_bits);
+    i2c.done();
+}
+
+#[test]
+fn strict_records_failed_write() {
+    let mut expectations = strict_classic_setup();
+    expectations.extend(transactions::save_report_format(1));
+    expectations.extend(transactions::verified_write(0xFE, 0x03, 0x01));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = strict_classic(&i2c);
+    assert!(matches!(
+        classic.set_report_mode(ReportMode::HiRes),
+        Err(Error::WriteVerifyFailed { addr: 0xFE, .. })
+    ));
+    assert!(classic.check_failures().register_write);
+    i2c.done();
+}
+
+#[test]
+fn strict_records_inconsistent_reads() {
+    let mut expectations = strict_nunchuk_setup();
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_IDLE));
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_BTN_C));
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_IDLE));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut nunchuk = strict_nunchuk(&i2c);
+    assert!(matches!(nunchuk.read(), Err(Error::InconsistentData)));
+    assert!(nunchuk.check_failures().inconsistent_reads);
+    i2c.done();
+}
+
+#[test]
+fn strict_detects_stuck_controller() {
+    let mut expectations = strict_nunchuk_setup();
+    // 50 identical reports, two per sample
+    for _ in 0..50 {
+        expectations.extend(transactions::read(&test_data::NUNCHUCK_JOY_D));
+    }
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut nunchuk = strict_nunchuk(&i2c);
+    for _ in 0..24 {
+        nunchuk.read().unwrap();
+    }
+    assert!(!nunchuk.check_failures().stuck);
+    assert!(matches!(nunchuk.read(), Err(Error::Stuck)));
+    assert!(nunchuk.check_failures().stuck);
+    i2c.done();
+}
+
+#[test]
+fn async_strict_rejects_blank_report() {
+    let mut expectations = strict_nunchuk_setup();
+    expectations.extend(transactions::read(&[0xFF; 6]));
+    expectations.extend(transactions::read_id(&test_data::NUNCHUCK_ID));
+    let mut i2c = i2c::Mock::new(&expectations);
+    block_on(async {
+        let mut nunchuk = wii_ext::async_impl::nunchuk::NunchukBuilder::new()
+            .strict(true)
+            .build(i2c.clone(), NoopDelay::new())
+            .await
+            .unwrap();
+        assert!(matches!(
+            nunchuk.read().await,
+            Err(Error::RejectedReport(ReportCheck::Blank))
+        ));
+        assert!(nunchuk.check_failures().blank_report);
+    });
+    i2c.done();
+}
@@ -0,0 +1,78 @@
+use embassy_futures::block_on;
+use embedded_hal_mock::eh1::{delay::NoopDelay, i2c};
+use wii_ext::blocking_impl::classic::Classic;
+use wii_ext::blocking_impl::nunchuk::Nunchuk;
+use wii_ext::Error;
+mod common;
+use common::{test_data, transactions};
+
+#[test]
+fn nunchuk_stuck_reports_reinit_once() {
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    for _ in 0..4 {
+        expectations.extend(transactions::read(&test_data::NUNCHUCK_BTN_C));
+    }
+    // The fourth identical report triggers a re-init
+    expectations.extend(transactions::init_no_calibration());
+    for _ in 0..3 {
+        expectations.extend(transactions::read(&test_data::NUNCHUCK_BTN_C));
+    }
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut nunchuk = Nunchuk::new(i2c.clone(), NoopDelay::new()).unwrap();
+    nunchuk.enable_stuck_detection(4);
+    for _ in 0..6 {
+        assert!(nunchuk.read_with_recovery(2).unwrap().button_c);
+    }
+    assert_eq!(nunchuk.stats().reinits, 1);
+    i2c.done();
+}
+
+#[test]
+fn classic_idle_reports_are_not_stuck() {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    for _ in 0..10 {
+        expectations.extend(transactions::read(&test_data::CLASSIC_IDLE));
+    }
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).unwrap();
+    classic.enable_stuck_detection(3);
+    for _ in 0..10 {
+        classic.read().unwrap();
+    }
+    i2c.done();
+}
+
+#[test]
+fn classic_changing_reports_are_not_stuck() {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    for _ in 0..3 {
+        expectations.extend(transactions::read(&test_data::CLASSIC_BTN_A));
+        expectations.extend(transactions::read(&test_data::CLASSIC_BTN_B));
+    }
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).unwrap();
+    classic.enable_stuck_detection(2);
+    for _ in 0..6 {
+        classic.read().unwrap();
+    }
+    i2c.done();
+}
+
+#[test]
+fn async_classic_reports_stuck() {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    for _ in 0..3 {
+        expectations.extend(transactions::read(&test_data::CLASSIC_RJOY_L));
+    }
+    let mut i2c = i2c::Mock::new(&expectations);
+    block_on(async {
+        let mut classic = wii_ext::async_impl::classic::Classic::new(i2c.clone(), NoopDelay::new())
+            .await
+            .unwrap();
+        classic.enable_stuck_detection(3);
+        classic.read().await.unwrap();
+        classic.read().await.unwrap();
+        assert!(matches!(classic.read().await, Err(Error::Stuck)));
+    });
+    i2c.done();
+}
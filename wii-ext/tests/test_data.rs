@@ -0,0 +1,24 @@
+#![cfg(feature = "test-support")]
+use wii_ext::core::classic::ClassicReading;
+use wii_ext::core::{identify_controller, ControllerType, ExtHdReport, ExtReport};
+use wii_ext::test_data;
+
+#[test]
+fn exported_reports_use_report_types() {
+    let report: ExtReport = test_data::CLASSIC_BTN_A;
+    assert!(ClassicReading::from_data(&report).unwrap().button_a);
+    let report: ExtHdReport = test_data::CLASSIC_HD_BTN_X;
+    assert!(ClassicReading::from_data(&report).unwrap().button_x);
+}
+
+#[test]
+fn exported_ids_identify_controllers() {
+    assert_eq!(
+        identify_controller(test_data::NUNCHUCK_ID),
+        Some(ControllerType::Nunchuk)
+    );
+    assert_eq!(
+        identify_controller(test_data::PRO_ID),
+        Some(ControllerType::ClassicPro)
+    );
+}
@@ -0,0 +1,80 @@
+use wii_ext::core::nunchuk::NunchukReadingCalibrated;
+use wii_ext::core::tilt::TiltStick;
+
+/// A frame from a nunchuk at rest, with the stick untouched
+fn frame(accel_x: u16, accel_y: u16, accel_z: u16) -> NunchukReadingCalibrated {
+    NunchukReadingCalibrated::default()
+        .with_accel_x(accel_x)
+        .with_accel_y(accel_y)
+        .with_accel_z(accel_z)
+}
+
+const LEVEL: (u16, u16, u16) = (512, 512, 712);
+
+#[test]
+fn level_is_centered() {
+    let input = TiltStick::default().apply(frame(LEVEL.0, LEVEL.1, LEVEL.2));
+    assert_eq!((input.joystick_x, input.joystick_y), (0, 0));
+    // Small wobbles stay inside the deadband
+    let input = TiltStick::default().apply(frame(520, 505, 712));
+    assert_eq!((input.joystick_x, input.joystick_y), (0, 0));
+}
+
+#[test]
+fn pitched_forward_moves_y_up() {
+    // About 30 degrees forward: Y drops by half a g
+    let input = TiltStick::default().apply(frame(512, 412, 685));
+    assert_eq!((input.joystick_x, input.joystick_y), (0, 127));
+    // Halfway into the active range
+    let input = TiltStick::default().apply(frame(512, 457, 705));
+    assert_eq!((input.joystick_x, input.joystick_y), (0, 63));
+}
+
+#[test]
+fn rolled_right_moves_x_right() {
+    let input = TiltStick::default().apply(frame(562, 512, 700));
+    assert_eq!((input.joystick_x, input.joystick_y), (56, 0));
+    // Tilting past full scale saturates
+    let input = TiltStick::default().apply(frame(712, 512, 512));
+    assert_eq!(input.joystick_x, 127);
+    let input = TiltStick::default().apply(frame(312, 612, 512));
+    assert_eq!((input.joystick_x, input.joystick_y), (-127, -127));
+}
+
+#[test]
+fn larger_deflection_wins() {
+    let tilt = TiltStick::default();
+    let mut r = frame(562, 512, 700);
+    r.joystick_y = -80;
+    // The stick is pushed further than the nunchuk is tilted
+    let input = tilt.apply(r);
+    assert_eq!((input.joystick_x, input.joystick_y), (0, -80));
+    r.joystick_y = -20;
+    let input = tilt.apply(r);
+    assert_eq!((input.joystick_x, input.joystick_y), (56, 0));
+}
+
+#[test]
+fn sensitivity_and_level_are_configurable() {
+    let mut tilt = TiltStick {
+        full_scale: 50,
+        deadband: 0,
+        ..Default::default()
+    };
+    tilt.set_level(&frame(500, 530, 712));
+    assert_eq!(tilt.tilt(&frame(500, 530, 712)), (0, 0));
+    assert_eq!(tilt.tilt(&frame(525, 505, 712)), (63, 63));
+    assert_eq!(tilt.tilt(&frame(550, 480, 712)), (127, 127));
+}
+
+#[test]
+fn other_fields_pass_through() {
+    let mut r = frame(712, 512, 512);
+    r.button_c = true;
+    let input = TiltStick::default().apply(r);
+    assert!(input.button_c);
+    assert_eq!(
+        (input.accel_x, input.accel_y, input.accel_z),
+        (712, 512, 512)
+    );
+}
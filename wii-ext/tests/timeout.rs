@@ -0,0 +1,98 @@
+use core::future::{pending, ready};
+use embassy_futures::block_on;
+use embedded_hal::i2c::{ErrorKind, ErrorType, Operation};
+use embedded_hal_async::i2c::I2c;
+use embedded_hal_mock::eh1::{delay::NoopDelay, i2c};
+use wii_ext::async_impl::classic::Classic;
+use wii_ext::async_impl::nunchuk::Nunchuk;
+use wii_ext::core::nunchuk::CalibrationData as NunchukCalibration;
+use wii_ext::Error;
+mod common;
+use common::{test_data, transactions};
+
+/// Wraps a mock bus, and never completes the first read issued on it
+struct StallFirstRead {
+    inner: i2c::Mock,
+    stall: bool,
+}
+
+impl ErrorType for StallFirstRead {
+    type Error = ErrorKind;
+}
+
+impl I2c for StallFirstRead {
+    async fn read(&mut self, address: u8, read: &mut [u8]) -> Result<(), Self::Error> {
+        if core::mem::take(&mut self.stall) {
+            pending::<()>().await;
+        }
+        self.inner.read(address, read).await
+    }
+
+    async fn write(&mut self, address: u8, write: &[u8]) -> Result<(), Self::Error> {
+        self.inner.write(address, write).await
+    }
+
+    async fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        self.inner.transaction(address, operations).await
+    }
+}
+
+#[test]
+fn nunchuk_read_completes_before_timeout() {
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_BTN_C));
+    let mut i2c = i2c::Mock::new(&expectations);
+    block_on(async {
+        let mut nunchuk = Nunchuk::new(i2c.clone(), NoopDelay::new()).await.unwrap();
+        let input = nunchuk.read_with_timeout(pending::<()>()).await.unwrap();
+        assert!(input.button_c);
+    });
+    i2c.done();
+}
+
+#[test]
+fn nunchuk_stalled_read_times_out_then_recovers() {
+    // The stalled read sets the cursor but never gets its data
+    let mut expectations = transactions::read(&test_data::NUNCHUCK_IDLE)[..1].to_vec();
+    // The next read sets the cursor again before reading
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_BTN_Z));
+    let mut mock = i2c::Mock::new(&expectations);
+    let bus = StallFirstRead {
+        inner: mock.clone(),
+        stall: true,
+    };
+    block_on(async {
+        let mut nunchuk = Nunchuk::new_unchecked(bus, NoopDelay::new());
+        nunchuk.set_calibration(NunchukCalibration {
+            joystick_x: test_data::NUNCHUCK_IDLE[0],
+            joystick_y: test_data::NUNCHUCK_IDLE[1],
+        });
+        let result = nunchuk.read_with_timeout(ready(())).await;
+        assert!(matches!(result, Err(Error::Timeout)));
+        assert_eq!(nunchuk.stats().i2c_errors, 1);
+        let input = nunchuk.read_with_timeout(pending::<()>()).await.unwrap();
+        assert!(input.button_z);
+        assert_eq!(nunchuk.stats().i2c_errors, 1);
+        assert_eq!(nunchuk.stats().successful_reads, 1);
+    });
+    mock.done();
+}
+
+#[test]
+fn classic_stalled_read_times_out() {
+    let mut mock = i2c::Mock::new(&transactions::read(&test_data::CLASSIC_IDLE)[..1]);
+    let bus = StallFirstRead {
+        inner: mock.clone(),
+        stall: true,
+    };
+    block_on(async {
+        let mut classic = Classic::new_unchecked(bus, NoopDelay::new());
+        let result = classic.read_with_timeout(ready(())).await;
+        assert!(matches!(result, Err(Error::Timeout)));
+    });
+    mock.done();
+}
@@ -0,0 +1,234 @@
+use embassy_futures::block_on;
+use embedded_hal_mock::eh1::i2c::{self, Transaction};
+use std::cell::Cell;
+use std::rc::Rc;
+use wii_ext::blocking_impl::classic::ClassicBuilder;
+use wii_ext::blocking_impl::nunchuk::NunchukBuilder;
+use wii_ext::core::{Timing, EXT_I2C_ADDR};
+mod common;
+use common::{test_data, transactions};
+
+/// A delay that adds up how long it was asked to wait
+#[derive(Clone, Default)]
+struct TotalDelay(Rc<Cell<u64>>);
+
+impl TotalDelay {
+    fn total_us(&self) -> u64 {
+        self.0.get() / 1000
+    }
+}
+
+impl embedded_hal::delay::DelayNs for TotalDelay {
+    fn delay_ns(&mut self, ns: u32) {
+        self.0.set(self.0.get() + u64::from(ns));
+    }
+}
+
+impl embedded_hal_async::delay::DelayNs for TotalDelay {
+    async fn delay_ns(&mut self, ns: u32) {
+        self.0.set(self.0.get() + u64::from(ns));
+    }
+}
+
+fn hires_startup() -> Vec<Transaction> {
+    let mut expectations = transactions::init_no_calibration();
+    expectations.extend(transactions::save_report_format(1));
+    expectations.push(Transaction::write(EXT_I2C_ADDR as u8, vec![254, 3]));
+    expectations.extend(transactions::read(&test_data::CLASSIC_HD_IDLE));
+    expectations.extend(transactions::read(&test_data::CLASSIC_HD_BTN_X));
+    expectations
+}
+
+/// Build a hi-res classic driver and read it once, returning the total delay
+fn classic_startup(timing: Timing) -> u64 {
+    let mut i2c = i2c::Mock::new(&hires_startup());
+    let delay = TotalDelay::default();
+    let mut classic = ClassicBuilder::new()
+        .hires(true)
+        .timing(timing)
+        .build(i2c.clone(), delay.clone())
+        .unwrap();
+    assert!(classic.read().unwrap().button_x);
+    i2c.done();
+    delay.total_us()
+}
+
+fn async_classic_startup(timing: Timing) -> u64 {
+    use wii_ext::async_impl::classic::ClassicBuilder;
+    let mut i2c = i2c::Mock::new(&hires_startup());
+    let delay = TotalDelay::default();
+    block_on(async {
+        let mut classic = ClassicBuilder::new()
+            .hires(true)
+            .timing(timing)
+            .build(i2c.clone(), delay.clone())
+            .await
+            .unwrap();
+        assert!(classic.read().await.unwrap().button_x);
+    });
+    i2c.done();
+    delay.total_us()
+}
+
+#[test]
+fn default_timing_is_conservative() {
+    assert_eq!(Timing::default(), Timing::CONSERVATIVE);
+    let fast = Timing::fast();
+    assert!(fast.init_step_us <= Timing::CONSERVATIVE.init_step_us);
+    assert!(fast.settle_us < Timing::CONSERVATIVE.settle_us);
+}
+
+#[test]
+fn both_profiles_send_the_same_transactions() {
+    let conservative = classic_startup(Timing::CONSERVATIVE);
+    let fast = classic_startup(Timing::fast());
+    assert!(fast < conservative / 10, "{fast}us vs {conservative}us");
+}
+
+#[test]
+fn blocking_and_async_wait_the_same_time() {
+    for timing in [Timing::CONSERVATIVE, Timing::fast()] {
+        assert_eq!(classic_startup(timing), async_classic_startup(timing));
+    }
+}
+
+#[test]
+fn custom_timing_is_used_for_init() {
+    let timing = Timing {
+        init_step_us: 1,
+        settle_us: 1000,
+        read_delay_us: 0,
+    };
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_BTN_C));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let delay = TotalDelay::default();
+    let mut nunchuk = NunchukBuilder::new()
+        .timing(timing)
+        .build(i2c.clone(), delay.clone())
+        .unwrap();
+    // Settle before and after the handshake, with a step between its writes
+    assert_eq!(delay.total_us(), 2002);
+    assert!(nunchuk.read().unwrap().button_c);
+    i2c.done();
+}
+
+/// One thing a driver did on the bus or the delay, in order
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Step {
+    Write(Vec<u8>),
+    Read(usize),
+    DelayUs(u32),
+}
+
+/// An i2c bus and delay logging to the same list, so waits can be checked against transactions
+///
+/// Every read returns an idle nunchuk report.
+#[derive(Clone, Default)]
+struct Sequence(Rc<std::cell::RefCell<Vec<Step>>>);
+
+impl Sequence {
+    fn take(&self) -> Vec<Step> {
+        self.0.take()
+    }
+
+    fn log(&mut self, operations: &mut [embedded_hal::i2c::Operation<'_>]) {
+        use embedded_hal::i2c::Operation;
+        for operation in operations {
+            let step = match operation {
+                Operation::Write(bytes) => Step::Write(bytes.to_vec()),
+                Operation::Read(buf) => {
+                    let report = test_data::NUNCHUCK_IDLE;
+                    buf.copy_from_slice(&report[..buf.len()]);
+                    Step::Read(buf.len())
+                }
+            };
+            self.0.borrow_mut().push(step);
+        }
+    }
+}
+
+impl embedded_hal::i2c::ErrorType for Sequence {
+    type Error = embedded_hal::i2c::ErrorKind;
+}
+
+impl embedded_hal::i2c::I2c for Sequence {
+    fn transaction(
+        &mut self,
+        _address: u8,
+        operations: &mut [embedded_hal::i2c::Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        self.log(operations);
+        Ok(())
+    }
+}
+
+impl embedded_hal_async::i2c::I2c for Sequence {
+    async fn transaction(
+        &mut self,
+        _address: u8,
+        operations: &mut [embedded_hal::i2c::Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        self.log(operations);
+        Ok(())
+    }
+}
+
+impl embedded_hal::delay::DelayNs for Sequence {
+    fn delay_ns(&mut self, ns: u32) {
+        self.0.borrow_mut().push(Step::DelayUs(ns / 1000));
+    }
+}
+
+impl embedded_hal_async::delay::DelayNs for Sequence {
+    async fn delay_ns(&mut self, ns: u32) {
+        self.0.borrow_mut().push(Step::DelayUs(ns / 1000));
+    }
+}
+
+#[test]
+fn nunchuk_waits_between_seek_and_read() {
+    let expected = [
+        Step::Write(vec![0]),
+        Step::DelayUs(Timing::CONSERVATIVE.read_delay_us),
+        Step::Read(6),
+    ];
+
+    let bus = Sequence::default();
+    let mut nunchuk =
+        wii_ext::blocking_impl::nunchuk::Nunchuk::new_unchecked(bus.clone(), bus.clone());
+    nunchuk.read().unwrap();
+    assert_eq!(bus.take(), expected);
+
+    let bus = Sequence::default();
+    let mut nunchuk =
+        wii_ext::async_impl::nunchuk::Nunchuk::new_unchecked(bus.clone(), bus.clone());
+    block_on(nunchuk.read()).unwrap();
+    assert_eq!(bus.take(), expected);
+}
+
+#[test]
+fn blocking_and_async_share_the_default_init_delays() {
+    let timing = Timing::default();
+    let init = [
+        Step::DelayUs(timing.settle_us),
+        Step::Write(vec![0]),
+        Step::DelayUs(timing.init_step_us),
+        Step::Write(vec![0xF0, 0x55]),
+        Step::DelayUs(timing.init_step_us),
+        Step::Write(vec![0xFB, 0x00]),
+        Step::DelayUs(timing.settle_us),
+    ];
+
+    let bus = Sequence::default();
+    NunchukBuilder::new()
+        .build(bus.clone(), bus.clone())
+        .unwrap();
+    let blocking = bus.take();
+    assert_eq!(blocking[..init.len()], init);
+
+    let bus = Sequence::default();
+    block_on(wii_ext::async_impl::nunchuk::NunchukBuilder::new().build(bus.clone(), bus.clone()))
+        .unwrap();
+    assert_eq!(bus.take(), blocking);
+}
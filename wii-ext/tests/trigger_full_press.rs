@@ -0,0 +1,146 @@
+use embassy_futures::block_on;
+use embedded_hal_mock::eh1::{
+    delay::NoopDelay,
+    i2c::{self, Transaction},
+};
+use wii_ext::blocking_impl::classic::{Classic, ClassicBuilder};
+use wii_ext::classic_hd_report;
+use wii_ext::core::classic::{ClassicReading, TriggerRange};
+use wii_ext::core::EXT_I2C_ADDR;
+mod common;
+use common::{test_data, transactions};
+
+/// Resting value of both triggers
+const REST: u8 = 20;
+
+/// Raw value of a trigger that doesn't reach the top of its range when fully pulled
+const FULL: u8 = 200;
+
+fn hires_setup() -> Vec<Transaction> {
+    let mut expectations = transactions::init_no_calibration();
+    expectations.extend(transactions::save_report_format(1));
+    expectations.push(Transaction::write(EXT_I2C_ADDR as u8, vec![254, 3]));
+    expectations.extend(transactions::read(
+        &classic_hd_report! { trigger_left: REST, trigger_right: REST },
+    ));
+    expectations
+}
+
+fn hires_classic(i2c: &i2c::Mock) -> Classic<i2c::Mock, NoopDelay> {
+    ClassicBuilder::new()
+        .hires(true)
+        .build(i2c.clone(), NoopDelay::new())
+        .unwrap()
+}
+
+fn triggers(left: u8, right: u8) -> Vec<Transaction> {
+    transactions::read(&classic_hd_report! { trigger_left: left, trigger_right: right })
+}
+
+#[test]
+fn full_press_scales_to_max() {
+    let mut expectations = hires_setup();
+    expectations.extend(triggers(FULL, FULL));
+    expectations.extend(triggers(FULL, FULL));
+    expectations.extend(triggers(110, REST));
+    expectations.extend(triggers(REST, REST));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = hires_classic(&i2c);
+    let range = classic.calibrate_trigger_full_press().unwrap();
+    assert_eq!(range.left_full, Some(FULL));
+    assert_eq!(range.right_full, Some(FULL));
+    let input = classic.read().unwrap();
+    assert_eq!((input.trigger_left, input.trigger_right), (127, 127));
+    // Half of the travel from rest to full
+    let input = classic.read().unwrap();
+    assert_eq!((input.trigger_left, input.trigger_right), (63, 0));
+    let input = classic.read().unwrap();
+    assert_eq!((input.trigger_left, input.trigger_right), (0, 0));
+    i2c.done();
+}
+
+#[test]
+fn unscaled_without_full_press() {
+    let mut expectations = hires_setup();
+    expectations.extend(triggers(110, REST));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = hires_classic(&i2c);
+    assert_eq!(classic.trigger_range(), TriggerRange::NONE);
+    assert_eq!(classic.read().unwrap().trigger_left, 90);
+    i2c.done();
+}
+
+#[test]
+fn triggers_can_be_pulled_one_at_a_time() {
+    let mut expectations = hires_setup();
+    expectations.extend(triggers(FULL, REST));
+    expectations.extend(triggers(REST + 1, 180));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = hires_classic(&i2c);
+    classic.calibrate_trigger_full_press().unwrap();
+    let range = classic.calibrate_trigger_full_press().unwrap();
+    assert_eq!(range.left_full, Some(FULL));
+    assert_eq!(range.right_full, Some(180));
+    i2c.done();
+}
+
+#[test]
+fn digital_triggers_are_not_recorded() {
+    let mut expectations = transactions::init(&test_data::PRO_IDLE);
+    // A Classic Pro reports the click bits, but its analogue triggers stay at rest
+    let idle = ClassicReading::from_data(&test_data::PRO_IDLE).unwrap();
+    let clicked = idle
+        .with_button_trigger_l(true)
+        .with_button_trigger_r(true)
+        .to_report();
+    expectations.extend(transactions::read(&clicked));
+    expectations.extend(transactions::read(&clicked));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).unwrap();
+    let range = classic.calibrate_trigger_full_press().unwrap();
+    assert_eq!(range, TriggerRange::NONE);
+    let input = classic.read().unwrap();
+    assert_eq!((input.trigger_left, input.trigger_right), (0, 0));
+    assert!(classic.trigger_left_engaged());
+    i2c.done();
+}
+
+#[test]
+fn builder_restores_saved_range() {
+    let mut expectations = hires_setup();
+    expectations.extend(triggers(FULL, FULL));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let saved = TriggerRange {
+        left_full: Some(FULL),
+        right_full: None,
+    };
+    let mut classic = ClassicBuilder::new()
+        .hires(true)
+        .trigger_range(saved)
+        .build(i2c.clone(), NoopDelay::new())
+        .unwrap();
+    let input = classic.read().unwrap();
+    // The right trigger is unscaled, and clamps at 127 from rest
+    assert_eq!((input.trigger_left, input.trigger_right), (127, 127));
+    assert_eq!(classic.trigger_range(), saved);
+    i2c.done();
+}
+
+#[test]
+fn async_full_press_scales_to_max() {
+    let mut expectations = hires_setup();
+    expectations.extend(triggers(FULL, FULL));
+    expectations.extend(triggers(110, 65));
+    let mut i2c = i2c::Mock::new(&expectations);
+    block_on(async {
+        let mut classic = wii_ext::async_impl::classic::ClassicBuilder::new()
+            .hires(true)
+            .build(i2c.clone(), NoopDelay::new())
+            .await
+            .unwrap();
+        classic.calibrate_trigger_full_press().await.unwrap();
+        let input = classic.read().await.unwrap();
+        assert_eq!((input.trigger_left, input.trigger_right), (63, 31));
+    });
+    i2c.done();
+}
@@ -0,0 +1,112 @@
+use embedded_hal_mock::eh1::{delay::NoopDelay, i2c};
+use wii_ext::blocking_impl::classic::ClassicBuilder;
+use wii_ext::classic_hd_report;
+use wii_ext::core::classic::{
+    CalibrationData, ClassicReadingCalibrated, TriggerThresholds, TriggerTracker,
+};
+mod common;
+use common::transactions;
+
+fn trigger_left(value: i8) -> ClassicReadingCalibrated {
+    ClassicReadingCalibrated::default().with_trigger_left(value)
+}
+
+/// Feed the tracker a sweep of left trigger values, returning the number of engages and releases
+fn sweep(tracker: &mut TriggerTracker, values: impl Iterator<Item = i8>) -> (u32, u32) {
+    let (mut engages, mut releases) = (0, 0);
+    for value in values {
+        let was_engaged = tracker.left_engaged();
+        tracker.update(&trigger_left(value));
+        match (was_engaged, tracker.left_engaged()) {
+            (false, true) => engages += 1,
+            (true, false) => releases += 1,
+            _ => {}
+        }
+    }
+    (engages, releases)
+}
+
+#[test]
+fn sweep_engages_and_releases_once() {
+    let mut tracker = TriggerTracker::default();
+    let up = 0..=127;
+    let down = (0..=127).rev();
+    assert_eq!(sweep(&mut tracker, up.chain(down)), (1, 1));
+    assert!(!tracker.left_engaged());
+    assert!(!tracker.right_engaged());
+}
+
+#[test]
+fn jitter_inside_band_does_not_chatter() {
+    let mut tracker = TriggerTracker::default();
+    // Noisy hold around the engage point, then around the release point
+    let noise = [
+        62, 63, 64, 63, 65, 62, 64, 58, 50, 49, 48, 49, 50, 48, 47, 49, 48, 47,
+    ];
+    assert_eq!(sweep(&mut tracker, noise.into_iter()), (1, 1));
+    assert!(!tracker.left_engaged());
+}
+
+#[test]
+fn thresholds_are_configurable_per_trigger() {
+    let mut tracker = TriggerTracker::new(
+        TriggerThresholds {
+            engage: 20,
+            release: 10,
+        },
+        TriggerThresholds::DEFAULT,
+    );
+    tracker.update(
+        &ClassicReadingCalibrated::default()
+            .with_trigger_left(30)
+            .with_trigger_right(30),
+    );
+    assert!(tracker.left_engaged());
+    assert!(!tracker.right_engaged());
+}
+
+#[test]
+fn click_bit_engages_triggers_without_analogue() {
+    let mut tracker = TriggerTracker::default();
+    tracker.update(&ClassicReadingCalibrated::default().with_button_trigger_r(true));
+    assert!(tracker.right_engaged());
+    assert!(!tracker.left_engaged());
+    tracker.update(&ClassicReadingCalibrated::default());
+    assert!(!tracker.right_engaged());
+}
+
+#[test]
+fn driver_tracks_triggers_across_reads() {
+    // Report, then whether the left and right triggers should be engaged after reading it
+    let reads = [
+        (classic_hd_report! { trigger_left: 70 }, true, false),
+        (classic_hd_report! { trigger_left: 55 }, true, false),
+        (classic_hd_report! { trigger_left: 40 }, false, false),
+        (classic_hd_report! { trigger_left: 55 }, false, false),
+        (classic_hd_report! { trigger_right: 30 }, false, true),
+    ];
+    let expectations: Vec<_> = reads
+        .iter()
+        .flat_map(|(report, _, _)| transactions::read(report))
+        .collect();
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = ClassicBuilder::new()
+        .hires(true)
+        .skip_init()
+        .calibration(CalibrationData::default())
+        .trigger_thresholds(
+            TriggerThresholds::DEFAULT,
+            TriggerThresholds {
+                engage: 20,
+                release: 10,
+            },
+        )
+        .build(i2c.clone(), NoopDelay::new())
+        .unwrap();
+    for (i, (_, left, right)) in reads.iter().enumerate() {
+        classic.read().unwrap();
+        assert_eq!(classic.trigger_left_engaged(), *left, "read {i}");
+        assert_eq!(classic.trigger_right_engaged(), *right, "read {i}");
+    }
+    i2c.done();
+}
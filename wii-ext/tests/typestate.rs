@@ -0,0 +1,76 @@
+use embassy_futures::block_on;
+use embedded_hal_mock::eh1::{delay::NoopDelay, i2c};
+use wii_ext::core::{Ready, Uninit};
+mod common;
+use common::{test_data, transactions};
+
+#[test]
+fn classic_new_uninit_does_not_touch_the_bus() {
+    let mut i2c = i2c::Mock::new(&[]);
+    let classic: wii_ext::blocking_impl::classic::Classic<_, _, Uninit> =
+        wii_ext::blocking_impl::classic::Classic::new_uninit(i2c.clone(), NoopDelay::new());
+    let (_i2c, _delay) = classic.destroy();
+    i2c.done();
+}
+
+#[test]
+fn classic_init_returns_ready_driver() {
+    use wii_ext::blocking_impl::classic::Classic;
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.extend(transactions::read(&test_data::CLASSIC_BTN_A));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let classic = Classic::new_uninit(i2c.clone(), NoopDelay::new());
+    let mut classic: Classic<_, _, Ready> = classic.init().unwrap();
+    assert!(classic.read().unwrap().button_a);
+    i2c.done();
+}
+
+#[test]
+fn classic_assume_ready_skips_init() {
+    use wii_ext::blocking_impl::classic::Classic;
+    let expectations = transactions::read(&test_data::CLASSIC_BTN_B);
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = Classic::new_uninit(i2c.clone(), NoopDelay::new()).assume_ready();
+    assert!(classic.read().unwrap().button_b);
+    i2c.done();
+}
+
+#[test]
+fn nunchuk_init_no_calibration_returns_ready_driver() {
+    use wii_ext::blocking_impl::nunchuk::Nunchuk;
+    let mut expectations = transactions::init_no_calibration();
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_BTN_C));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let nunchuk = Nunchuk::new_uninit(i2c.clone(), NoopDelay::new());
+    let mut nunchuk = nunchuk.init_no_calibration().unwrap();
+    assert!(nunchuk.read().unwrap().button_c);
+    i2c.done();
+}
+
+#[test]
+fn async_classic_init_returns_ready_driver() {
+    use wii_ext::async_impl::classic::Classic;
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.extend(transactions::read(&test_data::CLASSIC_BTN_X));
+    let mut i2c = i2c::Mock::new(&expectations);
+    block_on(async {
+        let classic = Classic::new_uninit(i2c.clone(), NoopDelay::new());
+        let mut classic = classic.init().await.unwrap();
+        assert!(classic.read().await.unwrap().button_x);
+    });
+    i2c.done();
+}
+
+#[test]
+fn async_nunchuk_init_returns_ready_driver() {
+    use wii_ext::async_impl::nunchuk::Nunchuk;
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_BTN_Z));
+    let mut i2c = i2c::Mock::new(&expectations);
+    block_on(async {
+        let nunchuk = Nunchuk::new_uninit(i2c.clone(), NoopDelay::new());
+        let mut nunchuk = nunchuk.init().await.unwrap();
+        assert!(nunchuk.read().await.unwrap().button_z);
+    });
+    i2c.done();
+}
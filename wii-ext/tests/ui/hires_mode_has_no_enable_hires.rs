@@ -0,0 +1,10 @@
+use embedded_hal_mock::eh1::{delay::NoopDelay, i2c::Mock};
+use wii_ext::blocking_impl::classic::Classic;
+
+fn main() {
+    let classic = Classic::new_unchecked(Mock::new(&[]), NoopDelay::new());
+    let Ok(hires) = classic.into_hires() else {
+        return;
+    };
+    let _hires = hires.enable_hires();
+}
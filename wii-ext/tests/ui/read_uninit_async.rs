@@ -0,0 +1,11 @@
+use embedded_hal_mock::eh1::{delay::NoopDelay, i2c::Mock};
+use wii_ext::async_impl::nunchuk::Nunchuk;
+
+async fn read() {
+    let mut nunchuk = Nunchuk::new_uninit(Mock::new(&[]), NoopDelay::new());
+    let _input = nunchuk.read().await;
+}
+
+fn main() {
+    let _ = read();
+}
@@ -0,0 +1,7 @@
+use embedded_hal_mock::eh1::{delay::NoopDelay, i2c::Mock};
+use wii_ext::blocking_impl::classic::Classic;
+
+fn main() {
+    let mut classic = Classic::new_uninit(Mock::new(&[]), NoopDelay::new());
+    let _input = classic.read();
+}
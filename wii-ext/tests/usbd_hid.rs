@@ -0,0 +1,59 @@
+#![cfg(feature = "usbd-human-interface-device")]
+
+use usbd_human_interface_device::device::joystick::JoystickReport;
+use wii_ext::core::classic::ClassicReadingCalibrated;
+use wii_ext::core::nunchuk::NunchukReadingCalibrated;
+
+#[test]
+fn center_is_zero() {
+    let report = JoystickReport::from(&ClassicReadingCalibrated::default());
+    assert_eq!(report, JoystickReport::default());
+    let report = JoystickReport::from(&NunchukReadingCalibrated::default());
+    assert_eq!(report, JoystickReport::default());
+}
+
+#[test]
+fn full_deflection() {
+    let reading = ClassicReadingCalibrated::default()
+        .with_joystick_left_x(-128)
+        .with_joystick_left_y(-128);
+    let report = JoystickReport::from(&reading);
+    // -128 is clamped to the logical minimum, and down is positive
+    assert_eq!((report.x, report.y), (-127, 127));
+    let reading = NunchukReadingCalibrated::default()
+        .with_joystick_x(127)
+        .with_joystick_y(127);
+    let report = JoystickReport::from(&reading);
+    assert_eq!((report.x, report.y), (127, -127));
+}
+
+#[test]
+fn button_bits() {
+    let classic_button = |set: fn(&mut ClassicReadingCalibrated)| {
+        let mut reading = ClassicReadingCalibrated::default();
+        set(&mut reading);
+        JoystickReport::from(&reading).buttons
+    };
+    assert_eq!(classic_button(|r| r.button_a = true), 1 << 0);
+    assert_eq!(classic_button(|r| r.button_b = true), 1 << 1);
+    assert_eq!(classic_button(|r| r.button_x = true), 1 << 2);
+    assert_eq!(classic_button(|r| r.button_y = true), 1 << 3);
+    assert_eq!(classic_button(|r| r.button_trigger_l = true), 1 << 4);
+    assert_eq!(classic_button(|r| r.button_trigger_r = true), 1 << 5);
+    assert_eq!(classic_button(|r| r.button_zl = true), 1 << 6);
+    assert_eq!(classic_button(|r| r.button_zr = true), 1 << 7);
+    // Only 8 buttons are declared
+    assert_eq!(classic_button(|r| r.button_minus = true), 0);
+    assert_eq!(classic_button(|r| r.button_plus = true), 0);
+    assert_eq!(classic_button(|r| r.button_home = true), 0);
+    assert_eq!(classic_button(|r| r.dpad_up = true), 0);
+
+    let nunchuk_button = |c, z| {
+        let reading = NunchukReadingCalibrated::default()
+            .with_button_c(c)
+            .with_button_z(z);
+        JoystickReport::from(&reading).buttons
+    };
+    assert_eq!(nunchuk_button(true, false), 1 << 0);
+    assert_eq!(nunchuk_button(false, true), 1 << 1);
+}
@@ -0,0 +1,129 @@
+use embassy_futures::block_on;
+use embedded_hal_mock::eh1::{delay::NoopDelay, i2c};
+use wii_ext::blocking_impl::classic::Classic;
+use wii_ext::blocking_impl::nunchuk::{Nunchuk, NunchukBuilder};
+use wii_ext::Error;
+mod common;
+use common::{test_data, transactions};
+
+/// Expect a read of each report in turn
+fn reads(reports: &[&[u8]]) -> Vec<i2c::Transaction> {
+    reports
+        .iter()
+        .flat_map(|report| transactions::read(report))
+        .collect()
+}
+
+#[test]
+fn matching_reports_are_accepted() {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.extend(reads(&[
+        &test_data::CLASSIC_BTN_A,
+        &test_data::CLASSIC_BTN_A,
+    ]));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).unwrap();
+    classic.set_verify_reads(true);
+    assert!(classic.read().unwrap().button_a);
+    i2c.done();
+}
+
+#[test]
+fn phantom_press_is_rejected_then_retried() {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.extend(reads(&[
+        &test_data::CLASSIC_BTN_B,
+        &test_data::CLASSIC_IDLE,
+        &test_data::CLASSIC_IDLE,
+    ]));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).unwrap();
+    classic.set_verify_reads(true);
+    assert!(!classic.read().unwrap().button_b);
+    i2c.done();
+}
+
+#[test]
+fn reports_that_keep_disagreeing_are_an_error() {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.extend(reads(&[
+        &test_data::CLASSIC_BTN_A,
+        &test_data::CLASSIC_BTN_B,
+        &test_data::CLASSIC_BTN_A,
+    ]));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = Classic::new(i2c.clone(), NoopDelay::new()).unwrap();
+    classic.set_verify_reads(true);
+    assert!(matches!(classic.read(), Err(Error::InconsistentData)));
+    i2c.done();
+}
+
+#[test]
+fn axes_may_move_by_one() {
+    let mut moved = test_data::NUNCHUCK_JOY_R;
+    moved[0] += 1;
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    // The calibration read is verified too
+    expectations.extend(reads(&[&test_data::NUNCHUCK_IDLE]));
+    expectations.extend(reads(&[&test_data::NUNCHUCK_JOY_R, &moved]));
+    // Two counts is more than noise
+    moved[0] += 1;
+    expectations.extend(reads(&[
+        &test_data::NUNCHUCK_JOY_R,
+        &moved,
+        &test_data::NUNCHUCK_JOY_R,
+    ]));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut nunchuk = NunchukBuilder::new()
+        .verify_reads(true)
+        .build(i2c.clone(), NoopDelay::new())
+        .unwrap();
+    assert!(nunchuk.read().unwrap().joystick_x > 90);
+    assert!(matches!(nunchuk.read(), Err(Error::InconsistentData)));
+    i2c.done();
+}
+
+#[test]
+fn verification_is_off_by_default() {
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    expectations.extend(reads(&[&test_data::NUNCHUCK_BTN_Z]));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut nunchuk = Nunchuk::new(i2c.clone(), NoopDelay::new()).unwrap();
+    assert!(nunchuk.read().unwrap().button_z);
+    i2c.done();
+}
+
+#[test]
+fn async_drivers_verify_reads() {
+    use wii_ext::async_impl::classic::Classic;
+    use wii_ext::async_impl::nunchuk::Nunchuk;
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.extend(reads(&[
+        &test_data::CLASSIC_BTN_X,
+        &test_data::CLASSIC_BTN_Y,
+        &test_data::CLASSIC_BTN_Y,
+    ]));
+    let mut classic_i2c = i2c::Mock::new(&expectations);
+    let mut expectations = transactions::init(&test_data::NUNCHUCK_IDLE);
+    expectations.extend(reads(&[
+        &test_data::NUNCHUCK_BTN_C,
+        &test_data::NUNCHUCK_BTN_Z,
+        &test_data::NUNCHUCK_BTN_C,
+    ]));
+    let mut nunchuk_i2c = i2c::Mock::new(&expectations);
+    block_on(async {
+        let mut classic = Classic::new(classic_i2c.clone(), NoopDelay::new())
+            .await
+            .unwrap();
+        classic.set_verify_reads(true);
+        let input = classic.read().await.unwrap();
+        assert!(input.button_y && !input.button_x);
+        let mut nunchuk = Nunchuk::new(nunchuk_i2c.clone(), NoopDelay::new())
+            .await
+            .unwrap();
+        nunchuk.set_verify_reads(true);
+        assert!(matches!(nunchuk.read().await, Err(Error::InconsistentData)));
+    });
+    classic_i2c.done();
+    nunchuk_i2c.done();
+}
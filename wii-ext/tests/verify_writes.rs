@@ -0,0 +1,131 @@
+use embassy_futures::block_on;
+use embedded_hal_mock::eh1::{
+    delay::NoopDelay,
+    i2c::{self, Transaction},
+};
+use wii_ext::blocking_impl::classic::ClassicBuilder;
+use wii_ext::blocking_impl::nunchuk::NunchukBuilder;
+use wii_ext::core::classic::ReportMode;
+use wii_ext::core::EXT_I2C_ADDR;
+use wii_ext::Error;
+mod common;
+use common::{test_data, transactions};
+
+#[test]
+fn nunchuk_writes_read_back() {
+    let mut expectations = transactions::init_verified();
+    // The report read seeks back to 0 after verifying
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_IDLE));
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_BTN_C));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut nunchuk = NunchukBuilder::new()
+        .verify_writes(true)
+        .build(i2c.clone(), NoopDelay::new())
+        .unwrap();
+    assert!(nunchuk.read().unwrap().button_c);
+    i2c.done();
+}
+
+#[test]
+fn ignored_write_fails_init() {
+    let mut expectations = vec![Transaction::write(EXT_I2C_ADDR as u8, vec![0])];
+    // A clone that doesn't latch the encryption register
+    expectations.extend(transactions::verified_write(0xF0, 0x55, 0xFF));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let result = NunchukBuilder::new()
+        .verify_writes(true)
+        .build(i2c.clone(), NoopDelay::new());
+    assert!(matches!(
+        result,
+        Err(Error::WriteVerifyFailed {
+            addr: 0xF0,
+            expected: 0x55,
+            got: 0xFF
+        })
+    ));
+    i2c.done();
+}
+
+#[test]
+fn classic_hires_write_read_back() {
+    let mut expectations = transactions::init_verified();
+    expectations.extend(transactions::save_report_format(1));
+    expectations.extend(transactions::verified_write(0xFE, 0x03, 0x03));
+    expectations.extend(transactions::read(&test_data::PRO_HD_IDLE));
+    let mut i2c = i2c::Mock::new(&expectations);
+    ClassicBuilder::new()
+        .hires(true)
+        .verify_writes(true)
+        .build(i2c.clone(), NoopDelay::new())
+        .unwrap();
+    i2c.done();
+}
+
+#[test]
+fn classic_report_mode_read_back_once() {
+    let mut expectations = transactions::init_verified();
+    expectations.extend(transactions::read(&test_data::CLASSIC_IDLE));
+    expectations.extend(transactions::save_report_format(1));
+    // No second read of 0xFE after the verified write
+    expectations.extend(transactions::verified_write(0xFE, 0x03, 0x03));
+    expectations.extend(transactions::read(&test_data::PRO_HD_IDLE));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = ClassicBuilder::new()
+        .verify_writes(true)
+        .build(i2c.clone(), NoopDelay::new())
+        .unwrap();
+    classic.set_report_mode(ReportMode::HiRes).unwrap();
+    i2c.done();
+}
+
+#[test]
+fn writes_not_verified_by_default() {
+    let mut expectations = transactions::init(&test_data::CLASSIC_IDLE);
+    expectations.extend(transactions::read(&test_data::CLASSIC_BTN_A));
+    let mut i2c = i2c::Mock::new(&expectations);
+    let mut classic = ClassicBuilder::new()
+        .build(i2c.clone(), NoopDelay::new())
+        .unwrap();
+    assert!(classic.read().unwrap().button_a);
+    i2c.done();
+}
+
+#[test]
+fn async_ignored_write_fails_init() {
+    let mut expectations = vec![Transaction::write(EXT_I2C_ADDR as u8, vec![0])];
+    expectations.extend(transactions::verified_write(0xF0, 0x55, 0x55));
+    expectations.extend(transactions::verified_write(0xFB, 0x00, 0x01));
+    let mut i2c = i2c::Mock::new(&expectations);
+    block_on(async {
+        let result = wii_ext::async_impl::nunchuk::NunchukBuilder::new()
+            .verify_writes(true)
+            .build(i2c.clone(), NoopDelay::new())
+            .await;
+        assert!(matches!(
+            result,
+            Err(Error::WriteVerifyFailed {
+                addr: 0xFB,
+                expected: 0x00,
+                got: 0x01
+            })
+        ));
+    });
+    i2c.done();
+}
+
+#[test]
+fn async_nunchuk_writes_read_back() {
+    let mut expectations = transactions::init_verified();
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_IDLE));
+    expectations.extend(transactions::read(&test_data::NUNCHUCK_BTN_C));
+    let mut i2c = i2c::Mock::new(&expectations);
+    block_on(async {
+        let mut nunchuk = wii_ext::async_impl::nunchuk::NunchukBuilder::new()
+            .verify_writes(true)
+            .build(i2c.clone(), NoopDelay::new())
+            .await
+            .unwrap();
+        assert!(nunchuk.read().await.unwrap().button_c);
+    });
+    i2c.done();
+}
@@ -0,0 +1,138 @@
+use wii_ext::core::classic::ClassicReadingCalibrated;
+use wii_ext::core::events::Inputs;
+use wii_ext::core::nunchuk::{NunchukReadingCalibrated, ACCEL_ZERO_G};
+use wii_ext::core::wire::{self, VERSION};
+
+fn classic_readings() -> Vec<ClassicReadingCalibrated> {
+    vec![
+        ClassicReadingCalibrated::default(),
+        ClassicReadingCalibrated::default()
+            .with_joystick_left_x(i8::MIN)
+            .with_joystick_left_y(i8::MAX)
+            .with_joystick_right_x(-1)
+            .with_joystick_right_y(1)
+            .with_trigger_left(-30)
+            .with_trigger_right(31)
+            .with_button_a(true)
+            .with_button_zr(true)
+            .with_dpad_right(true),
+        ClassicReadingCalibrated::default()
+            .with_dpad_up(true)
+            .with_dpad_down(true)
+            .with_dpad_left(true)
+            .with_dpad_right(true)
+            .with_button_b(true)
+            .with_button_a(true)
+            .with_button_x(true)
+            .with_button_y(true)
+            .with_button_trigger_l(true)
+            .with_button_trigger_r(true)
+            .with_button_zl(true)
+            .with_button_zr(true)
+            .with_button_minus(true)
+            .with_button_plus(true)
+            .with_button_home(true),
+    ]
+}
+
+#[test]
+fn classic_round_trip() {
+    for reading in classic_readings() {
+        let data = reading.to_wire();
+        assert_eq!(data.len(), wire::CLASSIC_SIZE);
+        assert_eq!(data[0], VERSION);
+        assert_eq!(ClassicReadingCalibrated::from_wire(&data), Some(reading));
+    }
+}
+
+#[test]
+fn classic_layout() {
+    let reading = &classic_readings()[1];
+    assert_eq!(
+        reading.to_wire(),
+        [
+            VERSION,
+            0b1000_0001,
+            0b0100_0000,
+            0x80,
+            0x7F,
+            0xFF,
+            1,
+            0xE2,
+            31
+        ]
+    );
+    // Every button, with the reserved bit clear
+    assert_eq!(classic_readings()[2].to_wire()[1..3], [0xFF, 0x7F]);
+}
+
+#[test]
+fn classic_button_bits_follow_button_order() {
+    for &button in ClassicReadingCalibrated::BUTTONS {
+        let mut data = ClassicReadingCalibrated::default().to_wire();
+        data[1..3].copy_from_slice(&(1u16 << button as u16).to_le_bytes());
+        let reading = ClassicReadingCalibrated::from_wire(&data).unwrap();
+        for &other in ClassicReadingCalibrated::BUTTONS {
+            assert_eq!(reading.button(other), other == button, "{button:?}");
+        }
+        assert_eq!(reading.to_wire(), data);
+    }
+}
+
+#[test]
+fn nunchuk_round_trip() {
+    let readings = [
+        NunchukReadingCalibrated::default(),
+        NunchukReadingCalibrated::default()
+            .with_joystick_x(i8::MIN)
+            .with_joystick_y(100)
+            .with_accel_x(1023)
+            .with_accel_y(0)
+            .with_accel_z(513)
+            .with_button_c(true)
+            .with_button_z(false),
+        NunchukReadingCalibrated::default()
+            .with_joystick_x(-5)
+            .with_joystick_y(i8::MAX)
+            .with_accel_x(1)
+            .with_accel_y(1023)
+            .with_accel_z(1023)
+            .with_button_c(false)
+            .with_button_z(true),
+    ];
+    for reading in readings {
+        let data = reading.to_wire();
+        assert_eq!(data.len(), wire::NUNCHUK_SIZE);
+        // The calibration isn't encoded, so offsets come back relative to the nominal 0g point
+        let offset = |axis: u16| axis as i16 - ACCEL_ZERO_G as i16;
+        let expected = reading.with_accel_offset([
+            offset(reading.accel_x),
+            offset(reading.accel_y),
+            offset(reading.accel_z),
+        ]);
+        assert_eq!(NunchukReadingCalibrated::from_wire(&data), Some(expected));
+    }
+    assert_eq!(
+        readings[1].to_wire(),
+        [VERSION, 0b01, 0x80, 100, 0xFF, 0x03, 0x10, 0x20]
+    );
+}
+
+#[test]
+fn nunchuk_accel_is_clamped_to_10_bits() {
+    let reading = NunchukReadingCalibrated::default().with_accel_y(0xFFFF);
+    let decoded = NunchukReadingCalibrated::from_wire(&reading.to_wire()).unwrap();
+    assert_eq!(decoded.accel_x, 0);
+    assert_eq!(decoded.accel_y, 1023);
+    assert_eq!(decoded.accel_z, 0);
+}
+
+#[test]
+fn other_versions_are_rejected() {
+    let mut data = ClassicReadingCalibrated::default().to_wire();
+    data[0] = VERSION + 1;
+    assert_eq!(ClassicReadingCalibrated::from_wire(&data), None);
+    let mut data = NunchukReadingCalibrated::default().to_wire();
+    data[0] = 0;
+    assert_eq!(NunchukReadingCalibrated::from_wire(&data), None);
+}